@@ -0,0 +1,481 @@
+use regex::Regex;
+use tracing::debug;
+
+use crate::agents::secrets::SecretsAgent;
+use crate::agents::{heuristics, Language, ScannedFile, SecurityAgent};
+use crate::engine::comments;
+use crate::report::finding::{Confidence, Finding, Severity};
+use crate::rules::{self, RuleMeta};
+
+/// Rule metadata; the matching logic lives in `AuthAgent`
+struct AuthRule {
+    rule_id: &'static str,
+    title: &'static str,
+    description: &'static str,
+    severity: Severity,
+    confidence: Confidence,
+    recommendation: &'static str,
+    cwe_id: &'static str,
+    tags: &'static [&'static str],
+}
+
+const EXPRESS_UNAUTHENTICATED: AuthRule = AuthRule {
+    rule_id: "ANTY-ATH-001",
+    title: "Express Route Without Auth Middleware",
+    description: "A route has no authentication middleware while other routes in the same file do",
+    severity: Severity::Medium,
+    confidence: Confidence::Medium,
+    recommendation: "Add the same auth middleware the other routes use, or protect the whole router with router.use(auth) if every route needs it.",
+    cwe_id: "CWE-306",
+    tags: &["auth"],
+};
+
+const FLASK_UNAUTHENTICATED: AuthRule = AuthRule {
+    rule_id: "ANTY-ATH-002",
+    title: "Flask Endpoint Without @login_required",
+    description: "A view has no login decorator while other views in the same file do",
+    severity: Severity::Medium,
+    confidence: Confidence::Medium,
+    recommendation: "Add @login_required (or the decorator the other views use), or check authentication for the whole blueprint in a before_request hook.",
+    cwe_id: "CWE-306",
+    tags: &["auth"],
+};
+
+const SESSION_SECRET: AuthRule = AuthRule {
+    rule_id: "ANTY-ATH-003",
+    title: "Hardcoded Session Secret",
+    description: "The key that signs session cookies is hardcoded, so anyone with the source can forge sessions",
+    severity: Severity::High,
+    confidence: Confidence::High,
+    recommendation: "Load the session secret from an environment variable or secrets manager, and rotate it since it has been committed.",
+    cwe_id: "CWE-798",
+    tags: &["auth", "secrets", "low-noise"],
+};
+
+const INSECURE_SESSION_COOKIE: AuthRule = AuthRule {
+    rule_id: "ANTY-ATH-004",
+    title: "Session Cookie Sent Over HTTP",
+    description: "SESSION_COOKIE_SECURE (or a related session cookie flag) is disabled, so the session cookie can leak over plain HTTP or to scripts",
+    severity: Severity::Medium,
+    confidence: Confidence::High,
+    recommendation: "Set SESSION_COOKIE_SECURE = True and SESSION_COOKIE_HTTPONLY = True in production settings.",
+    cwe_id: "CWE-614",
+    tags: &["auth", "misconfig", "low-noise"],
+};
+
+const ALLOW_ANONYMOUS: AuthRule = AuthRule {
+    rule_id: "ANTY-ATH-005",
+    title: "Permissive AllowAnonymous",
+    description: "[AllowAnonymous] opens a whole controller, or an action that changes state, to unauthenticated users",
+    severity: Severity::Medium,
+    confidence: Confidence::Medium,
+    recommendation: "Remove [AllowAnonymous] from the controller and put it only on the actions that must be public, such as login.",
+    cwe_id: "CWE-306",
+    tags: &["auth"],
+};
+
+const RULES: &[&AuthRule] = &[
+    &EXPRESS_UNAUTHENTICATED,
+    &FLASK_UNAUTHENTICATED,
+    &SESSION_SECRET,
+    &INSECURE_SESSION_COOKIE,
+    &ALLOW_ANONYMOUS,
+];
+
+/// First path segments that are public by design
+const PUBLIC_PATHS: &[&str] = &[
+    "login", "logout", "signin", "signout", "signup", "register", "auth", "oauth", "callback",
+    "health", "healthz", "status", "ping", "ready", "metrics", "public", "static", "assets",
+    "forgot-password", "reset-password", "verify-email", "webhook", "webhooks", "docs",
+    "favicon.ico", "robots.txt",
+];
+
+/// Finds authentication gaps: routes left unprotected next to protected
+/// ones (Express, Flask), hardcoded session secrets, insecure session
+/// cookie settings, and `[AllowAnonymous]` in ASP.NET.
+///
+/// Level A agent — regex-based, but reads each file as a whole because
+/// "unprotected" only means something relative to the file's other routes.
+pub struct AuthAgent {
+    express_route: Regex,
+    /// `app.use(auth)` / `router.use('/admin', auth)`
+    express_use: Regex,
+    /// Middleware and decorator names that authenticate
+    auth_name: Regex,
+    flask_route: Regex,
+    flask_auth: Regex,
+    session_secret: Regex,
+    session_cookie: Regex,
+    allow_anonymous: Regex,
+    /// Minimal API: `app.MapPost(...).AllowAnonymous()`
+    minimal_api: Regex,
+    http_write: Regex,
+}
+
+impl AuthAgent {
+    pub fn new() -> Self {
+        AuthAgent {
+            express_route: Regex::new(r#"\b(app|router|api|\w+Router)\.(get|post|put|patch|delete|all)\s*\(\s*['"`]([^'"`]*)['"`]\s*,(.*)$"#).unwrap(),
+            express_use: Regex::new(r#"\b(app|router|api|\w+Router)\.use\s*\(\s*(?:['"`]([^'"`]*)['"`]\s*,)?(.*)$"#).unwrap(),
+            auth_name: Regex::new(r"(?i)\b(\w*auth(enticat\w*|ori[sz]\w*|middleware|guard|check|required|user)?|passport\.authenticate|ensure\w*(logged\w*|authenticated)|is(logged\w*|authenticated)|require(login|user|session)|login_?required|verify(token|jwt)|check_?jwt|\w*jwt\w*|protect(ed)?)\b").unwrap(),
+            flask_route: Regex::new(r#"^@\w+\.(route|get|post|put|patch|delete)\s*\(\s*['"]([^'"]*)['"]"#).unwrap(),
+            flask_auth: Regex::new(r"(?i)^@(\w+\.)*\w*(login_required|jwt_required|auth_required|requires_auth|token_required|permission_required|roles_required|roles_accepted|admin_required)\b").unwrap(),
+            session_secret: Regex::new(r#"(?i)(\b(app\.secret_key|SECRET_KEY|SESSION_SECRET|COOKIE_SECRET)\s*=\s*|\bsecret\s*:\s*|\bkeys\s*:\s*\[\s*)(b?["'][^"']{4,}["'])"#).unwrap(),
+            session_cookie: Regex::new(r"(?i)\b(SESSION_COOKIE_SECURE|SESSION_COOKIE_HTTPONLY|REMEMBER_COOKIE_SECURE|REMEMBER_COOKIE_HTTPONLY)\s*[=:]\s*['\x22]?(False|false|0)\b").unwrap(),
+            allow_anonymous: Regex::new(r"\[\s*([\w.]+(\([^)]*\))?\s*,\s*)*AllowAnonymous\b").unwrap(),
+            minimal_api: Regex::new(r"\.Map(Post|Put|Patch|Delete)\s*\(.*\.AllowAnonymous\s*\(").unwrap(),
+            http_write: Regex::new(r"\bHttp(Post|Put|Patch|Delete)\b").unwrap(),
+        }
+    }
+
+    fn finding(rule: &AuthRule, file: &ScannedFile, line_index: usize, column: usize, evidence: String) -> Finding {
+        let line_number = file.line_offset + line_index + 1;
+        debug!("Auth: {} in {}:{}", rule.rule_id, file.rel_path.display(), line_number);
+        Finding {
+            id: Finding::generate_id(rule.rule_id, &file.rel_path, line_number),
+            rule_id: rule.rule_id.to_string(),
+            severity: rule.severity,
+            confidence: rule.confidence,
+            agent: "auth".to_string(),
+            title: rule.title.to_string(),
+            description: rule.description.to_string(),
+            file_path: file.rel_path.clone(),
+            line_start: line_number,
+            line_end: line_number,
+            column: column + 1,
+            column_end: column + 1,
+            evidence,
+            recommendation: rule.recommendation.to_string(),
+            cwe_id: Some(rule.cwe_id.to_string()),
+            remediation: None,
+            secret_hash: None,
+            related_rules: Vec::new(),
+            additional_locations: Vec::new(),
+            owners: Vec::new(),
+            blame: None,
+            context: None,
+            triage: None,
+            fix: None,
+            environment: None,
+            score: None,
+            docs_url: Some(rules::docs_url(rule.rule_id)),
+            references: rules::references(rule.cwe_id),
+        }
+    }
+
+    /// ANTY-ATH-001: routes without auth middleware in a file where other
+    /// routes have it, directly or through an earlier `.use(auth)`
+    fn check_express(&self, file: &ScannedFile, lines: &[&str], comment: &[bool], findings: &mut Vec<Finding>) {
+        // (line index, column, path, protected)
+        let mut routes = Vec::new();
+        // Path prefixes guarded by .use(auth); "" guards everything after it
+        let mut guarded: Vec<String> = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            if comment[i] {
+                continue;
+            }
+            if let Some(caps) = self.express_use.captures(line) {
+                if self.auth_name.is_match(&caps[3]) {
+                    guarded.push(caps.get(2).map_or("", |m| m.as_str()).to_string());
+                }
+                continue;
+            }
+            if let Some(caps) = self.express_route.captures(line) {
+                let path = caps[3].to_string();
+                let protected = self.auth_name.is_match(middleware(&caps[4]))
+                    || guarded
+                        .iter()
+                        .any(|prefix| path.starts_with(prefix.as_str()));
+                routes.push((i, caps.get(0).unwrap().start(), path, protected));
+            }
+        }
+
+        if !routes.iter().any(|r| r.3) {
+            return;
+        }
+        for (i, column, path, protected) in routes {
+            if !protected && !is_public(&path) {
+                findings.push(Self::finding(
+                    &EXPRESS_UNAUTHENTICATED,
+                    file,
+                    i,
+                    column,
+                    lines[i].trim().to_string(),
+                ));
+            }
+        }
+    }
+
+    /// ANTY-ATH-002: views without a login decorator in a file where other
+    /// views have one
+    fn check_flask(&self, file: &ScannedFile, lines: &[&str], comment: &[bool], findings: &mut Vec<Finding>) {
+        // A before_request hook usually checks the login for every view
+        if lines
+            .iter()
+            .any(|l| l.trim_start().starts_with('@') && l.contains("before_request"))
+        {
+            return;
+        }
+
+        // (route line index, path, protected) per decorated function
+        let mut views = Vec::new();
+        let mut route: Option<(usize, String)> = None;
+        let mut protected = false;
+        for (i, line) in lines.iter().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('@') {
+                if let Some(caps) = self.flask_route.captures(trimmed) {
+                    route.get_or_insert((i, caps[2].to_string()));
+                }
+                protected |= self.flask_auth.is_match(trimmed);
+                continue;
+            }
+            if trimmed.is_empty() || comment[i] {
+                continue;
+            }
+            if let Some((index, path)) = route.take() {
+                if trimmed.starts_with("def ") || trimmed.starts_with("async def ") {
+                    views.push((index, path, protected));
+                }
+            }
+            protected = false;
+        }
+
+        if !views.iter().any(|v| v.2) {
+            return;
+        }
+        for (i, path, protected) in views {
+            if !protected && !is_public(&path) {
+                let column = lines[i].len() - lines[i].trim_start().len();
+                findings.push(Self::finding(
+                    &FLASK_UNAUTHENTICATED,
+                    file,
+                    i,
+                    column,
+                    lines[i].trim().to_string(),
+                ));
+            }
+        }
+    }
+
+    /// ANTY-ATH-005: `[AllowAnonymous]` on a controller class or on a
+    /// POST/PUT/PATCH/DELETE action, and anonymous minimal-API writes
+    fn check_allow_anonymous(
+        &self,
+        file: &ScannedFile,
+        lines: &[&str],
+        comment: &[bool],
+        findings: &mut Vec<Finding>,
+    ) {
+        for (i, line) in lines.iter().enumerate() {
+            let trimmed = line.trim_start();
+            if comment[i] {
+                continue;
+            }
+            if let Some(m) = self.minimal_api.find(line).filter(|_| !is_public_action(line)) {
+                findings.push(Self::finding(
+                    &ALLOW_ANONYMOUS,
+                    file,
+                    i,
+                    m.start(),
+                    trimmed.trim_end().to_string(),
+                ));
+                continue;
+            }
+            let Some(m) = self.allow_anonymous.find(line) else {
+                continue;
+            };
+
+            // The attributes stacked with this one, up to the declaration
+            let mut writes = self.http_write.is_match(line);
+            let mut declaration = "";
+            let mut public = is_public_action(line);
+            for next in lines[..i]
+                .iter()
+                .rev()
+                .map(|l| l.trim())
+                .take_while(|l| l.starts_with('['))
+            {
+                writes |= self.http_write.is_match(next);
+                public |= is_public_action(next);
+            }
+            for (j, next) in lines.iter().enumerate().skip(i + 1) {
+                let next = next.trim();
+                if next.is_empty() || comment[j] {
+                    continue;
+                }
+                if next.starts_with('[') {
+                    writes |= self.http_write.is_match(next);
+                    public |= is_public_action(next);
+                    continue;
+                }
+                declaration = next;
+                break;
+            }
+            let on_class = declaration.split_whitespace().any(|word| word == "class");
+            if on_class || (writes && !public && !is_public_action(declaration)) {
+                findings.push(Self::finding(
+                    &ALLOW_ANONYMOUS,
+                    file,
+                    i,
+                    m.start(),
+                    trimmed.trim_end().to_string(),
+                ));
+            }
+        }
+    }
+}
+
+impl SecurityAgent for AuthAgent {
+    fn name(&self) -> &str {
+        "auth"
+    }
+
+    fn description(&self) -> &str {
+        "Finds authentication gaps: unprotected Express and Flask routes, hardcoded session secrets, insecure session cookies, and AllowAnonymous"
+    }
+
+    /// Route checks for the code languages, session settings for the rest
+    fn interested_in(&self, file: &ScannedFile) -> bool {
+        file.language.is_some_and(|l| self.languages().contains(&l))
+    }
+
+    fn scan_file(&self, file: &ScannedFile) -> Vec<Finding> {
+        let lines: Vec<&str> = file.content.lines().collect();
+        let comment = comments::comment_lines(&file.content, file.language);
+        let mut findings = Vec::new();
+
+        match file.language {
+            Some(Language::JavaScript | Language::TypeScript) => {
+                self.check_express(file, &lines, &comment, &mut findings)
+            }
+            Some(Language::Python) => self.check_flask(file, &lines, &comment, &mut findings),
+            Some(Language::CSharp) => self.check_allow_anonymous(file, &lines, &comment, &mut findings),
+            _ => {}
+        }
+
+        let code = matches!(
+            file.language,
+            Some(Language::JavaScript | Language::TypeScript | Language::Python)
+        );
+        let settings = code
+            || matches!(
+                file.language,
+                Some(Language::Yaml | Language::Json | Language::Toml | Language::Env)
+            );
+
+        for (i, line) in lines.iter().enumerate() {
+            if file.out_of_time() {
+                break;
+            }
+            let trimmed = line.trim();
+            if comment[i] {
+                continue;
+            }
+
+            if code {
+                if let Some(caps) = self.session_secret.captures(line) {
+                    let literal = caps.get(3).unwrap();
+                    let value = heuristics::secret_value(literal.as_str());
+                    if !heuristics::is_placeholder(value) {
+                        let evidence = SecretsAgent::redact_evidence(trimmed, value);
+                        let mut finding = Self::finding(
+                            &SESSION_SECRET,
+                            file,
+                            i,
+                            caps.get(0).unwrap().start(),
+                            evidence,
+                        );
+                        finding.secret_hash = Some(Finding::hash_secret(value));
+                        findings.push(finding);
+                    }
+                }
+            }
+            if settings {
+                if let Some(m) = self.session_cookie.find(line) {
+                    findings.push(Self::finding(
+                        &INSECURE_SESSION_COOKIE,
+                        file,
+                        i,
+                        m.start(),
+                        trimmed.to_string(),
+                    ));
+                }
+            }
+        }
+
+        findings
+    }
+
+    fn languages(&self) -> Vec<Language> {
+        vec![
+            Language::JavaScript,
+            Language::TypeScript,
+            Language::Python,
+            Language::CSharp,
+            Language::Yaml,
+            Language::Json,
+            Language::Toml,
+            Language::Env,
+        ]
+    }
+
+    fn rules(&self) -> Vec<RuleMeta> {
+        let patterns = [
+            &self.express_route,
+            &self.flask_route,
+            &self.session_secret,
+            &self.session_cookie,
+            &self.allow_anonymous,
+        ];
+        RULES
+            .iter()
+            .zip(patterns)
+            .map(|(r, pattern)| RuleMeta {
+                id: r.rule_id.to_string(),
+                agent: "auth".to_string(),
+                title: r.title.to_string(),
+                description: r.description.to_string(),
+                severity: r.severity,
+                confidence: r.confidence,
+                recommendation: r.recommendation.to_string(),
+                cwe_id: Some(r.cwe_id.to_string()),
+                pattern: pattern.as_str().to_string(),
+                remediation: None,
+                docs_url: rules::docs_url(r.rule_id),
+                references: rules::references(r.cwe_id),
+                tags: r.tags.iter().map(|t| t.to_string()).collect(),
+            })
+            .collect()
+    }
+}
+
+/// The middleware part of an Express route's arguments: everything before
+/// the inline handler (`(req, res) =>`, `function`, `async`)
+fn middleware(args: &str) -> &str {
+    ["(req", "function", "async", "=>"]
+        .iter()
+        .filter_map(|marker| args.find(marker))
+        .min()
+        .map_or(args, |end| &args[..end])
+}
+
+/// Login pages, health checks, webhooks, and static assets
+fn is_public(path: &str) -> bool {
+    let first = path
+        .trim_start_matches('/')
+        .split('/')
+        .next()
+        .unwrap_or("")
+        .to_lowercase();
+    first.is_empty() || PUBLIC_PATHS.contains(&first.as_str())
+}
+
+/// Login, registration, and token actions, which must accept anonymous users
+fn is_public_action(text: &str) -> bool {
+    let text = text.to_lowercase();
+    ["login", "logon", "signin", "signup", "register", "token", "forgot", "reset", "callback", "webhook"]
+        .iter()
+        .any(|word| text.contains(word))
+}
+
@@ -0,0 +1,255 @@
+use regex::Regex;
+use tracing::debug;
+
+use crate::agents::{ScannedFile, SecurityAgent};
+use crate::report::finding::{Confidence, Finding, Severity};
+use crate::rules::{self, RuleMeta};
+
+/// Which build system a file belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BuildFile {
+    /// pom.xml
+    Maven,
+    /// build.gradle(.kts), settings.gradle(.kts), init scripts
+    Gradle,
+    /// WORKSPACE, MODULE.bazel, BUILD, and .bzl files
+    Bazel,
+}
+
+impl BuildFile {
+    fn detect(file: &ScannedFile) -> Option<Self> {
+        let path = file.name_path();
+        let name = path.file_name()?.to_string_lossy();
+        match name.as_ref() {
+            "pom.xml" => Some(BuildFile::Maven),
+            "WORKSPACE" | "WORKSPACE.bazel" | "WORKSPACE.bzlmod" | "MODULE.bazel" | "BUILD" | "BUILD.bazel" => {
+                Some(BuildFile::Bazel)
+            }
+            name if name.ends_with(".gradle") || name.ends_with(".gradle.kts") => Some(BuildFile::Gradle),
+            name if name.ends_with(".bzl") => Some(BuildFile::Bazel),
+            _ => None,
+        }
+    }
+}
+
+struct BuildPattern {
+    rule_id: &'static str,
+    title: &'static str,
+    description: &'static str,
+    /// Matched against the whole file, since build blocks span lines. A
+    /// group named `at` marks where to report, when not at the start.
+    pattern: Regex,
+    severity: Severity,
+    confidence: Confidence,
+    recommendation: &'static str,
+    cwe_id: &'static str,
+    tags: &'static [&'static str],
+    files: &'static [BuildFile],
+}
+
+/// Checks Maven, Gradle, and Bazel build files: dependencies fetched over
+/// plain HTTP or without a checksum, and shell commands the build runs.
+///
+/// Level A agent — regex-based over whole blocks (a `<repository>`, an
+/// `exec {}` block, an `http_archive(...)` call), since build files spread
+/// one declaration over several lines.
+pub struct BuildFilesAgent {
+    patterns: Vec<BuildPattern>,
+    /// A plain-HTTP URL inside a Maven repository or mirror block
+    maven_http_url: Regex,
+    /// A checksum attribute of a Bazel download rule
+    checksum: Regex,
+}
+
+impl BuildFilesAgent {
+    pub fn new() -> Self {
+        let patterns = vec![
+            BuildPattern {
+                rule_id: "ANTY-BLD-001",
+                title: "Maven Repository Over HTTP",
+                description: "A Maven repository or mirror is fetched over plain HTTP, so anyone on the network path can swap the artifacts the build downloads",
+                pattern: Regex::new(r"(?s)<(repository|pluginRepository|snapshotRepository|mirror)>.*?</(repository|pluginRepository|snapshotRepository|mirror)>").unwrap(),
+                severity: Severity::High,
+                confidence: Confidence::High,
+                recommendation: "Use the repository's https:// URL. Maven 3.8.1+ blocks HTTP repositories by default; do not re-enable them in settings.xml.",
+                cwe_id: "CWE-494",
+                tags: &["supply-chain", "build", "low-noise"],
+                files: &[BuildFile::Maven],
+            },
+            BuildPattern {
+                rule_id: "ANTY-BLD-002",
+                title: "Shell Executed by maven-antrun-plugin",
+                description: "An Ant <exec> task in the Maven build starts a shell, which runs on every developer machine and CI runner that builds the project",
+                pattern: Regex::new(r#"(?s)<exec\b[^>]*\bexecutable\s*=\s*"(?:[^"]*[/\\])?(?:sh|bash|zsh|dash|cmd|cmd\.exe|powershell|powershell\.exe|pwsh)""#).unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::Medium,
+                recommendation: "Replace the shell call with a Maven plugin or an Ant task that does the same job. If a script is unavoidable, call it directly with fixed arguments and keep it in the repository.",
+                cwe_id: "CWE-78",
+                tags: &["supply-chain", "build", "injection"],
+                files: &[BuildFile::Maven],
+            },
+            BuildPattern {
+                rule_id: "ANTY-BLD-003",
+                title: "Gradle Repository Over HTTP",
+                description: "A Gradle repository is fetched over plain HTTP (or allowInsecureProtocol is set), so anyone on the network path can swap dependencies or plugins",
+                pattern: Regex::new(r#"(?i)\burl\s*(?:=\s*)?(?:\(\s*)?(?:uri\s*\(\s*)?["'](?P<at>http://[^"'\s]+)["']|\b(?:is)?allowInsecureProtocol\s*(?:=\s*|\(\s*)?true\b"#).unwrap(),
+                severity: Severity::High,
+                confidence: Confidence::High,
+                recommendation: "Use the repository's https:// URL (mavenCentral() and google() already do) and remove allowInsecureProtocol.",
+                cwe_id: "CWE-494",
+                tags: &["supply-chain", "build", "low-noise"],
+                files: &[BuildFile::Gradle],
+            },
+            BuildPattern {
+                rule_id: "ANTY-BLD-004",
+                title: "Gradle Exec With Interpolated Command",
+                description: "An exec block or Exec task builds its command line from interpolated strings, so a property, environment variable, or file name can change the command that runs",
+                pattern: Regex::new(r#"(?s)(?:\bexec|\bExec\s*\)|\btype\s*:\s*Exec\s*\))\s*\{[^}]*?\b(?:commandLine|args|executable)\b[^\n]*?(?P<at>"[^"\n]*\$[{A-Za-z_][^"\n]*")"#).unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::Medium,
+                recommendation: "Pass each argument as its own list element instead of interpolating into a shell string, and avoid `sh -c`. Validate values that come from properties or the environment.",
+                cwe_id: "CWE-78",
+                tags: &["supply-chain", "build", "injection"],
+                files: &[BuildFile::Gradle],
+            },
+            BuildPattern {
+                rule_id: "ANTY-BLD-005",
+                title: "Bazel Download Without Checksum",
+                description: "An http_archive, http_file, or http_jar has no sha256 or integrity, so Bazel accepts whatever the URL serves",
+                pattern: Regex::new(r"\b(?:http_archive|http_file|http_jar)\s*\((?:[^()]|\([^()]*\))*\)").unwrap(),
+                severity: Severity::High,
+                confidence: Confidence::High,
+                recommendation: "Add the archive's sha256 (or integrity) so a changed or tampered download fails the build.",
+                cwe_id: "CWE-494",
+                tags: &["supply-chain", "build", "low-noise"],
+                files: &[BuildFile::Bazel],
+            },
+        ];
+
+        BuildFilesAgent {
+            patterns,
+            maven_http_url: Regex::new(r"<url>\s*(?P<at>http://[^<\s]+)").unwrap(),
+            checksum: Regex::new(r"\b(?:sha256|integrity)\s*=").unwrap(),
+        }
+    }
+
+    /// Where to report a match of `pattern`, or None if it is not a finding
+    fn location(&self, pattern: &BuildPattern, caps: &regex::Captures) -> Option<(usize, usize)> {
+        let m = caps.get(0).unwrap();
+        let (start, end) = match pattern.rule_id {
+            "ANTY-BLD-001" => {
+                let url = self.maven_http_url.captures(m.as_str())?.name("at")?;
+                (m.start() + url.start(), m.start() + url.end())
+            }
+            // A macro forwarding **kwargs may pass the checksum along
+            "ANTY-BLD-005" if self.checksum.is_match(m.as_str()) || m.as_str().contains("**") => return None,
+            _ => caps.name("at").map_or((m.start(), m.end()), |at| (at.start(), at.end())),
+        };
+        Some((start, end))
+    }
+}
+
+/// Plain HTTP to this machine never crosses a network
+fn is_local(url: &str) -> bool {
+    let host = url.trim_start_matches("http://").split(['/', ':']).next().unwrap_or("");
+    matches!(host, "localhost" | "127.0.0.1" | "[::1]")
+}
+
+impl SecurityAgent for BuildFilesAgent {
+    fn name(&self) -> &str {
+        "build-files"
+    }
+
+    fn description(&self) -> &str {
+        "Checks Maven, Gradle, and Bazel build files for dependencies fetched over HTTP or without a checksum, and for shell commands run by the build"
+    }
+
+    fn interested_in(&self, file: &ScannedFile) -> bool {
+        BuildFile::detect(file).is_some()
+    }
+
+    fn scan_file(&self, file: &ScannedFile) -> Vec<Finding> {
+        let Some(kind) = BuildFile::detect(file) else {
+            return Vec::new();
+        };
+        let mut findings = Vec::new();
+
+        for pattern in self.patterns.iter().filter(|p| p.files.contains(&kind)) {
+            for caps in pattern.pattern.captures_iter(&file.content) {
+                if file.out_of_time() {
+                    return findings;
+                }
+                let Some((start, end)) = self.location(pattern, &caps) else {
+                    continue;
+                };
+                if file.content[start..end].starts_with("http://") && is_local(&file.content[start..end]) {
+                    continue;
+                }
+
+                let before = &file.content[..start];
+                let line_number = file.line_offset + before.matches('\n').count() + 1;
+                let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+                let line = file.content[line_start..].lines().next().unwrap_or("").trim();
+                if ["//", "#", "<!--"].iter().any(|c| line.starts_with(c)) {
+                    continue;
+                }
+
+                debug!("Build file: {} in {}:{}", pattern.rule_id, file.rel_path.display(), line_number);
+
+                findings.push(Finding {
+                    id: Finding::generate_id(pattern.rule_id, &file.rel_path, line_number),
+                    rule_id: pattern.rule_id.to_string(),
+                    severity: pattern.severity,
+                    confidence: pattern.confidence,
+                    agent: "build-files".to_string(),
+                    title: pattern.title.to_string(),
+                    description: pattern.description.to_string(),
+                    file_path: file.rel_path.clone(),
+                    line_start: line_number,
+                    line_end: line_number + file.content[start..end].matches('\n').count(),
+                    column: start - line_start + 1,
+                    column_end: file.content[start..end].lines().next().map_or(start, |l| start + l.len()) - line_start + 1,
+                    evidence: line.to_string(),
+                    recommendation: pattern.recommendation.to_string(),
+                    cwe_id: Some(pattern.cwe_id.to_string()),
+                    remediation: None,
+                    secret_hash: None,
+                    related_rules: Vec::new(),
+                    additional_locations: Vec::new(),
+                    owners: Vec::new(),
+                    blame: None,
+                    context: None,
+                    triage: None,
+                    fix: None,
+                    environment: None,
+                    score: None,
+                    docs_url: Some(rules::docs_url(pattern.rule_id)),
+                    references: rules::references(pattern.cwe_id),
+                });
+            }
+        }
+
+        findings
+    }
+
+    fn rules(&self) -> Vec<RuleMeta> {
+        self.patterns
+            .iter()
+            .map(|p| RuleMeta {
+                id: p.rule_id.to_string(),
+                agent: "build-files".to_string(),
+                title: p.title.to_string(),
+                description: p.description.to_string(),
+                severity: p.severity,
+                confidence: p.confidence,
+                recommendation: p.recommendation.to_string(),
+                cwe_id: Some(p.cwe_id.to_string()),
+                pattern: p.pattern.as_str().to_string(),
+                remediation: None,
+                docs_url: rules::docs_url(p.rule_id),
+                references: rules::references(p.cwe_id),
+                tags: p.tags.iter().map(|t| t.to_string()).collect(),
+            })
+            .collect()
+    }
+}
@@ -1,14 +1,21 @@
-use regex::Regex;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use regex::{Regex, RegexSet};
 use tracing::debug;
 
-use crate::agents::{Language, ScannedFile, SecurityAgent};
+use crate::agents::{self, Language, ScannedFile, SecurityAgent};
+use crate::engine::comments;
 use crate::report::finding::{Confidence, Finding, Severity};
+use crate::rules::{self, RuleMeta};
 
 /// Detects dangerous configuration patterns like CORS wildcards,
 /// debug mode enabled, insecure headers, etc.
 ///
 /// Level A agent — regex-based, language-aware.
 pub struct ConfigIssuesAgent {
+    /// Prefilter over all patterns, in the same order
+    set: RegexSet,
     patterns: Vec<ConfigPattern>,
 }
 
@@ -21,6 +28,7 @@ struct ConfigPattern {
     confidence: Confidence,
     recommendation: &'static str,
     cwe_id: &'static str,
+    tags: &'static [&'static str],
     file_types: FileTypeFilter,
 }
 
@@ -74,6 +82,7 @@ impl ConfigIssuesAgent {
                 confidence: Confidence::High,
                 recommendation: "Restrict CORS to specific trusted origins instead of using '*'.",
                 cwe_id: "CWE-942",
+                tags: &["misconfig", "low-noise"],
                 file_types: FileTypeFilter::Any,
             },
 
@@ -87,6 +96,7 @@ impl ConfigIssuesAgent {
                 confidence: Confidence::Low,
                 recommendation: "Ensure DEBUG is disabled in production. Use environment-specific configuration.",
                 cwe_id: "CWE-489",
+                tags: &["misconfig"],
                 file_types: FileTypeFilter::Any,
             },
 
@@ -100,6 +110,7 @@ impl ConfigIssuesAgent {
                 confidence: Confidence::Medium,
                 recommendation: "Use HTTPS for all external API endpoints and webhooks.",
                 cwe_id: "CWE-319",
+                tags: &["crypto"],
                 file_types: FileTypeFilter::Any,
             },
 
@@ -113,6 +124,7 @@ impl ConfigIssuesAgent {
                 confidence: Confidence::Medium,
                 recommendation: "Set secure: true and httpOnly: true on all authentication cookies.",
                 cwe_id: "CWE-614",
+                tags: &["auth", "misconfig"],
                 file_types: FileTypeFilter::Languages(vec![
                     Language::JavaScript,
                     Language::TypeScript,
@@ -130,6 +142,7 @@ impl ConfigIssuesAgent {
                 confidence: Confidence::Low,
                 recommendation: "In production, bind to specific interfaces. Use 127.0.0.1 for local-only access.",
                 cwe_id: "CWE-668",
+                tags: &["misconfig"],
                 file_types: FileTypeFilter::Any,
             },
 
@@ -138,11 +151,13 @@ impl ConfigIssuesAgent {
                 rule_id: "ANTY-CFG-006",
                 title: "Docker Container Running as Root",
                 description: "Dockerfile does not set a non-root user",
-                pattern: Regex::new(r"(?i)^FROM\s+.+").unwrap(), // Will check for absence of USER
+                // Matched per file in the project pass: flagged when no USER follows the last FROM
+                pattern: Regex::new(r"(?i)^FROM\s+.+").unwrap(),
                 severity: Severity::Low,
                 confidence: Confidence::Low,
                 recommendation: "Add a USER directive in your Dockerfile to run as a non-root user.",
                 cwe_id: "CWE-250",
+                tags: &["container", "misconfig"],
                 file_types: FileTypeFilter::Languages(vec![Language::Dockerfile]),
             },
 
@@ -156,6 +171,7 @@ impl ConfigIssuesAgent {
                 confidence: Confidence::High,
                 recommendation: "Never disable SSL verification in production. Use proper certificate management.",
                 cwe_id: "CWE-295",
+                tags: &["crypto", "low-noise"],
                 file_types: FileTypeFilter::Any,
             },
 
@@ -169,17 +185,169 @@ impl ConfigIssuesAgent {
                 confidence: Confidence::Low,
                 recommendation: "Implement rate limiting on authentication endpoints to prevent brute-force attacks.",
                 cwe_id: "CWE-307",
+                tags: &["auth"],
                 file_types: FileTypeFilter::Languages(vec![
                     Language::JavaScript,
                     Language::TypeScript,
                 ]),
             },
+
+            // ── File permissions ─────────────────────────────
+            ConfigPattern {
+                rule_id: "ANTY-CFG-009",
+                title: "World-Writable Path Created",
+                description: "chmod, mkdir -m, or install -m gives every user write access to a path",
+                pattern: Regex::new(r"\b(chmod|mkdir|install)\s+(-[\w-]+\s+)*(-m\s*|--mode=)?([0246]?[0-7]{2}[2367]|[ugo]*[ao][ugo]*[+=][rxXst]*w[rwxXst]*)\b").unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::High,
+                recommendation: "Grant write access only to the owner (e.g. chmod 755 or 644), and chown the path to the user that needs it.",
+                cwe_id: "CWE-732",
+                tags: &["permissions", "container", "low-noise"],
+                file_types: FileTypeFilter::Languages(vec![Language::Shell, Language::Dockerfile]),
+            },
+            ConfigPattern {
+                rule_id: "ANTY-CFG-010",
+                title: "World-Writable File Permissions",
+                description: "A file's mode is set so that every user can write to it",
+                pattern: Regex::new(r#"(?i)(\b[lf]?chmod(sync)?\s*\(([^)]*[\s,])?(0o?[0246]?[0-7]{2}[2367]\b|["'][0246]?[0-7]{2}[2367]["'])|\bfrom_mode\s*\(\s*0o[0246]?[0-7]{2}[2367]\b)"#).unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::High,
+                recommendation: "Use a mode without write access for others, such as 0o644 for files or 0o755 for directories.",
+                cwe_id: "CWE-732",
+                tags: &["permissions", "low-noise"],
+                file_types: FileTypeFilter::Languages(vec![
+                    Language::Python,
+                    Language::JavaScript,
+                    Language::TypeScript,
+                    Language::Go,
+                    Language::Ruby,
+                    Language::Php,
+                    Language::Rust,
+                ]),
+            },
+            ConfigPattern {
+                rule_id: "ANTY-CFG-011",
+                title: "Permissive umask",
+                description: "The umask does not remove write access for others, so every file the process creates is world-writable",
+                pattern: Regex::new(r"\bumask\s*(\(\s*)?(0o)?[0-7]{0,3}[0145]\s*(\)|$|;|&)").unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::Medium,
+                recommendation: "Use a umask of 022 or stricter (027, 077).",
+                cwe_id: "CWE-732",
+                tags: &["permissions"],
+                file_types: FileTypeFilter::Languages(vec![
+                    Language::Shell,
+                    Language::Dockerfile,
+                    Language::Python,
+                    Language::JavaScript,
+                    Language::TypeScript,
+                    Language::Go,
+                    Language::Ruby,
+                    Language::Php,
+                ]),
+            },
+
+            // ── ASP.NET ──────────────────────────────────────
+            ConfigPattern {
+                rule_id: "ANTY-CFG-012",
+                title: "ASP.NET Request Validation Disabled",
+                description: "Request validation is turned off, so markup and script in form fields and query strings reach the page unchecked",
+                pattern: Regex::new(r#"(?i)\bvalidateRequest\s*=\s*["']?false\b|\[\s*ValidateInput\s*\(\s*false\s*\)"#).unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::High,
+                recommendation: "Leave request validation on. Where a field must accept HTML, use [AllowHtml] on that property only and encode or sanitize it on output.",
+                cwe_id: "CWE-79",
+                tags: &["injection", "misconfig", "low-noise"],
+                file_types: FileTypeFilter::Any,
+            },
         ];
 
-        ConfigIssuesAgent { patterns }
+        let set = agents::pattern_set(patterns.iter().map(|p| p.pattern.as_str()));
+        ConfigIssuesAgent { set, patterns }
+    }
+
+    fn finding(pattern: &ConfigPattern, file: &ScannedFile, line_number: usize, columns: Range<usize>, evidence: &str) -> Finding {
+        debug!(
+            "Config issue: {} in {}:{}",
+            pattern.rule_id,
+            file.rel_path.display(),
+            line_number
+        );
+        Finding {
+            id: Finding::generate_id(
+                pattern.rule_id,
+                &file.rel_path,
+                line_number,
+            ),
+            rule_id: pattern.rule_id.to_string(),
+            severity: pattern.severity,
+            confidence: pattern.confidence,
+            agent: "config-issues".to_string(),
+            title: pattern.title.to_string(),
+            description: pattern.description.to_string(),
+            file_path: file.rel_path.clone(),
+            line_start: line_number,
+            line_end: line_number,
+            column: columns.start,
+            column_end: columns.end,
+            evidence: evidence.to_string(),
+            recommendation: pattern.recommendation.to_string(),
+            cwe_id: Some(pattern.cwe_id.to_string()),
+            remediation: None,
+            secret_hash: None,
+            related_rules: Vec::new(),
+            additional_locations: Vec::new(),
+            owners: Vec::new(),
+            blame: None,
+            context: None,
+            triage: None,
+            fix: None,
+            environment: None,
+            score: None,
+            docs_url: Some(rules::docs_url(pattern.rule_id)),
+            references: rules::references(pattern.cwe_id),
+        }
+    }
+
+    /// A Dockerfile whose final stage never switches to a non-root user,
+    /// reported on that stage's FROM line. USER in an earlier build stage
+    /// does not carry over to the image that ships.
+    fn runs_as_root(&self, file: &ScannedFile) -> Option<Finding> {
+        let pattern = self.patterns.iter().find(|p| p.rule_id == ROOT_USER_RULE)?;
+        let comment = comments::comment_lines(&file.content, file.language);
+        let mut last_from = None;
+        let mut user = None;
+        for (line_num, line) in file.content.lines().enumerate() {
+            if comment[line_num] {
+                continue;
+            }
+            let trimmed = line.trim();
+            if pattern.pattern.is_match(trimmed) {
+                last_from = Some((line_num, trimmed));
+                user = None;
+            } else if let Some(name) = USER.captures(trimmed).map(|c| c[1].to_string()) {
+                user = Some(name);
+            }
+        }
+        let (line_num, from) = last_from?;
+        if user.is_some_and(|u| !is_root(&u)) {
+            return None;
+        }
+        let column = file.content.lines().nth(line_num).map_or(1, |l| l.len() - l.trim_start().len() + 1);
+        Some(Self::finding(pattern, file, file.line_offset + line_num + 1, column..column + from.len(), from))
     }
 }
 
+/// Checked over whole Dockerfiles in the project pass, not line by line
+const ROOT_USER_RULE: &str = "ANTY-CFG-006";
+
+static USER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^USER\s+([^\s:]+)").unwrap());
+
+/// Whether a USER directive's user (or UID, or a variable) is root
+fn is_root(user: &str) -> bool {
+    matches!(user.trim_matches(['"', '\'']), "root" | "0")
+}
+
 impl SecurityAgent for ConfigIssuesAgent {
     fn name(&self) -> &str {
         "config-issues"
@@ -191,59 +359,78 @@ impl SecurityAgent for ConfigIssuesAgent {
 
     fn scan_file(&self, file: &ScannedFile) -> Vec<Finding> {
         let mut findings = Vec::new();
+        let comment = comments::comment_lines(&file.content, file.language);
 
         for (line_num, line) in file.content.lines().enumerate() {
-            let line_number = line_num + 1;
+            if file.out_of_time() {
+                break;
+            }
+            let line_number = file.line_offset + line_num + 1;
             let trimmed = line.trim();
 
             // Skip empty lines and pure comments
-            if trimmed.is_empty()
-                || trimmed.starts_with("//")
-                || trimmed.starts_with("#")
-                || trimmed.starts_with("<!--")
-            {
+            if trimmed.is_empty() || comment[line_num] {
                 continue;
             }
 
-            for pattern in &self.patterns {
-                if !pattern.applies_to(file) {
+            for idx in self.set.matches(line).iter() {
+                let pattern = &self.patterns[idx];
+                if pattern.rule_id == ROOT_USER_RULE || !pattern.applies_to(file) {
                     continue;
                 }
 
-                if pattern.pattern.is_match(line) {
-                    let finding = Finding {
-                        id: Finding::generate_id(
-                            pattern.rule_id,
-                            &file.rel_path,
-                            line_number,
-                        ),
-                        rule_id: pattern.rule_id.to_string(),
-                        severity: pattern.severity,
-                        confidence: pattern.confidence,
-                        agent: "config-issues".to_string(),
-                        title: pattern.title.to_string(),
-                        description: pattern.description.to_string(),
-                        file_path: file.rel_path.clone(),
-                        line_start: line_number,
-                        line_end: line_number,
-                        evidence: trimmed.to_string(),
-                        recommendation: pattern.recommendation.to_string(),
-                        cwe_id: Some(pattern.cwe_id.to_string()),
-                    };
-
-                    debug!(
-                        "Config issue: {} in {}:{}",
-                        pattern.rule_id,
-                        file.rel_path.display(),
-                        line_number
-                    );
-
-                    findings.push(finding);
-                    break;
+                if let Some(m) = pattern.pattern.find(line) {
+                    findings.push(Self::finding(pattern, file, line_number, m.start() + 1..m.end() + 1, trimmed));
                 }
             }
         }
 
         findings
     }
+
+    fn wants_project_file(&self, file: &ScannedFile) -> bool {
+        file.language == Some(Language::Dockerfile)
+    }
+
+    fn scan_project(&self, files: &[ScannedFile]) -> Vec<Finding> {
+        files.iter().filter_map(|file| self.runs_as_root(file)).collect()
+    }
+
+    fn languages(&self) -> Vec<Language> {
+        let mut languages = Vec::new();
+        for p in &self.patterns {
+            let covered: &[Language] = match &p.file_types {
+                FileTypeFilter::Any => &[],
+                FileTypeFilter::Languages(langs) => langs,
+                FileTypeFilter::ConfigFiles => &[Language::Yaml, Language::Json, Language::Toml, Language::Env],
+            };
+            for l in covered {
+                if !languages.contains(l) {
+                    languages.push(*l);
+                }
+            }
+        }
+        languages
+    }
+
+    fn rules(&self) -> Vec<RuleMeta> {
+        self.patterns
+            .iter()
+            .map(|p| RuleMeta {
+                id: p.rule_id.to_string(),
+                agent: "config-issues".to_string(),
+                title: p.title.to_string(),
+                description: p.description.to_string(),
+                severity: p.severity,
+                confidence: p.confidence,
+                recommendation: p.recommendation.to_string(),
+                cwe_id: Some(p.cwe_id.to_string()),
+                pattern: p.pattern.as_str().to_string(),
+                remediation: None,
+                docs_url: rules::docs_url(p.rule_id),
+                references: rules::references(p.cwe_id),
+                tags: p.tags.iter().map(|t| t.to_string()).collect(),
+            })
+            .collect()
+    }
 }
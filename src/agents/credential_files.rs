@@ -0,0 +1,204 @@
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::agents::pem;
+use crate::engine::comments;
+
+/// A file that holds credentials by design, whatever format they are in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialFile {
+    /// `id_rsa`, `id_ed25519`, ... (not the `.pub` half)
+    SshKey,
+    /// `.aws/credentials` or `.aws/config`
+    Aws,
+    /// `.kube/config` or a `*.kubeconfig`
+    KubeConfig,
+    /// gcloud application default credentials or a service account key
+    Gcloud,
+    /// `.pgpass` (`pgpass.conf` on Windows)
+    PgPass,
+}
+
+/// Private key file names `ssh-keygen` writes by default
+const SSH_KEY_NAMES: &[&str] = &["id_rsa", "id_dsa", "id_ecdsa", "id_ed25519", "id_ecdsa_sk", "id_ed25519_sk"];
+
+static GCLOUD_TYPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""type"\s*:\s*"(?:authorized_user|service_account)""#).unwrap());
+
+static AWS_SECRET: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^\s*aws_(?:secret_access_key|session_token)\s*=\s*(\S+)").unwrap());
+
+static KUBE_SECRET: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*(?:client-key-data|token|password)\s*:\s*["']?([^"'\s#]+)"#).unwrap());
+
+static GCLOUD_SECRET: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""(?:refresh_token|private_key)"\s*:\s*"((?:[^"\\]|\\.)+)""#).unwrap());
+
+/// One credential in a credential file
+pub struct Credential {
+    /// 0-based first and last line within the file
+    pub line: usize,
+    pub line_end: usize,
+    /// 1-based column of the value
+    pub column: usize,
+    /// The value as written, to redact from the evidence; None when the
+    /// line holds no part of it (a PEM header)
+    pub raw: Option<String>,
+    /// What identifies the credential across files: a key's base64 body,
+    /// the value itself otherwise
+    pub value: String,
+}
+
+impl CredentialFile {
+    /// Recognise a credential file by its path, and by content where the
+    /// name alone is too common (`credentials`, `config`, `*.json`)
+    pub fn detect(path: &Path, content: &str) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        let dir = path
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|d| d.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        let ssh_key = SSH_KEY_NAMES.iter().any(|key| {
+            name.strip_prefix(key)
+                .is_some_and(|rest| rest.is_empty() || (rest.starts_with(['.', '_', '-']) && !rest.ends_with(".pub")))
+        });
+        if ssh_key && (content.contains("PRIVATE KEY") || content.starts_with("PuTTY-User-Key-File")) {
+            return Some(CredentialFile::SshKey);
+        }
+        if dir == ".aws" && (name == "credentials" || name == "config") {
+            return Some(CredentialFile::Aws);
+        }
+        if (dir == ".kube" && name == "config") || name == "kubeconfig" || name.ends_with(".kubeconfig") {
+            return Some(CredentialFile::KubeConfig);
+        }
+        if name.ends_with(".json") && GCLOUD_TYPE.is_match(content) {
+            return Some(CredentialFile::Gcloud);
+        }
+        if name == ".pgpass" || name == "pgpass.conf" {
+            return Some(CredentialFile::PgPass);
+        }
+        None
+    }
+
+    /// What the file is, for the finding's description
+    pub fn describe(&self) -> &'static str {
+        match self {
+            CredentialFile::SshKey => "SSH private key file",
+            CredentialFile::Aws => "AWS CLI credentials file",
+            CredentialFile::KubeConfig => "kubeconfig with embedded user credentials",
+            CredentialFile::Gcloud => "Google Cloud credentials file",
+            CredentialFile::PgPass => "PostgreSQL password file",
+        }
+    }
+
+    /// The credentials in the file. An SSH key file is one credential
+    /// however it is encoded; other files give one per secret value.
+    pub fn credentials(&self, content: &str) -> Vec<Credential> {
+        let lines: Vec<&str> = content.lines().collect();
+        match self {
+            CredentialFile::SshKey => ssh_key(&lines).into_iter().collect(),
+            CredentialFile::Aws => matching_lines(&lines, &AWS_SECRET),
+            CredentialFile::KubeConfig => matching_lines(&lines, &KUBE_SECRET),
+            CredentialFile::Gcloud => matching_lines(&lines, &GCLOUD_SECRET)
+                .into_iter()
+                .map(|mut c| {
+                    // A service account's key is identified by its body, like any PEM key
+                    if let Some(key) = pem::key_block(&lines, c.line).filter(|k| !k.body.is_empty()) {
+                        c.value = key.body;
+                    }
+                    c
+                })
+                .collect(),
+            CredentialFile::PgPass => pgpass(&lines, &comments::hash_comment_lines(content)),
+        }
+    }
+}
+
+/// The whole key, from its first line to its last
+fn ssh_key(lines: &[&str]) -> Option<Credential> {
+    let first = lines.iter().position(|l| !l.trim().is_empty())?;
+    let last = lines.iter().rposition(|l| !l.trim().is_empty())?;
+    let header = lines.iter().position(|l| l.contains("PRIVATE KEY")).unwrap_or(first);
+    if let Some(key) = pem::key_block(lines, header).filter(|k| !k.body.is_empty()) {
+        return Some(Credential {
+            line: header,
+            line_end: key.end,
+            column: lines[header].len() - lines[header].trim_start().len() + 1,
+            raw: None,
+            value: key.body,
+        });
+    }
+    // PuTTY, SSH2, and other formats the PEM parser does not read
+    Some(Credential {
+        line: first,
+        line_end: last,
+        column: 1,
+        raw: None,
+        value: lines[first..=last].iter().map(|l| l.trim()).collect::<Vec<_>>().join("\n"),
+    })
+}
+
+/// Values captured by group 1 of `pattern`, one per line
+fn matching_lines(lines: &[&str], pattern: &Regex) -> Vec<Credential> {
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let value = pattern.captures(line)?.get(1)?;
+            Some(Credential {
+                line: i,
+                line_end: i,
+                column: value.start() + 1,
+                raw: Some(value.as_str().to_string()),
+                value: value.as_str().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Passwords of `host:port:database:user:password` lines; `\:` and `\\`
+/// are escapes within a field
+fn pgpass(lines: &[&str], comment: &[bool]) -> Vec<Credential> {
+    let mut credentials = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if comment[i] {
+            continue;
+        }
+        let mut fields = 0;
+        let mut escaped = false;
+        let mut start = None;
+        for (at, c) in line.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                ':' => {
+                    fields += 1;
+                    if fields == 4 {
+                        start = Some(at + 1);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let Some(start) = start else {
+            continue;
+        };
+        let password = line[start..].trim_end();
+        if password.is_empty() {
+            continue;
+        }
+        credentials.push(Credential {
+            line: i,
+            line_end: i,
+            column: start + 1,
+            raw: Some(password.to_string()),
+            value: password.replace("\\:", ":").replace("\\\\", "\\"),
+        });
+    }
+    credentials
+}
@@ -1,8 +1,10 @@
-use regex::Regex;
+use regex::{Regex, RegexSet};
 use tracing::debug;
 
-use crate::agents::{Language, ScannedFile, SecurityAgent};
+use crate::agents::{self, Language, ScannedFile, SecurityAgent};
+use crate::engine::comments;
 use crate::report::finding::{Confidence, Finding, Severity};
+use crate::rules::{self, RuleMeta};
 
 /// Pattern for a dangerous function call
 struct DangerousPattern {
@@ -14,6 +16,7 @@ struct DangerousPattern {
     confidence: Confidence,
     recommendation: &'static str,
     cwe_id: &'static str,
+    tags: &'static [&'static str],
     languages: &'static [Language],
 }
 
@@ -34,6 +37,8 @@ impl DangerousPattern {
 ///
 /// Level A agent — regex/string-based, no AST required.
 pub struct DangerousFunctionsAgent {
+    /// Prefilter over all patterns, in the same order
+    set: RegexSet,
     patterns: Vec<DangerousPattern>,
 }
 
@@ -50,6 +55,7 @@ impl DangerousFunctionsAgent {
                 confidence: Confidence::Medium,
                 recommendation: "Avoid eval(). Use JSON.parse() for data, or safer alternatives for dynamic execution.",
                 cwe_id: "CWE-95",
+                tags: &["injection"],
                 languages: &[Language::JavaScript, Language::TypeScript, Language::Python],
             },
             DangerousPattern {
@@ -61,6 +67,7 @@ impl DangerousFunctionsAgent {
                 confidence: Confidence::Medium,
                 recommendation: "Use parameterized command execution (e.g., subprocess.run with a list of args, execFile instead of exec).",
                 cwe_id: "CWE-78",
+                tags: &["injection"],
                 languages: &[Language::JavaScript, Language::TypeScript, Language::Python],
             },
 
@@ -74,6 +81,7 @@ impl DangerousFunctionsAgent {
                 confidence: Confidence::Medium,
                 recommendation: "Use parameterized queries or prepared statements instead of string concatenation.",
                 cwe_id: "CWE-89",
+                tags: &["injection"],
                 languages: &[],
             },
             DangerousPattern {
@@ -85,6 +93,7 @@ impl DangerousFunctionsAgent {
                 confidence: Confidence::Medium,
                 recommendation: "Use parameterized queries. Template literals with user input are as dangerous as string concatenation.",
                 cwe_id: "CWE-89",
+                tags: &["injection"],
                 languages: &[Language::JavaScript, Language::TypeScript],
             },
             DangerousPattern {
@@ -96,6 +105,7 @@ impl DangerousFunctionsAgent {
                 confidence: Confidence::Medium,
                 recommendation: "Use parameterized queries with cursor.execute(sql, params) instead of f-strings.",
                 cwe_id: "CWE-89",
+                tags: &["injection"],
                 languages: &[Language::Python],
             },
 
@@ -109,6 +119,7 @@ impl DangerousFunctionsAgent {
                 confidence: Confidence::High,
                 recommendation: "Avoid pickle for untrusted data. Use JSON or a safe serialization format.",
                 cwe_id: "CWE-502",
+                tags: &["deserialization", "low-noise"],
                 languages: &[Language::Python],
             },
             DangerousPattern {
@@ -120,6 +131,7 @@ impl DangerousFunctionsAgent {
                 confidence: Confidence::Medium,
                 recommendation: "Use yaml.safe_load() or yaml.load(data, Loader=yaml.SafeLoader).",
                 cwe_id: "CWE-502",
+                tags: &["deserialization"],
                 languages: &[Language::Python],
             },
 
@@ -133,6 +145,7 @@ impl DangerousFunctionsAgent {
                 confidence: Confidence::Medium,
                 recommendation: "Use textContent for text, or sanitize HTML with a library like DOMPurify.",
                 cwe_id: "CWE-79",
+                tags: &["injection"],
                 languages: &[Language::JavaScript, Language::TypeScript],
             },
             DangerousPattern {
@@ -144,6 +157,7 @@ impl DangerousFunctionsAgent {
                 confidence: Confidence::Medium,
                 recommendation: "Sanitize the HTML content with DOMPurify before passing it to dangerouslySetInnerHTML.",
                 cwe_id: "CWE-79",
+                tags: &["injection"],
                 languages: &[Language::JavaScript, Language::TypeScript],
             },
 
@@ -157,6 +171,7 @@ impl DangerousFunctionsAgent {
                 confidence: Confidence::Medium,
                 recommendation: "Use SHA-256 or better. For password hashing, use bcrypt, scrypt, or Argon2.",
                 cwe_id: "CWE-328",
+                tags: &["crypto"],
                 languages: &[],
             },
             DangerousPattern {
@@ -168,6 +183,7 @@ impl DangerousFunctionsAgent {
                 confidence: Confidence::Medium,
                 recommendation: "Use SHA-256 or SHA-3. For password hashing, use bcrypt, scrypt, or Argon2.",
                 cwe_id: "CWE-328",
+                tags: &["crypto"],
                 languages: &[],
             },
 
@@ -181,11 +197,115 @@ impl DangerousFunctionsAgent {
                 confidence: Confidence::High,
                 recommendation: "Use subprocess.run() with a list of arguments and shell=False (default).",
                 cwe_id: "CWE-78",
+                tags: &["injection", "low-noise"],
                 languages: &[Language::Python],
             },
+
+            // ── SQL scripts and migrations ───────────────────
+            DangerousPattern {
+                rule_id: "ANTY-DNG-013",
+                title: "GRANT ALL PRIVILEGES",
+                description: "Grants every privilege on an object or database, far more than an application account needs",
+                pattern: Regex::new(r"(?i)\bGRANT\s+ALL\b").unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::High,
+                recommendation: "Grant only the privileges the account uses (e.g. SELECT, INSERT, UPDATE on specific tables).",
+                cwe_id: "CWE-269",
+                tags: &["permissions", "low-noise"],
+                languages: &[Language::Sql],
+            },
+            DangerousPattern {
+                rule_id: "ANTY-DNG-014",
+                title: "Use of xp_cmdshell",
+                description: "xp_cmdshell runs operating system commands from SQL Server, turning any SQL injection into command execution",
+                pattern: Regex::new(r"(?i)\bxp_cmdshell\b").unwrap(),
+                severity: Severity::High,
+                confidence: Confidence::High,
+                recommendation: "Keep xp_cmdshell disabled. Move OS-level work into a separate service or SQL Agent job with a restricted proxy account.",
+                cwe_id: "CWE-78",
+                tags: &["injection", "low-noise"],
+                languages: &[Language::Sql],
+            },
+            DangerousPattern {
+                rule_id: "ANTY-DNG-015",
+                title: "Dynamic SQL Executed with EXEC()",
+                description: "A SQL string held in a variable is executed with EXEC(); if any part of it comes from input, this is SQL injection",
+                pattern: Regex::new(r"(?i)\bEXEC(UTE)?\s*\(\s*(@\w+|N?'[^']*'\s*\+)").unwrap(),
+                severity: Severity::High,
+                confidence: Confidence::Medium,
+                recommendation: "Use sp_executesql with parameters instead of concatenating values into the statement, and QUOTENAME() for identifiers.",
+                cwe_id: "CWE-89",
+                tags: &["injection"],
+                languages: &[Language::Sql],
+            },
+
+            // ── PowerShell ───────────────────────────────────
+            DangerousPattern {
+                rule_id: "ANTY-DNG-016",
+                title: "Invoke-Expression on Downloaded Content",
+                description: "Code fetched from the network is executed directly, so whoever controls the URL (or the connection) controls the machine",
+                pattern: Regex::new(r"(?i)\b(Invoke-Expression|iex)\b.*\b(DownloadString|Invoke-WebRequest|iwr|Invoke-RestMethod|irm)\b|\b(DownloadString|Invoke-WebRequest|iwr|Invoke-RestMethod|irm)\b.*\|\s*(Invoke-Expression|iex)\b").unwrap(),
+                severity: Severity::High,
+                confidence: Confidence::High,
+                recommendation: "Download to a file, verify its signature or hash (Get-AuthenticodeSignature, Get-FileHash), then run it.",
+                cwe_id: "CWE-494",
+                tags: &["supply-chain", "low-noise"],
+                languages: &[Language::PowerShell],
+            },
+            DangerousPattern {
+                rule_id: "ANTY-DNG-017",
+                title: "PowerShell Execution Policy Bypass",
+                description: "The execution policy is set to Bypass or Unrestricted, so unsigned and downloaded scripts run without prompting",
+                pattern: Regex::new(r"(?i)(-(ExecutionPolicy|ep|exec)\s+|Set-ExecutionPolicy\s+(-ExecutionPolicy\s+)?)(Bypass|Unrestricted)\b").unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::High,
+                recommendation: "Sign the scripts and use the RemoteSigned or AllSigned policy instead of bypassing it.",
+                cwe_id: "CWE-693",
+                tags: &["misconfig", "low-noise"],
+                languages: &[Language::PowerShell, Language::Yaml],
+            },
+
+            // ── C# / .NET ────────────────────────────────────
+            DangerousPattern {
+                rule_id: "ANTY-DNG-018",
+                title: "Insecure .NET Deserialization",
+                description: "BinaryFormatter and related formatters can run arbitrary code while deserializing untrusted data",
+                pattern: Regex::new(r"\bnew\s+(BinaryFormatter|SoapFormatter|NetDataContractSerializer|LosFormatter|ObjectStateFormatter)\s*\(|\b(BinaryFormatter|SoapFormatter|NetDataContractSerializer|LosFormatter|ObjectStateFormatter)\b.*\.Deserialize\s*\(").unwrap(),
+                severity: Severity::High,
+                confidence: Confidence::High,
+                recommendation: "Use System.Text.Json or XmlSerializer with known types. BinaryFormatter is obsolete and cannot be made safe.",
+                cwe_id: "CWE-502",
+                tags: &["deserialization", "low-noise"],
+                languages: &[Language::CSharp],
+            },
+            DangerousPattern {
+                rule_id: "ANTY-DNG-019",
+                title: "Process Started with Concatenated Arguments",
+                description: "A process command line is built by concatenation or interpolation, so input can inject extra arguments or commands",
+                pattern: Regex::new(r#"(\bProcess\.Start|\bnew\s+ProcessStartInfo)\s*\([^;]*(\+|(\$@?|@\$)"[^"]*\{)|\.Arguments\s*=\s*[^;]*(\+|(\$@?|@\$)"[^"]*\{)"#).unwrap(),
+                severity: Severity::High,
+                confidence: Confidence::Medium,
+                recommendation: "Pass arguments through ProcessStartInfo.ArgumentList, validate them against an allowlist, and never run them through cmd.exe /c.",
+                cwe_id: "CWE-78",
+                tags: &["injection"],
+                languages: &[Language::CSharp],
+            },
+            DangerousPattern {
+                rule_id: "ANTY-DNG-020",
+                title: "SQL Command Built with String Interpolation",
+                description: "Values are interpolated into the text of a SqlCommand (or a raw EF Core query), which allows SQL injection",
+                pattern: Regex::new(r#"(\bnew\s+\w*(Command|DataAdapter)\s*\(|\.CommandText\s*=|\b(FromSqlRaw|ExecuteSqlRaw|ExecuteSqlRawAsync|SqlQueryRaw)\s*\()\s*((\$@?|@\$)"[^"]*\{|string\.Format\s*\()"#).unwrap(),
+                severity: Severity::High,
+                confidence: Confidence::High,
+                recommendation: "Use parameters (@id with cmd.Parameters.AddWithValue) or EF Core's FromSqlInterpolated / FromSql, which parameterize interpolated values.",
+                cwe_id: "CWE-89",
+                tags: &["injection", "low-noise"],
+                languages: &[Language::CSharp],
+            },
         ];
 
-        DangerousFunctionsAgent { patterns }
+        let set = agents::pattern_set(patterns.iter().map(|p| p.pattern.as_str()));
+        DangerousFunctionsAgent { set, patterns }
     }
 }
 
@@ -200,22 +320,27 @@ impl SecurityAgent for DangerousFunctionsAgent {
 
     fn scan_file(&self, file: &ScannedFile) -> Vec<Finding> {
         let mut findings = Vec::new();
+        // Patterns match the code only, not calls mentioned in comments
+        let code = comments::mask(&file.content, file.language);
 
-        for (line_num, line) in file.content.lines().enumerate() {
-            let line_number = line_num + 1;
+        for (line_num, (line, code_line)) in file.content.lines().zip(code.lines()).enumerate() {
+            if file.out_of_time() {
+                break;
+            }
+            let line_number = file.line_offset + line_num + 1;
 
-            // Skip comment lines
             let trimmed = line.trim();
-            if trimmed.starts_with("//") || trimmed.starts_with("#") || trimmed.starts_with("*") {
+            if code_line.trim().is_empty() {
                 continue;
             }
 
-            for pattern in &self.patterns {
+            for idx in self.set.matches(code_line).iter() {
+                let pattern = &self.patterns[idx];
                 if !pattern.applies_to(file.language) {
                     continue;
                 }
 
-                if pattern.pattern.is_match(line) {
+                if let Some(m) = pattern.pattern.find(code_line) {
                     let finding = Finding {
                         id: Finding::generate_id(
                             pattern.rule_id,
@@ -231,9 +356,24 @@ impl SecurityAgent for DangerousFunctionsAgent {
                         file_path: file.rel_path.clone(),
                         line_start: line_number,
                         line_end: line_number,
+                        column: m.start() + 1,
+                        column_end: m.end() + 1,
                         evidence: trimmed.to_string(),
                         recommendation: pattern.recommendation.to_string(),
                         cwe_id: Some(pattern.cwe_id.to_string()),
+                        remediation: None,
+                        secret_hash: None,
+                        related_rules: Vec::new(),
+                        additional_locations: Vec::new(),
+                        owners: Vec::new(),
+                        blame: None,
+                        context: None,
+                        triage: None,
+                        fix: None,
+                        environment: None,
+                        score: None,
+                        docs_url: Some(rules::docs_url(pattern.rule_id)),
+                        references: rules::references(pattern.cwe_id),
                     };
 
                     debug!(
@@ -244,11 +384,41 @@ impl SecurityAgent for DangerousFunctionsAgent {
                     );
 
                     findings.push(finding);
-                    break;
                 }
             }
         }
 
         findings
     }
+
+    fn languages(&self) -> Vec<Language> {
+        let mut languages = Vec::new();
+        for l in self.patterns.iter().flat_map(|p| p.languages) {
+            if !languages.contains(l) {
+                languages.push(*l);
+            }
+        }
+        languages
+    }
+
+    fn rules(&self) -> Vec<RuleMeta> {
+        self.patterns
+            .iter()
+            .map(|p| RuleMeta {
+                id: p.rule_id.to_string(),
+                agent: "dangerous-functions".to_string(),
+                title: p.title.to_string(),
+                description: p.description.to_string(),
+                severity: p.severity,
+                confidence: p.confidence,
+                recommendation: p.recommendation.to_string(),
+                cwe_id: Some(p.cwe_id.to_string()),
+                pattern: p.pattern.as_str().to_string(),
+                remediation: None,
+                docs_url: rules::docs_url(p.rule_id),
+                references: rules::references(p.cwe_id),
+                tags: p.tags.iter().map(|t| t.to_string()).collect(),
+            })
+            .collect()
+    }
 }
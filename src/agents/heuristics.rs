@@ -0,0 +1,87 @@
+use std::path::Path;
+
+/// Substrings that mark a value as a stand-in rather than a real credential
+const PLACEHOLDER_WORDS: &[&str] = &[
+    "changeme",
+    "change_me",
+    "change-me",
+    "placeholder",
+    "example",
+    "sample",
+    "dummy",
+    "your_",
+    "your-",
+    "yourapikey",
+    "xxxx",
+    "redacted",
+    "replace",
+    "insert_",
+    "todo",
+    "fixme",
+];
+
+/// Directory names that hold sample code rather than production code
+const SAMPLE_DIRS: &[&str] = &[
+    "example",
+    "examples",
+    "fixture",
+    "fixtures",
+    "sample",
+    "samples",
+    "testdata",
+];
+
+/// Pull the assigned value out of a match like `password = "hunter22"`:
+/// the last quoted string if there is one, else whatever follows `=`/`:`
+/// (keeping base64 padding such as the `==` ending an Azure key).
+pub fn secret_value(matched: &str) -> &str {
+    let quoted = matched
+        .rfind(['"', '\''])
+        .and_then(|end| {
+            let quote = matched[end..].chars().next()?;
+            let start = matched[..end].rfind(quote)?;
+            Some(&matched[start + 1..end])
+        });
+    match quoted {
+        Some(value) => value,
+        None => {
+            let unpadded = matched.trim_end_matches('=');
+            let start = unpadded.rfind(['=', ':']).map_or(0, |i| i + 1);
+            matched[start..].trim()
+        }
+    }
+}
+
+/// Whether a matched value is clearly a placeholder, not a real secret
+pub fn is_placeholder(value: &str) -> bool {
+    let value = value.trim();
+    let Some(first) = value.chars().next() else {
+        return true;
+    };
+
+    // "xxxxxxxx", "********", "00000000"
+    if value.chars().all(|c| c == first) {
+        return true;
+    }
+
+    // Template references: <api-key>, ${API_KEY}, {{ api_key }}
+    if (value.starts_with('<') && value.ends_with('>'))
+        || value.starts_with("${")
+        || value.starts_with("{{")
+    {
+        return true;
+    }
+
+    let lower = value.to_lowercase();
+    PLACEHOLDER_WORDS.iter().any(|w| lower.contains(w))
+}
+
+/// Whether `path` lives under an examples/fixtures-style directory
+pub fn is_sample_path(path: &Path) -> bool {
+    path.parent().is_some_and(|dir| {
+        dir.components().any(|c| {
+            let name = c.as_os_str().to_string_lossy().to_lowercase();
+            SAMPLE_DIRS.contains(&name.as_str())
+        })
+    })
+}
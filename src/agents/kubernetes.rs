@@ -0,0 +1,168 @@
+use std::path::Path;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+/// A value stored in a Kubernetes Secret or a Helm values file
+pub struct SecretEntry {
+    /// 0-based line within the file
+    pub line: usize,
+    /// 1-based column of the value
+    pub column: usize,
+    /// The value as written in the file
+    pub raw: String,
+    /// The value to check: decoded for `data:`, as written otherwise
+    pub value: String,
+    /// Whether `value` was decoded from base64
+    pub decoded: bool,
+}
+
+/// Values under `data:` (decoded) and `stringData:` of every `kind: Secret`
+/// document in a manifest. Line-based so findings keep their line numbers;
+/// block scalars (`|`, `>`) are skipped.
+pub fn secret_entries(content: &str) -> Vec<SecretEntry> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut entries = Vec::new();
+    let mut start = 0;
+    while start < lines.len() {
+        let end = lines[start..]
+            .iter()
+            .position(|l| l.trim_end() == "---")
+            .map_or(lines.len(), |i| start + i);
+        let document = &lines[start..end];
+        if document.iter().any(|l| is_secret_kind(l)) {
+            collect_entries(document, start, &mut entries);
+        }
+        start = end + 1;
+    }
+    entries
+}
+
+fn is_secret_kind(line: &str) -> bool {
+    line.strip_prefix("kind:")
+        .is_some_and(|kind| kind.trim().trim_matches(['"', '\'']) == "Secret")
+}
+
+fn collect_entries(document: &[&str], offset: usize, entries: &mut Vec<SecretEntry>) {
+    // (base64-encoded, indent of the block's keys once known)
+    let mut block: Option<(bool, Option<usize>)> = None;
+    for (i, line) in document.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if indent == 0 {
+            block = match trimmed {
+                "data:" => Some((true, None)),
+                "stringData:" => Some((false, None)),
+                _ => None,
+            };
+            continue;
+        }
+        let Some((encoded, ref mut key_indent)) = block else {
+            continue;
+        };
+        if *key_indent.get_or_insert(indent) != indent {
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let raw = unquote(value.trim());
+        if raw.is_empty() || raw.starts_with(['|', '>']) {
+            continue;
+        }
+        let value = if encoded {
+            match STANDARD.decode(raw).ok().and_then(|b| String::from_utf8(b).ok()) {
+                Some(plain) => plain,
+                None => continue,
+            }
+        } else {
+            raw.to_string()
+        };
+        let column = indent + key.len() + 1 + value_offset(&trimmed[key.len() + 1..], raw) + 1;
+        entries.push(SecretEntry {
+            line: offset + i,
+            column,
+            raw: raw.to_string(),
+            value,
+            decoded: encoded,
+        });
+    }
+}
+
+/// Whether a file is a Helm chart's values file (values.yaml, values-prod.yaml, ...)
+pub fn is_helm_values(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|name| {
+            let name = name.to_lowercase();
+            name.starts_with("values") && (name.ends_with(".yaml") || name.ends_with(".yml"))
+        })
+}
+
+/// Key names that point at a secret or configure one rather than hold it:
+/// `existingSecret`, `passwordSecretRef`, `tokenFile`, `secretName`, ...
+const REFERENCE_KEY_PREFIXES: &[&str] = &["existing"];
+const REFERENCE_KEY_SUFFIXES: &[&str] = &[
+    "name", "ref", "file", "path", "enabled", "passwordkey", "tokenkey", "ttl", "expiry", "timeout", "length", "type",
+];
+
+/// Literal values of keys named like credentials in a Helm values file.
+/// Templated (`{{ }}`, `${}`), empty, boolean, and numeric values are skipped.
+pub fn helm_values(content: &str, key_pattern: &regex::Regex) -> Vec<SecretEntry> {
+    let mut entries = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start().trim_start_matches("- ");
+        if trimmed.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let lower = key.trim().to_lowercase();
+        if !key_pattern.is_match(&lower)
+            || REFERENCE_KEY_PREFIXES.iter().any(|p| lower.starts_with(p))
+            || REFERENCE_KEY_SUFFIXES.iter().any(|s| lower.ends_with(s))
+        {
+            continue;
+        }
+        // Trailing comments are not part of the value
+        let value = value.split(" #").next().unwrap_or_default().trim();
+        let raw = unquote(value);
+        if raw.len() < 4
+            || raw.starts_with(['|', '>', '{', '[', '&', '*'])
+            || raw.contains("{{")
+            || raw.contains("${")
+            || matches!(raw.to_lowercase().as_str(), "true" | "false" | "null" | "none")
+            || raw.parse::<f64>().is_ok()
+        {
+            continue;
+        }
+        let column = line.len() - trimmed.len() + key.len() + 1 + value_offset(&trimmed[key.len() + 1..], raw) + 1;
+        entries.push(SecretEntry {
+            line: i,
+            column,
+            raw: raw.to_string(),
+            value: raw.to_string(),
+            decoded: false,
+        });
+    }
+    entries
+}
+
+fn unquote(value: &str) -> &str {
+    let quoted = value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"')) || (value.starts_with('\'') && value.ends_with('\'')));
+    if quoted {
+        &value[1..value.len() - 1]
+    } else {
+        value
+    }
+}
+
+/// Offset of `raw` within the text after a key's colon
+fn value_offset(after_colon: &str, raw: &str) -> usize {
+    after_colon.find(raw).unwrap_or(0)
+}
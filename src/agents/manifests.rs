@@ -0,0 +1,591 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+use crate::agents::{Language, ScannedFile, SecurityAgent};
+use crate::config;
+use crate::engine::comments;
+use crate::report::finding::{Confidence, Finding, Severity};
+use crate::rules::{self, RuleMeta};
+
+/// Which package manifest a file is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Manifest {
+    /// Cargo.toml
+    Cargo,
+    /// package.json
+    Npm,
+    /// pyproject.toml, PEP 621 and Poetry tables
+    PyProject,
+    /// build.rs, which Cargo compiles and runs before the crate
+    BuildScript,
+}
+
+impl Manifest {
+    fn detect(file: &ScannedFile) -> Option<Self> {
+        let path = file.name_path();
+        match path.file_name()?.to_string_lossy().as_ref() {
+            "Cargo.toml" => Some(Manifest::Cargo),
+            "package.json" => Some(Manifest::Npm),
+            "pyproject.toml" => Some(Manifest::PyProject),
+            "build.rs" => Some(Manifest::BuildScript),
+            _ => None,
+        }
+    }
+}
+
+/// A dependency declared in a manifest
+struct Dependency {
+    name: String,
+    /// 0-based line and 1-based column of what the dependency resolves
+    /// from: its git URL, path, or version
+    line: usize,
+    column: usize,
+    source: Source,
+}
+
+enum Source {
+    Git(GitRef),
+    Path(String),
+    /// An exact version pin; ranges are left to the lockfile
+    Exact(String),
+}
+
+/// What a git dependency is fetched at
+#[derive(Debug, PartialEq, Eq)]
+enum GitRef {
+    /// A commit, or a tag that names a version
+    Pinned,
+    Branch,
+    /// No ref: whatever the default branch points to
+    Default,
+}
+
+/// Versions pulled from their registry (`yanked.toml`), by registry and
+/// normalized package name
+#[derive(Debug, Default, Deserialize)]
+struct YankedVersions {
+    #[serde(default)]
+    crates: HashMap<String, HashSet<String>>,
+    #[serde(default)]
+    npm: HashMap<String, HashSet<String>>,
+    #[serde(default)]
+    pypi: HashMap<String, HashSet<String>>,
+}
+
+const BUILTIN_YANKED: &str = include_str!("yanked.toml");
+
+impl YankedVersions {
+    /// The list built into Anty, plus `$ANTY_HOME/yanked.toml` when present
+    fn load() -> Self {
+        let mut yanked: YankedVersions = toml::from_str(BUILTIN_YANKED).expect("built-in yanked.toml is valid");
+        let Some(path) = config::anty_home().map(|home| home.join("yanked.toml")).filter(|p| p.is_file()) else {
+            return yanked;
+        };
+        let extra = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| toml::from_str::<YankedVersions>(&content).map_err(|e| e.to_string()));
+        match extra {
+            Ok(extra) => {
+                info!("Loaded yanked versions from {}", path.display());
+                for (registry, packages) in [
+                    (&mut yanked.crates, extra.crates),
+                    (&mut yanked.npm, extra.npm),
+                    (&mut yanked.pypi, extra.pypi),
+                ] {
+                    for (name, versions) in packages {
+                        registry.entry(name).or_default().extend(versions);
+                    }
+                }
+            }
+            Err(e) => warn!("Could not load {}, using the built-in list: {}", path.display(), e),
+        }
+        yanked
+    }
+
+    fn contains(&self, manifest: Manifest, name: &str, version: &str) -> bool {
+        let (registry, name) = match manifest {
+            // crates.io and PyPI treat `-` and `_` (PyPI also `.`) as one
+            Manifest::Cargo => (&self.crates, name.to_lowercase().replace('_', "-")),
+            Manifest::PyProject => (&self.pypi, name.to_lowercase().replace(['_', '.'], "-")),
+            Manifest::Npm => (&self.npm, name.to_string()),
+            Manifest::BuildScript => return false,
+        };
+        registry.get(&name).is_some_and(|versions| versions.contains(version))
+    }
+}
+
+struct ManifestPattern {
+    rule_id: &'static str,
+    title: &'static str,
+    description: &'static str,
+    /// Documents what the rule looks for; the manifest parsers decide
+    pattern: Regex,
+    severity: Severity,
+    confidence: Confidence,
+    recommendation: &'static str,
+    cwe_id: &'static str,
+    tags: &'static [&'static str],
+}
+
+/// Checks package manifests themselves (Cargo.toml, package.json,
+/// pyproject.toml) rather than lockfiles: git dependencies that follow a
+/// branch, path dependencies outside the repository, code that runs at
+/// build or install time, and exact pins of versions the registry pulled.
+///
+/// Level A agent — line-based over TOML tables, with package.json read as
+/// JSON, since one dependency's source, ref, and version are separate keys.
+pub struct ManifestsAgent {
+    patterns: Vec<ManifestPattern>,
+    yanked: YankedVersions,
+}
+
+/// npm lifecycle scripts that run when the package is installed
+const INSTALL_HOOKS: &[&str] = &["preinstall", "install", "postinstall"];
+
+/// package.json objects that list dependencies
+const NPM_SECTIONS: &[&str] = &["dependencies", "devDependencies", "optionalDependencies", "peerDependencies"];
+
+/// pyproject.toml tables whose strings are PEP 508 requirements
+const PEP_508_TABLES: &[&str] = &["project", "project.optional-dependencies", "dependency-groups", "build-system"];
+
+static TOML_HEADER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*\[\[?\s*([^\]]+?)\s*\]\]?\s*(?:#.*)?$").unwrap());
+
+static TOML_ENTRY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*(?:"([^"]+)"|'([^']+)'|([A-Za-z0-9_.-]+))\s*=\s*(.*)$"#).unwrap());
+
+/// A string key of a dependency table or inline table
+static TOML_KEY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\b(git|branch|rev|tag|path|version)\s*=\s*["']([^"']*)["']"#).unwrap());
+
+static QUOTED: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#""([^"\\]*)"|'([^']*)'"#).unwrap());
+
+/// `name[extras] @ url` or `name[extras] == version`, before any marker
+static PEP_508: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*([A-Za-z0-9][A-Za-z0-9._-]*)\s*(?:\[[^\]]*\])?\s*(?:@\s*(\S+)|===?\s*([0-9][^\s,;]*)\s*(?:;.*)?$)").unwrap()
+});
+
+static NPM_GIT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:git\+[a-z]+://|git://|github:|gitlab:|bitbucket:|gist:|https?://\S+\.git(?:#|$)|[A-Za-z0-9][\w.-]*/[\w.-]+(?:#|$))").unwrap()
+});
+
+static VERSION_TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^v?\d+(?:\.\d+)*(?:[-+.][0-9A-Za-z.]+)?$").unwrap());
+
+impl ManifestsAgent {
+    pub fn new() -> Self {
+        let patterns = vec![
+            ManifestPattern {
+                rule_id: "ANTY-DEP-001",
+                title: "Git Dependency Tracks a Branch",
+                description: "A dependency is fetched from git at a branch (or the default branch), so whoever can push to it changes the code every fresh build pulls in",
+                pattern: Regex::new(r#"\bgit\s*=\s*"[^"]+"|"(?:git\+[a-z]+://|github:|[\w.-]+/[\w.-]+#)[^"]*"|\bgit\+[a-z]+://\S+"#).unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::High,
+                recommendation: "Pin the dependency to a commit (`rev = \"<sha>\"` in Cargo or Poetry, `#<sha>` in npm, `@<sha>` in a PEP 508 URL) or a release tag, or depend on a published version.",
+                cwe_id: "CWE-829",
+                tags: &["supply-chain", "dependencies", "low-noise"],
+            },
+            ManifestPattern {
+                rule_id: "ANTY-DEP-002",
+                title: "Path Dependency Outside the Repository",
+                description: "A dependency is read from a path outside the scanned tree, so the build depends on whatever sits there on the machine that runs it",
+                pattern: Regex::new(r#"\bpath\s*=\s*"(?:\.\./|/|~)[^"]*"|"(?:file|link):[^"]*"|\bfile:/{2,3}\S+"#).unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::Medium,
+                recommendation: "Vendor the dependency into the repository or publish it to a registry you control, so every checkout builds the same code.",
+                cwe_id: "CWE-829",
+                tags: &["supply-chain", "dependencies"],
+            },
+            ManifestPattern {
+                rule_id: "ANTY-DEP-003",
+                title: "Code Runs at Build or Install Time",
+                description: "A Cargo build script or an npm install hook runs with the user's permissions as soon as the project is built or installed, before any of its code is reviewed",
+                pattern: Regex::new(r#"^build\.rs$|"(?:preinstall|install|postinstall)"\s*:"#).unwrap(),
+                severity: Severity::Low,
+                confidence: Confidence::Medium,
+                recommendation: "Review what the script does before building or installing untrusted code; install with `npm install --ignore-scripts` and build in a sandbox.",
+                cwe_id: "CWE-829",
+                tags: &["supply-chain", "dependencies", "untrusted"],
+            },
+            ManifestPattern {
+                rule_id: "ANTY-DEP-004",
+                title: "Pinned Version Pulled From Registry",
+                description: "A dependency is pinned to a version its registry yanked or removed, typically for a serious bug or a compromised release",
+                pattern: Regex::new(r#"=\s*"=\d[^"]*"|"=?v?\d+\.\d+\.\d+[^"]*"|===?\s*\d\S*"#).unwrap(),
+                severity: Severity::High,
+                confidence: Confidence::High,
+                recommendation: "Move to a version the registry still serves, and check the registry's advisory for why this one was pulled.",
+                cwe_id: "CWE-1104",
+                tags: &["supply-chain", "dependencies", "low-noise"],
+            },
+        ];
+
+        ManifestsAgent { patterns, yanked: YankedVersions::load() }
+    }
+
+    fn pattern(&self, rule_id: &str) -> &ManifestPattern {
+        self.patterns.iter().find(|p| p.rule_id == rule_id).expect("known rule")
+    }
+
+    fn finding(&self, rule_id: &str, file: &ScannedFile, line: usize, column: usize) -> Finding {
+        let pattern = self.pattern(rule_id);
+        let line_number = file.line_offset + line + 1;
+        debug!("Manifest: {} in {}:{}", rule_id, file.rel_path.display(), line_number);
+        Finding {
+            id: Finding::generate_id(rule_id, &file.rel_path, line_number),
+            rule_id: rule_id.to_string(),
+            severity: pattern.severity,
+            confidence: pattern.confidence,
+            agent: "manifests".to_string(),
+            title: pattern.title.to_string(),
+            description: pattern.description.to_string(),
+            file_path: file.rel_path.clone(),
+            line_start: line_number,
+            line_end: line_number,
+            column,
+            column_end: column,
+            evidence: file.content.lines().nth(line).unwrap_or("").trim().to_string(),
+            recommendation: pattern.recommendation.to_string(),
+            cwe_id: Some(pattern.cwe_id.to_string()),
+            remediation: None,
+            secret_hash: None,
+            related_rules: Vec::new(),
+            additional_locations: Vec::new(),
+            owners: Vec::new(),
+            blame: None,
+            context: None,
+            triage: None,
+            fix: None,
+            environment: None,
+            score: None,
+            docs_url: Some(rules::docs_url(rule_id)),
+            references: rules::references(pattern.cwe_id),
+        }
+    }
+}
+
+/// The dependencies of a Cargo.toml or pyproject.toml: entries of
+/// `[*dependencies]` and `[patch.*]` tables (Cargo and Poetry share the
+/// `git`/`branch`/`rev`/`tag`/`path` keys), and in pyproject.toml the
+/// PEP 508 strings of `[project]` and friends
+fn toml_dependencies(content: &str, manifest: Manifest) -> Vec<Dependency> {
+    let mut deps = Vec::new();
+    let mut header = String::new();
+    // Keys of a `[dependencies.name]` table, settled at its end
+    let mut table: Option<(String, Vec<Key>)> = None;
+    let comment = comments::comment_lines(content, Some(Language::Toml));
+
+    for (i, line) in content.lines().enumerate() {
+        if let Some(caps) = TOML_HEADER.captures(line) {
+            if let Some((name, keys)) = table.take() {
+                deps.extend(table_dependency(name, &keys, manifest));
+            }
+            header = caps[1].replace(['"', '\'', ' '], "");
+            if let Some((parent, name)) = header.rsplit_once('.') {
+                if is_dependency_table(parent) {
+                    table = Some((name.to_string(), Vec::new()));
+                }
+            }
+            continue;
+        }
+        if comment[i] {
+            continue;
+        }
+        if let Some((_, ref mut keys)) = table {
+            keys.extend(line_keys(line, i));
+            continue;
+        }
+        if is_dependency_table(&header) {
+            let Some(caps) = TOML_ENTRY.captures(line) else {
+                continue;
+            };
+            let name = caps.get(1).or(caps.get(2)).or(caps.get(3)).unwrap().as_str();
+            let value = caps.get(4).unwrap();
+            if value.as_str().starts_with('{') {
+                deps.extend(table_dependency(name.to_string(), &line_keys(line, i), manifest));
+            } else if let Some(version) = QUOTED.captures(value.as_str()) {
+                let version = version.get(1).or(version.get(2)).unwrap();
+                if let Some(exact) = exact_version(manifest, version.as_str()).filter(|_| name != "python") {
+                    deps.push(Dependency {
+                        name: name.to_string(),
+                        line: i,
+                        column: value.start() + version.start() + 1,
+                        source: Source::Exact(exact),
+                    });
+                }
+            }
+        } else if manifest == Manifest::PyProject && PEP_508_TABLES.contains(&header.as_str()) {
+            for string in QUOTED.captures_iter(line) {
+                let string = string.get(1).or(string.get(2)).unwrap();
+                if let Some((name, source)) = pep_508(string.as_str()) {
+                    deps.push(Dependency { name, line: i, column: string.start() + 1, source });
+                }
+            }
+        }
+    }
+    if let Some((name, keys)) = table {
+        deps.extend(table_dependency(name, &keys, manifest));
+    }
+    deps
+}
+
+/// `[dependencies]`, `[target.'cfg(unix)'.dev-dependencies]`,
+/// `[tool.poetry.group.dev.dependencies]`, `[patch.crates-io]`, ...
+fn is_dependency_table(header: &str) -> bool {
+    header.ends_with("dependencies") || header.starts_with("patch.") || header == "replace"
+}
+
+/// A string key of a dependency, with where its value starts
+struct Key {
+    key: String,
+    value: String,
+    line: usize,
+    column: usize,
+}
+
+fn line_keys(line: &str, i: usize) -> Vec<Key> {
+    TOML_KEY
+        .captures_iter(line)
+        .map(|caps| Key {
+            key: caps[1].to_string(),
+            value: caps[2].to_string(),
+            line: i,
+            column: caps.get(2).unwrap().start() + 1,
+        })
+        .collect()
+}
+
+/// A dependency from its table's keys: git first, then path, then version
+fn table_dependency(name: String, keys: &[Key], manifest: Manifest) -> Option<Dependency> {
+    let get = |key: &str| keys.iter().find(|k| k.key == key);
+    let (at, source) = if let Some(git) = get("git") {
+        let reference = match get("branch") {
+            Some(_) => GitRef::Branch,
+            None if get("rev").is_some() || get("tag").is_some() => GitRef::Pinned,
+            None => GitRef::Default,
+        };
+        (git, Source::Git(reference))
+    } else if let Some(path) = get("path") {
+        (path, Source::Path(path.value.clone()))
+    } else {
+        let version = get("version")?;
+        (version, Source::Exact(exact_version(manifest, &version.value)?))
+    };
+    Some(Dependency { name, line: at.line, column: at.column, source })
+}
+
+/// A PEP 508 requirement fetched from git or a local path, or pinned
+/// with `==`
+fn pep_508(requirement: &str) -> Option<(String, Source)> {
+    let caps = PEP_508.captures(requirement)?;
+    let name = caps[1].to_string();
+    if let Some(version) = caps.get(3) {
+        return Some((name, Source::Exact(version.as_str().to_string())));
+    }
+    let url = caps.get(2)?.as_str();
+    if let Some(path) = url.strip_prefix("file://").or_else(|| url.strip_prefix("file:")) {
+        return Some((name, Source::Path(path.to_string())));
+    }
+    let rest = url.strip_prefix("git+")?.split_once("://")?.1;
+    // `git@host` in an ssh URL is a user, not a ref; refs follow the path
+    let path = &rest[rest.find('/')?..];
+    let path = path.split('#').next().unwrap_or(path);
+    Some((name, Source::Git(git_ref(path.rsplit_once('@').map(|(_, r)| r)))))
+}
+
+/// The dependencies of a package.json, with the lines they are on
+fn npm_dependencies(content: &str) -> Vec<Dependency> {
+    let Ok(serde_json::Value::Object(json)) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let mut deps = Vec::new();
+    for section in NPM_SECTIONS {
+        let Some(serde_json::Value::Object(entries)) = json.get(*section) else {
+            continue;
+        };
+        let from = content.find(&format!("\"{}\"", section)).unwrap_or(0);
+        for (name, spec) in entries {
+            let Some(spec) = spec.as_str() else {
+                continue;
+            };
+            let Some(source) = npm_source(spec) else {
+                continue;
+            };
+            if let Some((line, column)) = json_key(content, from, name) {
+                deps.push(Dependency { name: name.clone(), line, column, source });
+            }
+        }
+    }
+    deps
+}
+
+fn npm_source(spec: &str) -> Option<Source> {
+    let spec = spec.trim();
+    if let Some(path) = spec.strip_prefix("file:").or_else(|| spec.strip_prefix("link:")) {
+        return Some(Source::Path(path.to_string()));
+    }
+    if ["./", "../", "/", "~/"].iter().any(|prefix| spec.starts_with(prefix)) {
+        return Some(Source::Path(spec.to_string()));
+    }
+    if NPM_GIT.is_match(spec) {
+        return Some(Source::Git(git_ref(spec.split_once('#').map(|(_, r)| r))));
+    }
+    exact_version(Manifest::Npm, spec).map(Source::Exact)
+}
+
+/// 0-based line and 1-based column of the value of `"key":`, searching
+/// from byte `from`
+fn json_key(content: &str, from: usize, key: &str) -> Option<(usize, usize)> {
+    let pattern = Regex::new(&format!(r#""{}"\s*:\s*"?"#, regex::escape(key))).ok()?;
+    let m = pattern.find_at(content, from)?;
+    let before = &content[..m.end()];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Some((before.matches('\n').count(), m.end() - line_start + 1))
+}
+
+/// What a git ref pins: a commit SHA, `semver:` range, or version tag
+/// counts as pinned; any other name is taken for a branch
+fn git_ref(reference: Option<&str>) -> GitRef {
+    let Some(reference) = reference.map(str::trim).filter(|r| !r.is_empty()) else {
+        return GitRef::Default;
+    };
+    let is_sha = reference.len() >= 7 && reference.len() <= 40 && reference.chars().all(|c| c.is_ascii_hexdigit());
+    if is_sha || reference.starts_with("semver:") || VERSION_TAG.is_match(reference) {
+        GitRef::Pinned
+    } else {
+        GitRef::Branch
+    }
+}
+
+/// The version of an exact pin: `=1.2.3` in Cargo, `1.2.3` in npm and
+/// Poetry (`==1.2.3` too)
+fn exact_version(manifest: Manifest, spec: &str) -> Option<String> {
+    let spec = spec.trim();
+    let version = match manifest {
+        Manifest::Cargo => spec.strip_prefix('=')?.trim_start(),
+        Manifest::Npm => spec.strip_prefix('=').unwrap_or(spec).trim_start_matches('v'),
+        Manifest::PyProject => spec.trim_start_matches('=').trim_start(),
+        Manifest::BuildScript => return None,
+    };
+    let exact = version.starts_with(|c: char| c.is_ascii_digit())
+        && version.chars().all(|c| c.is_ascii_alphanumeric() || ".+-".contains(c));
+    exact.then(|| version.to_string())
+}
+
+/// Whether a path dependency of the manifest at `manifest` (relative to
+/// the scan root) leaves the scanned tree: absolute, home-relative, or
+/// with more `..` than the manifest is deep
+fn escapes_root(manifest: &Path, path: &str) -> bool {
+    let path = path.trim();
+    if path.starts_with(['/', '\\', '~']) || path.get(1..2) == Some(":") {
+        return true;
+    }
+    let mut depth = manifest.parent().map_or(0, |dir| dir.components().count()) as isize;
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                depth -= 1;
+                if depth < 0 {
+                    return true;
+                }
+            }
+            _ => depth += 1,
+        }
+    }
+    false
+}
+
+/// Install hooks in the `scripts` of a package.json, with their lines
+fn install_hooks(content: &str) -> Vec<(usize, usize)> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let Some(scripts) = json.get("scripts").and_then(|s| s.as_object()) else {
+        return Vec::new();
+    };
+    let from = content.find("\"scripts\"").unwrap_or(0);
+    INSTALL_HOOKS
+        .iter()
+        .filter(|hook| scripts.contains_key(**hook))
+        .filter_map(|hook| json_key(content, from, hook))
+        .map(|(line, _)| {
+            let text = content.lines().nth(line).unwrap_or("");
+            (line, text.len() - text.trim_start().len() + 1)
+        })
+        .collect()
+}
+
+impl SecurityAgent for ManifestsAgent {
+    fn name(&self) -> &str {
+        "manifests"
+    }
+
+    fn description(&self) -> &str {
+        "Checks Cargo.toml, package.json, and pyproject.toml for git dependencies on branches, path dependencies outside the repository, build and install scripts, and yanked versions"
+    }
+
+    fn interested_in(&self, file: &ScannedFile) -> bool {
+        Manifest::detect(file).is_some()
+    }
+
+    fn scan_file(&self, file: &ScannedFile) -> Vec<Finding> {
+        let Some(manifest) = Manifest::detect(file) else {
+            return Vec::new();
+        };
+        let mut findings = Vec::new();
+
+        let deps = match manifest {
+            Manifest::Cargo | Manifest::PyProject => toml_dependencies(&file.content, manifest),
+            Manifest::Npm => npm_dependencies(&file.content),
+            Manifest::BuildScript => {
+                let line = file.content.lines().position(|l| l.contains("fn main")).unwrap_or(0);
+                return vec![self.finding("ANTY-DEP-003", file, line, 1)];
+            }
+        };
+        if manifest == Manifest::Npm {
+            for (line, column) in install_hooks(&file.content) {
+                findings.push(self.finding("ANTY-DEP-003", file, line, column));
+            }
+        }
+
+        for dep in deps {
+            let rule_id = match dep.source {
+                Source::Git(GitRef::Pinned) => continue,
+                Source::Git(_) => "ANTY-DEP-001",
+                Source::Path(ref path) if escapes_root(&file.rel_path, path) => "ANTY-DEP-002",
+                Source::Path(_) => continue,
+                Source::Exact(ref version) if self.yanked.contains(manifest, &dep.name, version) => "ANTY-DEP-004",
+                Source::Exact(_) => continue,
+            };
+            findings.push(self.finding(rule_id, file, dep.line, dep.column));
+        }
+
+        findings
+    }
+
+    fn rules(&self) -> Vec<RuleMeta> {
+        self.patterns
+            .iter()
+            .map(|p| RuleMeta {
+                id: p.rule_id.to_string(),
+                agent: "manifests".to_string(),
+                title: p.title.to_string(),
+                description: p.description.to_string(),
+                severity: p.severity,
+                confidence: p.confidence,
+                recommendation: p.recommendation.to_string(),
+                cwe_id: Some(p.cwe_id.to_string()),
+                pattern: p.pattern.as_str().to_string(),
+                remediation: None,
+                docs_url: rules::docs_url(p.rule_id),
+                references: rules::references(p.cwe_id),
+                tags: p.tags.iter().map(|t| t.to_string()).collect(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,234 @@
+use regex::Regex;
+use tracing::debug;
+
+use crate::agents::secrets::SecretsAgent;
+use crate::agents::{heuristics, ScannedFile, SecurityAgent};
+use crate::report::finding::{Confidence, Finding, Severity};
+use crate::rules::{self, RuleMeta};
+
+/// Which mobile config file a rule reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MobileFile {
+    /// AndroidManifest.xml
+    Manifest,
+    /// res/xml/network_security_config.xml
+    NetworkConfig,
+    /// res/values*/strings.xml
+    Strings,
+    /// Info.plist
+    InfoPlist,
+}
+
+impl MobileFile {
+    fn detect(file: &ScannedFile) -> Option<Self> {
+        let path = file.name_path();
+        let name = path.file_name()?.to_string_lossy();
+        match name.as_ref() {
+            "AndroidManifest.xml" => Some(MobileFile::Manifest),
+            "network_security_config.xml" => Some(MobileFile::NetworkConfig),
+            "strings.xml" => Some(MobileFile::Strings),
+            "Info.plist" => Some(MobileFile::InfoPlist),
+            _ => None,
+        }
+    }
+}
+
+struct MobilePattern {
+    rule_id: &'static str,
+    title: &'static str,
+    description: &'static str,
+    /// Matched against the whole file, since XML elements span lines
+    pattern: Regex,
+    severity: Severity,
+    confidence: Confidence,
+    recommendation: &'static str,
+    cwe_id: &'static str,
+    tags: &'static [&'static str],
+    files: &'static [MobileFile],
+}
+
+/// Checks Android and iOS app configuration: AndroidManifest.xml,
+/// network security config, strings.xml resources, and Info.plist.
+///
+/// Level A agent — regex-based, but matches whole elements rather than
+/// single lines because manifest attributes are usually spread over several.
+pub struct MobileAgent {
+    patterns: Vec<MobilePattern>,
+    /// Any permission attribute on an exported component
+    permission: Regex,
+}
+
+impl MobileAgent {
+    pub fn new() -> Self {
+        let patterns = vec![
+            MobilePattern {
+                rule_id: "ANTY-MOB-001",
+                title: "Debuggable Android Build",
+                description: "android:debuggable=\"true\" lets anyone attach a debugger to the app and read its memory",
+                pattern: Regex::new(r#"android:debuggable\s*=\s*"true""#).unwrap(),
+                severity: Severity::High,
+                confidence: Confidence::High,
+                recommendation: "Remove android:debuggable from the manifest; the build system sets it for debug builds only.",
+                cwe_id: "CWE-489",
+                tags: &["mobile", "misconfig", "low-noise"],
+                files: &[MobileFile::Manifest],
+            },
+            MobilePattern {
+                rule_id: "ANTY-MOB-002",
+                title: "Exported Component Without Permission",
+                description: "An activity, service, receiver, or provider is exported to other apps without requiring a permission",
+                pattern: Regex::new(r#"<(activity|activity-alias|service|receiver|provider)\b[^>]*android:exported\s*=\s*"true"[^>]*>"#).unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::Medium,
+                recommendation: "Set android:exported=\"false\" unless other apps must reach the component, and protect it with android:permission (signature-level where possible).",
+                cwe_id: "CWE-926",
+                tags: &["mobile", "permissions"],
+                files: &[MobileFile::Manifest],
+            },
+            MobilePattern {
+                rule_id: "ANTY-MOB-003",
+                title: "Cleartext Traffic Allowed (Android)",
+                description: "The app allows unencrypted HTTP connections",
+                pattern: Regex::new(r#"(android:usesCleartextTraffic|cleartextTrafficPermitted)\s*=\s*"true""#).unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::High,
+                recommendation: "Serve everything over HTTPS. If one legacy host needs HTTP, allow it for that domain only in network_security_config.xml.",
+                cwe_id: "CWE-319",
+                tags: &["mobile", "crypto", "low-noise"],
+                files: &[MobileFile::Manifest, MobileFile::NetworkConfig],
+            },
+            MobilePattern {
+                rule_id: "ANTY-MOB-004",
+                title: "App Transport Security Disabled (iOS)",
+                description: "NSAllowsArbitraryLoads (or an insecure-HTTP exception) turns off App Transport Security",
+                pattern: Regex::new(r"<key>\s*(NSAllowsArbitraryLoads(InWebContent|ForMedia)?|NSExceptionAllowsInsecureHTTPLoads)\s*</key>\s*<true\s*/>").unwrap(),
+                severity: Severity::Medium,
+                confidence: Confidence::High,
+                recommendation: "Remove the key and use HTTPS. If a specific host cannot, add an NSExceptionDomains entry for that host only.",
+                cwe_id: "CWE-319",
+                tags: &["mobile", "crypto", "low-noise"],
+                files: &[MobileFile::InfoPlist],
+            },
+            MobilePattern {
+                rule_id: "ANTY-MOB-005",
+                title: "API Key in Android String Resource",
+                description: "A key, token, or secret is stored in strings.xml, which ships inside the APK and is trivial to extract",
+                pattern: Regex::new(r#"(?i)<string\s+name\s*=\s*"[^"]*(api_?key|secret|token|password|client_?id)[^"]*"[^>]*>(?P<value>[^<]{8,})</string>"#).unwrap(),
+                severity: Severity::High,
+                confidence: Confidence::Medium,
+                recommendation: "Keep secrets on a backend the app calls. For keys that must ship (e.g. Maps), restrict them to your package name and signing certificate.",
+                cwe_id: "CWE-798",
+                tags: &["mobile", "secrets"],
+                files: &[MobileFile::Strings],
+            },
+        ];
+
+        MobileAgent {
+            patterns,
+            permission: Regex::new(r"android:(permission|readPermission|writePermission)\s*=").unwrap(),
+        }
+    }
+}
+
+impl SecurityAgent for MobileAgent {
+    fn name(&self) -> &str {
+        "mobile"
+    }
+
+    fn description(&self) -> &str {
+        "Checks Android manifests, string resources, and iOS Info.plist for debuggable builds, exported components, cleartext traffic, and embedded keys"
+    }
+
+    fn interested_in(&self, file: &ScannedFile) -> bool {
+        MobileFile::detect(file).is_some()
+    }
+
+    fn scan_file(&self, file: &ScannedFile) -> Vec<Finding> {
+        let Some(kind) = MobileFile::detect(file) else {
+            return Vec::new();
+        };
+        let mut findings = Vec::new();
+
+        for pattern in self.patterns.iter().filter(|p| p.files.contains(&kind)) {
+            for caps in pattern.pattern.captures_iter(&file.content) {
+                if file.out_of_time() {
+                    return findings;
+                }
+                let m = caps.get(0).unwrap();
+
+                if pattern.rule_id == "ANTY-MOB-002" && self.permission.is_match(m.as_str()) {
+                    continue;
+                }
+                let secret = (pattern.rule_id == "ANTY-MOB-005").then(|| caps["value"].trim());
+                if secret.is_some_and(|v| v.starts_with('@') || heuristics::is_placeholder(v)) {
+                    continue;
+                }
+
+                let before = &file.content[..m.start()];
+                let line_number = file.line_offset + before.matches('\n').count() + 1;
+                let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+                let line = file.content[line_start..].lines().next().unwrap_or("").trim();
+                let evidence = match secret {
+                    Some(value) => SecretsAgent::redact_evidence(line, value),
+                    None => line.to_string(),
+                };
+
+                debug!("Mobile: {} in {}:{}", pattern.rule_id, file.rel_path.display(), line_number);
+
+                findings.push(Finding {
+                    id: Finding::generate_id(pattern.rule_id, &file.rel_path, line_number),
+                    rule_id: pattern.rule_id.to_string(),
+                    severity: pattern.severity,
+                    confidence: pattern.confidence,
+                    agent: "mobile".to_string(),
+                    title: pattern.title.to_string(),
+                    description: pattern.description.to_string(),
+                    file_path: file.rel_path.clone(),
+                    line_start: line_number,
+                    line_end: line_number + m.as_str().matches('\n').count(),
+                    column: m.start() - line_start + 1,
+                    column_end: m.start() + m.as_str().lines().next().unwrap_or("").len() - line_start + 1,
+                    evidence,
+                    recommendation: pattern.recommendation.to_string(),
+                    cwe_id: Some(pattern.cwe_id.to_string()),
+                    remediation: None,
+                    secret_hash: secret.map(Finding::hash_secret),
+                    related_rules: Vec::new(),
+                    additional_locations: Vec::new(),
+                    owners: Vec::new(),
+                    blame: None,
+                    context: None,
+                    triage: None,
+                    fix: None,
+                    environment: None,
+                    score: None,
+                    docs_url: Some(rules::docs_url(pattern.rule_id)),
+                    references: rules::references(pattern.cwe_id),
+                });
+            }
+        }
+
+        findings
+    }
+
+    fn rules(&self) -> Vec<RuleMeta> {
+        self.patterns
+            .iter()
+            .map(|p| RuleMeta {
+                id: p.rule_id.to_string(),
+                agent: "mobile".to_string(),
+                title: p.title.to_string(),
+                description: p.description.to_string(),
+                severity: p.severity,
+                confidence: p.confidence,
+                recommendation: p.recommendation.to_string(),
+                cwe_id: Some(p.cwe_id.to_string()),
+                pattern: p.pattern.as_str().to_string(),
+                remediation: None,
+                docs_url: rules::docs_url(p.rule_id),
+                references: rules::references(p.cwe_id),
+                tags: p.tags.iter().map(|t| t.to_string()).collect(),
+            })
+            .collect()
+    }
+}
@@ -1,8 +1,24 @@
 pub mod secrets;
 pub mod dangerous_functions;
 pub mod config_issues;
+pub mod mobile;
+pub mod auth;
+pub mod build_files;
+pub mod manifests;
+pub mod web_server;
+pub mod semgrep;
+pub mod heuristics;
+pub mod kubernetes;
+pub mod credential_files;
+pub mod pem;
+pub mod playbooks;
+
+use std::time::Instant;
+
+use regex::RegexSet;
 
 use crate::report::finding::Finding;
+use crate::rules::RuleMeta;
 
 /// A scanned file with its content ready for analysis
 #[derive(Debug, Clone)]
@@ -10,12 +26,34 @@ pub struct ScannedFile {
     /// Relative path from scan root
     pub rel_path: std::path::PathBuf,
     /// Absolute path
-    #[allow(dead_code)]
     pub abs_path: std::path::PathBuf,
     /// File content as string
     pub content: String,
     /// Detected language (if any)
     pub language: Option<Language>,
+    /// Number of lines preceding `content` in the file (non-zero for
+    /// chunks of large files that are scanned in pieces)
+    pub line_offset: usize,
+    /// End of this file's scan time budget (--file-timeout)
+    pub deadline: Option<Instant>,
+}
+
+impl ScannedFile {
+    /// Whether the file's time budget is used up. Agents check this between
+    /// lines so one pathological file cannot stall the whole scan.
+    pub fn out_of_time(&self) -> bool {
+        self.deadline.is_some_and(|d| Instant::now() >= d)
+    }
+
+    /// Path to recognize the file by name: the relative path, or the
+    /// absolute one in a single-file scan, whose relative path is empty
+    pub fn name_path(&self) -> &std::path::Path {
+        if self.rel_path.as_os_str().is_empty() {
+            &self.abs_path
+        } else {
+            &self.rel_path
+        }
+    }
 }
 
 /// Supported languages
@@ -36,6 +74,9 @@ pub enum Language {
     Toml,
     Dockerfile,
     Env,
+    Terraform,
+    Sql,
+    PowerShell,
     Unknown,
 }
 
@@ -57,10 +98,63 @@ impl Language {
             "json" => Language::Json,
             "toml" => Language::Toml,
             "env" => Language::Env,
+            "tf" | "tfvars" => Language::Terraform,
+            "sql" | "ddl" => Language::Sql,
+            "ps1" | "psm1" | "psd1" => Language::PowerShell,
             _ => Language::Unknown,
         }
     }
 
+    /// Parse a language name as written in config, e.g. "python" or "shell"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "javascript" | "js" => Some(Language::JavaScript),
+            "typescript" | "ts" => Some(Language::TypeScript),
+            "python" | "py" => Some(Language::Python),
+            "rust" => Some(Language::Rust),
+            "go" => Some(Language::Go),
+            "java" => Some(Language::Java),
+            "ruby" | "rb" => Some(Language::Ruby),
+            "php" => Some(Language::Php),
+            "csharp" | "c#" | "cs" => Some(Language::CSharp),
+            "shell" | "bash" | "sh" => Some(Language::Shell),
+            "yaml" | "yml" => Some(Language::Yaml),
+            "json" => Some(Language::Json),
+            "toml" => Some(Language::Toml),
+            "dockerfile" | "docker" => Some(Language::Dockerfile),
+            "env" | "dotenv" => Some(Language::Env),
+            "terraform" | "hcl" => Some(Language::Terraform),
+            "sql" => Some(Language::Sql),
+            "powershell" | "pwsh" | "ps1" => Some(Language::PowerShell),
+            _ => None,
+        }
+    }
+
+    /// Lowercase name as written in config ("other" for Unknown)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::JavaScript => "javascript",
+            Language::TypeScript => "typescript",
+            Language::Python => "python",
+            Language::Rust => "rust",
+            Language::Go => "go",
+            Language::Java => "java",
+            Language::Ruby => "ruby",
+            Language::Php => "php",
+            Language::CSharp => "csharp",
+            Language::Shell => "shell",
+            Language::Yaml => "yaml",
+            Language::Json => "json",
+            Language::Toml => "toml",
+            Language::Dockerfile => "dockerfile",
+            Language::Env => "env",
+            Language::Terraform => "terraform",
+            Language::Sql => "sql",
+            Language::PowerShell => "powershell",
+            Language::Unknown => "other",
+        }
+    }
+
     /// Detect from filename (for files without extension)
     pub fn from_filename(name: &str) -> Self {
         match name.to_lowercase().as_str() {
@@ -80,8 +174,69 @@ pub trait SecurityAgent: Send + Sync {
     /// Short description of what this agent looks for
     fn description(&self) -> &str;
 
-    /// Run the agent against a single file and return findings
+    /// Run the agent against a single file and return findings. Only
+    /// called for files the agent is `interested_in`.
     fn scan_file(&self, file: &ScannedFile) -> Vec<Finding>;
+
+    /// Cheap pre-filter run before `scan_file`: whether the file (or chunk
+    /// of a large file) can hold anything this agent reports. Agents that
+    /// parse what they scan should answer from the path, the language, or
+    /// a literal search of the content, so that only relevant files are
+    /// parsed.
+    fn interested_in(&self, _file: &ScannedFile) -> bool {
+        true
+    }
+
+    /// Static metadata for every rule this agent can report
+    fn rules(&self) -> Vec<RuleMeta>;
+
+    /// Languages this agent has language-specific rules for. Empty for
+    /// agents whose rules apply to any file, such as secrets.
+    fn languages(&self) -> Vec<Language> {
+        Vec::new()
+    }
+
+    /// Whether `scan_project` needs this file. Files are dropped once
+    /// scanned, so only the ones an agent asks for are kept for the project
+    /// pass. Files too large to read whole are never kept.
+    fn wants_project_file(&self, _file: &ScannedFile) -> bool {
+        false
+    }
+
+    /// Run once after every file was scanned, over the files this agent
+    /// asked for (sorted by path), for rules that depend on more than one
+    /// file or on a file as a whole
+    fn scan_project(&self, _files: &[ScannedFile]) -> Vec<Finding> {
+        Vec::new()
+    }
+
+    /// Called last, for agents that gather state across `scan_file` calls.
+    /// Returns the findings that state adds and clears it, so the agent can
+    /// be used for another scan. `anty batch` shares agents between scans
+    /// running at the same time, so cross-file rules that can work from
+    /// `scan_project` alone should.
+    fn finish(&self) -> Vec<Finding> {
+        Vec::new()
+    }
+}
+
+/// Run every pass of `agent` over one file on its own, as if it were the
+/// whole project
+pub fn scan_alone(agent: &dyn SecurityAgent, file: &ScannedFile) -> Vec<Finding> {
+    let mut findings = if agent.interested_in(file) { agent.scan_file(file) } else { Vec::new() };
+    if agent.wants_project_file(file) {
+        findings.extend(agent.scan_project(std::slice::from_ref(file)));
+    }
+    findings.extend(agent.finish());
+    findings
+}
+
+/// Combine an agent's pattern sources into a single `RegexSet`.
+///
+/// Agents test each line against the set once and only run the individual
+/// regexes whose indices matched, instead of every pattern on every line.
+pub fn pattern_set<'a>(sources: impl IntoIterator<Item = &'a str>) -> RegexSet {
+    RegexSet::new(sources).expect("agent patterns are valid regexes")
 }
 
 /// Registry of all available agents
@@ -90,6 +245,11 @@ pub fn all_agents() -> Vec<Box<dyn SecurityAgent>> {
         Box::new(secrets::SecretsAgent::new()),
         Box::new(dangerous_functions::DangerousFunctionsAgent::new()),
         Box::new(config_issues::ConfigIssuesAgent::new()),
+        Box::new(mobile::MobileAgent::new()),
+        Box::new(auth::AuthAgent::new()),
+        Box::new(build_files::BuildFilesAgent::new()),
+        Box::new(web_server::WebServerAgent::new()),
+        Box::new(manifests::ManifestsAgent::new()),
     ]
 }
 
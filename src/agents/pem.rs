@@ -0,0 +1,183 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+/// Lines searched for the END line of a key; an 8192-bit RSA key is about
+/// 100 lines of base64
+const MAX_BLOCK_LINES: usize = 200;
+
+/// A PEM private key block, from its BEGIN line to its END line
+pub struct KeyBlock {
+    /// 0-based line of the END line (the BEGIN line itself when the key
+    /// sits on one line with `\n` escapes, or has no END line)
+    pub end: usize,
+    /// Key algorithm, e.g. "RSA", "EC", "Ed25519"
+    pub algorithm: Option<&'static str>,
+    /// Size in bits, when the key could be decoded
+    pub bits: Option<usize>,
+    /// The base64 body, so the same key can be recognized across files
+    pub body: String,
+}
+
+impl KeyBlock {
+    /// "2048-bit RSA key", "Ed25519 key", or "private key"
+    pub fn describe(&self) -> String {
+        match (self.bits, self.algorithm) {
+            (Some(bits), Some(algorithm)) => format!("{}-bit {} key", bits, algorithm),
+            (None, Some(algorithm)) => format!("{} key", algorithm),
+            _ => "private key".to_string(),
+        }
+    }
+}
+
+/// The private key whose `-----BEGIN ... PRIVATE KEY-----` header is on
+/// `lines[start]`, with its body decoded far enough to tell the key type
+/// and size. Keys inside string literals (`"...\n..."`) are handled too.
+pub fn key_block(lines: &[&str], start: usize) -> Option<KeyBlock> {
+    let header = lines[start];
+    let begin = header.find("-----BEGIN ")?;
+    let label_end = begin + header[begin..].find("PRIVATE KEY-----")?;
+    let label = header[begin + "-----BEGIN ".len()..label_end].trim();
+    let after_header = &header[label_end + "PRIVATE KEY-----".len()..];
+
+    let (end, body) = if let Some(footer) = after_header.find("-----END ") {
+        // One line with escaped newlines, as in JSON or .env files
+        (start, base64_chars(&after_header[..footer]))
+    } else {
+        let last = lines.len().min(start + MAX_BLOCK_LINES);
+        match (start + 1..last).find(|&i| lines[i].contains("-----END ")) {
+            Some(end) => {
+                let body: String = lines[start + 1..end]
+                    .iter()
+                    // Legacy encrypted keys carry `Proc-Type:` / `DEK-Info:` headers
+                    .filter(|line| !line.contains(':'))
+                    .map(|line| base64_chars(line))
+                    .collect();
+                (end, body)
+            }
+            None => (start, String::new()),
+        }
+    };
+
+    let der = STANDARD.decode(&body).unwrap_or_default();
+    let (algorithm, bits) = match label {
+        "RSA" => (Some("RSA"), first_integer_bits(&der)),
+        "DSA" => (Some("DSA"), first_integer_bits(&der)),
+        "EC" => (Some("EC"), sec1_bits(&der)),
+        "OPENSSH" => openssh(&der),
+        "ENCRYPTED" => (None, None),
+        _ => pkcs8(&der),
+    };
+    Some(KeyBlock { end, algorithm, bits, body })
+}
+
+/// The base64 characters of a body line, without quotes, commas, or
+/// `\n` escapes around them
+fn base64_chars(text: &str) -> String {
+    text.replace("\\n", "")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '='))
+        .collect()
+}
+
+/// One DER element: tag, contents, and what follows it
+fn tlv(data: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = data.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (len, rest) = if first < 0x80 {
+        (first as usize, rest)
+    } else {
+        let n = (first & 0x7f) as usize;
+        if n == 0 || n > 4 || rest.len() < n {
+            return None;
+        }
+        (rest[..n].iter().fold(0, |len, &b| len << 8 | b as usize), &rest[n..])
+    };
+    (rest.len() >= len).then(|| (tag, &rest[..len], &rest[len..]))
+}
+
+/// Bit length of a big-endian unsigned integer
+fn integer_bits(bytes: &[u8]) -> Option<usize> {
+    let start = bytes.iter().position(|&b| b != 0)?;
+    Some((bytes.len() - start) * 8 - bytes[start].leading_zeros() as usize)
+}
+
+/// PKCS#1 RSA and OpenSSL DSA keys: a SEQUENCE whose version is followed
+/// by the modulus (RSA) or prime p (DSA)
+fn first_integer_bits(der: &[u8]) -> Option<usize> {
+    let (_, fields, _) = tlv(der).filter(|(tag, ..)| *tag == 0x30)?;
+    let (_, _, rest) = tlv(fields)?;
+    let (_, modulus, _) = tlv(rest).filter(|(tag, ..)| *tag == 0x02)?;
+    integer_bits(modulus)
+}
+
+/// SEC1 EC keys: the private key octet string is as long as the curve order
+fn sec1_bits(der: &[u8]) -> Option<usize> {
+    let (_, fields, _) = tlv(der).filter(|(tag, ..)| *tag == 0x30)?;
+    let (_, _, rest) = tlv(fields)?;
+    let (_, key, _) = tlv(rest).filter(|(tag, ..)| *tag == 0x04)?;
+    Some(match key.len() {
+        66 => 521,
+        len => len * 8,
+    })
+}
+
+const OID_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+const OID_EC: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+const OID_DSA: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x38, 0x04, 0x01];
+const OID_ED25519: &[u8] = &[0x2b, 0x65, 0x70];
+const OID_ED448: &[u8] = &[0x2b, 0x65, 0x71];
+
+/// PKCS#8 (`BEGIN PRIVATE KEY`): the algorithm identifier names the key
+/// type, and the wrapped key is parsed like its own PEM type
+fn pkcs8(der: &[u8]) -> (Option<&'static str>, Option<usize>) {
+    let parsed = || {
+        let (_, fields, _) = tlv(der).filter(|(tag, ..)| *tag == 0x30)?;
+        let (_, _, rest) = tlv(fields)?;
+        let (_, algorithm, rest) = tlv(rest).filter(|(tag, ..)| *tag == 0x30)?;
+        let (_, oid, params) = tlv(algorithm).filter(|(tag, ..)| *tag == 0x06)?;
+        let (_, key, _) = tlv(rest).filter(|(tag, ..)| *tag == 0x04)?;
+        Some(match oid {
+            OID_RSA => (Some("RSA"), first_integer_bits(key)),
+            OID_EC => (Some("EC"), sec1_bits(key)),
+            OID_DSA => {
+                let (_, dss, _) = tlv(params)?;
+                (Some("DSA"), tlv(dss).and_then(|(_, p, _)| integer_bits(p)))
+            }
+            OID_ED25519 => (Some("Ed25519"), Some(256)),
+            OID_ED448 => (Some("Ed448"), Some(456)),
+            _ => (None, None),
+        })
+    };
+    parsed().unwrap_or((None, None))
+}
+
+/// OpenSSH keys: the unencrypted public key section names the key type
+/// and, for RSA and DSA, holds the modulus or prime
+fn openssh(data: &[u8]) -> (Option<&'static str>, Option<usize>) {
+    fn string(data: &[u8]) -> Option<(&[u8], &[u8])> {
+        let len = u32::from_be_bytes(data.get(..4)?.try_into().ok()?) as usize;
+        let rest = &data[4..];
+        (rest.len() >= len).then(|| (&rest[..len], &rest[len..]))
+    }
+    let parsed = || {
+        let rest = data.strip_prefix(b"openssh-key-v1\0")?;
+        let (_cipher, rest) = string(rest)?;
+        let (_kdf, rest) = string(rest)?;
+        let (_kdf_options, rest) = string(rest)?;
+        let (public, _) = string(rest.get(4..)?)?;
+        let (key_type, fields) = string(public)?;
+        Some(match key_type {
+            b"ssh-rsa" => {
+                let (_e, fields) = string(fields)?;
+                (Some("RSA"), integer_bits(string(fields)?.0))
+            }
+            b"ssh-dss" => (Some("DSA"), integer_bits(string(fields)?.0)),
+            b"ssh-ed25519" => (Some("Ed25519"), Some(256)),
+            b"ecdsa-sha2-nistp256" => (Some("ECDSA"), Some(256)),
+            b"ecdsa-sha2-nistp384" => (Some("ECDSA"), Some(384)),
+            b"ecdsa-sha2-nistp521" => (Some("ECDSA"), Some(521)),
+            _ => (Some("OpenSSH"), None),
+        })
+    };
+    parsed().unwrap_or((Some("OpenSSH"), None))
+}
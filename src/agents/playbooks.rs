@@ -0,0 +1,323 @@
+use crate::report::finding::Remediation;
+
+/// How to respond to a leaked credential from one provider
+struct Playbook {
+    rule_ids: &'static [&'static str],
+    provider: &'static str,
+    revoke_url: &'static str,
+    steps: &'static [&'static str],
+    docs_url: &'static str,
+}
+
+const PLAYBOOKS: &[Playbook] = &[
+    Playbook {
+        rule_ids: &["ANTY-SEC-001", "ANTY-SEC-002"],
+        provider: "AWS",
+        revoke_url: "https://console.aws.amazon.com/iam/home#/security_credentials",
+        steps: &[
+            "Deactivate the access key in IAM (Users → Security credentials)",
+            "Create a replacement key and update everything that uses the old one",
+            "Delete the old key once nothing depends on it",
+            "Check CloudTrail for activity from the leaked key",
+        ],
+        docs_url: "https://docs.aws.amazon.com/IAM/latest/UserGuide/id_credentials_access-keys.html",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-003"],
+        provider: "GitHub",
+        revoke_url: "https://github.com/settings/tokens",
+        steps: &[
+            "Delete the token under Settings → Developer settings → Personal access tokens",
+            "Create a new token with only the scopes and repositories it needs",
+            "Review the account security log for use of the old token",
+        ],
+        docs_url: "https://docs.github.com/en/authentication/keeping-your-account-and-data-secure/token-expiration-and-revocation",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-004"],
+        provider: "GitHub",
+        revoke_url: "https://github.com/settings/applications",
+        steps: &[
+            "Revoke the OAuth app's access under Settings → Applications",
+            "Re-authorize the app to issue a fresh token",
+            "Review the account security log for use of the old token",
+        ],
+        docs_url: "https://docs.github.com/en/authentication/keeping-your-account-and-data-secure/token-expiration-and-revocation",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-005", "ANTY-SEC-006"],
+        provider: "Stripe",
+        revoke_url: "https://dashboard.stripe.com/apikeys",
+        steps: &[
+            "Roll the key in the Dashboard (Developers → API keys → Roll key)",
+            "Deploy the new key, then let the old one expire immediately",
+            "Review recent API request logs for unexpected activity",
+        ],
+        docs_url: "https://docs.stripe.com/keys",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-007", "ANTY-SEC-008"],
+        provider: "OpenAI",
+        revoke_url: "https://platform.openai.com/api-keys",
+        steps: &[
+            "Delete the key on the API keys page",
+            "Create a new key, scoped to a single project if possible",
+            "Check the usage page for unexpected spend",
+        ],
+        docs_url: "https://help.openai.com/en/articles/5112595-best-practices-for-api-key-safety",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-009"],
+        provider: "Slack",
+        revoke_url: "https://api.slack.com/apps",
+        steps: &[
+            "Open the app → OAuth & Permissions and revoke its tokens",
+            "Reinstall the app to the workspace to issue a new token",
+            "Review the workspace access logs for use of the old token",
+        ],
+        docs_url: "https://api.slack.com/authentication/best-practices",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-010"],
+        provider: "Slack",
+        revoke_url: "https://api.slack.com/apps",
+        steps: &[
+            "Open the app → Incoming Webhooks and remove the leaked webhook",
+            "Add a new webhook and store its URL as a secret",
+        ],
+        docs_url: "https://api.slack.com/messaging/webhooks",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-015"],
+        provider: "Google Cloud",
+        revoke_url: "https://console.cloud.google.com/apis/credentials",
+        steps: &[
+            "Regenerate or delete the key under APIs & Services → Credentials",
+            "Restrict the new key to the APIs and apps that need it",
+            "Check billing and API metrics for unexpected usage",
+        ],
+        docs_url: "https://cloud.google.com/docs/authentication/api-keys",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-016"],
+        provider: "Heroku",
+        revoke_url: "https://dashboard.heroku.com/account",
+        steps: &[
+            "Regenerate the API key under Account settings → API Key",
+            "Revoke any leaked OAuth authorizations with `heroku authorizations:revoke`",
+            "Update CI and scripts that use the old key",
+        ],
+        docs_url: "https://devcenter.heroku.com/articles/authentication",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-017"],
+        provider: "SendGrid",
+        revoke_url: "https://app.sendgrid.com/settings/api_keys",
+        steps: &[
+            "Delete the key under Settings → API Keys",
+            "Create a new key with only the permissions it needs",
+            "Check email activity for messages you did not send",
+        ],
+        docs_url: "https://www.twilio.com/docs/sendgrid/ui/account-and-settings/api-keys",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-018"],
+        provider: "Twilio",
+        revoke_url: "https://console.twilio.com/us1/account/keys-credentials/api-keys",
+        steps: &[
+            "Delete the API key in the Console",
+            "Create a new API key and update everything that uses it",
+            "If the Auth Token leaked as well, rotate it under Account → API keys & tokens",
+        ],
+        docs_url: "https://www.twilio.com/docs/iam/api-keys",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-025", "ANTY-SEC-026"],
+        provider: "Azure Storage",
+        revoke_url: "https://portal.azure.com/#browse/Microsoft.Storage%2FStorageAccounts",
+        steps: &[
+            "Rotate the leaked key under the storage account's Access keys (this also invalidates SAS tokens it signed)",
+            "Update connection strings to the other key, then rotate that one too",
+            "Review the storage account's activity and diagnostic logs for unexpected access",
+        ],
+        docs_url: "https://learn.microsoft.com/en-us/azure/storage/common/storage-account-keys-manage",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-027"],
+        provider: "Google Cloud",
+        revoke_url: "https://console.cloud.google.com/iam-admin/serviceaccounts",
+        steps: &[
+            "Delete the key under the service account's Keys tab (the key ID is private_key_id in the JSON)",
+            "Switch the workload to Workload Identity or attached service accounts instead of key files",
+            "Check Cloud Audit Logs for activity by the service account",
+        ],
+        docs_url: "https://cloud.google.com/iam/docs/keys-create-delete",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-028"],
+        provider: "DigitalOcean",
+        revoke_url: "https://cloud.digitalocean.com/account/api/tokens",
+        steps: &[
+            "Delete the token under API → Tokens",
+            "Create a new token with only the scopes it needs",
+            "Review the team's security history for actions by the old token",
+        ],
+        docs_url: "https://docs.digitalocean.com/reference/api/create-personal-access-token/",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-029"],
+        provider: "Cloudflare",
+        revoke_url: "https://dash.cloudflare.com/profile/api-tokens",
+        steps: &[
+            "Roll or delete the token under My Profile → API Tokens (or change the Global API Key)",
+            "Create a scoped token limited to the zones and permissions it needs",
+            "Review the account's audit log for changes made with the old credential",
+        ],
+        docs_url: "https://developers.cloudflare.com/fundamentals/api/get-started/create-token/",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-030"],
+        provider: "npm",
+        revoke_url: "https://www.npmjs.com/settings/~/tokens",
+        steps: &[
+            "Delete the token under Access Tokens, or run `npm token revoke <id>`",
+            "Create a granular token limited to the packages it publishes",
+            "Check recently published versions of your packages for releases you did not make",
+        ],
+        docs_url: "https://docs.npmjs.com/revoking-access-tokens",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-031"],
+        provider: "PyPI",
+        revoke_url: "https://pypi.org/manage/account/token/",
+        steps: &[
+            "Remove the token under Account settings → API tokens",
+            "Publish with trusted publishing, or create a token scoped to one project",
+            "Check your projects' release history and security log for uploads you did not make",
+        ],
+        docs_url: "https://docs.pypi.org/trusted-publishers/",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-032"],
+        provider: "Docker Hub",
+        revoke_url: "https://app.docker.com/settings/personal-access-tokens",
+        steps: &[
+            "Delete the token under Account settings → Personal access tokens",
+            "Create a new token with read-only access unless the job pushes images",
+            "Check your repositories for image tags you did not push",
+        ],
+        docs_url: "https://docs.docker.com/security/for-developers/access-tokens/",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-033"],
+        provider: "HashiCorp Vault",
+        revoke_url: "https://developer.hashicorp.com/vault/docs/commands/token/revoke",
+        steps: &[
+            "Revoke the token and its children with `vault token revoke <token>`",
+            "Move the client to an auth method that issues short-lived tokens (AppRole, Kubernetes, cloud IAM)",
+            "Search Vault's audit device logs for requests made with the token's accessor",
+        ],
+        docs_url: "https://developer.hashicorp.com/vault/docs/concepts/tokens",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-034"],
+        provider: "Discord",
+        revoke_url: "https://discord.com/developers/applications",
+        steps: &[
+            "Reset the token under your application's Bot page; the old token stops working at once",
+            "Deploy the new token from an environment variable",
+            "Check the servers the bot is in for messages or changes it did not make",
+        ],
+        docs_url: "https://discord.com/developers/docs/topics/oauth2#bots",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-035"],
+        provider: "Telegram",
+        revoke_url: "https://t.me/BotFather",
+        steps: &[
+            "Send /revoke to @BotFather and pick the bot to issue a new token",
+            "Deploy the new token from an environment variable",
+            "Re-register the webhook if the bot uses one (setWebhook)",
+        ],
+        docs_url: "https://core.telegram.org/bots/features#botfather",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-036"],
+        provider: "Mailgun",
+        revoke_url: "https://app.mailgun.com/settings/api_security",
+        steps: &[
+            "Delete the key under API Security and create a new one",
+            "Use a domain sending key where the code only sends mail",
+            "Check sending logs for messages you did not send",
+        ],
+        docs_url: "https://documentation.mailgun.com/docs/mailgun/user-manual/get-started/#api-keys",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-037"],
+        provider: "Mailchimp",
+        revoke_url: "https://us1.admin.mailchimp.com/account/api/",
+        steps: &[
+            "Revoke the key under Account & billing → Extras → API keys",
+            "Create a new key and update everything that uses it",
+            "Review audience exports and campaigns for activity you did not start",
+        ],
+        docs_url: "https://mailchimp.com/help/about-api-keys/",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-038"],
+        provider: "Datadog",
+        revoke_url: "https://app.datadoghq.com/organization-settings/api-keys",
+        steps: &[
+            "Revoke the key under Organization Settings → API Keys (or Application Keys)",
+            "Create a replacement and roll it out to agents and integrations",
+            "Check the Audit Trail for requests made with the old key",
+        ],
+        docs_url: "https://docs.datadoghq.com/account_management/api-app-keys/",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-039"],
+        provider: "New Relic",
+        revoke_url: "https://one.newrelic.com/admin-portal/api-keys/home",
+        steps: &[
+            "Delete or rotate the key in the API keys UI",
+            "Update agents and scripts with the new key",
+            "Review NrAuditEvent for changes made with the old key",
+        ],
+        docs_url: "https://docs.newrelic.com/docs/apis/intro-apis/new-relic-api-keys/",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-040"],
+        provider: "Sentry",
+        revoke_url: "https://sentry.io/settings/account/api/auth-tokens/",
+        steps: &[
+            "Revoke the auth token, or disable the project's client key if a DSN secret leaked",
+            "Create a new key or token and update everything that uses it",
+            "Check the organization's audit log for changes made with it",
+        ],
+        docs_url: "https://docs.sentry.io/account/auth-tokens/",
+    },
+    Playbook {
+        rule_ids: &["ANTY-SEC-041"],
+        provider: "Firebase",
+        revoke_url: "https://console.firebase.google.com/project/_/settings/cloudmessaging",
+        steps: &[
+            "Delete the legacy server key under Project settings → Cloud Messaging",
+            "Send messages through the HTTP v1 API with a service account instead",
+            "Watch FCM delivery reports for notifications you did not send",
+        ],
+        docs_url: "https://firebase.google.com/docs/cloud-messaging/migrate-v1",
+    },
+];
+
+/// Provider-specific remediation for a secret rule, if it has a playbook
+pub fn for_rule(rule_id: &str) -> Option<Remediation> {
+    PLAYBOOKS
+        .iter()
+        .find(|p| p.rule_ids.contains(&rule_id))
+        .map(|p| Remediation {
+            provider: p.provider.to_string(),
+            revoke_url: Some(p.revoke_url.to_string()),
+            rotation_steps: p.steps.iter().map(|s| s.to_string()).collect(),
+            docs_url: Some(p.docs_url.to_string()),
+        })
+}
@@ -1,8 +1,17 @@
-use regex::Regex;
+use std::collections::HashSet;
+
+use regex::{Regex, RegexSet};
 use tracing::debug;
 
-use crate::agents::{ScannedFile, SecurityAgent};
+use crate::agents::credential_files::CredentialFile;
+use crate::agents::{self, heuristics, kubernetes, pem, playbooks, ScannedFile, SecurityAgent};
+use crate::engine::comments;
 use crate::report::finding::{Confidence, Finding, Severity};
+use crate::rules::{self, bundle, RuleMeta};
+
+/// Private keys are reported as their whole PEM block; other rules do not
+/// match inside it
+const PRIVATE_KEY_RULE: &str = "ANTY-SEC-013";
 
 /// Secret pattern definition
 struct SecretPattern {
@@ -10,10 +19,48 @@ struct SecretPattern {
     title: &'static str,
     description: &'static str,
     pattern: Regex,
+    /// Must also match somewhere in the file
+    requires: Option<Regex>,
     severity: Severity,
     confidence: Confidence,
     recommendation: &'static str,
     cwe_id: &'static str,
+    tags: Vec<String>,
+}
+
+/// A secret found in a file, before it becomes a [`Finding`]
+struct Hit<'a> {
+    pattern: &'a SecretPattern,
+    /// 1-based line number
+    line: usize,
+    /// 1-based last line (a private key's END line)
+    line_end: usize,
+    column: usize,
+    column_end: usize,
+    evidence: String,
+    /// The secret itself, hashed for cross-file matching
+    secret: &'a str,
+    description: String,
+    /// Rules whose matches overlapped this one on the line
+    related_rules: Vec<String>,
+    /// Not the rule's first match on the line
+    repeat: bool,
+}
+
+/// The secret in a pattern match. Patterns that take in the character
+/// before a token can leave an unpaired quote on the value.
+fn value_of(matched_text: &str) -> &str {
+    heuristics::secret_value(matched_text).trim_matches(['"', '\''])
+}
+
+/// One match of a pattern on a line, located by the secret value in it
+struct LineMatch<'a> {
+    pattern: &'a SecretPattern,
+    m: regex::Match<'a>,
+    /// Byte range of the secret value within the line
+    start: usize,
+    end: usize,
+    related_rules: Vec<String>,
 }
 
 /// The Secrets agent detects hardcoded secrets, API keys, tokens,
@@ -22,244 +69,91 @@ struct SecretPattern {
 /// This is a Level A agent — no AST required. Uses regex + known patterns.
 pub struct SecretsAgent {
     patterns: Vec<SecretPattern>,
+    /// Prefilter over all patterns, in the same order
+    set: RegexSet,
+    /// Data values of `kind: Secret` manifests that no specific pattern matches
+    k8s_secret: SecretPattern,
+    /// Literal credentials in Helm `values.yaml` files
+    helm_value: SecretPattern,
+    /// SSH keys, cloud CLI credentials, and other files that exist to hold
+    /// a credential
+    credential_file: SecretPattern,
 }
 
 impl SecretsAgent {
     pub fn new() -> Self {
-        let patterns = vec![
-            // ── AWS ──────────────────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-001",
-                title: "AWS Access Key ID",
-                description: "Hardcoded AWS Access Key ID found in source code",
-                pattern: Regex::new(r"(?i)(^|[^a-zA-Z0-9])(AKIA[0-9A-Z]{16})([^a-zA-Z0-9]|$)").unwrap(),
-                severity: Severity::Critical,
-                confidence: Confidence::High,
-                recommendation: "Use environment variables or AWS IAM roles. Never commit AWS keys to source control.",
-                cwe_id: "CWE-798",
-            },
-            SecretPattern {
-                rule_id: "ANTY-SEC-002",
-                title: "AWS Secret Access Key",
-                description: "Potential AWS Secret Access Key found",
-                pattern: Regex::new(r#"(?i)(aws_secret_access_key|aws_secret_key|secret_access_key)\s*[=:]\s*["']?([A-Za-z0-9/+=]{40})["']?"#).unwrap(),
-                severity: Severity::Critical,
-                confidence: Confidence::High,
-                recommendation: "Remove the secret key and rotate it immediately. Use AWS IAM roles or environment variables.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── GitHub ───────────────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-003",
-                title: "GitHub Personal Access Token",
-                description: "GitHub personal access token (classic or fine-grained) found",
-                pattern: Regex::new(r"(?i)(^|[^a-zA-Z0-9])(ghp_[a-zA-Z0-9]{36,255})([^a-zA-Z0-9]|$)").unwrap(),
-                severity: Severity::Critical,
-                confidence: Confidence::High,
-                recommendation: "Revoke this token on GitHub and use environment variables or a secrets manager.",
-                cwe_id: "CWE-798",
-            },
-            SecretPattern {
-                rule_id: "ANTY-SEC-004",
-                title: "GitHub OAuth Access Token",
-                description: "GitHub OAuth access token found",
-                pattern: Regex::new(r"(?i)(^|[^a-zA-Z0-9])(gho_[a-zA-Z0-9]{36,255})([^a-zA-Z0-9]|$)").unwrap(),
-                severity: Severity::Critical,
-                confidence: Confidence::High,
-                recommendation: "Revoke this token immediately and use proper OAuth flow with secure token storage.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── Stripe ───────────────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-005",
-                title: "Stripe Secret Key",
-                description: "Stripe secret API key found in source code",
-                pattern: Regex::new(r"(?i)(^|[^a-zA-Z0-9])(sk_live_[a-zA-Z0-9]{24,99})([^a-zA-Z0-9]|$)").unwrap(),
-                severity: Severity::Critical,
-                confidence: Confidence::High,
-                recommendation: "Remove the Stripe key and rotate it in the Stripe dashboard. Use environment variables.",
-                cwe_id: "CWE-798",
-            },
-            SecretPattern {
-                rule_id: "ANTY-SEC-006",
-                title: "Stripe Restricted Key",
-                description: "Stripe restricted API key found",
-                pattern: Regex::new(r"(?i)(^|[^a-zA-Z0-9])(rk_live_[a-zA-Z0-9]{24,99})([^a-zA-Z0-9]|$)").unwrap(),
-                severity: Severity::High,
-                confidence: Confidence::High,
-                recommendation: "Remove the key and rotate it in the Stripe dashboard.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── OpenAI ───────────────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-007",
-                title: "OpenAI API Key",
-                description: "OpenAI API key found in source code",
-                pattern: Regex::new(r"(?i)(^|[^a-zA-Z0-9])(sk-[a-zA-Z0-9]{20}T3BlbkFJ[a-zA-Z0-9]{20})([^a-zA-Z0-9]|$)").unwrap(),
-                severity: Severity::Critical,
-                confidence: Confidence::High,
-                recommendation: "Rotate the key in your OpenAI dashboard and use environment variables.",
-                cwe_id: "CWE-798",
-            },
-            // Newer OpenAI key format
-            SecretPattern {
-                rule_id: "ANTY-SEC-008",
-                title: "OpenAI API Key (project-scoped)",
-                description: "OpenAI project-scoped API key found",
-                pattern: Regex::new(r"(?i)(^|[^a-zA-Z0-9])(sk-proj-[a-zA-Z0-9_-]{40,200})([^a-zA-Z0-9]|$)").unwrap(),
-                severity: Severity::Critical,
-                confidence: Confidence::High,
-                recommendation: "Rotate the key in your OpenAI dashboard and use environment variables.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── Slack ────────────────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-009",
-                title: "Slack Bot Token",
-                description: "Slack bot token found in source code",
-                pattern: Regex::new(r"(?i)(^|[^a-zA-Z0-9])(xoxb-[0-9]{10,13}-[0-9]{10,13}-[a-zA-Z0-9]{24,34})([^a-zA-Z0-9]|$)").unwrap(),
-                severity: Severity::Critical,
-                confidence: Confidence::High,
-                recommendation: "Revoke this token in Slack and use environment variables.",
-                cwe_id: "CWE-798",
-            },
-            SecretPattern {
-                rule_id: "ANTY-SEC-010",
-                title: "Slack Webhook URL",
-                description: "Slack incoming webhook URL found",
-                pattern: Regex::new(r"https://hooks\.slack\.com/services/T[A-Z0-9]{8,}/B[A-Z0-9]{8,}/[a-zA-Z0-9]{24,}").unwrap(),
-                severity: Severity::High,
-                confidence: Confidence::High,
-                recommendation: "Remove the webhook URL and store it in environment variables or a secrets manager.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── Generic Passwords ────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-011",
-                title: "Hardcoded Password",
-                description: "Potential hardcoded password assignment found",
-                pattern: Regex::new(r#"(?i)(password|passwd|pwd|pass)\s*[=:]\s*["'][^"']{8,}["']"#).unwrap(),
-                severity: Severity::High,
-                confidence: Confidence::Medium,
-                recommendation: "Never hardcode passwords. Use environment variables, a secrets manager, or configuration files excluded from version control.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── Database URLs ────────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-012",
-                title: "Database Connection String with Credentials",
-                description: "Database connection string with embedded credentials found",
-                pattern: Regex::new(r#"(?i)(mongodb(\+srv)?|postgres(ql)?|mysql|redis|amqp)://[a-zA-Z0-9_]+:[^@\s]{3,}@[^\s"']{3,}"#).unwrap(),
-                severity: Severity::Critical,
-                confidence: Confidence::High,
-                recommendation: "Use environment variables for database connection strings. Never embed credentials in code.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── Private Keys ─────────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-013",
-                title: "Private Key",
-                description: "Private key found in source code",
-                pattern: Regex::new(r"-----BEGIN\s+(RSA |EC |DSA |OPENSSH )?PRIVATE KEY-----").unwrap(),
-                severity: Severity::Critical,
-                confidence: Confidence::High,
-                recommendation: "Remove the private key from source code. Store keys in a secure vault or use managed key services.",
-                cwe_id: "CWE-321",
-            },
-
-            // ── JWT Secrets ──────────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-014",
-                title: "Hardcoded JWT Secret",
-                description: "Potential hardcoded JWT signing secret found",
-                pattern: Regex::new(r#"(?i)(jwt[_-]?secret|jwt[_-]?key|token[_-]?secret)\s*[=:]\s*["'][^"']{8,}["']"#).unwrap(),
-                severity: Severity::High,
-                confidence: Confidence::Medium,
-                recommendation: "Use environment variables for JWT secrets and ensure they are cryptographically random.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── Google ───────────────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-015",
-                title: "Google API Key",
-                description: "Google API key found in source code",
-                pattern: Regex::new(r"(?i)(^|[^a-zA-Z0-9])(AIza[0-9A-Za-z\-_]{35})([^a-zA-Z0-9]|$)").unwrap(),
-                severity: Severity::High,
-                confidence: Confidence::Medium,
-                recommendation: "Restrict the API key in Google Cloud Console and use environment variables.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── Heroku ───────────────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-016",
-                title: "Heroku API Key",
-                description: "Heroku API key found",
-                pattern: Regex::new(r"(?i)(heroku[_-]?api[_-]?key|HEROKU_API_KEY)\s*[=:]\s*[a-f0-9]{8}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{12}").unwrap(),
-                severity: Severity::High,
-                confidence: Confidence::High,
-                recommendation: "Remove the Heroku API key and regenerate it. Use environment variables.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── SendGrid ─────────────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-017",
-                title: "SendGrid API Key",
-                description: "SendGrid API key found in source code",
-                pattern: Regex::new(r"(?i)(^|[^a-zA-Z0-9])(SG\.[a-zA-Z0-9_-]{22}\.[a-zA-Z0-9_-]{43})([^a-zA-Z0-9]|$)").unwrap(),
-                severity: Severity::High,
-                confidence: Confidence::High,
-                recommendation: "Revoke the SendGrid key and use environment variables.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── Twilio ───────────────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-018",
-                title: "Twilio API Key",
-                description: "Twilio API key or auth token found",
-                pattern: Regex::new(r"(?i)(twilio[_-]?(auth[_-]?token|api[_-]?key|api[_-]?secret))\s*[=:]\s*[a-f0-9]{32}").unwrap(),
-                severity: Severity::High,
-                confidence: Confidence::High,
-                recommendation: "Rotate the Twilio credentials and use environment variables.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── Generic API Key ──────────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-019",
-                title: "Generic API Key Assignment",
-                description: "Potential API key or secret assignment found",
-                pattern: Regex::new(r#"(?i)(api[_-]?key|api[_-]?secret|secret[_-]?key|access[_-]?key)\s*[=:]\s*["'][a-zA-Z0-9_\-/.+=]{16,}["']"#).unwrap(),
-                severity: Severity::Medium,
-                confidence: Confidence::Low,
-                recommendation: "Verify if this is a real secret. If so, use environment variables or a secrets manager.",
-                cwe_id: "CWE-798",
-            },
-
-            // ── .env file patterns ───────────────────────────
-            SecretPattern {
-                rule_id: "ANTY-SEC-020",
-                title: "Secret in Environment File",
-                description: "Potential secret value found in an environment file that may be committed to source control",
-                pattern: Regex::new(r#"(?im)^(DB_PASSWORD|DATABASE_PASSWORD|SECRET_KEY|API_SECRET|PRIVATE_KEY|AUTH_TOKEN|ENCRYPTION_KEY)\s*=\s*\S{4,}"#).unwrap(),
-                severity: Severity::High,
-                confidence: Confidence::Medium,
-                recommendation: "Ensure .env files are in .gitignore. Use .env.example with placeholder values instead.",
-                cwe_id: "CWE-798",
-            },
-        ];
-
-        SecretsAgent { patterns }
+        // Patterns come from the rule bundle so new providers can ship
+        // without a new release (see `anty update-rules`)
+        let patterns: Vec<SecretPattern> = bundle::active()
+            .secrets
+            .iter()
+            .map(|rule| SecretPattern {
+                rule_id: &rule.id,
+                title: &rule.title,
+                description: &rule.description,
+                pattern: Regex::new(&rule.pattern).expect("bundle patterns are validated on load"),
+                requires: rule
+                    .requires
+                    .as_deref()
+                    .map(|r| Regex::new(r).expect("bundle patterns are validated on load")),
+                severity: rule.severity,
+                confidence: rule.confidence,
+                recommendation: &rule.recommendation,
+                cwe_id: &rule.cwe,
+                tags: rule.tags.clone(),
+            })
+            .collect();
+
+        let set = agents::pattern_set(patterns.iter().map(|p| p.pattern.as_str()));
+
+        // These are driven by code rather than a line pattern, so they are
+        // defined here instead of in the bundle
+        let k8s_secret = SecretPattern {
+            rule_id: "ANTY-SEC-048",
+            title: "Kubernetes Secret with Committed Data",
+            description: "A Kubernetes Secret manifest contains a value under data or stringData. Base64 in data is an encoding, not encryption: anyone with the repository can read it.",
+            pattern: Regex::new(r#"(?m)^kind:\s*["']?Secret["']?\s*$"#).expect("valid regex"),
+            requires: None,
+            severity: Severity::High,
+            confidence: Confidence::Medium,
+            recommendation: "Do not commit Secret values. Create the Secret at deploy time, or use Sealed Secrets, SOPS, or External Secrets Operator, and rotate any value that was committed.",
+            cwe_id: "CWE-798",
+            tags: vec!["secrets".to_string(), "container".to_string()],
+        };
+        let helm_value = SecretPattern {
+            rule_id: "ANTY-SEC-049",
+            title: "Hardcoded Secret in Helm Values",
+            description: "A Helm values file sets a password, token, or secret key to a literal value, which ends up in the chart's repository and release history",
+            pattern: Regex::new(r"(?i)(passw(or)?d|token|secret|api[_-]?key|credentials?)").expect("valid regex"),
+            requires: None,
+            severity: Severity::High,
+            confidence: Confidence::Medium,
+            recommendation: "Leave the value empty in values.yaml and pass it at install time (--set, a values file outside the repository), or reference an existing Secret (existingSecret).",
+            cwe_id: "CWE-798",
+            tags: vec!["secrets".to_string(), "container".to_string()],
+        };
+        let credential_file = SecretPattern {
+            rule_id: "ANTY-SEC-050",
+            title: "Committed Credential File",
+            description: "A file that exists to hold credentials is committed; whatever format the secret is in, treat it as leaked",
+            // Documents the files recognized; CredentialFile::detect decides
+            pattern: Regex::new(r"(?i)(?:^|/)(?:id_(?:rsa|dsa|ecdsa|ed25519)(?:_sk)?|\.aws/(?:credentials|config)|\.kube/config|[^/]*kubeconfig|[^/]*\.json|\.pgpass|pgpass\.conf)$")
+                .expect("valid regex"),
+            requires: None,
+            severity: Severity::Critical,
+            confidence: Confidence::High,
+            recommendation: "Remove the file from the repository and its history, add it to .gitignore, and rotate what it holds: replace the SSH key, deactivate the cloud access key or token, or change the database password.",
+            cwe_id: "CWE-522",
+            tags: vec!["secrets".to_string(), "low-noise".to_string()],
+        };
+
+        SecretsAgent {
+            patterns,
+            set,
+            k8s_secret,
+            helm_value,
+            credential_file,
+        }
     }
 
     /// Check if a file should be skipped (binary, lock files, etc.)
@@ -294,7 +188,7 @@ impl SecretsAgent {
     }
 
     /// Redact the actual secret value in evidence
-    fn redact_evidence(line: &str, secret_match: &str) -> String {
+    pub(crate) fn redact_evidence(line: &str, secret_match: &str) -> String {
         if secret_match.len() <= 8 {
             return line.replace(secret_match, "****");
         }
@@ -303,6 +197,154 @@ impl SecretsAgent {
         let redacted = format!("{}…****…{}", prefix, suffix);
         line.replace(secret_match, &redacted)
     }
+
+    fn finding(file: &ScannedFile, hit: Hit) -> Finding {
+        let pattern = hit.pattern;
+        // Sample code only gets the benefit of the doubt for patterns that
+        // could be guessing in the first place
+        let (severity, confidence) =
+            if pattern.confidence < Confidence::High && heuristics::is_sample_path(&file.rel_path) {
+                (Severity::Low, Confidence::Low)
+            } else {
+                (pattern.severity, pattern.confidence)
+            };
+
+        debug!(
+            "Secret found: {} in {}:{}",
+            pattern.rule_id,
+            file.rel_path.display(),
+            hit.line
+        );
+
+        Finding {
+            id: if hit.repeat {
+                Finding::generate_id_at(pattern.rule_id, &file.rel_path, hit.line, hit.column)
+            } else {
+                Finding::generate_id(pattern.rule_id, &file.rel_path, hit.line)
+            },
+            rule_id: pattern.rule_id.to_string(),
+            severity,
+            confidence,
+            agent: "secrets".to_string(),
+            title: pattern.title.to_string(),
+            description: hit.description,
+            file_path: file.rel_path.clone(),
+            line_start: hit.line,
+            line_end: hit.line_end,
+            column: hit.column,
+            column_end: hit.column_end,
+            evidence: hit.evidence,
+            recommendation: pattern.recommendation.to_string(),
+            cwe_id: Some(pattern.cwe_id.to_string()),
+            remediation: playbooks::for_rule(pattern.rule_id),
+            secret_hash: Some(Finding::hash_secret(hit.secret)),
+            related_rules: hit.related_rules,
+            additional_locations: Vec::new(),
+            owners: Vec::new(),
+            blame: None,
+            context: None,
+            triage: None,
+            fix: None,
+            environment: None,
+            score: None,
+            docs_url: Some(rules::docs_url(pattern.rule_id)),
+            references: rules::references(pattern.cwe_id),
+        }
+    }
+
+    /// Values of Kubernetes Secrets (decoded from base64 under `data:`) and
+    /// credentials in Helm values files. A decoded value that a specific
+    /// pattern recognizes is reported under that rule; lines the line scan
+    /// already reported are left alone.
+    fn scan_manifest(&self, file: &ScannedFile, findings: &mut Vec<Finding>) {
+        let lines: Vec<&str> = file.content.lines().collect();
+        let reported: HashSet<usize> = findings.iter().map(|f| f.line_start).collect();
+
+        let mut entries: Vec<(&SecretPattern, kubernetes::SecretEntry)> = Vec::new();
+        if self.k8s_secret.pattern.is_match(&file.content) {
+            entries.extend(kubernetes::secret_entries(&file.content).into_iter().map(|e| (&self.k8s_secret, e)));
+        } else if kubernetes::is_helm_values(&file.rel_path) {
+            entries.extend(
+                kubernetes::helm_values(&file.content, &self.helm_value.pattern)
+                    .into_iter()
+                    .map(|e| (&self.helm_value, e)),
+            );
+        }
+
+        for (fallback, entry) in &entries {
+            let line_number = file.line_offset + entry.line + 1;
+            if reported.contains(&line_number) || heuristics::is_placeholder(&entry.value) {
+                continue;
+            }
+            let evidence = Self::redact_evidence(lines[entry.line].trim(), &entry.raw);
+
+            // The decoded value may be a key with a pattern of its own
+            let known = entry
+                .decoded
+                .then(|| self.set.matches(&entry.value).into_iter().next())
+                .flatten()
+                .map(|idx| &self.patterns[idx])
+                .filter(|p| p.requires.as_ref().is_none_or(|r| r.is_match(&entry.value)));
+            let (pattern, description) = match known {
+                Some(p) => (p, format!("{} (base64-encoded in a Kubernetes Secret)", p.description)),
+                None => (*fallback, fallback.description.to_string()),
+            };
+
+            findings.push(Self::finding(
+                file,
+                Hit {
+                    pattern,
+                    line: line_number,
+                    line_end: line_number,
+                    column: entry.column,
+                    column_end: entry.column + entry.raw.len(),
+                    evidence,
+                    secret: &entry.value,
+                    description,
+                    related_rules: Vec::new(),
+                    repeat: false,
+                },
+            ));
+        }
+    }
+
+    /// Credentials in a credential file, reported whether or not a line
+    /// pattern recognizes them. A value a line pattern also found keeps
+    /// the same hash, so the two fold into one finding.
+    fn scan_credential_file(&self, file: &ScannedFile, findings: &mut Vec<Finding>) {
+        let path = file.name_path();
+        let Some(kind) = CredentialFile::detect(path, &file.content) else {
+            return;
+        };
+        let lines: Vec<&str> = file.content.lines().collect();
+        for credential in kind.credentials(&file.content) {
+            if heuristics::is_placeholder(&credential.value) {
+                continue;
+            }
+            let line = lines[credential.line].trim();
+            let evidence = match credential.raw {
+                Some(ref raw) => Self::redact_evidence(line, raw),
+                None if line.contains("PRIVATE KEY") => line.to_string(),
+                // The first line of a key in another format is key material
+                None => "…".to_string(),
+            };
+            findings.push(Self::finding(
+                file,
+                Hit {
+                    pattern: &self.credential_file,
+                    line: file.line_offset + credential.line + 1,
+                    line_end: file.line_offset + credential.line_end + 1,
+                    column: credential.column,
+                    column_end: credential.column + credential.raw.as_ref().map_or(0, String::len),
+                    evidence,
+                    secret: &credential.value,
+                    description: format!("{}: {}", self.credential_file.description, kind.describe()),
+                    related_rules: Vec::new(),
+                    repeat: false,
+                },
+            ));
+        }
+    }
 }
 
 impl SecurityAgent for SecretsAgent {
@@ -320,56 +362,182 @@ impl SecurityAgent for SecretsAgent {
         }
 
         let mut findings = Vec::new();
-
-        for (line_num, line) in file.content.lines().enumerate() {
-            let line_number = line_num + 1; // 1-based
+        let lines: Vec<&str> = file.content.lines().collect();
+        let comment = comments::comment_lines(&file.content, file.language);
+        // Last line of the private key block being skipped
+        let mut key_end = None;
+
+        for (line_num, line) in lines.iter().enumerate() {
+            if file.out_of_time() {
+                break;
+            }
+            if key_end.is_some_and(|end| line_num <= end) {
+                continue;
+            }
+            let line_number = file.line_offset + line_num + 1; // 1-based
 
             // Skip comment-only lines that look like documentation
             let trimmed = line.trim();
-            if trimmed.starts_with("//") && trimmed.contains("example") {
+            if comment[line_num] && trimmed.contains("example") {
                 continue;
             }
 
-            for pattern in &self.patterns {
-                if let Some(m) = pattern.pattern.find(line) {
+            // Every match of every pattern on the line; where matches
+            // overlap, the most confident one is reported and the others
+            // become its related rules
+            let mut hits: Vec<LineMatch> = Vec::new();
+            for idx in self.set.matches(line).iter() {
+                let pattern = &self.patterns[idx];
+                if pattern.requires.as_ref().is_some_and(|r| !r.is_match(&file.content)) {
+                    continue;
+                }
+                let mut at = 0;
+                while let Some(m) = pattern.pattern.find_at(line, at) {
                     let matched_text = m.as_str().trim();
-
-                    // Build evidence with redacted secrets
-                    let evidence = Self::redact_evidence(line.trim(), matched_text);
-
-                    let finding = Finding {
-                        id: Finding::generate_id(
-                            pattern.rule_id,
-                            &file.rel_path,
-                            line_number,
-                        ),
-                        rule_id: pattern.rule_id.to_string(),
-                        severity: pattern.severity,
-                        confidence: pattern.confidence,
-                        agent: "secrets".to_string(),
-                        title: pattern.title.to_string(),
-                        description: pattern.description.to_string(),
-                        file_path: file.rel_path.clone(),
-                        line_start: line_number,
-                        line_end: line_number,
-                        evidence,
-                        recommendation: pattern.recommendation.to_string(),
-                        cwe_id: Some(pattern.cwe_id.to_string()),
+                    let secret = value_of(matched_text);
+                    let start = m.start() + m.as_str().find(secret).unwrap_or(0);
+                    let end = start + secret.len();
+                    // Patterns take in the character around a token, which
+                    // may start the next one: resume at the end of the value
+                    at = if end > m.start() {
+                        end
+                    } else {
+                        m.start() + line[m.start()..].chars().next().map_or(1, char::len_utf8)
                     };
 
-                    debug!(
-                        "Secret found: {} in {}:{}",
-                        pattern.rule_id,
-                        file.rel_path.display(),
-                        line_number
-                    );
+                    // "changeme", "your-api-key-here", "xxxxxxxx" and friends
+                    if heuristics::is_placeholder(secret) {
+                        debug!(
+                            "Placeholder value skipped: {} in {}:{}",
+                            pattern.rule_id,
+                            file.rel_path.display(),
+                            line_number
+                        );
+                    } else {
+                        hits.push(LineMatch { pattern, m, start, end, related_rules: Vec::new() });
+                    }
+                    if at > line.len() {
+                        break;
+                    }
+                }
+            }
+            hits.sort_by(|a, b| {
+                b.pattern
+                    .confidence
+                    .cmp(&a.pattern.confidence)
+                    .then_with(|| b.pattern.severity.cmp(&a.pattern.severity))
+                    .then_with(|| (b.end - b.start).cmp(&(a.end - a.start)))
+            });
+            let mut kept: Vec<LineMatch> = Vec::new();
+            for hit in hits {
+                match kept.iter_mut().find(|k| hit.start < k.end && k.start < hit.end) {
+                    Some(k) => {
+                        let rule = hit.pattern.rule_id.to_string();
+                        if rule != k.pattern.rule_id && !k.related_rules.contains(&rule) {
+                            k.related_rules.push(rule);
+                        }
+                    }
+                    None => kept.push(hit),
+                }
+            }
+            kept.sort_by_key(|hit| hit.start);
+            // Each finding's evidence hides every secret on the line, not just
+            // its own. Matches can share a boundary character, so a value
+            // left whole by an earlier redaction is redacted on its own.
+            let mut evidence = kept.iter().fold(line.trim().to_string(), |evidence, hit| {
+                Self::redact_evidence(&evidence, hit.m.as_str().trim())
+            });
+            for hit in &kept {
+                let value = &line[hit.start..hit.end];
+                if !value.is_empty() && evidence.contains(value) {
+                    evidence = Self::redact_evidence(&evidence, value);
+                }
+            }
 
-                    findings.push(finding);
-                    break; // One finding per line per agent
+            // A rule matching twice on the line needs the column to tell its findings apart
+            let mut rules_on_line = HashSet::new();
+            for LineMatch { pattern, m, start, end: hit_end, related_rules } in kept {
+                let matched_text = m.as_str().trim();
+                let repeat = !rules_on_line.insert(pattern.rule_id);
+
+                if pattern.rule_id == PRIVATE_KEY_RULE {
+                    if let Some(key) = pem::key_block(&lines, line_num) {
+                        findings.push(Self::finding(
+                            file,
+                            Hit {
+                                pattern,
+                                line: line_number,
+                                line_end: file.line_offset + key.end + 1,
+                                column: m.start() + 1,
+                                column_end: m.end() + 1,
+                                // The header only; the key itself stays out of the report
+                                evidence: line[..m.end()].trim().to_string(),
+                                secret: if key.body.is_empty() { matched_text } else { &key.body },
+                                description: if key.end > line_num {
+                                    format!(
+                                        "{}: {} ({} lines)",
+                                        pattern.description,
+                                        key.describe(),
+                                        key.end - line_num + 1
+                                    )
+                                } else {
+                                    format!("{}: {}", pattern.description, key.describe())
+                                },
+                                related_rules,
+                                repeat,
+                            },
+                        ));
+                        key_end = Some(key.end);
+                        continue;
+                    }
                 }
+
+                findings.push(Self::finding(
+                    file,
+                    Hit {
+                        pattern,
+                        line: line_number,
+                        line_end: line_number,
+                        column: start + 1,
+                        column_end: hit_end + 1,
+                        evidence: evidence.clone(),
+                        secret: &line[start..hit_end],
+                        description: pattern.description.to_string(),
+                        related_rules,
+                        repeat,
+                    },
+                ));
             }
         }
 
+        let extension = file.rel_path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+        if matches!(extension, "yaml" | "yml") {
+            self.scan_manifest(file, &mut findings);
+        }
+        self.scan_credential_file(file, &mut findings);
+
         findings
     }
+
+    fn rules(&self) -> Vec<RuleMeta> {
+        self.patterns
+            .iter()
+            .chain([&self.k8s_secret, &self.helm_value, &self.credential_file])
+            .map(|p| RuleMeta {
+                id: p.rule_id.to_string(),
+                agent: "secrets".to_string(),
+                title: p.title.to_string(),
+                description: p.description.to_string(),
+                severity: p.severity,
+                confidence: p.confidence,
+                recommendation: p.recommendation.to_string(),
+                cwe_id: Some(p.cwe_id.to_string()),
+                pattern: p.pattern.as_str().to_string(),
+                remediation: playbooks::for_rule(p.rule_id),
+                docs_url: rules::docs_url(p.rule_id),
+                references: rules::references(p.cwe_id),
+                tags: p.tags.iter().map(|t| t.to_string()).collect(),
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,654 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::Regex;
+use serde_yaml::{Mapping, Value};
+use tracing::{debug, info, warn};
+
+use crate::agents::{Language, ScannedFile, SecurityAgent};
+use crate::engine::comments;
+use crate::report::finding::{Confidence, Finding, Severity};
+use crate::rules::{self, RuleMeta};
+
+/// What a `$X` metavariable matches: a name, attribute chain, or index
+/// expression (optionally called with flat arguments), or a string literal.
+/// Semgrep matches any expression; this covers the common cases on one line.
+const METAVARIABLE: &str = r#"(?:[\w$.\[\]]+(?:\([^()]*\))?|"[^"]*"|'[^']*')"#;
+
+/// Semgrep language names that mean "any file"
+const GENERIC_LANGUAGES: &[&str] = &["generic", "regex", "none"];
+
+/// One `pattern` or `pattern-regex` translated to a line regex
+struct LinePattern {
+    regex: Regex,
+    /// Metavariable name → capture groups of each of its occurrences
+    /// (all occurrences must bind the same text)
+    metavariables: Vec<(String, Vec<String>)>,
+    /// Code patterns skip comment lines; `pattern-regex` sees everything
+    code: bool,
+}
+
+/// Which paths a rule applies to (`paths.include` / `paths.exclude`)
+struct PathFilter {
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+}
+
+/// A Semgrep rule that the supported subset can express
+struct SemgrepRule {
+    id: String,
+    title: String,
+    message: String,
+    severity: Severity,
+    confidence: Confidence,
+    recommendation: String,
+    cwe_id: Option<String>,
+    docs_url: Option<String>,
+    references: Vec<String>,
+    /// `metadata.tags`, for `--tags` / `--exclude-tags`
+    tags: Vec<String>,
+    /// None = any file (`generic`, `regex`)
+    languages: Option<Vec<Language>>,
+    paths: PathFilter,
+    /// Alternatives (`pattern-either`); a line matching any of them matches
+    patterns: Vec<LinePattern>,
+    /// `metavariable-regex` constraints, anchored at the start like Semgrep's
+    metavariable_regex: Vec<(String, Regex)>,
+}
+
+/// Runs Semgrep-style YAML rules loaded with `--rules-dir` or from rule packs.
+///
+/// Level A agent — supports a subset of Semgrep: `pattern`, `pattern-regex`,
+/// `pattern-either`, and `patterns` combining one of those with
+/// `metavariable-regex`. Each pattern is translated to a regex and matched
+/// against single lines. Rules using anything else (`pattern-not`,
+/// `pattern-inside`, taint mode, ...) are skipped when loading.
+pub struct SemgrepAgent {
+    rules: Vec<SemgrepRule>,
+}
+
+impl SemgrepAgent {
+    /// Load the rules under each of `dirs` (`--rules-dir` and rule packs)
+    /// into one agent
+    pub fn load_all(dirs: &[PathBuf]) -> Result<Self> {
+        let mut rules = Vec::new();
+        for dir in dirs {
+            rules.extend(Self::load(dir)?.rules);
+        }
+        Ok(SemgrepAgent { rules })
+    }
+
+    /// Load every rule from the `*.yml` / `*.yaml` files under `dir`
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut files = Vec::new();
+        collect_yaml_files(dir, &mut files)
+            .with_context(|| format!("Could not read rules directory {}", dir.display()))?;
+        files.sort();
+
+        let mut rules = Vec::new();
+        let mut skipped = 0;
+        for path in &files {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Could not read {}", path.display()))?;
+            let doc: Value = match serde_yaml::from_str(&content) {
+                Ok(doc) => doc,
+                Err(e) => {
+                    warn!("Skipping {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            // Other YAML files (CI configs, test targets) have no `rules:` list
+            let Some(list) = doc.get("rules").and_then(Value::as_sequence) else {
+                debug!("No rules in {}", path.display());
+                continue;
+            };
+            for raw in list {
+                match compile(raw) {
+                    Ok(rule) => rules.push(rule),
+                    Err(reason) => {
+                        let id = raw.get("id").and_then(Value::as_str).unwrap_or("<no id>");
+                        debug!("Skipping Semgrep rule {} in {}: {}", id, path.display(), reason);
+                        skipped += 1;
+                    }
+                }
+            }
+        }
+
+        if skipped > 0 {
+            info!(
+                "Loaded {} Semgrep rules from {} ({} skipped: unsupported syntax, see -v)",
+                rules.len(),
+                dir.display(),
+                skipped
+            );
+        } else {
+            info!("Loaded {} Semgrep rules from {}", rules.len(), dir.display());
+        }
+        if rules.is_empty() {
+            warn!("No usable Semgrep rules in {}", dir.display());
+        }
+        Ok(SemgrepAgent { rules })
+    }
+}
+
+impl SecurityAgent for SemgrepAgent {
+    fn name(&self) -> &str {
+        "semgrep"
+    }
+
+    fn description(&self) -> &str {
+        "Runs Semgrep-style YAML rules from --rules-dir and rule packs"
+    }
+
+    fn interested_in(&self, file: &ScannedFile) -> bool {
+        self.rules.iter().any(|r| r.applies_to(file))
+    }
+
+    fn scan_file(&self, file: &ScannedFile) -> Vec<Finding> {
+        let applicable: Vec<&SemgrepRule> = self
+            .rules
+            .iter()
+            .filter(|r| r.applies_to(file))
+            .collect();
+        if applicable.is_empty() {
+            return Vec::new();
+        }
+
+        let mut findings = Vec::new();
+        let comment_lines = comments::comment_lines(&file.content, file.language);
+        for (line_num, line) in file.content.lines().enumerate() {
+            if file.out_of_time() {
+                break;
+            }
+            let line_number = file.line_offset + line_num + 1;
+            let comment = comment_lines[line_num];
+
+            for rule in &applicable {
+                let Some((columns, bindings)) = rule.find(line, comment) else {
+                    continue;
+                };
+                debug!("Semgrep: {} in {}:{}", rule.id, file.rel_path.display(), line_number);
+
+                findings.push(Finding {
+                    id: Finding::generate_id(&rule.id, &file.rel_path, line_number),
+                    rule_id: rule.id.clone(),
+                    severity: rule.severity,
+                    confidence: rule.confidence,
+                    agent: "semgrep".to_string(),
+                    title: rule.title.clone(),
+                    description: interpolate(&rule.message, &bindings),
+                    file_path: file.rel_path.clone(),
+                    line_start: line_number,
+                    line_end: line_number,
+                    column: columns.start + 1,
+                    column_end: columns.end + 1,
+                    evidence: line.trim().to_string(),
+                    recommendation: rule.recommendation.clone(),
+                    cwe_id: rule.cwe_id.clone(),
+                    remediation: None,
+                    secret_hash: None,
+                    related_rules: Vec::new(),
+                    additional_locations: Vec::new(),
+                    owners: Vec::new(),
+                    blame: None,
+                    context: None,
+                    triage: None,
+                    fix: None,
+                    environment: None,
+                    score: None,
+                    docs_url: rule.docs_url.clone(),
+                    references: rule.references.clone(),
+                });
+            }
+        }
+
+        findings
+    }
+
+    fn languages(&self) -> Vec<Language> {
+        let mut languages = Vec::new();
+        for l in self.rules.iter().filter_map(|r| r.languages.as_ref()).flatten() {
+            if !languages.contains(l) {
+                languages.push(*l);
+            }
+        }
+        languages
+    }
+
+    fn rules(&self) -> Vec<RuleMeta> {
+        self.rules
+            .iter()
+            .map(|r| RuleMeta {
+                id: r.id.clone(),
+                agent: "semgrep".to_string(),
+                title: r.title.clone(),
+                description: r.message.clone(),
+                severity: r.severity,
+                confidence: r.confidence,
+                recommendation: r.recommendation.clone(),
+                cwe_id: r.cwe_id.clone(),
+                pattern: r
+                    .patterns
+                    .iter()
+                    .map(|p| p.regex.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" | "),
+                remediation: None,
+                docs_url: r.docs_url.clone().unwrap_or_default(),
+                references: r.references.clone(),
+                tags: r.tags.clone(),
+            })
+            .collect()
+    }
+}
+
+impl SemgrepRule {
+    fn applies_to(&self, file: &ScannedFile) -> bool {
+        if let Some(ref languages) = self.languages {
+            if !file.language.is_some_and(|l| languages.contains(&l)) {
+                return false;
+            }
+        }
+        let path = &file.rel_path;
+        self.paths.include.as_ref().is_none_or(|g| g.is_match(path))
+            && !self.paths.exclude.as_ref().is_some_and(|g| g.is_match(path))
+    }
+
+    /// Columns and metavariable bindings of the first match on `line`
+    fn find<'l>(&self, line: &'l str, comment: bool) -> Option<(Range<usize>, HashMap<&str, &'l str>)> {
+        for pattern in self.patterns.iter().filter(|p| !(comment && p.code)) {
+            'matches: for caps in pattern.regex.captures_iter(line) {
+                let mut bindings = HashMap::new();
+                for (name, groups) in &pattern.metavariables {
+                    let mut values = groups.iter().filter_map(|g| caps.name(g)).map(|m| m.as_str());
+                    let Some(first) = values.next() else {
+                        continue 'matches;
+                    };
+                    if values.any(|v| v != first) {
+                        continue 'matches;
+                    }
+                    bindings.insert(name.as_str(), first);
+                }
+                for (name, regex) in &self.metavariable_regex {
+                    if !bindings.get(name.as_str()).is_some_and(|v| regex.is_match(v)) {
+                        continue 'matches;
+                    }
+                }
+                return Some((caps.get(0).unwrap().range(), bindings));
+            }
+        }
+        None
+    }
+}
+
+/// Translate one raw rule, or say why it is outside the supported subset
+fn compile(raw: &Value) -> std::result::Result<SemgrepRule, String> {
+    let field = |key: &str| raw.get(key).and_then(Value::as_str);
+    let id = field("id").ok_or("missing id")?.to_string();
+    let message = field("message").unwrap_or_default().trim().to_string();
+
+    if let Some(mode) = field("mode") {
+        if mode != "search" {
+            return Err(format!("mode '{}'", mode));
+        }
+    }
+
+    let mut patterns = Vec::new();
+    let mut metavariable_regex = Vec::new();
+    let top_level = ["pattern", "pattern-regex", "pattern-either", "patterns"];
+    let operators: Vec<&str> = top_level.iter().copied().filter(|k| raw.get(*k).is_some()).collect();
+    match operators.as_slice() {
+        [key] => add_operator(key, &raw[*key], &mut patterns, &mut metavariable_regex, true)?,
+        [] => return Err("no pattern".to_string()),
+        _ => return Err(format!("more than one of {}", operators.join(", "))),
+    }
+    if patterns.is_empty() {
+        return Err("no positive pattern".to_string());
+    }
+
+    let languages = raw
+        .get("languages")
+        .and_then(Value::as_sequence)
+        .map(|list| list.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let languages = if languages.is_empty() || languages.iter().any(|l| GENERIC_LANGUAGES.contains(l)) {
+        None
+    } else {
+        let known: Vec<Language> = languages.iter().filter_map(|l| Language::from_name(l)).collect();
+        if known.is_empty() {
+            return Err(format!("unsupported languages {}", languages.join(", ")));
+        }
+        Some(known)
+    };
+
+    let paths = raw.get("paths");
+    let paths = PathFilter {
+        include: path_globs(paths.and_then(|p| p.get("include")))?,
+        exclude: path_globs(paths.and_then(|p| p.get("exclude")))?,
+    };
+
+    let metadata = raw.get("metadata");
+    let meta_list = |key: &str| -> Vec<String> {
+        match metadata.and_then(|m| m.get(key)) {
+            Some(Value::String(s)) => vec![s.clone()],
+            Some(Value::Sequence(list)) => list.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+            _ => Vec::new(),
+        }
+    };
+    // "CWE-89: Improper Neutralization of ..." → "CWE-89"
+    let cwe_id = meta_list("cwe")
+        .first()
+        .and_then(|c| c.split(':').next())
+        .map(|c| c.trim().to_string());
+    let mut references = cwe_id.as_deref().map(rules::references).unwrap_or_default();
+    for reference in meta_list("references") {
+        if !references.iter().any(|r| r.trim_end_matches('/') == reference.trim_end_matches('/')) {
+            references.push(reference);
+        }
+    }
+    let docs_url = meta_list("source")
+        .into_iter()
+        .next()
+        .or_else(|| meta_list("references").into_iter().next());
+    let confidence = match meta_list("confidence").first().map(|c| c.to_uppercase()).as_deref() {
+        Some("HIGH") => Confidence::High,
+        Some("LOW") => Confidence::Low,
+        _ => Confidence::Medium,
+    };
+
+    let severity = match field("severity").unwrap_or_default().to_uppercase().as_str() {
+        "CRITICAL" => Severity::Critical,
+        "ERROR" | "HIGH" => Severity::High,
+        "INFO" | "LOW" => Severity::Low,
+        _ => Severity::Medium,
+    };
+
+    let recommendation = match field("fix") {
+        Some(fix) => format!("Replace with: {}", fix.trim()),
+        None => "See the rule's references for how to fix this.".to_string(),
+    };
+
+    Ok(SemgrepRule {
+        title: title_from_id(&id),
+        id,
+        message,
+        severity,
+        confidence,
+        recommendation,
+        cwe_id,
+        docs_url,
+        references,
+        tags: meta_list("tags"),
+        languages,
+        paths,
+        patterns,
+        metavariable_regex,
+    })
+}
+
+/// Add the patterns of one operator. `top` is false inside `patterns`,
+/// where `metavariable-regex` may appear next to one positive pattern.
+fn add_operator(
+    key: &str,
+    value: &Value,
+    patterns: &mut Vec<LinePattern>,
+    metavariable_regex: &mut Vec<(String, Regex)>,
+    top: bool,
+) -> std::result::Result<(), String> {
+    match key {
+        "pattern" => {
+            let source = value.as_str().ok_or("pattern is not a string")?;
+            patterns.push(translate(source)?);
+        }
+        "pattern-regex" => {
+            let source = value.as_str().ok_or("pattern-regex is not a string")?;
+            let regex = Regex::new(source).map_err(|e| format!("invalid pattern-regex: {}", e))?;
+            patterns.push(LinePattern {
+                regex,
+                metavariables: Vec::new(),
+                code: false,
+            });
+        }
+        "pattern-either" => {
+            for item in value.as_sequence().ok_or("pattern-either is not a list")? {
+                let (key, value) = single_entry(item)?;
+                if key != "pattern" && key != "pattern-regex" {
+                    return Err(format!("{} inside pattern-either", key));
+                }
+                add_operator(key, value, patterns, metavariable_regex, false)?;
+            }
+        }
+        "patterns" if top => {
+            for item in value.as_sequence().ok_or("patterns is not a list")? {
+                let (key, value) = single_entry(item)?;
+                match key {
+                    "pattern" | "pattern-regex" | "pattern-either" if patterns.is_empty() => {
+                        add_operator(key, value, patterns, metavariable_regex, false)?
+                    }
+                    "metavariable-regex" => {
+                        let name = value
+                            .get("metavariable")
+                            .and_then(Value::as_str)
+                            .ok_or("metavariable-regex without metavariable")?;
+                        let source = value
+                            .get("regex")
+                            .and_then(Value::as_str)
+                            .ok_or("metavariable-regex without regex")?;
+                        let regex = Regex::new(&format!("^(?:{})", source))
+                            .map_err(|e| format!("invalid metavariable-regex: {}", e))?;
+                        metavariable_regex.push((name.trim_start_matches('$').to_string(), regex));
+                    }
+                    _ => return Err(format!("{} inside patterns", key)),
+                }
+            }
+        }
+        _ => return Err(format!("nested {}", key)),
+    }
+    Ok(())
+}
+
+/// The key and value of a one-entry mapping like `- pattern: foo(...)`
+fn single_entry(item: &Value) -> std::result::Result<(&str, &Value), String> {
+    let map: &Mapping = item.as_mapping().ok_or("list item is not a mapping")?;
+    match map.iter().next() {
+        Some((Value::String(key), value)) if map.len() == 1 => Ok((key.as_str(), value)),
+        _ => Err("list item is not a single operator".to_string()),
+    }
+}
+
+/// A token of a Semgrep code pattern
+enum Token {
+    Word(String),
+    Metavariable(String),
+    /// `...`, `$...ARGS`
+    Ellipsis,
+    /// `"..."`: any string literal with this quote
+    AnyString(char),
+    Literal(String),
+}
+
+impl Token {
+    fn is_wordy(&self) -> bool {
+        matches!(self, Token::Word(_) | Token::Metavariable(_))
+    }
+}
+
+/// Translate a Semgrep code pattern into a regex over one line.
+///
+/// Whitespace between tokens is optional (required only between two
+/// words), `...` matches anything, `"..."` any string literal, and `$X` a
+/// simple expression, with repeated metavariables required to match the
+/// same text.
+fn translate(pattern: &str) -> std::result::Result<LinePattern, String> {
+    let chars: Vec<char> = pattern.trim().chars().collect();
+    let mut tokens: Vec<(Token, bool)> = Vec::new(); // (token, whitespace before)
+    let mut space = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let rest: String = chars[i..].iter().take(4).collect();
+        let token = if c.is_whitespace() {
+            space = true;
+            i += 1;
+            continue;
+        } else if rest.starts_with("...") {
+            i += 3;
+            Token::Ellipsis
+        } else if rest.starts_with("$...") {
+            i += 4;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            Token::Ellipsis
+        } else if c == '$' && chars.get(i + 1).is_some_and(|n| n.is_ascii_uppercase() || *n == '_') {
+            let start = i + 1;
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_uppercase() || chars[i].is_ascii_digit() || chars[i] == '_') {
+                i += 1;
+            }
+            Token::Metavariable(chars[start..i].iter().collect())
+        } else if c == '"' || c == '\'' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i = (i + 1).min(chars.len());
+            let literal: String = chars[start..i].iter().collect();
+            if literal[1..].trim_end_matches(c) == "..." {
+                Token::AnyString(c)
+            } else {
+                Token::Literal(literal)
+            }
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            Token::Word(chars[start..i].iter().collect())
+        } else {
+            i += 1;
+            Token::Literal(c.to_string())
+        };
+        tokens.push((token, space));
+        space = false;
+    }
+
+    if tokens.is_empty() {
+        return Err("empty pattern".to_string());
+    }
+
+    // An argument-list `...` may be empty, taking its comma with it:
+    // `f(..., x)` matches `f(x)` and `f(x, ...)` matches `f(x)`
+    let is_comma = |i: usize| matches!(tokens.get(i), Some((Token::Literal(t), _)) if t == ",");
+    let is_ellipsis = |i: usize| matches!(tokens.get(i), Some((Token::Ellipsis, _)));
+
+    let mut source = String::new();
+    let mut metavariables: Vec<(String, Vec<String>)> = Vec::new();
+    let mut group = 0;
+    let mut skip = false;
+    for (index, (token, space)) in tokens.iter().enumerate() {
+        if std::mem::take(&mut skip) {
+            continue;
+        }
+        if is_ellipsis(index) && is_comma(index + 1) {
+            source.push_str(r"\s*(?:.*?,)?");
+            skip = true;
+            continue;
+        }
+        if is_comma(index) && is_ellipsis(index + 1) {
+            source.push_str(r"(?:\s*,.*?)?");
+            skip = true;
+            continue;
+        }
+        if index == 0 {
+            if let Token::Word(_) = token {
+                source.push_str(r"\b");
+            }
+        } else if tokens[index - 1].0.is_wordy() && token.is_wordy() {
+            source.push_str(if *space { r"\s+" } else { "" });
+        } else {
+            source.push_str(r"\s*");
+        }
+        match token {
+            Token::Word(word) => source.push_str(&regex::escape(word)),
+            Token::Literal(text) => source.push_str(&regex::escape(text)),
+            Token::Ellipsis => source.push_str(".*?"),
+            Token::AnyString(quote) => source.push_str(&format!("{0}[^{0}]*{0}", quote)),
+            Token::Metavariable(name) => {
+                let group_name = format!("mv{}", group);
+                group += 1;
+                source.push_str(&format!("(?P<{}>{})", group_name, METAVARIABLE));
+                match metavariables.iter_mut().find(|(n, _)| n == name) {
+                    Some((_, groups)) => groups.push(group_name),
+                    None => metavariables.push((name.clone(), vec![group_name])),
+                }
+            }
+        }
+    }
+    if let Some((Token::Word(_), _)) = tokens.last() {
+        source.push_str(r"\b");
+    }
+
+    let regex = Regex::new(&source).map_err(|e| format!("pattern does not translate: {}", e))?;
+    Ok(LinePattern {
+        regex,
+        metavariables,
+        code: true,
+    })
+}
+
+/// Semgrep path globs match anywhere in the tree unless they start with `/`
+fn path_globs(value: Option<&Value>) -> std::result::Result<Option<GlobSet>, String> {
+    let Some(list) = value.and_then(Value::as_sequence) else {
+        return Ok(None);
+    };
+    let mut builder = GlobSetBuilder::new();
+    for pattern in list.iter().filter_map(Value::as_str) {
+        let base = match pattern.strip_prefix('/') {
+            Some(anchored) => anchored.trim_end_matches('/').to_string(),
+            None => format!("**/{}", pattern.trim_end_matches('/')),
+        };
+        for glob in [base.clone(), format!("{}/**", base)] {
+            builder.add(Glob::new(&glob).map_err(|e| format!("invalid path glob '{}': {}", pattern, e))?);
+        }
+    }
+    builder.build().map(Some).map_err(|e| e.to_string())
+}
+
+/// Substitute `$X` in a message with what the metavariable matched
+fn interpolate(message: &str, bindings: &HashMap<&str, &str>) -> String {
+    let mut names: Vec<&&str> = bindings.keys().collect();
+    // $FOO before $FO
+    names.sort_by_key(|n| std::cmp::Reverse(n.len()));
+    let mut out = message.to_string();
+    for name in names {
+        out = out.replace(&format!("${}", name), bindings[name]);
+    }
+    out
+}
+
+/// `python.lang.security.audit.eval-detected` → "Eval detected"
+fn title_from_id(id: &str) -> String {
+    let last = id.rsplit('.').next().unwrap_or(id).replace(['-', '_'], " ");
+    let mut chars = last.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => id.to_string(),
+    }
+}
+
+fn collect_yaml_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_yaml_files(&path, files)?;
+        } else if path.extension().is_some_and(|e| e == "yml" || e == "yaml") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,413 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use tracing::debug;
+
+use crate::agents::{ScannedFile, SecurityAgent};
+use crate::engine::comments;
+use crate::report::finding::{Confidence, Finding, Severity};
+use crate::rules::{self, RuleMeta};
+
+/// Which web server a config file is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ServerConfig {
+    Nginx,
+    Apache,
+}
+
+/// Directories that hold site configs, often without an extension
+const CONFIG_DIRS: &[&str] = &[
+    "sites-available",
+    "sites-enabled",
+    "conf.d",
+    "conf-available",
+    "conf-enabled",
+    "vhosts.d",
+];
+
+static NGINX_SYNTAX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*(?:(?:http|server|events|upstream\s+\S+|location\s[^{\n]*)\s*\{|(?:listen|server_name|ssl_protocols|add_header|proxy_pass|autoindex|server_tokens|root)\s[^\n]*;)").unwrap()
+});
+
+static APACHE_SYNTAX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?mi)^[ \t]*(?:<(?:VirtualHost|Directory|Location|IfModule|Files)\b|(?:ServerName|DocumentRoot|SSLEngine|SSLProtocol|ServerTokens|Options)\s)").unwrap()
+});
+
+impl ServerConfig {
+    /// Known config names are taken as they are; other `.conf` files and
+    /// files in site directories are recognised by their syntax
+    fn detect(file: &ScannedFile) -> Option<Self> {
+        let path = file.name_path();
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        match name.as_str() {
+            "nginx.conf" => return Some(ServerConfig::Nginx),
+            "httpd.conf" | "apache2.conf" | ".htaccess" => return Some(ServerConfig::Apache),
+            _ => {}
+        }
+        let in_config_dir = path
+            .parent()
+            .and_then(|p| p.file_name())
+            .is_some_and(|dir| CONFIG_DIRS.contains(&dir.to_string_lossy().as_ref()));
+        if !name.ends_with(".conf") && !in_config_dir {
+            return None;
+        }
+        if NGINX_SYNTAX.is_match(&file.content) {
+            Some(ServerConfig::Nginx)
+        } else if APACHE_SYNTAX.is_match(&file.content) {
+            Some(ServerConfig::Apache)
+        } else {
+            None
+        }
+    }
+}
+
+/// How a rule is checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Check {
+    /// `pattern` matches a directive on a non-comment line; nginx allows
+    /// several on one line, as in `location / { autoindex on; }`
+    Line,
+    /// `pattern` matches a directive setting a response header; sites are
+    /// reported when no config in the project sets it
+    MissingHeader {
+        /// Only sites served over TLS need it
+        tls_only: bool,
+    },
+}
+
+struct WebPattern {
+    rule_id: &'static str,
+    title: &'static str,
+    description: &'static str,
+    pattern: Regex,
+    check: Check,
+    severity: Severity,
+    confidence: Confidence,
+    recommendation: &'static str,
+    cwe_id: &'static str,
+    tags: &'static [&'static str],
+}
+
+/// A `server {}` block or `<VirtualHost>` section
+struct Site<'a> {
+    /// Line of its opening, 0-based
+    line: usize,
+    opening: &'a str,
+    body: &'a str,
+}
+
+/// Checks nginx and Apache configs: directory listings, missing security
+/// headers, legacy TLS, proxying to a client-chosen host, and version
+/// disclosure.
+///
+/// Level A agent — regex-based over directives, plus a project pass for the
+/// headers, which are often set once in a shared snippet.
+pub struct WebServerAgent {
+    patterns: Vec<WebPattern>,
+    /// Enables a legacy protocol: `TLSv1`, `+TLSv1.1`, `SSLv3`, or `all`
+    protocol: Regex,
+}
+
+impl WebServerAgent {
+    pub fn new() -> Self {
+        let patterns = vec![
+            WebPattern {
+                rule_id: "ANTY-WEB-001",
+                title: "Directory Listing Enabled",
+                description: "The server lists the files of directories without an index page, exposing backups, configs, and other files never meant to be linked",
+                pattern: Regex::new(r"(?i)(?:^|[{;]\s*)(?:autoindex\s+on\s*;|Options\s+(?:[^#]*\s)?\+?Indexes\b)").unwrap(),
+                check: Check::Line,
+                severity: Severity::Medium,
+                confidence: Confidence::High,
+                recommendation: "Remove `autoindex on` (nginx) or use `Options -Indexes` (Apache), and serve an index page where a listing is really wanted.",
+                cwe_id: "CWE-548",
+                tags: &["web-server", "config", "low-noise"],
+            },
+            WebPattern {
+                rule_id: "ANTY-WEB-002",
+                title: "Missing X-Frame-Options Header",
+                description: "No server config sets X-Frame-Options or a CSP frame-ancestors, so other sites can frame these pages for clickjacking",
+                pattern: Regex::new(r#"(?im)^[ \t]*(?:add_header|more_set_headers|Header\s+(?:always\s+)?(?:set|append|add|merge|setifempty))\s+["']?(?:X-Frame-Options|Content-Security-Policy\b[^\n]*frame-ancestors)"#).unwrap(),
+                check: Check::MissingHeader { tls_only: false },
+                severity: Severity::Low,
+                confidence: Confidence::Medium,
+                recommendation: "Add `add_header X-Frame-Options DENY always;` (nginx) or `Header always set X-Frame-Options DENY` (Apache), or a Content-Security-Policy with frame-ancestors.",
+                cwe_id: "CWE-1021",
+                tags: &["web-server", "config", "headers"],
+            },
+            WebPattern {
+                rule_id: "ANTY-WEB-003",
+                title: "Missing Strict-Transport-Security Header",
+                description: "A site served over TLS never sets Strict-Transport-Security, so browsers still try plain HTTP first, where the connection can be downgraded",
+                pattern: Regex::new(r#"(?im)^[ \t]*(?:add_header|more_set_headers|Header\s+(?:always\s+)?(?:set|append|add|merge|setifempty))\s+["']?Strict-Transport-Security"#).unwrap(),
+                check: Check::MissingHeader { tls_only: true },
+                severity: Severity::Medium,
+                confidence: Confidence::Medium,
+                recommendation: "Add `add_header Strict-Transport-Security \"max-age=31536000; includeSubDomains\" always;` (nginx) or `Header always set Strict-Transport-Security \"max-age=31536000; includeSubDomains\"` (Apache).",
+                cwe_id: "CWE-319",
+                tags: &["web-server", "config", "headers", "tls"],
+            },
+            WebPattern {
+                rule_id: "ANTY-WEB-004",
+                title: "Legacy TLS Protocol Enabled",
+                description: "TLS 1.0, TLS 1.1, or SSLv3 is enabled; these protocols have known weaknesses and are deprecated (RFC 8996)",
+                pattern: Regex::new(r"(?i)(?:^|[{;]\s*)(?:(?:proxy_)?ssl_protocols|SSL(?:Proxy)?Protocol)\s").unwrap(),
+                check: Check::Line,
+                severity: Severity::High,
+                confidence: Confidence::High,
+                recommendation: "Allow only TLS 1.2 and 1.3: `ssl_protocols TLSv1.2 TLSv1.3;` (nginx) or `SSLProtocol -all +TLSv1.2 +TLSv1.3` (Apache).",
+                cwe_id: "CWE-326",
+                tags: &["web-server", "config", "tls", "low-noise"],
+            },
+            WebPattern {
+                rule_id: "ANTY-WEB-005",
+                title: "Proxy to Client-Controlled Upstream",
+                description: "The upstream host of proxy_pass comes from the request (a query argument, header, cookie, Host, or URI capture), so a client can make the server fetch from any host it names (SSRF)",
+                pattern: Regex::new(r"(?i)(?:^|[{;]\s*)(?:proxy_pass|fastcgi_pass|uwsgi_pass|scgi_pass|grpc_pass)\s+(?:[a-z]+://)?[^/;\s$]*\$\{?(?:arg_\w+|http_\w+|cookie_\w+|host|args|query_string|request_uri|uri|document_uri|request|[1-9])\b").unwrap(),
+                check: Check::Line,
+                severity: Severity::High,
+                confidence: Confidence::Medium,
+                recommendation: "Proxy to a fixed upstream or a `map` of allowed hosts; never build the upstream host from request data.",
+                cwe_id: "CWE-918",
+                tags: &["web-server", "config", "ssrf"],
+            },
+            WebPattern {
+                rule_id: "ANTY-WEB-006",
+                title: "Server Version Disclosed",
+                description: "Error pages and the Server header give the exact server version, telling attackers which known vulnerabilities to try",
+                pattern: Regex::new(r"(?i)(?:^|[{;]\s*)(?:server_tokens\s+on\s*;|ServerTokens\s+(?:Full|OS|Minor|Min|Minimal|Major)\b|ServerSignature\s+On\b)").unwrap(),
+                check: Check::Line,
+                severity: Severity::Low,
+                confidence: Confidence::High,
+                recommendation: "Use `server_tokens off;` (nginx) or `ServerTokens Prod` and `ServerSignature Off` (Apache).",
+                cwe_id: "CWE-200",
+                tags: &["web-server", "config", "low-noise"],
+            },
+        ];
+
+        WebServerAgent {
+            patterns,
+            protocol: Regex::new(r"(?i)^[+-]?(?:TLSv1(?:\.1)?|SSLv[23]|all)$").unwrap(),
+        }
+    }
+
+    /// Whether an `ssl_protocols` or `SSLProtocol` line leaves a legacy
+    /// protocol enabled. Apache adds and removes with `+`/`-`, starting
+    /// from `all`; nginx just lists them.
+    fn enables_legacy_tls(&self, line: &str) -> bool {
+        let mut enabled: Vec<String> = Vec::new();
+        let words = line
+            .split(|c: char| c.is_whitespace() || c == ';')
+            .skip(1)
+            .filter(|w| self.protocol.is_match(w));
+        for word in words {
+            let (remove, name) = match word.strip_prefix('-') {
+                Some(name) => (true, name),
+                None => (false, word.trim_start_matches('+')),
+            };
+            let names: Vec<String> = if name.eq_ignore_ascii_case("all") {
+                vec!["tlsv1".to_string(), "tlsv1.1".to_string()]
+            } else {
+                vec![name.to_lowercase()]
+            };
+            if remove {
+                enabled.retain(|n| !names.contains(n));
+            } else {
+                enabled.extend(names);
+            }
+        }
+        !enabled.is_empty()
+    }
+
+    fn finding(pattern: &WebPattern, file: &ScannedFile, line_number: usize, column: usize, evidence: &str) -> Finding {
+        debug!("Web server: {} in {}:{}", pattern.rule_id, file.rel_path.display(), line_number);
+        Finding {
+            id: Finding::generate_id(pattern.rule_id, &file.rel_path, line_number),
+            rule_id: pattern.rule_id.to_string(),
+            severity: pattern.severity,
+            confidence: pattern.confidence,
+            agent: "web-server".to_string(),
+            title: pattern.title.to_string(),
+            description: pattern.description.to_string(),
+            file_path: file.rel_path.clone(),
+            line_start: line_number,
+            line_end: line_number,
+            column,
+            column_end: column,
+            evidence: evidence.to_string(),
+            recommendation: pattern.recommendation.to_string(),
+            cwe_id: Some(pattern.cwe_id.to_string()),
+            remediation: None,
+            secret_hash: None,
+            related_rules: Vec::new(),
+            additional_locations: Vec::new(),
+            owners: Vec::new(),
+            blame: None,
+            context: None,
+            triage: None,
+            fix: None,
+            environment: None,
+            score: None,
+            docs_url: Some(rules::docs_url(pattern.rule_id)),
+            references: rules::references(pattern.cwe_id),
+        }
+    }
+}
+
+static NGINX_SERVER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^[ \t]*server\s*\{").unwrap());
+static APACHE_VHOST: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?mi)^[ \t]*<VirtualHost\b[^>\n]*>").unwrap());
+static APACHE_VHOST_END: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)</VirtualHost\s*>").unwrap());
+
+static TLS_SITE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?mi)^[ \t]*(?:listen\s[^;#\n]*\b(?:ssl|quic)\b|ssl_certificate\s|SSLEngine\s+on\b)").unwrap()
+});
+
+/// Serves content, as opposed to only redirecting elsewhere
+static SERVES_CONTENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?mi)^[ \t]*(?:location|root|alias|try_files|index|(?:proxy|fastcgi|uwsgi|grpc)_pass|DocumentRoot|ProxyPass|Alias|ScriptAlias|WSGIScriptAlias)\s").unwrap()
+});
+
+static REDIRECT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?mi)^[ \t]*(?:return\s+30[1278]\b|Redirect(?:Permanent|Match)?\s|RewriteRule\s)").unwrap());
+
+/// The `server {}` blocks or `<VirtualHost>` sections of a config
+fn sites(file: &ScannedFile, kind: ServerConfig) -> Vec<Site<'_>> {
+    let content = file.content.as_str();
+    let opening = match kind {
+        ServerConfig::Nginx => &*NGINX_SERVER,
+        ServerConfig::Apache => &*APACHE_VHOST,
+    };
+    opening
+        .find_iter(content)
+        .map(|m| {
+            let body_start = m.end();
+            let body_end = match kind {
+                ServerConfig::Nginx => closing_brace(&content[body_start..]),
+                ServerConfig::Apache => APACHE_VHOST_END.find(&content[body_start..]).map(|e| e.start()),
+            }
+            .map_or(content.len(), |end| body_start + end);
+            Site {
+                line: content[..m.start()].matches('\n').count(),
+                opening: m.as_str().trim(),
+                body: &content[body_start..body_end],
+            }
+        })
+        .collect()
+}
+
+/// Offset of the `}` closing a block whose `{` was just passed
+fn closing_brace(body: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in body.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(i),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+impl SecurityAgent for WebServerAgent {
+    fn name(&self) -> &str {
+        "web-server"
+    }
+
+    fn description(&self) -> &str {
+        "Checks nginx and Apache configs for directory listings, missing security headers, legacy TLS, client-controlled proxying, and version disclosure"
+    }
+
+    fn interested_in(&self, file: &ScannedFile) -> bool {
+        ServerConfig::detect(file).is_some()
+    }
+
+    fn scan_file(&self, file: &ScannedFile) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        let code = comments::mask_hash(&file.content);
+
+        for (line_num, (line, code)) in file.content.lines().zip(code.lines()).enumerate() {
+            if file.out_of_time() {
+                break;
+            }
+            let trimmed = code.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            for pattern in self.patterns.iter().filter(|p| p.check == Check::Line) {
+                let Some(m) = pattern.pattern.find(trimmed) else {
+                    continue;
+                };
+                let directive = trimmed[m.start()..].trim_start_matches(['{', ';', ' ', '\t']);
+                if pattern.rule_id == "ANTY-WEB-004" && !self.enables_legacy_tls(directive) {
+                    continue;
+                }
+                let column = code.len() - code.trim_start().len() + trimmed.len() - directive.len() + 1;
+                findings.push(Self::finding(pattern, file, file.line_offset + line_num + 1, column, line.trim()));
+            }
+        }
+
+        findings
+    }
+
+    fn wants_project_file(&self, file: &ScannedFile) -> bool {
+        ServerConfig::detect(file).is_some()
+    }
+
+    /// Headers are often set once, in a snippet every site includes, so a
+    /// site is only reported when no config in the project sets the header
+    fn scan_project(&self, files: &[ScannedFile]) -> Vec<Finding> {
+        let configs: Vec<(&ScannedFile, ServerConfig)> =
+            files.iter().filter_map(|f| ServerConfig::detect(f).map(|kind| (f, kind))).collect();
+        // A header only counts as set outside comments
+        let code: Vec<String> = configs.iter().map(|(file, _)| comments::mask_hash(&file.content)).collect();
+        let mut findings = Vec::new();
+
+        for pattern in &self.patterns {
+            let Check::MissingHeader { tls_only } = pattern.check else {
+                continue;
+            };
+            if code.iter().any(|code| pattern.pattern.is_match(code)) {
+                continue;
+            }
+            for &(file, kind) in &configs {
+                for site in sites(file, kind) {
+                    let tls = TLS_SITE.is_match(site.body) || site.opening.contains(":443");
+                    if tls_only && !tls {
+                        continue;
+                    }
+                    // Framing a redirect shows nothing
+                    if !tls_only && REDIRECT.is_match(site.body) && !SERVES_CONTENT.is_match(site.body) {
+                        continue;
+                    }
+                    let line = file.content.lines().nth(site.line).unwrap_or("");
+                    let column = line.len() - line.trim_start().len() + 1;
+                    findings.push(Self::finding(pattern, file, file.line_offset + site.line + 1, column, site.opening));
+                }
+            }
+        }
+
+        findings
+    }
+
+    fn rules(&self) -> Vec<RuleMeta> {
+        self.patterns
+            .iter()
+            .map(|p| RuleMeta {
+                id: p.rule_id.to_string(),
+                agent: "web-server".to_string(),
+                title: p.title.to_string(),
+                description: p.description.to_string(),
+                severity: p.severity,
+                confidence: p.confidence,
+                recommendation: p.recommendation.to_string(),
+                cwe_id: Some(p.cwe_id.to_string()),
+                pattern: p.pattern.as_str().to_string(),
+                remediation: None,
+                docs_url: rules::docs_url(p.rule_id),
+                references: rules::references(p.cwe_id),
+                tags: p.tags.iter().map(|t| t.to_string()).collect(),
+            })
+            .collect()
+    }
+}
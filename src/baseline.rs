@@ -0,0 +1,184 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::report::finding::{self, ExpiredSuppression, Finding, SuppressedCounts};
+
+/// Location of the baseline file, relative to the scan root
+pub const BASELINE_PATH: &str = ".anty/baseline.toml";
+
+/// Directory whose `.anty/baseline.toml` applies to a scan of `scan_path`
+pub fn root_for(scan_path: &Path) -> PathBuf {
+    if scan_path.is_file() {
+        scan_path.parent().unwrap_or(scan_path).to_path_buf()
+    } else {
+        scan_path.to_path_buf()
+    }
+}
+
+/// Triage decision recorded for a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Decision {
+    /// Confirmed real issue — kept in reports
+    Confirmed,
+    /// Not actually a problem — suppressed
+    FalsePositive,
+    /// Real, but the risk is accepted — suppressed
+    AcceptedRisk,
+}
+
+impl Decision {
+    /// Whether findings with this decision are left out of reports
+    pub fn suppresses(&self) -> bool {
+        !matches!(self, Decision::Confirmed)
+    }
+}
+
+/// One recorded decision, keyed by the finding's deterministic ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub id: String,
+    pub rule_id: String,
+    #[serde(deserialize_with = "finding::deserialize_portable_path")]
+    pub file: PathBuf,
+    pub decision: Decision,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Date the decision lapses: from then on the finding is reported
+    /// again, so an accepted risk cannot be forgotten
+    #[serde(default, deserialize_with = "deserialize_date", skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+}
+
+impl Entry {
+    /// Whether the entry still suppresses its finding on `today`
+    fn active(&self, today: NaiveDate) -> bool {
+        self.decision.suppresses() && self.expires.is_none_or(|expires| today < expires)
+    }
+
+    fn expired(&self, today: NaiveDate) -> bool {
+        self.decision.suppresses() && !self.active(today)
+    }
+}
+
+/// `expires = "2025-09-01"` or a bare TOML date, `expires = 2025-09-01`
+fn deserialize_date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<NaiveDate>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Date {
+        Text(String),
+        Toml(toml::value::Datetime),
+    }
+    let text = match Option::<Date>::deserialize(deserializer)? {
+        None => return Ok(None),
+        Some(Date::Text(text)) => text,
+        Some(Date::Toml(date)) => date.to_string(),
+    };
+    NaiveDate::parse_from_str(&text, "%Y-%m-%d")
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("'{}' is not a date (expected YYYY-MM-DD)", text)))
+}
+
+/// Recorded triage decisions for a repository (`.anty/baseline.toml`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    #[serde(default, rename = "finding")]
+    pub entries: Vec<Entry>,
+}
+
+impl Baseline {
+    /// Load the baseline under `root`, if present and valid
+    pub fn load(root: &Path) -> Option<Self> {
+        let path = root.join(BASELINE_PATH);
+        let content = std::fs::read_to_string(&path).ok()?;
+        match toml::from_str::<Baseline>(&content) {
+            Ok(baseline) => {
+                info!("Loaded {} baseline entries from {}", baseline.entries.len(), path.display());
+                Some(baseline)
+            }
+            Err(e) => {
+                warn!("Failed to parse {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Write the baseline under `root`, creating `.anty/` as needed
+    pub fn save(&self, root: &Path) -> Result<PathBuf> {
+        let path = root.join(BASELINE_PATH);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = format!(
+            "# Anty baseline: triage decisions for findings in this repository.\n\
+             # false-positive / accepted-risk entries are hidden from reports.\n\n{}",
+            toml::to_string_pretty(self)?
+        );
+        std::fs::write(&path, content)
+            .with_context(|| format!("Could not write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Record a decision for `finding`, replacing any earlier one
+    pub fn record(&mut self, finding: &Finding, decision: Decision, reason: Option<String>, expires: Option<NaiveDate>) {
+        self.entries.retain(|e| e.id != finding.id);
+        self.entries.push(Entry {
+            id: finding.id.clone(),
+            rule_id: finding.rule_id.clone(),
+            file: finding.file_path.clone(),
+            decision,
+            reason,
+            expires,
+        });
+        self.entries.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.id.cmp(&b.id)));
+    }
+
+    /// Remove suppressed findings, returning how many were dropped per
+    /// rule. Findings whose entry expired before `today` are kept.
+    pub fn apply(&self, findings: &mut Vec<Finding>, today: NaiveDate) -> BTreeMap<String, SuppressedCounts> {
+        let decisions: HashMap<&str, &Entry> = self
+            .entries
+            .iter()
+            .map(|e| (e.id.as_str(), e))
+            .collect();
+
+        let mut suppressed: BTreeMap<String, SuppressedCounts> = BTreeMap::new();
+        findings.retain(|f| {
+            let Some(entry) = decisions.get(f.id.as_str()).filter(|e| e.active(today)) else {
+                return true;
+            };
+            debug!("Suppressed by baseline: {} ({})", f.id, f.rule_id);
+            let counts = suppressed.entry(f.rule_id.clone()).or_default();
+            match entry.decision {
+                Decision::FalsePositive => counts.false_positive += 1,
+                _ => counts.accepted_risk += 1,
+            }
+            false
+        });
+        suppressed
+    }
+
+    /// Expired entries whose finding is among `findings` again
+    pub fn expired(&self, findings: &[Finding], today: NaiveDate) -> Vec<ExpiredSuppression> {
+        let found: HashMap<&str, &Finding> = findings.iter().map(|f| (f.id.as_str(), f)).collect();
+        self.entries
+            .iter()
+            .filter(|e| e.expired(today))
+            .filter_map(|e| {
+                let finding = found.get(e.id.as_str())?;
+                Some(ExpiredSuppression {
+                    id: e.id.clone(),
+                    rule_id: finding.rule_id.clone(),
+                    file_path: finding.file_path.clone(),
+                    expires: e.expires?.to_string(),
+                    reason: e.reason.clone(),
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,347 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use anstream::println;
+use anyhow::{bail, Context, Result};
+use comfy_table::{presets::UTF8_FULL_CONDENSED, Table};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::agents::SecurityAgent;
+use crate::cli::{exit_code, BatchArgs, Cli, ScanArgs};
+use crate::engine::{self, Scanner};
+use crate::report::finding::{HotspotCount, ScanReport, HOTSPOT_LIMIT};
+use crate::report::json;
+
+/// File name of the organisation-level report in `--out-dir`
+pub const ROLLUP_FILE: &str = "rollup.json";
+
+/// A repository listed in `--repos`
+#[derive(Debug)]
+struct Repo {
+    /// Unique name, used for its report file
+    name: String,
+    /// The line of `--repos` it came from
+    source: String,
+    location: Location,
+}
+
+#[derive(Debug)]
+enum Location {
+    Local(PathBuf),
+    Git(String),
+}
+
+/// One repository's outcome in the rollup
+#[derive(Debug, Serialize)]
+pub struct RepoResult {
+    pub name: String,
+    pub source: String,
+    /// Its JSON report, relative to `--out-dir`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<PathBuf>,
+    pub files_scanned: usize,
+    pub total: usize,
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    /// `[policy]` requirements the repository did not meet
+    pub policy_violations: usize,
+    /// What `anty scan` would have exited with for this repository
+    pub exit_code: i32,
+    /// Why the repository could not be scanned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Rule of each finding, for the rollup's ranking
+    #[serde(skip)]
+    rules: Vec<String>,
+}
+
+/// Organisation-level report of `anty batch` (`rollup.json`)
+#[derive(Debug, Serialize)]
+pub struct Rollup {
+    pub version: String,
+    pub timestamp: String,
+    /// Repositories in the order of `--repos`
+    pub repos: Vec<RepoResult>,
+    /// Repositories that could not be scanned
+    pub failed: usize,
+    pub total: usize,
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    /// Rules with the most findings across all repositories, most first
+    pub rules: Vec<HotspotCount>,
+}
+
+/// `anty batch`: scan every repository in `--repos`, `--jobs` at a time,
+/// with one set of agents. Each repository's JSON report goes to
+/// `--out-dir`, next to a rollup of all of them.
+pub fn run(cli: &Cli, args: &BatchArgs) -> Result<i32> {
+    if args.scan.path != Path::new(".") {
+        bail!("anty batch scans the repositories listed in --repos; it takes no path");
+    }
+    if args.scan.files_from.is_some() {
+        bail!("--files-from lists files of one tree; anty batch scans whole repositories");
+    }
+    let scan = &args.scan;
+    let ignored: Vec<&str> = [
+        ("--format", scan.format != "terminal"),
+        ("--out", scan.out.is_some()),
+        ("--sign-report", scan.sign_report.is_some()),
+        ("--diff-against-previous", scan.diff_against_previous),
+        ("--fail-fast", scan.fail_fast),
+        ("--group-by", scan.group_by.is_some()),
+        ("--summary-only", scan.summary_only),
+    ]
+    .into_iter()
+    .filter_map(|(flag, given)| given.then_some(flag))
+    .collect();
+    if !ignored.is_empty() {
+        bail!(
+            "anty batch writes a JSON report per repository to --out-dir; it does not take {}",
+            ignored.join(", ")
+        );
+    }
+    let repos = read_repos(&args.repos)?;
+    if repos.is_empty() {
+        bail!("{} lists no repositories", args.repos.display());
+    }
+    std::fs::create_dir_all(&args.out_dir)
+        .with_context(|| format!("Could not create {}", args.out_dir.display()))?;
+    let checkouts = std::env::temp_dir().join(format!("anty-batch-{}", std::process::id()));
+
+    let agents = engine::load_agents(&args.scan)?;
+    let jobs = args.jobs.clamp(1, repos.len());
+    info!("Scanning {} repositories, {} at a time", repos.len(), jobs);
+
+    // Workers take the next repository until none are left; each scan
+    // still spreads its files over every core
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<RepoResult>>> = Mutex::new(repos.iter().map(|_| None).collect());
+    std::thread::scope(|s| {
+        for _ in 0..jobs {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(repo) = repos.get(i) else {
+                    break;
+                };
+                info!("[{}/{}] Scanning {}", i + 1, repos.len(), repo.source);
+                let result = scan_repo(cli, args, &agents, repo, &checkouts);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+    let _ = std::fs::remove_dir_all(&checkouts);
+
+    let mut rule_counts: HashMap<String, usize> = HashMap::new();
+    let mut repos = Vec::new();
+    for mut result in results.into_inner().unwrap().into_iter().flatten() {
+        for rule in std::mem::take(&mut result.rules) {
+            *rule_counts.entry(rule).or_default() += 1;
+        }
+        repos.push(result);
+    }
+    let mut rules: Vec<HotspotCount> =
+        rule_counts.into_iter().map(|(name, count)| HotspotCount { name, count }).collect();
+    rules.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    rules.truncate(HOTSPOT_LIMIT);
+
+    let sum = |count: fn(&RepoResult) -> usize| repos.iter().map(count).sum();
+    let rollup = Rollup {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        failed: repos.iter().filter(|r| r.error.is_some()).count(),
+        total: sum(|r| r.total),
+        critical: sum(|r| r.critical),
+        high: sum(|r| r.high),
+        medium: sum(|r| r.medium),
+        low: sum(|r| r.low),
+        rules,
+        repos,
+    };
+    let path = args.out_dir.join(ROLLUP_FILE);
+    std::fs::write(&path, serde_json::to_string_pretty(&rollup)? + "\n")
+        .with_context(|| format!("Could not write {}", path.display()))?;
+    info!("Rollup written to {}", path.display());
+
+    render(&rollup);
+    // The worst outcome of any repository; one that failed counts as an error
+    Ok(rollup.repos.iter().map(|r| r.exit_code).max().unwrap_or(exit_code::CLEAN))
+}
+
+/// Scan one repository, write its report, and return its rollup entry
+fn scan_repo(
+    cli: &Cli,
+    args: &BatchArgs,
+    agents: &[Arc<dyn SecurityAgent>],
+    repo: &Repo,
+    checkouts: &Path,
+) -> RepoResult {
+    let mut result = RepoResult {
+        name: repo.name.clone(),
+        source: repo.source.clone(),
+        report: None,
+        files_scanned: 0,
+        total: 0,
+        critical: 0,
+        high: 0,
+        medium: 0,
+        low: 0,
+        policy_violations: 0,
+        exit_code: exit_code::ERROR,
+        error: None,
+        rules: Vec::new(),
+    };
+    let report = match scan(cli, args, agents, repo, checkouts) {
+        Ok(report) => report,
+        Err(e) => {
+            warn!("Could not scan {}: {:#}", repo.source, e);
+            result.error = Some(format!("{:#}", e));
+            return result;
+        }
+    };
+
+    let file = PathBuf::from(format!("{}.json", repo.name));
+    let written = json::render(&report).and_then(|content| {
+        let path = args.out_dir.join(&file);
+        std::fs::write(&path, content).with_context(|| format!("Could not write {}", path.display()))
+    });
+    match written {
+        Ok(()) => result.report = Some(file),
+        Err(e) => warn!("{:#}", e),
+    }
+
+    let scan = &args.scan;
+    result.exit_code =
+        exit_code::for_report(&report, scan.fail_on.as_deref(), scan.fail_on_score, scan.fail_on_tests.as_deref());
+    result.files_scanned = report.files_scanned;
+    result.total = report.summary.total;
+    result.critical = report.summary.critical;
+    result.high = report.summary.high;
+    result.medium = report.summary.medium;
+    result.low = report.summary.low;
+    result.policy_violations = report.policy_violations.len();
+    result.rules = report.findings.into_iter().map(|f| f.rule_id).collect();
+    result
+}
+
+fn scan(
+    cli: &Cli,
+    args: &BatchArgs,
+    agents: &[Arc<dyn SecurityAgent>],
+    repo: &Repo,
+    checkouts: &Path,
+) -> Result<ScanReport> {
+    let path = match repo.location {
+        Location::Local(ref path) => path.clone(),
+        Location::Git(ref url) => {
+            let dir = checkouts.join(&repo.name);
+            clone(url, &dir)?;
+            dir
+        }
+    };
+    // Reports go to --out-dir; progress bars of concurrent scans would
+    // overwrite each other
+    let scan_args = ScanArgs {
+        path,
+        format: "json".to_string(),
+        out: None,
+        ..args.scan.clone()
+    };
+    Scanner::with_agents(cli, &scan_args, agents)?.run()
+}
+
+/// Shallow-clone the default branch of `url` into `dir`
+fn clone(url: &str, dir: &Path) -> Result<()> {
+    if url.starts_with('-') {
+        bail!("'{}' is not a repository URL", url);
+    }
+    let output = Command::new("git")
+        .args(["clone", "--quiet", "--depth", "1", "--", url])
+        .arg(dir)
+        // Never stop the batch to ask for credentials
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .context("Could not run git; scanning git URLs needs git installed")?;
+    if !output.status.success() {
+        bail!("git clone failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+/// Parse `--repos`: one local path or git URL per line; blank lines and
+/// `#` comments are skipped. Relative paths are relative to the list.
+fn read_repos(list: &Path) -> Result<Vec<Repo>> {
+    let content =
+        std::fs::read_to_string(list).with_context(|| format!("Could not read {}", list.display()))?;
+    let base = list.parent().unwrap_or(Path::new(""));
+    let mut names = BTreeSet::new();
+    let mut repos = Vec::new();
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        let location = if line.contains("://") || line.starts_with("git@") {
+            Location::Git(line.to_string())
+        } else {
+            Location::Local(base.join(line))
+        };
+        let stem = line
+            .trim_end_matches('/')
+            .rsplit(['/', ':', '\\'])
+            .next()
+            .map(|n| n.trim_end_matches(".git"))
+            .filter(|n| !n.is_empty() && *n != "." && *n != "..")
+            .unwrap_or("repo");
+        // Two repositories of the same name get their own report files
+        let mut name = stem.to_string();
+        let mut n = 1;
+        while !names.insert(name.clone()) {
+            n += 1;
+            name = format!("{}-{}", stem, n);
+        }
+        repos.push(Repo {
+            name,
+            source: line.to_string(),
+            location,
+        });
+    }
+    Ok(repos)
+}
+
+/// Print the per-repository counts and totals
+fn render(rollup: &Rollup) {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    table.set_header(vec!["Repository", "Critical", "High", "Medium", "Low", "Status"]);
+    for repo in &rollup.repos {
+        let status = match repo.exit_code {
+            _ if repo.error.is_some() => "error",
+            exit_code::FAIL_ON => "fail",
+            exit_code::FINDINGS => "findings",
+            _ => "clean",
+        };
+        table.add_row(vec![
+            repo.name.clone(),
+            repo.critical.to_string(),
+            repo.high.to_string(),
+            repo.medium.to_string(),
+            repo.low.to_string(),
+            status.to_string(),
+        ]);
+    }
+    println!("{}", table);
+    println!(
+        " {} repositories: {} findings ({} critical, {} high, {} medium, {} low){}",
+        rollup.repos.len(),
+        rollup.total,
+        rollup.critical,
+        rollup.high,
+        rollup.medium,
+        rollup.low,
+        if rollup.failed > 0 { format!(", {} could not be scanned", rollup.failed) } else { String::new() }
+    );
+}
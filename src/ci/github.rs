@@ -0,0 +1,280 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use tracing::{info, warn};
+
+use crate::cli::{exit_code, Cli, GithubArgs, ScanArgs};
+use crate::engine::Scanner;
+use crate::git;
+use crate::report::{self, finding::{Finding, ScanReport, Severity}};
+
+/// GitHub accepts at most 50 annotations per check-run request
+const ANNOTATIONS_PER_REQUEST: usize = 50;
+
+const CHECK_NAME: &str = "Anty";
+
+/// Pull request context read from `$GITHUB_EVENT_PATH`
+struct PullRequest {
+    head_sha: String,
+    base_sha: String,
+}
+
+/// Where a check run gets posted
+pub struct CheckTarget {
+    token: String,
+    repo: String,
+    sha: String,
+    pr: Option<PullRequest>,
+}
+
+impl CheckTarget {
+    /// Resolve explicit values, falling back to the GitHub Actions environment
+    pub fn resolve(token: Option<String>, repo: Option<String>, sha: Option<String>) -> Result<Self> {
+        let token = token
+            .or_else(|| std::env::var("GITHUB_TOKEN").ok())
+            .context("No GitHub token: pass --token or set GITHUB_TOKEN")?;
+        let repo = repo
+            .or_else(|| std::env::var("GITHUB_REPOSITORY").ok())
+            .context("No repository: pass --repo owner/name or set GITHUB_REPOSITORY")?;
+        let pr = read_pull_request();
+        let sha = sha
+            .or_else(|| pr.as_ref().map(|p| p.head_sha.clone()))
+            .or_else(|| std::env::var("GITHUB_SHA").ok())
+            .context("No commit SHA: pass --sha or set GITHUB_SHA")?;
+        Ok(CheckTarget { token, repo, sha, pr })
+    }
+}
+
+/// Scan, print the report, and publish it as a GitHub Check Run.
+pub fn run(cli: &Cli, args: &GithubArgs) -> Result<i32> {
+    let target = CheckTarget::resolve(args.token.clone(), args.repo.clone(), args.sha.clone())?;
+
+    let report = Scanner::new(cli, &args.scan)?.run()?;
+    report::emit(&report, &args.scan, cli.verbose)?;
+    let code = exit_code::for_ci(exit_code::for_report(
+        &report,
+        args.scan.fail_on.as_deref(),
+        args.scan.fail_on_score,
+        args.scan.fail_on_tests.as_deref(),
+    ));
+
+    publish_check_run(&report, &args.scan, &target, args.all_lines)?;
+    Ok(code)
+}
+
+/// Post `report` as a completed check run, annotating changed lines only
+/// when the target is a pull request (unless `all_lines`).
+pub fn publish_check_run(
+    report: &ScanReport,
+    scan: &ScanArgs,
+    target: &CheckTarget,
+    all_lines: bool,
+) -> Result<()> {
+    let code = exit_code::for_report(report, scan.fail_on.as_deref(), scan.fail_on_score, scan.fail_on_tests.as_deref());
+    let prefix = repo_prefix(&scan.path)?;
+    let repo_root = git::repo_root(&scan.path).unwrap_or_else(|| scan.path.clone());
+
+    let changed = match (&target.pr, all_lines) {
+        (Some(pr), false) => {
+            let changed = git::changed_lines(&repo_root, &pr.base_sha);
+            if changed.is_none() {
+                warn!(
+                    "Could not diff against the pull request base {}, so every finding is annotated; \
+                     check out with `fetch-depth: 0` (or fetch the base commit) to annotate changed lines only",
+                    pr.base_sha
+                );
+            }
+            changed
+        }
+        _ => None,
+    };
+
+    let annotations: Vec<Value> = report
+        .findings
+        .iter()
+        .filter_map(|f| {
+            let path = prefix.join(&f.file_path);
+            if let Some(ref changed) = changed {
+                let lines = changed.get(&path)?;
+                if !(f.line_start..=f.line_end).any(|l| lines.contains(&l)) {
+                    return None;
+                }
+            }
+            Some(annotation(f, &path, scan.fail_on.as_deref()))
+        })
+        .collect();
+
+    let conclusion = match code {
+        exit_code::FAIL_ON => "failure",
+        exit_code::FINDINGS => "neutral",
+        _ => "success",
+    };
+
+    let api = std::env::var("GITHUB_API_URL").unwrap_or_else(|_| "https://api.github.com".to_string());
+    let client = Client {
+        api,
+        repo: target.repo.clone(),
+        token: target.token.clone(),
+    };
+    let title = format!("{} issues found", report.summary.total);
+    let summary = summary_markdown(report, annotations.len(), changed.is_some());
+
+    let mut batches = annotations.chunks(ANNOTATIONS_PER_REQUEST);
+    let first = batches.next().unwrap_or(&[]);
+    let created = client.post(
+        "check-runs",
+        json!({
+            "name": CHECK_NAME,
+            "head_sha": target.sha,
+            "status": "completed",
+            "conclusion": conclusion,
+            "output": { "title": title, "summary": summary, "annotations": first },
+        }),
+    )?;
+    let id = created["id"].as_u64().context("GitHub returned no check run id")?;
+
+    for batch in batches {
+        client.patch(
+            &format!("check-runs/{}", id),
+            json!({ "output": { "title": title, "summary": summary, "annotations": batch } }),
+        )?;
+    }
+
+    info!(
+        "Posted check run {} with {} annotations ({})",
+        id,
+        annotations.len(),
+        conclusion
+    );
+    Ok(())
+}
+
+/// Print findings as Actions workflow commands (`::error file=...::`), which
+/// GitHub turns into annotations without needing a token.
+pub fn print_workflow_commands(report: &ScanReport, scan: &ScanArgs) -> Result<()> {
+    let prefix = repo_prefix(&scan.path)?;
+    for f in &report.findings {
+        let level = match f.severity {
+            Severity::Critical | Severity::High => "error",
+            Severity::Medium => "warning",
+            Severity::Low => "notice",
+        };
+        let path = prefix.join(&f.file_path).to_string_lossy().replace('\\', "/");
+        println!(
+            "::{} file={},line={},endLine={},col={},title={}::{}",
+            level,
+            escape_property(&path),
+            f.line_start,
+            f.line_end,
+            f.column.max(1),
+            escape_property(&format!("{} {}", f.rule_id, f.title)),
+            escape_data(&format!("{}\n{}{}", f.description, f.recommendation, links_text(f))),
+        );
+    }
+    Ok(())
+}
+
+/// Path of the scan root relative to its repository root
+pub fn repo_prefix(scan_path: &Path) -> Result<PathBuf> {
+    let scan_root = std::fs::canonicalize(scan_path)?;
+    let repo_root = git::repo_root(&scan_root).unwrap_or_else(|| scan_root.clone());
+    Ok(scan_root.strip_prefix(&repo_root).unwrap_or(Path::new("")).to_path_buf())
+}
+
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+struct Client {
+    api: String,
+    repo: String,
+    token: String,
+}
+
+impl Client {
+    fn post(&self, endpoint: &str, body: Value) -> Result<Value> {
+        self.send("POST", endpoint, body)
+    }
+
+    fn patch(&self, endpoint: &str, body: Value) -> Result<Value> {
+        self.send("PATCH", endpoint, body)
+    }
+
+    fn send(&self, method: &str, endpoint: &str, body: Value) -> Result<Value> {
+        let url = format!("{}/repos/{}/{}", self.api, self.repo, endpoint);
+        let response = ureq::request(method, &url)
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .set("Accept", "application/vnd.github+json")
+            .set("User-Agent", concat!("anty/", env!("CARGO_PKG_VERSION")))
+            .send_json(body)
+            .with_context(|| format!("GitHub API request failed: {} {}", method, url))?;
+        Ok(response.into_json()?)
+    }
+}
+
+/// Read the PR head/base SHAs from the Actions event payload, if this is a PR build
+fn read_pull_request() -> Option<PullRequest> {
+    let path = std::env::var("GITHUB_EVENT_PATH").ok()?;
+    let raw = std::fs::read_to_string(&path).ok()?;
+    let event: Value = match serde_json::from_str(&raw) {
+        Ok(v) => v,
+        Err(e) => {
+            warn!("Could not parse {}: {}", path, e);
+            return None;
+        }
+    };
+    let pr = event.get("pull_request")?;
+    Some(PullRequest {
+        head_sha: pr["head"]["sha"].as_str()?.to_string(),
+        base_sha: pr["base"]["sha"].as_str()?.to_string(),
+    })
+}
+
+fn annotation(finding: &Finding, path: &Path, fail_on: Option<&str>) -> Value {
+    let failing = fail_on.is_some_and(|t| finding.severity >= Severity::from_str(t));
+    let level = if failing || finding.severity >= Severity::High {
+        "failure"
+    } else if finding.severity == Severity::Medium {
+        "warning"
+    } else {
+        "notice"
+    };
+
+    json!({
+        "path": path.to_string_lossy().replace('\\', "/"),
+        "start_line": finding.line_start,
+        "end_line": finding.line_end,
+        "annotation_level": level,
+        "title": format!("[{}] {} ({})", finding.severity, finding.title, finding.rule_id),
+        "message": format!("{}\n\n{}{}", finding.description, finding.recommendation, links_text(finding)),
+    })
+}
+
+/// Docs and reference URLs for the end of an annotation message
+fn links_text(finding: &Finding) -> String {
+    finding
+        .docs_url
+        .iter()
+        .chain(&finding.references)
+        .map(|url| format!("\n{}", url))
+        .collect()
+}
+
+fn summary_markdown(report: &ScanReport, annotated: usize, changed_only: bool) -> String {
+    let s = &report.summary;
+    let scope = if changed_only {
+        "on lines changed by this pull request"
+    } else {
+        "in total"
+    };
+    format!(
+        "| Severity | Findings |\n|---|---|\n| CRITICAL | {} |\n| HIGH | {} |\n| MEDIUM | {} |\n| LOW | {} |\n\n\
+         Scanned {} files. {} findings annotated {}.",
+        s.critical, s.high, s.medium, s.low, report.files_scanned, annotated, scope
+    )
+}
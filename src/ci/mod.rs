@@ -0,0 +1,169 @@
+pub mod github;
+
+use std::path::Path;
+
+use anyhow::Result;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use crate::cli::{exit_code, Cli, ScanArgs};
+use crate::engine::Scanner;
+use crate::report::{self, finding::{ScanReport, Severity}};
+
+/// CI systems `anty ci` knows how to configure itself for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    GithubActions,
+    GitlabCi,
+    Jenkins,
+    Unknown,
+}
+
+impl Provider {
+    /// Detect the CI system from its well-known environment variables
+    pub fn detect() -> Self {
+        let set = |name: &str| std::env::var_os(name).is_some_and(|v| !v.is_empty());
+        if std::env::var("GITHUB_ACTIONS").is_ok_and(|v| v == "true") {
+            Provider::GithubActions
+        } else if set("GITLAB_CI") {
+            Provider::GitlabCi
+        } else if set("JENKINS_URL") {
+            Provider::Jenkins
+        } else {
+            Provider::Unknown
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Provider::GithubActions => "GitHub Actions",
+            Provider::GitlabCi => "GitLab CI",
+            Provider::Jenkins => "Jenkins",
+            Provider::Unknown => "unknown CI",
+        }
+    }
+}
+
+/// GitLab picks Code Quality reports up from this artifact path by convention
+const GITLAB_CODE_QUALITY_PATH: &str = "gl-code-quality-report.json";
+
+/// Scan with output, annotations, artifact, and exit behavior chosen for
+/// the detected CI system. Explicit flags always win over the defaults;
+/// `format_given` says whether `--format` was one of them.
+pub fn run(cli: &Cli, args: &ScanArgs, format_given: bool) -> Result<i32> {
+    let provider = Provider::detect();
+    info!("Detected {}", provider.name());
+
+    let mut args = args.clone();
+    // CI logs don't need colors or multi-line blocks
+    if !format_given {
+        args.format = "compact".to_string();
+    }
+    // Fail the pipeline on HIGH+ unless told otherwise
+    if args.fail_on.is_none() {
+        args.fail_on = Some("HIGH".to_string());
+    }
+    // Always keep the full JSON report as a build artifact; several
+    // formats go next to it as anty-report.<ext>
+    if args.out.is_none() {
+        args.out = Some(if args.format.contains(',') { ".".into() } else { "anty-report.json".into() });
+    }
+
+    let report = Scanner::new(cli, &args)?.run()?;
+    let code = exit_code::for_ci(exit_code::for_report(
+        &report,
+        args.fail_on.as_deref(),
+        args.fail_on_score,
+        args.fail_on_tests.as_deref(),
+    ));
+
+    match provider {
+        Provider::GithubActions => {
+            // Workflow commands annotate the run on their own; a token upgrades
+            // this to a Check Run scoped to the PR's changed lines.
+            if std::env::var_os("GITHUB_TOKEN").is_some() {
+                report::emit(&report, &args, cli.verbose)?;
+                let target = github::CheckTarget::resolve(None, None, None)?;
+                github::publish_check_run(&report, &args, &target, false)?;
+            } else {
+                // Annotations replace the log output; JSON still goes to --out
+                let mut artifact_only = args.clone();
+                artifact_only.format = "json".to_string();
+                report::emit(&report, &artifact_only, cli.verbose)?;
+                github::print_workflow_commands(&report, &args)?;
+            }
+            write_step_summary(&report)?;
+        }
+        Provider::GitlabCi => {
+            report::emit(&report, &args, cli.verbose)?;
+            std::fs::write(GITLAB_CODE_QUALITY_PATH, gitlab_code_quality(&report, &args.path)?)?;
+            info!("Code Quality report written to {}", GITLAB_CODE_QUALITY_PATH);
+        }
+        Provider::Jenkins | Provider::Unknown => {
+            // Compact output is the gcc-style format warnings parsers understand
+            report::emit(&report, &args, cli.verbose)?;
+        }
+    }
+
+    Ok(code)
+}
+
+/// Append a severity table to the Actions job summary, if available
+fn write_step_summary(report: &ScanReport) -> Result<()> {
+    let Some(path) = std::env::var_os("GITHUB_STEP_SUMMARY") else {
+        return Ok(());
+    };
+    let s = &report.summary;
+    let markdown = format!(
+        "### 🐜 Anty: {} issues\n\n| Severity | Findings |\n|---|---|\n| CRITICAL | {} |\n| HIGH | {} |\n| MEDIUM | {} |\n| LOW | {} |\n",
+        s.total, s.critical, s.high, s.medium, s.low
+    );
+    use std::io::Write;
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(markdown.as_bytes())?;
+    Ok(())
+}
+
+/// Render findings in GitLab's Code Quality report format. GitLab matches
+/// `location.path` against the repository, so paths are prefixed with
+/// where the scan root sits in it.
+fn gitlab_code_quality(report: &ScanReport, scan_path: &Path) -> Result<String> {
+    let prefix = github::repo_prefix(scan_path)?;
+    let issues: Vec<_> = report
+        .findings
+        .iter()
+        .map(|f| {
+            let severity = match f.severity {
+                Severity::Critical => "critical",
+                Severity::High => "major",
+                Severity::Medium => "minor",
+                Severity::Low => "info",
+            };
+            let path = prefix.join(&f.file_path).to_string_lossy().replace('\\', "/");
+            // Markdown shown when the issue is expanded in the merge request
+            let mut body = f.recommendation.clone();
+            if let Some(ref url) = f.docs_url {
+                body.push_str(&format!("\n\nDocumentation: {}", url));
+            }
+            if !f.references.is_empty() {
+                body.push_str("\n\nReferences:\n");
+                for url in &f.references {
+                    body.push_str(&format!("\n- {}", url));
+                }
+            }
+            json!({
+                "description": format!("{}: {}", f.rule_id, f.title),
+                "check_name": f.rule_id,
+                "content": { "body": body },
+                "fingerprint": format!("{:x}", Sha256::digest(f.id.as_bytes())),
+                "severity": severity,
+                "location": { "path": path, "lines": { "begin": f.line_start } },
+            })
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&issues)?)
+}
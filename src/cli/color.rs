@@ -0,0 +1,48 @@
+use anstream::ColorChoice;
+
+/// Apply `--color`. `auto` follows `NO_COLOR` / `CLICOLOR` / `CLICOLOR_FORCE`
+/// and only styles output when stdout is a terminal.
+pub fn init(choice: &str) {
+    let choice = match choice {
+        "always" => ColorChoice::Always,
+        "never" => ColorChoice::Never,
+        _ => ColorChoice::Auto,
+    };
+    choice.write_global();
+}
+
+/// Whether stdout gets colors and emoji
+pub fn enabled() -> bool {
+    anstream::AutoStream::choice(&std::io::stdout()) != ColorChoice::Never
+}
+
+/// `fancy` when styled output is enabled, otherwise the plain-text `fallback`
+pub fn icon(fancy: &'static str, fallback: &'static str) -> &'static str {
+    if enabled() {
+        fancy
+    } else {
+        fallback
+    }
+}
+
+/// Drop the wizard's prompt styling too when colors are disabled
+pub fn init_prompts() {
+    if !enabled() {
+        inquire::set_global_render_config(inquire::ui::RenderConfig::empty());
+    }
+}
+
+/// Whether stdout is a terminal that understands OSC 8 hyperlinks
+/// (`FORCE_HYPERLINK=1` overrides detection). Never with colors off.
+pub fn hyperlinks() -> bool {
+    enabled() && supports_hyperlinks::on(supports_hyperlinks::Stream::Stdout)
+}
+
+/// `text` as a clickable link to `url` when the terminal supports it
+pub fn link(text: &str, url: &str) -> String {
+    if hyperlinks() {
+        format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+    } else {
+        text.to_string()
+    }
+}
@@ -1,5 +1,6 @@
 use clap::Subcommand;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Subcommand, Debug)]
 #[allow(clippy::large_enum_variant)]
@@ -7,36 +8,444 @@ pub enum Commands {
     /// Scan a directory for security issues
     Scan(ScanArgs),
 
+    /// Apply automatic fixes for rules with a safe mechanical remediation
+    Fix(FixArgs),
+
     /// Initialize an .anty.toml config file in the current directory
-    Init,
+    Init {
+        /// Start from a project template: "node", "python", "rust", or "monorepo"
+        #[arg(long, value_parser = crate::config::init::TEMPLATES.to_vec())]
+        template: Option<String>,
+
+        /// Ask about the project, agents, excludes, and profile before writing
+        #[arg(short, long)]
+        interactive: bool,
+    },
 
     /// List all available security rules
     ListRules,
+
+    /// Scan with settings auto-detected for the current CI system
+    Ci(CiArgs),
+
+    /// Scan many repositories at once and write a report per repository
+    /// plus an organisation-level rollup
+    Batch(BatchArgs),
+
+    /// Print the JSON Schema of the `--format json` report
+    Schema,
+
+    /// Check a JSON report against its --sign-report signature
+    VerifyReport {
+        /// JSON report written with --out
+        report: PathBuf,
+
+        /// Key file the report was signed with
+        #[arg(long, value_name = "KEY_FILE")]
+        key: PathBuf,
+
+        /// Signature file (defaults to `<report>.sig`)
+        #[arg(long)]
+        signature: Option<PathBuf>,
+    },
+
+    /// Check .anty.toml and the user config for unknown keys and invalid values
+    ValidateConfig {
+        /// Config file, or directory whose .anty.toml to check
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+
+    /// Run a Language Server Protocol server on stdio for editor diagnostics
+    Lsp,
+
+    /// Manage rule metadata and offline rule snapshots
+    Rules {
+        #[command(subcommand)]
+        command: RulesCommand,
+    },
+
+    /// Show one finding in full: source context, rule examples, and docs
+    Explain {
+        /// Finding ID (e.g. ANTY-a1b2c3d4) or a unique prefix of it
+        id: String,
+
+        /// JSON report to read (defaults to the last scan's report)
+        #[arg(long)]
+        report: Option<PathBuf>,
+
+        /// Lines of source to show before and after the finding
+        #[arg(short = 'C', long, default_value_t = 3)]
+        context: usize,
+    },
+
+    /// Print the secrets a report found as CI log-masking commands, so later
+    /// log output in the same job has them scrubbed
+    Mask {
+        /// JSON report to read (defaults to the last scan's report)
+        #[arg(long)]
+        report: Option<PathBuf>,
+
+        /// "github" (::add-mask::), "azure" (##vso[task.setsecret]), "plain"
+        /// (one value per line), or "regex" (escaped); defaults to the
+        /// detected CI system, then "plain"
+        #[arg(long, value_parser = crate::report::mask::FORMATS.to_vec())]
+        format: Option<String>,
+    },
+
+    /// Write a README badge with a report's critical and high counts: a
+    /// shields-style SVG and an endpoint JSON for shields.io
+    Badge {
+        /// JSON report to read (defaults to the last scan's report)
+        #[arg(long)]
+        report: Option<PathBuf>,
+
+        /// SVG file to write
+        #[arg(short, long, default_value = "anty-badge.svg")]
+        out: PathBuf,
+
+        /// shields.io endpoint JSON to write (defaults to the SVG's path
+        /// with a .json extension)
+        #[arg(long)]
+        endpoint: Option<PathBuf>,
+    },
+
+    /// Record status, assignee, and notes for findings in `.anty/triage.toml`
+    Triage {
+        #[command(subcommand)]
+        command: TriageCommand,
+    },
+
+    /// Print the last scan's report again without rescanning
+    Last {
+        /// Scanned path whose last report to show (defaults to the current
+        /// directory, then the most recent scan)
+        path: Option<PathBuf>,
+
+        /// Output format: "terminal", "json", "sarif", "html", "compact", or "count"
+        #[arg(short, long, default_value = "terminal")]
+        format: String,
+
+        /// Group terminal output by "file", "rule", "severity", "agent", or "owner"
+        #[arg(long, value_parser = ["file", "rule", "severity", "agent", "owner"])]
+        group_by: Option<String>,
+
+        /// Only print the severity counts table (terminal format)
+        #[arg(long)]
+        summary_only: bool,
+    },
+
+    /// Combine JSON reports of separate scans (e.g. CI matrix shards) into one
+    Merge {
+        /// JSON reports written with --out
+        #[arg(required = true)]
+        reports: Vec<PathBuf>,
+
+        /// Write the combined report here instead of printing it
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Download and install the latest signed rule bundle (uses the network)
+    UpdateRules {
+        /// Bundle URL (defaults to `rules.update_url` in the user config, then anty.dev)
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Only report whether a newer bundle is available
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Check every rule against its example snippets
+    TestRules {
+        /// Directory of fixture files to use instead of the built-in ones
+        #[arg(long, value_name = "DIR")]
+        fixtures: Option<PathBuf>,
+    },
+}
+
+impl Commands {
+    /// Scan options of commands that run a scan
+    pub fn scan_args_mut(&mut self) -> Option<&mut ScanArgs> {
+        match self {
+            Commands::Scan(args) => Some(args),
+            Commands::Fix(args) => Some(&mut args.scan),
+            Commands::Batch(args) => Some(&mut args.scan),
+            Commands::Ci(args) => match &mut args.command {
+                Some(CiCommand::Github(gh)) => Some(&mut gh.scan),
+                None => Some(&mut args.scan),
+            },
+            _ => None,
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct CiArgs {
+    #[command(subcommand)]
+    pub command: Option<CiCommand>,
+
+    #[command(flatten)]
+    pub scan: ScanArgs,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct FixArgs {
+    /// Print the fixes as a unified diff without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Write the fixes without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+
+    #[command(flatten)]
+    pub scan: ScanArgs,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct BatchArgs {
+    /// File listing the repositories, one per line: a local path (relative
+    /// to the file) or a git URL to shallow-clone. Blank lines and # comments
+    /// are skipped
+    #[arg(long, value_name = "FILE")]
+    pub repos: PathBuf,
+
+    /// Directory for each repository's JSON report and rollup.json
+    #[arg(long, value_name = "DIR", default_value = "anty-batch")]
+    pub out_dir: PathBuf,
+
+    /// Repositories scanned at the same time
+    #[arg(short, long, default_value_t = 4)]
+    pub jobs: usize,
+
+    #[command(flatten)]
+    pub scan: ScanArgs,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CiCommand {
+    /// Scan and post findings as a GitHub Check Run with inline annotations
+    Github(GithubArgs),
 }
 
 #[derive(clap::Args, Debug)]
+pub struct GithubArgs {
+    /// GitHub token with checks:write permission (defaults to $GITHUB_TOKEN)
+    #[arg(long)]
+    pub token: Option<String>,
+
+    /// Repository as owner/name (defaults to $GITHUB_REPOSITORY)
+    #[arg(long)]
+    pub repo: Option<String>,
+
+    /// Commit to attach the check run to (defaults to the PR head, then $GITHUB_SHA)
+    #[arg(long)]
+    pub sha: Option<String>,
+
+    /// Annotate every finding, not only those on lines changed by the PR
+    #[arg(long)]
+    pub all_lines: bool,
+
+    #[command(flatten)]
+    pub scan: ScanArgs,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RulesCommand {
+    /// Export built-in rule metadata and installed packs to a snapshot archive
+    Export {
+        /// Output archive path (e.g. snapshot.tar)
+        path: PathBuf,
+    },
+
+    /// Verify a snapshot archive and replace the installed rule packs with its own
+    Import {
+        /// Snapshot archive to import
+        path: PathBuf,
+    },
+
+    /// Show which rules match the most and how many of their matches were
+    /// marked false positives, with suggestions to tune or disable them
+    Stats {
+        /// Scanned path whose last report to read (defaults to the current
+        /// directory, then the most recent scan)
+        path: Option<PathBuf>,
+
+        /// JSON report to read instead of a cached scan
+        #[arg(long)]
+        report: Option<PathBuf>,
+
+        /// Number of rules to list
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+
+        /// "terminal" or "json"
+        #[arg(short, long, default_value = "terminal", value_parser = crate::rules::stats::FORMATS.to_vec())]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TriageCommand {
+    /// Set a finding's status, assignee, or note; what is not given keeps
+    /// its earlier value
+    Set {
+        /// Finding ID (e.g. ANTY-a1b2c3d4) or a unique prefix of it
+        id: String,
+
+        /// "open", "accepted", or "false-positive"
+        #[arg(long, value_parser = crate::report::finding::TriageStatus::NAMES.to_vec())]
+        status: Option<String>,
+
+        /// Who is handling the finding ("" to clear)
+        #[arg(long)]
+        assignee: Option<String>,
+
+        /// Why, or what was decided ("" to clear)
+        #[arg(long)]
+        note: Option<String>,
+
+        /// JSON report the finding is in (defaults to the last scan's report)
+        #[arg(long)]
+        report: Option<PathBuf>,
+    },
+}
+
+#[derive(clap::Args, Debug, Clone)]
 pub struct ScanArgs {
     /// Path to scan (defaults to current directory)
     #[arg(default_value = ".")]
     pub path: PathBuf,
 
-    /// Output format: "terminal" or "json"
+    /// Output format: "terminal", "json", "sarif", "html", "compact"
+    /// (path:line:col per finding), or "count" (total findings only).
+    /// Several, comma-separated, need --out DIR: e.g. terminal,json,sarif,html
     #[arg(short, long, default_value = "terminal")]
     pub format: String,
 
-    /// Write report to file
+    /// Write report to file, or with a directory, write each file format
+    /// of --format to DIR/anty-report.<json|sarif|html|txt>
     #[arg(short, long)]
     pub out: Option<PathBuf>,
 
-    /// Fail (exit code 1) if findings at or above this severity are found.
+    /// Compare with the report already at --out and print only new and
+    /// resolved findings (terminal format); the full report is still written
+    #[arg(long, requires = "out")]
+    pub diff_against_previous: bool,
+
+    /// Sign the --out report with the HMAC key in this file, writing
+    /// `<out>.sig` (check it later with `anty verify-report`). Only JSON
+    /// and SARIF reports are signed.
+    #[arg(long, value_name = "KEY_FILE", requires = "out")]
+    pub sign_report: Option<PathBuf>,
+
+    /// Fail (exit code 2) if findings at or above this severity are found.
     /// Values: CRITICAL, HIGH, MEDIUM, LOW
     #[arg(long)]
     pub fail_on: Option<String>,
 
-    /// Maximum file size in bytes to scan (skip larger files)
+    /// Fail (exit code 2) if any finding scores at or above this (0.0-10.0)
+    #[arg(long, value_name = "SCORE")]
+    pub fail_on_score: Option<f32>,
+
+    /// Report findings in test files (`tests/`, `__tests__/`, `test_x.py`,
+    /// `x_test.go`, `x.spec.ts`) apart from the rest, as `test_findings`.
+    /// --fail-on and `[policy]` then only apply to production code.
+    #[arg(long)]
+    pub separate_tests: bool,
+
+    /// Fail (exit code 2) if findings in test files are at or above this
+    /// severity. Values: CRITICAL, HIGH, MEDIUM, LOW. Implies --separate-tests
+    #[arg(long, value_name = "SEVERITY")]
+    pub fail_on_tests: Option<String>,
+
+    /// Stop at the first finding at or above --fail-on and exit 2 right
+    /// away, with a report of only that finding (for pre-push hooks)
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Only report findings at or above this severity.
+    /// Values: CRITICAL, HIGH, MEDIUM, LOW (overrides `output.min_severity`)
+    #[arg(long)]
+    pub min_severity: Option<String>,
+
+    /// Only report findings at or above this confidence.
+    /// Values: HIGH, MEDIUM, LOW
+    #[arg(long)]
+    pub min_confidence: Option<String>,
+
+    /// Only report findings in these environments, as their paths tell
+    /// (comma-separated): dev (`docker-compose.dev.yml`, `*.sample`), test
+    /// (`config/test.*`, `tests/`), prod-unknown (everything else)
+    #[arg(long, value_delimiter = ',', value_parser = crate::report::finding::Environment::NAMES.to_vec())]
+    pub environment: Vec<String>,
+
+    /// Preset of --min-severity, --min-confidence, --tags, --max-findings,
+    /// and --fail-on; flags and config settings override it.
+    /// strict: everything, fail on any finding; default: fail on HIGH;
+    /// lenient: low-noise rules, MEDIUM+ severity and confidence, fail on CRITICAL
+    #[arg(long, value_parser = crate::profile::NAMES.to_vec())]
+    pub profile: Option<String>,
+
+    /// Maximum file size in bytes to scan (skip larger files).
+    /// Files over 4 MB are streamed in chunks, so this can be raised safely.
     #[arg(long, default_value = "1048576")]
     pub max_file_size: u64,
 
+    /// Time budget per file in seconds; slower files are abandoned and
+    /// listed under errors (0 = no limit, default 10)
+    #[arg(long, value_name = "SECONDS")]
+    pub file_timeout: Option<u64>,
+
+    /// Time budget for the whole scan, e.g. "120s", "5m", or plain seconds.
+    /// When it runs out, discovery and scanning stop and the report covers
+    /// what was scanned so far (marked `partial`); --fail-on still applies.
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
+
+    /// Cap the file contents held in memory at once, e.g. "512M" or "2G".
+    /// Reading pauses until scanned files are released. Default: no cap.
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    pub max_memory: Option<u64>,
+
+    /// Also scan hidden files and directories (.env, .github/, .npmrc)
+    #[arg(long)]
+    pub hidden: bool,
+
+    /// Also scan files ignored by .gitignore (where local .env files usually are)
+    #[arg(long)]
+    pub no_gitignore: bool,
+
+    /// Annotate findings with the commit, author, and date of the line (git blame)
+    #[arg(long)]
+    pub blame: bool,
+
+    /// Embed N lines of source before and after each finding in JSON and
+    /// HTML reports, so they can be triaged without the repository (default 3)
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "3")]
+    pub include_context: Option<usize>,
+
+    /// Map findings onto the controls of a compliance framework and summarize
+    /// which controls passed or were violated
+    #[arg(long, value_parser = crate::compliance::FRAMEWORKS.to_vec())]
+    pub compliance: Option<String>,
+
+    /// Break the report down per package (directories with package.json,
+    /// Cargo.toml, go.mod, or pyproject.toml), checking --fail-on for each
+    #[arg(long)]
+    pub monorepo: bool,
+
+    /// List every file that was not scanned and why
+    #[arg(long)]
+    pub explain_skips: bool,
+
+    /// Follow symlinked files and directories (symlink loops are skipped)
+    #[arg(long)]
+    pub follow_symlinks: bool,
+
     /// Glob patterns to include (can be repeated)
     #[arg(long)]
     pub include: Vec<String>,
@@ -45,6 +454,30 @@ pub struct ScanArgs {
     #[arg(long)]
     pub exclude: Vec<String>,
 
+    /// Only scan files in these languages, after detection (e.g. ts,py)
+    #[arg(long, value_delimiter = ',')]
+    pub lang: Vec<String>,
+
+    /// Skip files in these languages (e.g. json,yaml)
+    #[arg(long, value_delimiter = ',')]
+    pub exclude_lang: Vec<String>,
+
+    /// Only run rules with any of these tags (e.g. low-noise or secrets,crypto);
+    /// `anty list-rules` lists the tags
+    #[arg(long, value_delimiter = ',')]
+    pub tags: Vec<String>,
+
+    /// Skip rules with any of these tags
+    #[arg(long, value_delimiter = ',')]
+    pub exclude_tags: Vec<String>,
+
+    /// Scan only the files in this newline-delimited list ("-" for stdin)
+    /// instead of walking PATH, e.g. `git diff --name-only | anty scan
+    /// --files-from -`. Listed files must be under PATH; .gitignore and
+    /// --hidden do not apply to them, --include and --exclude still do.
+    #[arg(long, value_name = "FILE")]
+    pub files_from: Option<PathBuf>,
+
     /// Only scan files changed in git (compared to HEAD)
     #[arg(long)]
     pub changed_only: bool,
@@ -53,12 +486,130 @@ pub struct ScanArgs {
     #[arg(long)]
     pub agents: Option<String>,
 
+    /// Also run the Semgrep-style YAML rules in this directory (a subset:
+    /// pattern, pattern-either, pattern-regex, metavariable-regex)
+    #[arg(long, value_name = "DIR")]
+    pub rules_dir: Option<PathBuf>,
+
     /// Ignore .anty.toml config files found in the scanned repository.
     /// Recommended when scanning untrusted code.
     #[arg(long)]
     pub no_config: bool,
 
-    /// Maximum number of findings to report (0 = unlimited)
-    #[arg(long, default_value = "1000")]
-    pub max_findings: usize,
+    /// Order findings by "severity" or by "score" (highest first).
+    /// --max-findings keeps the first findings in this order.
+    #[arg(long, default_value = "severity", value_parser = ["severity", "score"])]
+    pub sort: String,
+
+    /// Maximum number of findings to report (0 = unlimited) [default: 1000]
+    #[arg(long)]
+    pub max_findings: Option<usize>,
+
+    /// Maximum number of findings to report per rule (0 = unlimited)
+    #[arg(long, default_value = "25")]
+    pub max_per_rule: usize,
+
+    /// Group terminal output by "file", "rule", "severity", "agent", or "owner" (CODEOWNERS)
+    #[arg(long, value_parser = ["file", "rule", "severity", "agent", "owner"])]
+    pub group_by: Option<String>,
+
+    /// Only print the severity counts table (terminal format)
+    #[arg(long)]
+    pub summary_only: bool,
+
+    /// Byte-identical reports for identical trees (snapshot tests): no
+    /// timestamp, durations, or git details, and the scan path relative
+    /// to the working directory
+    #[arg(long)]
+    pub deterministic: bool,
+}
+
+impl ScanArgs {
+    /// `anty scan <path>` with every other flag at its default
+    pub fn for_path(path: &Path) -> Self {
+        ScanArgs {
+            path: PathBuf::from(path),
+            format: "terminal".to_string(),
+            out: None,
+            diff_against_previous: false,
+            sign_report: None,
+            fail_on: None,
+            fail_on_score: None,
+            separate_tests: false,
+            fail_on_tests: None,
+            fail_fast: false,
+            deterministic: false,
+            max_file_size: 1_048_576,
+            file_timeout: None,
+            timeout: None,
+            max_memory: None,
+            follow_symlinks: false,
+            hidden: false,
+            no_gitignore: false,
+            explain_skips: false,
+            monorepo: false,
+            compliance: None,
+            blame: false,
+            include_context: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            lang: Vec::new(),
+            exclude_lang: Vec::new(),
+            tags: Vec::new(),
+            exclude_tags: Vec::new(),
+            changed_only: false,
+            files_from: None,
+            agents: None,
+            rules_dir: None,
+            no_config: false,
+            min_severity: None,
+            min_confidence: None,
+            environment: Vec::new(),
+            profile: None,
+            max_findings: None,
+            max_per_rule: 25,
+            sort: "severity".to_string(),
+            group_by: None,
+            summary_only: false,
+        }
+    }
+}
+
+/// Parse a duration: plain seconds or an s/m/h suffix, e.g. "90", "120s", "5m"
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let lower = s.trim().to_lowercase();
+    let (number, unit) = match lower.char_indices().last() {
+        Some((i, 's')) => (&lower[..i], 1u64),
+        Some((i, 'm')) => (&lower[..i], 60),
+        Some((i, 'h')) => (&lower[..i], 3600),
+        _ => (lower.as_str(), 1),
+    };
+    number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(unit))
+        .filter(|&n| n > 0)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("invalid duration '{}' (expected e.g. 120s or 5m)", s))
+}
+
+/// Parse a byte size: plain bytes or a K/M/G suffix (binary units, an
+/// optional trailing "B" or "iB", any case)
+fn parse_size(s: &str) -> Result<u64, String> {
+    let upper = s.trim().to_uppercase();
+    let digits = upper.trim_end_matches("IB").trim_end_matches('B');
+    let (number, unit) = match digits.char_indices().last() {
+        Some((i, 'K')) => (&digits[..i], 1u64 << 10),
+        Some((i, 'M')) => (&digits[..i], 1 << 20),
+        Some((i, 'G')) => (&digits[..i], 1 << 30),
+        _ => (digits, 1),
+    };
+    number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(unit))
+        .filter(|&n| n > 0)
+        .ok_or_else(|| format!("invalid size '{}' (expected e.g. 512M or 2G)", s))
 }
@@ -0,0 +1,59 @@
+//! Process exit codes. Documented in the README; treat as a stable contract.
+
+use crate::report::finding::{ScanReport, Severity};
+
+/// Scan completed with no findings
+pub const CLEAN: i32 = 0;
+
+/// Findings were reported, none at or above `--fail-on`
+pub const FINDINGS: i32 = 1;
+
+/// Findings at or above the `--fail-on` / `--fail-on-score` threshold, or a
+/// `[policy]` violation
+pub const FAIL_ON: i32 = 2;
+
+/// The scanner itself failed (bad arguments, I/O error, ...)
+pub const ERROR: i32 = 3;
+
+/// Exit code for a finished scan given the optional `--fail-on`,
+/// `--fail-on-score`, and `--fail-on-tests` thresholds
+pub fn for_report(
+    report: &ScanReport,
+    fail_on: Option<&str>,
+    fail_on_score: Option<f32>,
+    fail_on_tests: Option<&str>,
+) -> i32 {
+    if !report.policy_violations.is_empty() {
+        return FAIL_ON;
+    }
+    if let Some(threshold) = fail_on_score {
+        if report.has_findings_scored_at_or_above(threshold) {
+            return FAIL_ON;
+        }
+    }
+    if let Some(fail_on) = fail_on {
+        if report.has_findings_at_or_above(Severity::from_str(fail_on)) {
+            return FAIL_ON;
+        }
+    }
+    if let Some(fail_on_tests) = fail_on_tests {
+        if report.test_findings.iter().any(|f| f.severity >= Severity::from_str(fail_on_tests)) {
+            return FAIL_ON;
+        }
+    }
+    if report.findings.is_empty() && report.test_findings.is_empty() {
+        CLEAN
+    } else {
+        FINDINGS
+    }
+}
+
+/// Exit code of a CI step: findings below the thresholds are reported but
+/// do not fail the job, so only `FAIL_ON` and `ERROR` are non-zero
+pub fn for_ci(code: i32) -> i32 {
+    if code == FINDINGS {
+        CLEAN
+    } else {
+        code
+    }
+}
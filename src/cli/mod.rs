@@ -1,9 +1,11 @@
+pub mod color;
 pub mod commands;
+pub mod exit_code;
 pub mod wizard;
 
 use clap::Parser;
 
-pub use commands::{Commands, ScanArgs};
+pub use commands::{BatchArgs, CiCommand, Commands, FixArgs, GithubArgs, RulesCommand, ScanArgs, TriageCommand};
 
 /// Anty — Developer-first security scanner
 ///
@@ -27,4 +29,19 @@ pub struct Cli {
     /// Suppress all output except errors
     #[arg(short, long, global = true)]
     pub quiet: bool,
+
+    /// When to use colors and emoji: auto, always, never (auto honours NO_COLOR)
+    #[arg(long, global = true, default_value = "auto", value_parser = ["auto", "always", "never"])]
+    pub color: String,
+
+    /// Log format: "text", or "json" for one JSON object per line on stderr
+    /// with scan telemetry (phases, file counts, agent timings, errors)
+    #[arg(long, global = true, default_value = "text", value_parser = ["text", "json"])]
+    pub log_format: String,
+
+    /// Language of the wizard and terminal report: "auto" (from ANTY_LOCALE,
+    /// LC_ALL, LC_MESSAGES, or LANG), "en", or "es". JSON and SARIF output
+    /// and rule texts stay in English.
+    #[arg(long, global = true, default_value = "auto", value_parser = ["auto", "en", "es"])]
+    pub locale: String,
 }
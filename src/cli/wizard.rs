@@ -2,12 +2,18 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
-use inquire::{Confirm, Select, Text};
+use chrono::NaiveDate;
+use inquire::validator::Validation;
+use inquire::{Confirm, MultiSelect, Select, Text};
+use anstream::{print, println};
 use owo_colors::OwoColorize;
 
-use crate::cli::{Cli, ScanArgs};
+use crate::agents;
+use crate::baseline::{self, Baseline, Decision};
+use crate::cli::{color, Cli, ScanArgs};
 use crate::engine::Scanner;
-use crate::report;
+use crate::i18n::t;
+use crate::report::{self, finding::ScanReport};
 
 // ── Constants ────────────────────────────────────────────────────────
 
@@ -110,38 +116,35 @@ fn is_binary_ext(path: &Path) -> bool {
 
 // ── Scan execution (reuses the engine) ──────────────────────────────
 
-/// Build default ScanArgs for a given path.
-fn default_scan_args(path: &Path) -> ScanArgs {
-    ScanArgs {
-        path: PathBuf::from(path),
-        format: "terminal".to_string(),
-        out: None,
-        fail_on: None,
-        max_file_size: 1_048_576,
-        include: Vec::new(),
-        exclude: Vec::new(),
-        changed_only: false,
-        agents: None,
-        no_config: false,
-        max_findings: 1000,
-    }
+/// Scan settings picked in the wizard (mirrors `--agents` and `--min-severity`).
+#[derive(Debug, Clone, Default)]
+struct ScanChoices {
+    agents: Option<String>,
+    min_severity: Option<String>,
 }
 
 /// Run the scan engine with wizard-friendly defaults and display results.
-fn execute_scan(path: &Path) -> Result<()> {
-    let args = default_scan_args(path);
+fn execute_scan(path: &Path, choices: &ScanChoices) -> Result<()> {
+    let mut args = ScanArgs::for_path(path);
+    args.agents = choices.agents.clone();
+    args.min_severity = choices.min_severity.clone();
+    // The wizard has no --profile, but a `[profile]` in the config applies
+    crate::profile::apply(&mut args)?;
 
     let cli = Cli {
-        command: crate::cli::Commands::Scan(default_scan_args(path)),
+        command: crate::cli::Commands::Scan(args.clone()),
         verbose: false,
         quiet: false,
+        color: "auto".to_string(),
+        log_format: "text".to_string(),
+        locale: "auto".to_string(),
     };
 
     println!();
     println!(
-        "  {} Scanning {}",
+        "  {} {}",
         "🔍".bold(),
-        path.display().to_string().cyan()
+        t!("wizard.scanning", path = path.display().to_string().cyan())
     );
     println!();
 
@@ -149,30 +152,60 @@ fn execute_scan(path: &Path) -> Result<()> {
     let scan_report = scanner.run()?;
 
     // Render to terminal
-    report::terminal::render(&scan_report);
+    report::terminal::render(&scan_report, &report::terminal::RenderOptions::default());
 
     // Summary bar
     separator();
     println!(
-        "  {} {}  |  {} critical  {} high  {} medium  {} low",
+        "  {} {}  |  {}  {}  {}  {}",
         "📊".bold(),
-        format!("{} issues found", scan_report.summary.total).bold(),
-        scan_report.summary.critical.to_string().red().bold(),
-        scan_report.summary.high.to_string().yellow().bold(),
-        scan_report.summary.medium.to_string().blue(),
-        scan_report.summary.low.to_string().dimmed(),
+        t!("wizard.issues_found", count = scan_report.summary.total).bold(),
+        t!("terminal.critical", count = scan_report.summary.critical).red().bold(),
+        t!("terminal.high", count = scan_report.summary.high).yellow().bold(),
+        t!("terminal.medium", count = scan_report.summary.medium).blue(),
+        t!("terminal.low", count = scan_report.summary.low).dimmed(),
     );
     separator();
     println!();
 
+    // Offer to triage what was found
+    if !scan_report.findings.is_empty() {
+        let review = Confirm::new(&t!("wizard.review_prompt"))
+            .with_default(false)
+            .prompt()
+            .unwrap_or(false);
+        if review {
+            triage_findings(&scan_report, &baseline::root_for(path))?;
+        }
+    }
+
+    // The HTML report is the readable one for people who will not open JSON
+    let open = Confirm::new(&t!("wizard.open_html"))
+        .with_default(true)
+        .prompt()
+        .unwrap_or(false);
+
+    if open {
+        let file = std::env::temp_dir().join(format!("anty-report-{}.html", std::process::id()));
+        std::fs::write(&file, report::html::render(&scan_report))?;
+        if let Err(e) = open_in_browser(&file) {
+            println!("  {} {}", "⚠️".bold(), t!("wizard.no_browser", error = e));
+        }
+        println!(
+            "  {} {}",
+            "📄".bold(),
+            t!("wizard.html_at", path = file.display().to_string().green())
+        );
+    }
+
     // Ask if the user wants a JSON report saved
-    let save = Confirm::new("Save JSON report?")
+    let save = Confirm::new(&t!("wizard.save_json"))
         .with_default(false)
         .prompt()
         .unwrap_or(false);
 
     if save {
-        let filename = Text::new("Filename:")
+        let filename = Text::new(&t!("wizard.filename"))
             .with_default("anty-report.json")
             .prompt()
             .unwrap_or_else(|_| "anty-report.json".to_string());
@@ -180,9 +213,9 @@ fn execute_scan(path: &Path) -> Result<()> {
         let json = report::json::render(&scan_report)?;
         std::fs::write(&filename, &json)?;
         println!(
-            "  {} Report written to {}",
+            "  {} {}",
             "✅".bold(),
-            filename.green()
+            t!("wizard.report_written", path = filename.green())
         );
     }
 
@@ -190,6 +223,109 @@ fn execute_scan(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Open a file with the system's default handler (the browser, for HTML)
+fn open_in_browser(file: &Path) -> io::Result<()> {
+    let mut command = if cfg!(windows) {
+        // `start` is a cmd builtin; its first quoted argument is the window title
+        let mut c = std::process::Command::new("cmd");
+        c.args(["/C", "start", ""]);
+        c
+    } else if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    let status = command
+        .arg(file)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("exited with {}", status)))
+    }
+}
+
+fn parse_date(input: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d").ok()
+}
+
+/// Step through findings one by one and record triage decisions in the
+/// repository baseline, so false positives and accepted risks stop showing up.
+fn triage_findings(scan_report: &ScanReport, root: &Path) -> Result<()> {
+    let mut baseline = Baseline::load(root).unwrap_or_default();
+    let total = scan_report.findings.len();
+    let mut decided = 0;
+    let links = report::terminal::Links::for_report(scan_report);
+
+    for (i, finding) in scan_report.findings.iter().enumerate() {
+        println!();
+        println!("  {}", t!("wizard.finding_n", n = i + 1, total = total).dimmed());
+        report::terminal::render_finding(finding, &links);
+
+        let options = vec![
+            t!("wizard.real_issue"),
+            t!("wizard.false_positive"),
+            t!("wizard.accept_risk"),
+            t!("wizard.skip"),
+            t!("wizard.stop"),
+        ];
+        let help = t!("wizard.triage_help");
+        let choice = Select::new(&t!("wizard.triage_prompt"), options.clone())
+            .with_help_message(&help)
+            .prompt_skippable()
+            .unwrap_or(None);
+
+        let decision = match choice.and_then(|c| options.iter().position(|o| *o == c)) {
+            Some(0) => Decision::Confirmed,
+            Some(1) => Decision::FalsePositive,
+            Some(2) => Decision::AcceptedRisk,
+            Some(3) => continue,
+            _ => break,
+        };
+
+        let reason = if decision.suppresses() {
+            Text::new(&t!("wizard.reason"))
+                .prompt_skippable()
+                .unwrap_or(None)
+                .filter(|r| !r.trim().is_empty())
+        } else {
+            None
+        };
+        let expires = if decision.suppresses() {
+            let invalid = t!("wizard.invalid_date");
+            Text::new(&t!("wizard.expires"))
+                .with_validator(move |input: &str| {
+                    Ok(if input.trim().is_empty() || parse_date(input).is_some() {
+                        Validation::Valid
+                    } else {
+                        Validation::Invalid(invalid.clone().into())
+                    })
+                })
+                .prompt_skippable()
+                .unwrap_or(None)
+                .and_then(|date| parse_date(&date))
+        } else {
+            None
+        };
+
+        baseline.record(finding, decision, reason, expires);
+        decided += 1;
+    }
+
+    if decided > 0 {
+        let saved = baseline.save(root)?;
+        println!();
+        println!(
+            "  {} {}",
+            "✅".bold(),
+            t!("wizard.saved_decisions", count = decided, path = saved.display().to_string().green())
+        );
+    }
+    Ok(())
+}
+
 // ── Interactive pickers ─────────────────────────────────────────────
 
 /// B) Pick a target folder — text input or interactive browser.
@@ -198,16 +334,17 @@ fn pick_target_folder() -> Result<PathBuf> {
     separator();
     println!(
         "  📂 {}",
-        "Where do you want to scan?".bold()
+        t!("wizard.where_title").bold()
     );
     separator();
     println!();
 
     loop {
-        let input = Text::new("Type a folder path (or press Enter to browse):")
-            .with_help_message("Paste a path, or leave empty to browse directories")
+        let help = t!("wizard.path_help");
+        let input = Text::new(&t!("wizard.path_prompt"))
+            .with_help_message(&help)
             .prompt()
-            .context("Wizard cancelled")?;
+            .context(t!("wizard.cancelled"))?;
 
         if input.trim().is_empty() {
             // Interactive folder browser
@@ -215,7 +352,7 @@ fn pick_target_folder() -> Result<PathBuf> {
                 Ok(Some(p)) => return Ok(p),
                 Ok(None) => {
                     // User cancelled browsing, re-prompt
-                    println!("  {}", "Browsing cancelled. Try typing a path instead.".dimmed());
+                    println!("  {}", t!("wizard.browse_retry").dimmed());
                     continue;
                 }
                 Err(e) => {
@@ -229,16 +366,12 @@ fn pick_target_folder() -> Result<PathBuf> {
         if path.is_dir() {
             return Ok(std::fs::canonicalize(&path).unwrap_or(path));
         } else if path.is_file() {
-            println!(
-                "  {} That's a file. Please enter a {} path.",
-                "⚠".yellow(),
-                "folder".bold()
-            );
+            println!("  {} {}", "⚠".yellow(), t!("wizard.is_file"));
         } else {
             println!(
-                "  {} \"{}\" does not exist. Please try again.",
+                "  {} {}",
                 "⚠".yellow(),
-                path.display()
+                t!("wizard.not_found", path = path.display())
             );
         }
     }
@@ -249,8 +382,8 @@ fn browse_folders(start: PathBuf) -> Result<Option<PathBuf>> {
     let mut current = start;
 
     loop {
-        let mut entries = vec![">>> [Select this folder] <<<".to_string()];
-        entries.push(".. (parent directory)".to_string());
+        let mut entries = vec![format!(">>> [{}] <<<", t!("wizard.select_folder"))];
+        entries.push(format!(".. ({})", t!("wizard.parent_folder")));
 
         // List subdirectories
         let mut dirs: Vec<String> = Vec::new();
@@ -274,11 +407,12 @@ fn browse_folders(start: PathBuf) -> Result<Option<PathBuf>> {
             entries.push(format!("📁 {d}"));
         }
 
-        let prompt_msg = format!("Browse: {}", current.display());
+        let prompt_msg = t!("wizard.browse", path = current.display());
+        let help = t!("wizard.browse_help");
         let selection = Select::new(&prompt_msg, entries)
-            .with_help_message("↑/↓ navigate, Enter to open/select, Esc to cancel")
+            .with_help_message(&help)
             .prompt_skippable()
-            .context("Browse cancelled")?;
+            .context(t!("wizard.browse_cancelled"))?;
 
         match selection {
             None => return Ok(None), // Esc pressed
@@ -305,23 +439,17 @@ fn confirm_trust(target: &Path) -> bool {
     separator();
     println!(
         "  🔒 {}",
-        "Do you trust the files in this folder?".bold()
+        t!("wizard.trust_title").bold()
     );
     separator();
     println!();
     println!("    {}", target.display().to_string().cyan().bold());
     println!();
-    println!(
-        "  {}",
-        "Anty will read files in this folder to scan for security issues.".dimmed()
-    );
-    println!(
-        "  {}",
-        "It will NOT execute code.".dimmed()
-    );
+    println!("  {}", t!("wizard.trust_reads").dimmed());
+    println!("  {}", t!("wizard.trust_no_exec").dimmed());
     println!();
 
-    Confirm::new("Proceed with scan?")
+    Confirm::new(&t!("wizard.proceed"))
         .with_default(true)
         .prompt()
         .unwrap_or(false)
@@ -335,23 +463,75 @@ enum ScanScope {
 }
 
 fn pick_scope() -> Result<ScanScope> {
-    let options = vec![
-        "Entire folder (all files)",
-        "A single file",
-    ];
+    let single = t!("wizard.scope_file");
+    let options = vec![t!("wizard.scope_folder"), single.clone()];
 
-    let choice = Select::new("What do you want to scan?", options)
-        .with_help_message("↑/↓ navigate, Enter to select")
+    let help = t!("wizard.select_help");
+    let choice = Select::new(&t!("wizard.scope_prompt"), options)
+        .with_help_message(&help)
         .prompt()
-        .context("Wizard cancelled")?;
+        .context(t!("wizard.cancelled"))?;
 
-    if choice.starts_with("A single") {
+    if choice == single {
         Ok(ScanScope::SingleFile)
     } else {
         Ok(ScanScope::Folder)
     }
 }
 
+/// F) Agent selection — all agents are ticked by default.
+fn pick_agents() -> Result<Option<String>> {
+    let all = agents::all_agents();
+    let options: Vec<String> = all
+        .iter()
+        .map(|a| format!("{} — {}", a.name(), a.description()))
+        .collect();
+
+    let help = t!("wizard.agents_help");
+    let chosen = MultiSelect::new(&t!("wizard.agents_prompt"), options)
+        .with_all_selected_by_default()
+        .with_help_message(&help)
+        .prompt()
+        .context(t!("wizard.cancelled"))?;
+
+    if chosen.is_empty() {
+        println!("  {}", t!("wizard.no_agents").dimmed());
+        return Ok(None);
+    }
+    if chosen.len() == all.len() {
+        return Ok(None);
+    }
+
+    let names: Vec<&str> = chosen
+        .iter()
+        .filter_map(|c| c.split(" — ").next())
+        .collect();
+    Ok(Some(names.join(",")))
+}
+
+/// G) Minimum severity to report.
+fn pick_min_severity() -> Result<Option<String>> {
+    let options = vec![
+        t!("wizard.severity_all"),
+        t!("wizard.severity_medium"),
+        t!("wizard.severity_high"),
+        t!("wizard.severity_critical"),
+    ];
+
+    let help = t!("wizard.select_help");
+    let choice = Select::new(&t!("wizard.severity_prompt"), options.clone())
+        .with_help_message(&help)
+        .prompt()
+        .context(t!("wizard.cancelled"))?;
+
+    Ok(match options.iter().position(|o| *o == choice) {
+        Some(1) => Some("MEDIUM".to_string()),
+        Some(2) => Some("HIGH".to_string()),
+        Some(3) => Some("CRITICAL".to_string()),
+        _ => None,
+    })
+}
+
 /// E) Interactive file picker inside a directory.
 fn pick_single_file(dir: &Path) -> Result<Option<PathBuf>> {
     let mut files: Vec<String> = Vec::new();
@@ -383,21 +563,22 @@ fn pick_single_file(dir: &Path) -> Result<Option<PathBuf>> {
 
     if files.is_empty() {
         println!(
-            "  {} No scannable files found in {}",
+            "  {} {}",
             "⚠".yellow(),
-            dir.display()
+            t!("wizard.no_files", path = dir.display())
         );
         return Ok(None);
     }
 
     files.sort_by_key(|a| a.to_lowercase());
 
-    let prompt_msg = format!("Select a file from {}", dir.display());
+    let prompt_msg = t!("wizard.file_prompt", path = dir.display());
+    let help = t!("wizard.file_help");
     let selection = Select::new(&prompt_msg, files)
-        .with_help_message("↑/↓ navigate, Enter to select, Esc to cancel")
+        .with_help_message(&help)
         .with_page_size(15)
         .prompt_skippable()
-        .context("File selection cancelled")?;
+        .context(t!("wizard.file_cancelled"))?;
 
     match selection {
         Some(name) => Ok(Some(dir.join(name))),
@@ -419,41 +600,32 @@ fn screen_welcome() {
         format!("v{}", env!("CARGO_PKG_VERSION")).dimmed()
     );
     println!();
-    println!(
-        "  {}",
-        "Developer-first security scanner".bold()
-    );
-    println!(
-        "  {}",
-        "Anty scans your code locally and never uploads it.".dimmed()
-    );
+    println!("  {}", t!("wizard.tagline").bold());
+    println!("  {}", t!("wizard.runs_locally").dimmed());
     println!();
     separator();
     println!();
-    press_enter("  Press Enter to continue...");
+    press_enter(&format!("  {}", t!("wizard.continue")));
 }
 
 /// B) Security & privacy notes
 fn screen_security_notes() {
     println!();
-    println!("  {}", "Security & Privacy".bold().underline());
+    println!("  {}", t!("wizard.privacy_title").bold().underline());
     println!();
     println!(
-        "  {}  Anty {} executes scanned code — it only reads files.",
+        "  {}  {}",
         "•".green(),
-        "never".bold()
+        t!("wizard.never_executes", never = t!("wizard.never").bold())
     );
     println!(
-        "  {}  Scans run {} on your machine. Nothing is uploaded.",
-        "•".green(),
-        "locally".bold()
-    );
-    println!(
-        "  {}  No telemetry, no tracking, no network calls.",
+        "  {}  {}",
         "•".green(),
+        t!("wizard.no_upload", locally = t!("wizard.locally").bold())
     );
+    println!("  {}  {}", "•".green(), t!("wizard.no_telemetry"));
     println!();
-    press_enter("  Press Enter to continue...");
+    press_enter(&format!("  {}", t!("wizard.continue")));
 }
 
 // ── Public entry-points ─────────────────────────────────────────────
@@ -461,6 +633,7 @@ fn screen_security_notes() {
 /// Interactive onboarding wizard (no-args mode).
 pub fn run_wizard() -> Result<()> {
     init_quiet_logging();
+    color::init_prompts();
 
     // A) Welcome
     screen_welcome();
@@ -473,8 +646,8 @@ pub fn run_wizard() -> Result<()> {
         Ok(t) => t,
         Err(_) => {
             println!();
-            println!("  {}", "Wizard cancelled. Goodbye! 👋".dimmed());
-            press_enter("  Press Enter to exit...");
+            println!("  {}", t!("wizard.cancelled_bye").dimmed());
+            press_enter(&format!("  {}", t!("wizard.exit")));
             return Ok(());
         }
     };
@@ -482,8 +655,8 @@ pub fn run_wizard() -> Result<()> {
     // D) Trust prompt for the chosen target
     if !confirm_trust(&target) {
         println!();
-        println!("  {}", "Scan cancelled. Goodbye! 👋".dimmed());
-        press_enter("  Press Enter to exit...");
+        println!("  {}", t!("wizard.scan_cancelled_bye").dimmed());
+        press_enter(&format!("  {}", t!("wizard.exit")));
         return Ok(());
     }
 
@@ -492,39 +665,56 @@ pub fn run_wizard() -> Result<()> {
         Ok(s) => s,
         Err(_) => {
             println!();
-            println!("  {}", "Wizard cancelled. Goodbye! 👋".dimmed());
-            press_enter("  Press Enter to exit...");
+            println!("  {}", t!("wizard.cancelled_bye").dimmed());
+            press_enter(&format!("  {}", t!("wizard.exit")));
+            return Ok(());
+        }
+    };
+
+    // F) Agents and G) minimum severity
+    let choices = match pick_agents().and_then(|agents| {
+        Ok(ScanChoices {
+            agents,
+            min_severity: pick_min_severity()?,
+        })
+    }) {
+        Ok(c) => c,
+        Err(_) => {
+            println!();
+            println!("  {}", t!("wizard.cancelled_bye").dimmed());
+            press_enter(&format!("  {}", t!("wizard.exit")));
             return Ok(());
         }
     };
 
-    // F) Execute scan
+    // H) Execute scan
     match scope {
         ScanScope::Folder => {
-            execute_scan(&target)?;
+            execute_scan(&target, &choices)?;
         }
         ScanScope::SingleFile => {
             match pick_single_file(&target) {
                 Ok(Some(file_path)) => {
-                    execute_scan(&file_path)?;
+                    execute_scan(&file_path, &choices)?;
                 }
                 Ok(None) => {
-                    println!("  {}", "No file selected.".dimmed());
+                    println!("  {}", t!("wizard.no_file_selected").dimmed());
                 }
                 Err(_) => {
-                    println!("  {}", "File selection cancelled.".dimmed());
+                    println!("  {}", t!("wizard.file_selection_cancelled").dimmed());
                 }
             }
         }
     }
 
-    press_enter("  Press Enter to exit...");
+    press_enter(&format!("  {}", t!("wizard.exit")));
     Ok(())
 }
 
 /// Drag-and-drop mode: a single directory was passed without a subcommand.
 pub fn run_drag_drop(path: &Path) -> Result<()> {
     init_quiet_logging();
+    color::init_prompts();
 
     println!();
     for line in LOGO.lines() {
@@ -533,18 +723,18 @@ pub fn run_drag_drop(path: &Path) -> Result<()> {
     separator();
     println!();
 
-    let proceed = Confirm::new(&format!("Scan this folder?  {}", path.display()))
+    let proceed = Confirm::new(&t!("wizard.drop_prompt", path = path.display()))
         .with_default(true)
         .prompt()
         .unwrap_or(false);
 
     if proceed {
-        execute_scan(path)?;
+        execute_scan(path, &ScanChoices::default())?;
     } else {
-        println!("  {}", "Scan cancelled.".dimmed());
+        println!("  {}", t!("wizard.scan_cancelled").dimmed());
     }
 
-    press_enter("  Press Enter to exit...");
+    press_enter(&format!("  {}", t!("wizard.exit")));
     Ok(())
 }
 
@@ -0,0 +1,232 @@
+use std::collections::BTreeSet;
+
+use crate::report::finding::{ComplianceSummary, ControlResult, ControlStatus, Finding};
+use crate::rules::RuleMeta;
+
+/// Values accepted by `--compliance`
+pub const FRAMEWORKS: &[&str] = &["pci", "soc2", "owasp-asvs"];
+
+/// A control of a compliance framework and the weaknesses that break it
+struct Control {
+    id: &'static str,
+    title: &'static str,
+    cwes: &'static [&'static str],
+}
+
+/// A framework's controls that Anty rules can provide evidence for
+struct Framework {
+    key: &'static str,
+    name: &'static str,
+    controls: &'static [Control],
+}
+
+const PCI_DSS: Framework = Framework {
+    key: "pci",
+    name: "PCI DSS v4.0",
+    controls: &[
+        Control {
+            id: "2.2.6",
+            title: "System security parameters are configured to prevent misuse",
+            cwes: &["CWE-489", "CWE-942", "CWE-668", "CWE-250", "CWE-693", "CWE-926"],
+        },
+        Control {
+            id: "3.6.1",
+            title: "Cryptographic keys are protected against disclosure",
+            cwes: &["CWE-321"],
+        },
+        Control {
+            id: "4.2.1",
+            title: "Strong cryptography protects data in transit",
+            cwes: &["CWE-319", "CWE-295", "CWE-614"],
+        },
+        Control {
+            id: "6.2.4",
+            title: "Software engineering techniques prevent common attacks",
+            cwes: &["CWE-95", "CWE-78", "CWE-89", "CWE-502", "CWE-79", "CWE-328", "CWE-494", "CWE-306"],
+        },
+        Control {
+            id: "7.2.2",
+            title: "Access is assigned with the least privileges necessary",
+            cwes: &["CWE-269", "CWE-732"],
+        },
+        Control {
+            id: "8.3.4",
+            title: "Invalid authentication attempts are limited",
+            cwes: &["CWE-307"],
+        },
+        Control {
+            id: "8.6.2",
+            title: "Account passwords are not hard coded in scripts, config, or source code",
+            cwes: &["CWE-798", "CWE-522"],
+        },
+    ],
+};
+
+const SOC2: Framework = Framework {
+    key: "soc2",
+    name: "SOC 2 (2017 Trust Services Criteria)",
+    controls: &[
+        Control {
+            id: "CC6.1",
+            title: "Logical access security over protected information assets",
+            cwes: &["CWE-798", "CWE-522", "CWE-321", "CWE-307", "CWE-306", "CWE-942", "CWE-926"],
+        },
+        Control {
+            id: "CC6.3",
+            title: "Access is granted on least privilege",
+            cwes: &["CWE-250", "CWE-269", "CWE-732"],
+        },
+        Control {
+            id: "CC6.6",
+            title: "Boundary protection against threats from outside the system",
+            cwes: &["CWE-668"],
+        },
+        Control {
+            id: "CC6.7",
+            title: "Transmission of information is restricted and protected",
+            cwes: &["CWE-319", "CWE-295", "CWE-614"],
+        },
+        Control {
+            id: "CC7.1",
+            title: "Vulnerabilities and insecure configurations are detected",
+            cwes: &[
+                "CWE-95", "CWE-78", "CWE-89", "CWE-502", "CWE-79", "CWE-328", "CWE-489", "CWE-494",
+                "CWE-693",
+            ],
+        },
+    ],
+};
+
+const OWASP_ASVS: Framework = Framework {
+    key: "owasp-asvs",
+    name: "OWASP ASVS 4.0.3",
+    controls: &[
+        Control {
+            id: "V2.2.1",
+            title: "Anti-automation controls limit credential attacks",
+            cwes: &["CWE-307"],
+        },
+        Control {
+            id: "V2.10.4",
+            title: "Passwords, API keys, and secrets are not included in source code",
+            cwes: &["CWE-798", "CWE-522"],
+        },
+        Control {
+            id: "V3.4.1",
+            title: "Cookie-based session tokens have the Secure attribute",
+            cwes: &["CWE-614"],
+        },
+        Control {
+            id: "V4.1.3",
+            title: "Users and services have only the privileges they need (least privilege)",
+            cwes: &["CWE-269", "CWE-926", "CWE-306", "CWE-732"],
+        },
+        Control {
+            id: "V5.2.4",
+            title: "eval() and other dynamic code execution are avoided",
+            cwes: &["CWE-95"],
+        },
+        Control {
+            id: "V5.3.3",
+            title: "Output encoding protects against reflected, stored, and DOM XSS",
+            cwes: &["CWE-79"],
+        },
+        Control {
+            id: "V5.3.4",
+            title: "Database queries use parameterized queries or an ORM",
+            cwes: &["CWE-89"],
+        },
+        Control {
+            id: "V5.3.8",
+            title: "The application protects against OS command injection",
+            cwes: &["CWE-78"],
+        },
+        Control {
+            id: "V5.5.3",
+            title: "Deserialization of untrusted data is avoided or protected",
+            cwes: &["CWE-502"],
+        },
+        Control {
+            id: "V6.2.5",
+            title: "Known insecure algorithms (MD5, SHA1, ...) are not used",
+            cwes: &["CWE-328"],
+        },
+        Control {
+            id: "V6.4.1",
+            title: "A secrets management solution holds key material",
+            cwes: &["CWE-321"],
+        },
+        Control {
+            id: "V9.1.1",
+            title: "TLS is used for all client connectivity",
+            cwes: &["CWE-319"],
+        },
+        Control {
+            id: "V9.2.1",
+            title: "Connections use trusted TLS certificates",
+            cwes: &["CWE-295"],
+        },
+        Control {
+            id: "V10.3.2",
+            title: "Code and updates are integrity-checked (code signing, hashes)",
+            cwes: &["CWE-494"],
+        },
+        Control {
+            id: "V14.3.2",
+            title: "Debug modes are disabled in production",
+            cwes: &["CWE-489"],
+        },
+        Control {
+            id: "V14.5.3",
+            title: "The CORS Access-Control-Allow-Origin header uses a strict allow list",
+            cwes: &["CWE-942"],
+        },
+    ],
+};
+
+const ALL: &[&Framework] = &[&PCI_DSS, &SOC2, &OWASP_ASVS];
+
+/// Map the rules that ran and their findings onto the controls of `framework`.
+/// Returns `None` for an unknown framework key.
+pub fn evaluate(framework: &str, rules: &[RuleMeta], findings: &[Finding]) -> Option<ComplianceSummary> {
+    let framework = ALL.iter().find(|f| f.key == framework)?;
+
+    let controls = framework
+        .controls
+        .iter()
+        .map(|control| {
+            let maps = |cwe: &Option<String>| {
+                cwe.as_deref().is_some_and(|c| control.cwes.contains(&c))
+            };
+            let checked_by: BTreeSet<String> = rules
+                .iter()
+                .filter(|r| maps(&r.cwe_id))
+                .map(|r| r.id.clone())
+                .collect();
+            let violations: Vec<String> = findings
+                .iter()
+                .filter(|f| maps(&f.cwe_id))
+                .map(|f| f.id.clone())
+                .collect();
+            let status = if !violations.is_empty() {
+                ControlStatus::Violated
+            } else if !checked_by.is_empty() {
+                ControlStatus::Passed
+            } else {
+                ControlStatus::NotChecked
+            };
+            ControlResult {
+                id: control.id.to_string(),
+                title: control.title.to_string(),
+                status,
+                rules: checked_by.into_iter().collect(),
+                findings: violations,
+            }
+        })
+        .collect();
+
+    Some(ComplianceSummary {
+        framework: framework.name.to_string(),
+        controls,
+    })
+}
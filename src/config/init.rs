@@ -0,0 +1,309 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use inquire::{Confirm, MultiSelect, Select, Text};
+
+use crate::agents;
+use crate::cli::color;
+use crate::profile;
+
+/// Project templates for `anty init --template`
+pub const TEMPLATES: &[&str] = &["node", "python", "rust", "monorepo"];
+
+/// What goes into a generated .anty.toml
+struct InitOptions {
+    template: Option<&'static str>,
+    exclude: Vec<String>,
+    /// Agents to enable (empty = all)
+    agents: Vec<String>,
+    profile: Option<String>,
+}
+
+impl InitOptions {
+    /// Settings for a template, or the generic defaults without one
+    fn for_template(template: Option<&str>) -> Self {
+        let (template, exclude, agents): (Option<&'static str>, &[&str], &[&str]) = match template {
+            Some("node") => (
+                Some("node"),
+                &[
+                    "**/*.min.js",
+                    "**/*.bundle.js",
+                    "**/*.test.*",
+                    "**/*.spec.*",
+                    "**/__tests__/**",
+                    "**/__mocks__/**",
+                    "package-lock.json",
+                    "yarn.lock",
+                    "pnpm-lock.yaml",
+                ],
+                &["secrets", "dangerous-functions", "config-issues", "auth"],
+            ),
+            Some("python") => (
+                Some("python"),
+                &[
+                    "tests/fixtures/**",
+                    "**/test_*.py",
+                    "**/*_test.py",
+                    "**/conftest.py",
+                    "*.egg-info/**",
+                    ".mypy_cache/**",
+                    ".pytest_cache/**",
+                    "htmlcov/**",
+                    "poetry.lock",
+                ],
+                &["secrets", "dangerous-functions", "config-issues", "auth"],
+            ),
+            Some("rust") => (
+                Some("rust"),
+                &["target/**", "tests/fixtures/**", "Cargo.lock"],
+                &["secrets", "dangerous-functions", "config-issues"],
+            ),
+            // Every agent: packages differ in language and platform
+            Some("monorepo") => (
+                Some("monorepo"),
+                &[
+                    "**/tests/fixtures/**",
+                    "**/*.min.js",
+                    "**/*.test.*",
+                    "**/*.spec.*",
+                    "**/target/**",
+                    "**/vendor/**",
+                    "**/package-lock.json",
+                    "**/yarn.lock",
+                    "**/pnpm-lock.yaml",
+                    "**/Cargo.lock",
+                    "**/poetry.lock",
+                ],
+                &[],
+            ),
+            _ => (None, &["tests/fixtures/**", "**/*.test.*", "**/*.spec.*"], &[]),
+        };
+        InitOptions {
+            template,
+            exclude: exclude.iter().map(|s| s.to_string()).collect(),
+            agents: agents.iter().map(|s| s.to_string()).collect(),
+            profile: None,
+        }
+    }
+
+    fn render(&self) -> String {
+        let notes = match self.template {
+            Some("monorepo") => "# Template: monorepo. Scan with --monorepo for per-package counts.\n".to_string(),
+            Some(name) => format!("# Template: {}\n", name),
+            None => String::new(),
+        };
+        let exclude = if self.exclude.is_empty() {
+            "exclude = []".to_string()
+        } else {
+            let lines: Vec<String> = self.exclude.iter().map(|e| format!("    {},", quote(e))).collect();
+            format!("exclude = [\n{}\n]", lines.join("\n"))
+        };
+        let agents = if self.agents.is_empty() {
+            let all: Vec<String> = agents::all_agents().iter().map(|a| quote(a.name())).collect();
+            format!("# enable = [{}]", all.join(", "))
+        } else {
+            let enabled: Vec<String> = self.agents.iter().map(|a| quote(a)).collect();
+            format!("enable = [{}]", enabled.join(", "))
+        };
+        let profile = match self.profile {
+            Some(ref name) => format!("name = {}", quote(name)),
+            None => "# name = \"default\"".to_string(),
+        };
+        TEMPLATE
+            .replace("@NOTES@", &notes)
+            .replace("@EXCLUDE@", &exclude)
+            .replace("@AGENTS@", &agents)
+            .replace("@PROFILE@", &profile)
+    }
+}
+
+/// A TOML basic string
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Create .anty.toml in the current directory, from a project template or
+/// by asking a few questions (--interactive)
+pub fn run(template: Option<&str>, interactive: bool) -> Result<()> {
+    let dir = std::env::current_dir()?;
+    let config_path = dir.join(".anty.toml");
+
+    if config_path.exists() {
+        let overwrite = interactive
+            && Confirm::new(".anty.toml already exists. Overwrite it?")
+                .with_default(false)
+                .prompt()
+                .unwrap_or(false);
+        if !overwrite {
+            println!("{}.anty.toml already exists in this directory", color::icon("⚠️  ", ""));
+            return Ok(());
+        }
+    }
+
+    let options = if interactive {
+        ask(&dir, template)?
+    } else {
+        InitOptions::for_template(template)
+    };
+
+    std::fs::write(&config_path, options.render())?;
+    match options.template {
+        Some(name) => println!("{}Created .anty.toml from the {} template", color::icon("✅ ", ""), name),
+        None => println!("{}Created .anty.toml", color::icon("✅ ", "")),
+    }
+    println!("{}Edit it to customize your scan settings.", color::icon("   ", ""));
+
+    Ok(())
+}
+
+/// The template that fits the project in `dir`, judged by its manifests
+fn detect_template(dir: &Path) -> Option<&'static str> {
+    let has = |name: &str| dir.join(name).exists();
+    let manifests = [
+        has("package.json"),
+        has("pyproject.toml") || has("setup.py") || has("requirements.txt"),
+        has("Cargo.toml"),
+    ];
+    if has("pnpm-workspace.yaml") || has("lerna.json") || has("nx.json") || manifests.iter().filter(|m| **m).count() > 1 {
+        return Some("monorepo");
+    }
+    TEMPLATES.iter().zip(manifests).find(|(_, found)| *found).map(|(name, _)| *name)
+}
+
+/// Interactive questions, starting from the given or detected template
+fn ask(dir: &Path, template: Option<&str>) -> Result<InitOptions> {
+    const LABELS: &[(&str, Option<&str>)] = &[
+        ("Node.js / TypeScript", Some("node")),
+        ("Python", Some("python")),
+        ("Rust", Some("rust")),
+        ("Monorepo (several languages or packages)", Some("monorepo")),
+        ("Something else", None),
+    ];
+    let guess = template.or_else(|| detect_template(dir));
+    let labels: Vec<&str> = LABELS.iter().map(|(label, _)| *label).collect();
+    let cursor = LABELS.iter().position(|(_, t)| *t == guess).unwrap_or(LABELS.len() - 1);
+    let kind = Select::new("What kind of project is this?", labels)
+        .with_starting_cursor(cursor)
+        .prompt()
+        .context("Init cancelled")?;
+    let template = LABELS.iter().find(|(label, _)| *label == kind).and_then(|(_, t)| *t);
+    let mut options = InitOptions::for_template(template);
+
+    let names: Vec<String> = agents::all_agents().iter().map(|a| a.name().to_string()).collect();
+    let selected: Vec<usize> = names
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| options.agents.is_empty() || options.agents.contains(n))
+        .map(|(i, _)| i)
+        .collect();
+    let chosen = MultiSelect::new("Which agents should run?", names.clone())
+        .with_default(&selected)
+        .with_help_message("Space to toggle, Enter to confirm")
+        .prompt()
+        .context("Init cancelled")?;
+    // All of them is written as "empty", so new agents are picked up too
+    options.agents = if chosen.len() == names.len() { Vec::new() } else { chosen };
+
+    let extra = Text::new("Other paths to exclude (comma-separated globs):")
+        .with_help_message("e.g. generated/**, docs/**; leave empty for none")
+        .prompt_skippable()
+        .unwrap_or(None)
+        .unwrap_or_default();
+    options.exclude.extend(
+        extra
+            .split(',')
+            .map(str::trim)
+            .filter(|g| !g.is_empty())
+            .map(str::to_string),
+    );
+
+    let profile = Select::new("How strict should the scan be?", profile::NAMES.to_vec())
+        .with_starting_cursor(profile::NAMES.iter().position(|p| *p == "default").unwrap_or(0))
+        .with_help_message("strict fails on any finding; lenient reports only likely-real MEDIUM or worse")
+        .prompt()
+        .context("Init cancelled")?;
+    options.profile = (profile != "default").then(|| profile.to_string());
+
+    Ok(options)
+}
+
+/// .anty.toml with `@NAME@` placeholders filled in by `InitOptions::render`
+const TEMPLATE: &str = r#"# Anty Security Scanner Configuration
+# https://anty.dev/docs/config
+@NOTES@
+[scan]
+# Glob patterns to exclude from scanning
+@EXCLUDE@
+
+# Only scan these languages, or skip some (names as in [languages])
+# lang = ["typescript", "python"]
+# exclude_lang = ["json", "yaml"]
+
+# Only run rules with these tags, or skip some (see `anty list-rules`)
+# tags = ["low-noise"]
+# exclude_tags = ["misconfig"]
+
+# Max file size to scan (bytes). Default: 1MB
+# max_file_size = 1048576
+
+# Give up on a file after this many seconds (0 = no limit). Default: 10
+# file_timeout = 10
+
+# Follow symlinks (for deployment repos that are mostly symlink farms)
+# follow_symlinks = false
+
+[agents]
+# Enable specific agents (empty = all)
+@AGENTS@
+
+# Disable specific agents
+# disable = []
+
+# Minimum severity per agent, instead of output.min_severity
+# [agents.thresholds]
+# secrets = "LOW"
+# config-issues = "HIGH"
+
+[output]
+# Default output format: "terminal" or "json"
+format = "terminal"
+
+# Minimum severity to report: "LOW", "MEDIUM", "HIGH", "CRITICAL"
+# min_severity = "LOW"
+
+# Open file:line links in your editor (terminals with hyperlink support)
+# editor_url_template = "vscode://file/{path}:{line}"
+
+[languages]
+# Map in-house extensions or filenames to a language
+# "*.gyb" = "python"
+# "Justfile" = "shell"
+
+[profile]
+# Preset of min severity and confidence, rule tags, max findings, and
+# fail-on threshold: "strict", "default", or "lenient". Settings in this
+# file and command-line flags override it.
+@PROFILE@
+
+[policy]
+# Fail the scan (exit code 2) unless these agents run
+# require_agents = ["secrets"]
+
+# No CRITICAL findings in payment code
+# [[policy.rules]]
+# paths = ["src/payments/**"]
+# severity = "CRITICAL"
+# max = 0
+
+# At most 5 MEDIUM-or-worse findings in total
+# [[policy.rules]]
+# severity = "MEDIUM"
+# max = 5
+
+# Quieter rules for part of the tree (globs relative to the scan root)
+# [[overrides]]
+# paths = ["examples/**", "legacy/**"]
+# disable_rules = ["ANTY-CFG-003"]
+# disable_agents = ["auth"]
+# max_severity = "LOW"
+"#;
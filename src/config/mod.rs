@@ -0,0 +1,268 @@
+pub mod init;
+pub mod overrides;
+pub mod validate;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use self::overrides::Override;
+use crate::policy::Policy;
+use crate::profile::ProfileConfig;
+
+/// Anty configuration (loaded from .anty.toml)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AntyConfig {
+    #[serde(default)]
+    pub scan: ScanConfig,
+
+    #[serde(default)]
+    pub agents: AgentsConfig,
+
+    #[serde(default)]
+    pub output: OutputConfig,
+
+    /// Extra extension/filename → language mappings, e.g. `"*.gyb" = "python"`
+    #[serde(default)]
+    pub languages: BTreeMap<String, String>,
+
+    /// Requirements checked after the scan that decide the exit code
+    #[serde(default)]
+    pub policy: Policy,
+
+    /// Preset of scan settings (`--profile`)
+    #[serde(default)]
+    pub profile: ProfileConfig,
+
+    /// Path-scoped settings, e.g. rules disabled under `examples/`
+    #[serde(default)]
+    pub overrides: Vec<Override>,
+
+    /// Rule bundle updates (`anty update-rules`)
+    #[serde(default)]
+    pub rules: RulesConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ScanConfig {
+    /// Glob patterns to exclude
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// Glob patterns to include
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Languages to scan (empty = all)
+    #[serde(default)]
+    pub lang: Vec<String>,
+
+    /// Languages to skip
+    #[serde(default)]
+    pub exclude_lang: Vec<String>,
+
+    /// Rule tags to run (empty = all rules)
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Rule tags to skip
+    #[serde(default)]
+    pub exclude_tags: Vec<String>,
+
+    /// Max file size in bytes
+    #[serde(default = "default_max_file_size")]
+    pub max_file_size: u64,
+
+    /// Time budget per file in seconds (0 = no limit)
+    #[serde(default)]
+    pub file_timeout: Option<u64>,
+
+    /// Follow symlinked files and directories
+    #[serde(default)]
+    pub follow_symlinks: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AgentsConfig {
+    /// Agents to enable (empty = all)
+    #[serde(default)]
+    pub enable: Vec<String>,
+
+    /// Agents to disable
+    #[serde(default)]
+    pub disable: Vec<String>,
+
+    /// Agent → minimum severity it reports, e.g. `config-issues = "HIGH"`.
+    /// Replaces `output.min_severity` for that agent.
+    #[serde(default)]
+    pub thresholds: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OutputConfig {
+    /// Default output format
+    #[serde(default = "default_format")]
+    pub format: String,
+
+    /// Minimum severity to report
+    #[serde(default)]
+    pub min_severity: Option<String>,
+
+    /// Link `file:line` in terminal output to an editor, e.g.
+    /// `vscode://file/{path}:{line}`. Placeholders: {path} (absolute, without
+    /// the leading slash), {line}, {column}. Defaults to a `file://` link.
+    #[serde(default)]
+    pub editor_url_template: Option<String>,
+}
+
+/// `update_url` and `trusted_keys` are read from the user config only: a
+/// scanned repository must not be able to point updates elsewhere or trust
+/// its own signing key.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RulesConfig {
+    /// Bundle URL (its signature is fetched from `<url>.sig`)
+    #[serde(default)]
+    pub update_url: Option<String>,
+
+    /// Extra hex Ed25519 public keys accepted on bundles, for self-hosted mirrors
+    #[serde(default)]
+    pub trusted_keys: Vec<String>,
+
+    /// Shared rule packs, `git+<url>#<ref>`, fetched into `~/.anty/rules/git`
+    #[serde(default)]
+    pub packs: Vec<String>,
+
+    /// Pack → the commit its ref must resolve to
+    #[serde(default)]
+    pub pins: BTreeMap<String, String>,
+}
+
+fn default_max_file_size() -> u64 {
+    1_048_576 // 1MB
+}
+
+fn default_format() -> String {
+    "terminal".to_string()
+}
+
+impl AntyConfig {
+    /// Load the user config layered beneath the nearest .anty.toml.
+    ///
+    /// Precedence, highest first: CLI flags, the repo's `.anty.toml`, the
+    /// user config (see [`user_config_path`]), built-in defaults. Tables are
+    /// merged key by key, lists (excludes, includes, ...) are combined, and
+    /// any other value set in the repo replaces the user's.
+    pub fn load(scan_path: &Path) -> Option<Self> {
+        let user = user_config_path().filter(|p| p.is_file()).and_then(|p| read_layer(&p));
+        let repo = find_config_file(scan_path).and_then(|p| read_layer(&p));
+        let merged = match (user, repo) {
+            (Some(mut user), Some(repo)) => {
+                merge_layer(&mut user, repo);
+                user
+            }
+            (user, repo) => repo.or(user)?,
+        };
+        Self::from_layer(merged)
+    }
+
+    /// Load only the user config (for --no-config, which distrusts the repo)
+    pub fn load_user() -> Option<Self> {
+        user_config_path()
+            .filter(|p| p.is_file())
+            .and_then(|p| read_layer(&p))
+            .and_then(Self::from_layer)
+    }
+
+    fn from_layer(layer: toml::Table) -> Option<Self> {
+        match toml::Value::Table(layer).try_into::<AntyConfig>() {
+            Ok(config) => Some(config),
+            Err(e) => {
+                tracing::warn!("Invalid config, ignoring it: {} (run `anty validate-config`)", e);
+                None
+            }
+        }
+    }
+}
+
+/// Per-user config file: `$XDG_CONFIG_HOME/anty/config.toml`, falling back to
+/// `~/.config/anty/config.toml` (`%APPDATA%\anty\config.toml` on Windows).
+pub fn user_config_path() -> Option<PathBuf> {
+    #[cfg(windows)]
+    let dir = std::env::var_os("APPDATA").map(PathBuf::from);
+    #[cfg(not(windows))]
+    let dir = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")));
+
+    dir.map(|d| d.join("anty").join("config.toml"))
+}
+
+/// Read one config file as a raw TOML table, warning if it does not parse
+fn read_layer(path: &Path) -> Option<toml::Table> {
+    debug!("Found config: {}", path.display());
+    match std::fs::read_to_string(path) {
+        Ok(content) => match content.parse::<toml::Table>() {
+            Ok(table) => {
+                info!("Loaded config from {}", path.display());
+                Some(table)
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to parse {}, ignoring it (run `anty validate-config`): {}",
+                    path.display(),
+                    e
+                );
+                None
+            }
+        },
+        Err(e) => {
+            debug!("Could not read {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Merge `over` into `base`: nested tables merge, lists append, values replace
+fn merge_layer(base: &mut toml::Table, over: toml::Table) {
+    for (key, value) in over {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(b)), toml::Value::Table(o)) => merge_layer(b, o),
+            (Some(toml::Value::Array(b)), toml::Value::Array(o)) => b.extend(o),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Per-user Anty data directory: `$ANTY_HOME`, or `~/.anty` by default.
+/// Installed rule packs live under `rules/` in here.
+pub fn anty_home() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("ANTY_HOME") {
+        return Some(PathBuf::from(dir));
+    }
+
+    #[cfg(windows)]
+    let home = std::env::var_os("USERPROFILE");
+    #[cfg(not(windows))]
+    let home = std::env::var_os("HOME");
+
+    home.map(|h| PathBuf::from(h).join(".anty"))
+}
+
+/// Walk up from the scan path to find .anty.toml
+fn find_config_file(start: &Path) -> Option<PathBuf> {
+    let mut current = start.to_path_buf();
+    loop {
+        let config = current.join(".anty.toml");
+        if config.exists() {
+            return Some(config);
+        }
+        if !current.pop() {
+            return None;
+        }
+    }
+}
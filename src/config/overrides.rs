@@ -0,0 +1,88 @@
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::report::finding::{Finding, Severity};
+
+/// Settings for part of the tree (`[[overrides]]` in .anty.toml), e.g.
+/// quieter rules under `examples/` or `legacy/`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Override {
+    /// Globs relative to the scan root
+    #[serde(default)]
+    pub paths: Vec<String>,
+
+    /// Rule IDs not reported under these paths
+    #[serde(default)]
+    pub disable_rules: Vec<String>,
+
+    /// Agents whose findings are not reported under these paths
+    #[serde(default)]
+    pub disable_agents: Vec<String>,
+
+    /// Report findings under these paths at this severity at most
+    #[serde(default)]
+    pub max_severity: Option<String>,
+}
+
+impl Override {
+    pub fn path_matcher(&self) -> Result<GlobSet, String> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.paths {
+            let glob = GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .case_insensitive(cfg!(windows))
+                .build()
+                .map_err(|e| format!("invalid path pattern '{}': {}", pattern, e))?;
+            builder.add(glob);
+        }
+        builder.build().map_err(|e| e.to_string())
+    }
+}
+
+/// Overrides with their globs compiled, applied to each finding by the
+/// file it is in. Every override matching a file applies, in order.
+#[derive(Default)]
+pub struct Overrides {
+    entries: Vec<(GlobSet, Override)>,
+}
+
+impl Overrides {
+    pub fn new(overrides: &[Override]) -> Result<Self> {
+        let entries = overrides
+            .iter()
+            .enumerate()
+            .map(|(i, o)| {
+                let paths = o
+                    .path_matcher()
+                    .map_err(anyhow::Error::msg)
+                    .with_context(|| format!("Invalid overrides.{}.paths in config", i))?;
+                Ok((paths, o.clone()))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Overrides { entries })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drop disabled findings and lower capped severities. Runs before
+    /// --min-severity so that lowered findings can be filtered out too.
+    pub fn apply(&self, findings: &mut Vec<Finding>) {
+        findings.retain_mut(|finding| {
+            for (paths, o) in &self.entries {
+                if !paths.is_match(&finding.file_path) {
+                    continue;
+                }
+                if o.disable_rules.contains(&finding.rule_id) || o.disable_agents.contains(&finding.agent) {
+                    return false;
+                }
+                if let Some(max) = o.max_severity.as_deref().map(Severity::from_str) {
+                    finding.severity = finding.severity.min(max);
+                }
+            }
+            true
+        });
+    }
+}
@@ -0,0 +1,245 @@
+use std::path::{Path, PathBuf};
+
+use anstream::println;
+use anyhow::Result;
+use ignore::overrides::OverrideBuilder;
+use owo_colors::OwoColorize;
+
+use super::{find_config_file, user_config_path, AntyConfig};
+use crate::agents::{self, Language};
+use crate::cli::color;
+use crate::cli::exit_code;
+use crate::profile;
+use crate::report;
+use crate::rules::{bundle, packs};
+
+const SEVERITIES: &[&str] = &["CRITICAL", "HIGH", "MEDIUM", "LOW"];
+
+/// Something wrong with one key of a config file
+struct Problem {
+    /// Dotted key path, e.g. `scan.exclude`; empty for whole-file errors
+    key: String,
+    message: String,
+}
+
+impl Problem {
+    fn new(key: impl Into<String>, message: impl Into<String>) -> Self {
+        Problem {
+            key: key.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// `anty validate-config`: check the user config and the `.anty.toml` that
+/// applies to `path` (or `path` itself, if it is a file).
+/// Exits with [`exit_code::ERROR`] if any problem is found.
+pub fn run(path: &Path) -> Result<i32> {
+    let files: Vec<PathBuf> = if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        let repo = find_config_file(&std::fs::canonicalize(path)?);
+        user_config_path()
+            .filter(|p| p.is_file())
+            .into_iter()
+            .chain(repo)
+            .collect()
+    };
+
+    if files.is_empty() {
+        println!("No config files found (looked for .anty.toml and the user config).");
+        return Ok(exit_code::CLEAN);
+    }
+
+    let mut total = 0;
+    for file in &files {
+        let problems = check_file(file);
+        total += problems.len();
+        if problems.is_empty() {
+            println!("{}{} is valid", color::icon("✅ ", ""), file.display());
+            continue;
+        }
+        println!(
+            "{} {}: {} problem{}",
+            color::icon("✗", "x").red().bold(),
+            file.display(),
+            problems.len(),
+            if problems.len() == 1 { "" } else { "s" }
+        );
+        for problem in &problems {
+            // TOML parse errors span several lines (source excerpt and caret)
+            let message = problem.message.trim_end().replace('\n', "\n      ");
+            if problem.key.is_empty() {
+                println!("    {}", message);
+            } else {
+                println!("    {}: {}", problem.key.bold(), message);
+            }
+        }
+    }
+
+    Ok(if total > 0 { exit_code::ERROR } else { exit_code::CLEAN })
+}
+
+/// Every problem in one config file
+fn check_file(path: &Path) -> Vec<Problem> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return vec![Problem::new("", format!("could not read file: {}", e))],
+    };
+    let table = match content.parse::<toml::Table>() {
+        Ok(table) => table,
+        Err(e) => return vec![Problem::new("", e.to_string())],
+    };
+
+    // serde silently drops unknown keys; collect them instead
+    let mut problems = Vec::new();
+    let config: AntyConfig = match serde_ignored::deserialize(toml::Value::Table(table), |key| {
+        problems.push(Problem::new(key.to_string(), "unknown key"));
+    }) {
+        Ok(config) => config,
+        Err(e) => {
+            problems.push(Problem::new("", e.to_string()));
+            return problems;
+        }
+    };
+
+    check_values(&config, &mut problems);
+    problems
+}
+
+/// Checks the types alone cannot express
+fn check_values(config: &AntyConfig, problems: &mut Vec<Problem>) {
+    if let Some(ref severity) = config.output.min_severity {
+        check_severity("output.min_severity", severity, problems);
+    }
+    // Empty when the [output] table is left out entirely
+    let format = config.output.format.as_str();
+    if !format.is_empty() && !report::FORMATS.contains(&format) {
+        problems.push(Problem::new(
+            "output.format",
+            format!("unknown format '{}' (expected {})", format, report::FORMATS.join(", ")),
+        ));
+    }
+
+    if let Some(ref name) = config.profile.name {
+        if profile::find(name).is_none() {
+            problems.push(Problem::new(
+                "profile.name",
+                format!("unknown profile '{}' (expected {})", name, profile::NAMES.join(", ")),
+            ));
+        }
+    }
+
+    for pattern in &config.scan.exclude {
+        let mut builder = OverrideBuilder::new(".");
+        if let Err(e) = builder.add(&format!("!{}", pattern)) {
+            problems.push(Problem::new("scan.exclude", format!("invalid glob '{}': {}", pattern, e)));
+        }
+    }
+
+    let all_agents = agents::all_agents();
+    let agent_names: Vec<&str> = all_agents.iter().map(|a| a.name()).collect();
+    let agent_lists = [
+        ("agents.enable", &config.agents.enable),
+        ("agents.disable", &config.agents.disable),
+        ("policy.require_agents", &config.policy.require_agents),
+    ];
+    for (key, names) in agent_lists {
+        for name in names {
+            if !agent_names.iter().any(|a| a.eq_ignore_ascii_case(name)) {
+                problems.push(Problem::new(
+                    key,
+                    format!("unknown agent '{}' (expected {})", name, agent_names.join(", ")),
+                ));
+            }
+        }
+    }
+
+    for (agent, severity) in &config.agents.thresholds {
+        let key = format!("agents.thresholds.{}", agent);
+        if !agent_names.iter().any(|a| a.eq_ignore_ascii_case(agent)) {
+            problems.push(Problem::new(
+                key.clone(),
+                format!("unknown agent '{}' (expected {})", agent, agent_names.join(", ")),
+            ));
+        }
+        check_severity(&key, severity, problems);
+    }
+
+    for (pattern, language) in &config.languages {
+        if Language::from_name(language).is_none() {
+            problems.push(Problem::new(
+                format!("languages.\"{}\"", pattern),
+                format!("unknown language '{}'", language),
+            ));
+        }
+    }
+
+    let language_lists = [("scan.lang", &config.scan.lang), ("scan.exclude_lang", &config.scan.exclude_lang)];
+    for (key, names) in language_lists {
+        for name in names {
+            if Language::from_name(name).is_none() {
+                problems.push(Problem::new(key, format!("unknown language '{}'", name)));
+            }
+        }
+    }
+
+    for (i, o) in config.overrides.iter().enumerate() {
+        let key = format!("overrides.{}", i);
+        if o.paths.is_empty() {
+            problems.push(Problem::new(format!("{}.paths", key), "no paths; use the top-level settings for the whole tree"));
+        }
+        if let Err(message) = o.path_matcher() {
+            problems.push(Problem::new(format!("{}.paths", key), message));
+        }
+        for name in &o.disable_agents {
+            if !agent_names.contains(&name.as_str()) {
+                problems.push(Problem::new(
+                    format!("{}.disable_agents", key),
+                    format!("unknown agent '{}' (expected {})", name, agent_names.join(", ")),
+                ));
+            }
+        }
+        if let Some(ref severity) = o.max_severity {
+            check_severity(&format!("{}.max_severity", key), severity, problems);
+        }
+    }
+
+    for key in &config.rules.trusted_keys {
+        if let Err(e) = bundle::parse_key(key) {
+            problems.push(Problem::new("rules.trusted_keys", format!("invalid key '{}': {}", key, e)));
+        }
+    }
+
+    for spec in &config.rules.packs {
+        if let Err(e) = packs::PackSpec::parse(spec) {
+            problems.push(Problem::new("rules.packs", e.to_string()));
+        }
+    }
+    for (spec, pin) in &config.rules.pins {
+        if let Err(e) = packs::check_pin(pin) {
+            problems.push(Problem::new(format!("rules.pins.\"{}\"", spec), e.to_string()));
+        }
+    }
+
+    for (i, rule) in config.policy.rules.iter().enumerate() {
+        // Same key style as serde_ignored reports: policy.rules.0.severity
+        let key = format!("policy.rules.{}", i);
+        if let Some(ref severity) = rule.severity {
+            check_severity(&format!("{}.severity", key), severity, problems);
+        }
+        if let Err(message) = rule.path_matcher() {
+            problems.push(Problem::new(format!("{}.paths", key), message));
+        }
+    }
+}
+
+/// Unknown severities would otherwise be read as LOW
+fn check_severity(key: &str, value: &str, problems: &mut Vec<Problem>) {
+    if !SEVERITIES.contains(&value.to_uppercase().as_str()) {
+        problems.push(Problem::new(
+            key,
+            format!("unknown severity '{}' (expected {})", value, SEVERITIES.join(", ")),
+        ));
+    }
+}
@@ -0,0 +1,54 @@
+use std::sync::{Condvar, Mutex};
+
+/// Caps the bytes of file content held in memory at once (--max-memory).
+///
+/// Readers reserve a file's size before reading it and the reservation is
+/// released when the scan drops the file, so reading stalls while the scan
+/// threads are behind instead of piling contents up in memory.
+pub struct MemoryBudget {
+    /// None = unlimited
+    limit: Option<u64>,
+    used: Mutex<u64>,
+    released: Condvar,
+}
+
+/// Bytes reserved from a [`MemoryBudget`], given back on drop
+pub struct Reservation<'a> {
+    budget: &'a MemoryBudget,
+    bytes: u64,
+}
+
+impl MemoryBudget {
+    pub fn new(limit: Option<u64>) -> Self {
+        MemoryBudget {
+            limit,
+            used: Mutex::new(0),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Block until `bytes` fit in the budget. A single file larger than the
+    /// whole budget is let through once nothing else is held, so it is
+    /// scanned alone rather than never.
+    pub fn reserve(&self, bytes: u64) -> Reservation<'_> {
+        let Some(limit) = self.limit else {
+            return Reservation { budget: self, bytes: 0 };
+        };
+        let mut used = self.used.lock().unwrap();
+        while *used > 0 && *used + bytes > limit {
+            used = self.released.wait(used).unwrap();
+        }
+        *used += bytes;
+        Reservation { budget: self, bytes }
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if self.bytes == 0 {
+            return;
+        }
+        *self.budget.used.lock().unwrap() -= self.bytes;
+        self.budget.released.notify_all();
+    }
+}
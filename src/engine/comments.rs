@@ -0,0 +1,278 @@
+use crate::agents::Language;
+
+/// How comments and strings are written in a language
+struct Syntax {
+    /// Markers that comment out the rest of the line
+    line: &'static [&'static str],
+    /// Block comment delimiters
+    block: Option<(&'static str, &'static str)>,
+    /// Block comments nest (Rust)
+    nested: bool,
+    /// The block delimiters only count at the start of a line (Ruby's
+    /// `=begin` / `=end`)
+    block_at_line_start: bool,
+    /// `#` starts a comment only at the start of a line or after
+    /// whitespace, as in `url: http://host/#anchor`
+    hash_after_space: bool,
+    /// Line markers only count as the first thing on a line (files of
+    /// unknown language)
+    line_start_only: bool,
+    /// String delimiters; comment markers inside strings are code
+    quotes: &'static [u8],
+    /// Python: `"""` strings, and docstrings count as comments
+    triple_quotes: bool,
+    /// Rust: `'` is a char literal or a lifetime, not a string
+    char_literals: bool,
+}
+
+const C_LIKE: Syntax = Syntax {
+    line: &["//"],
+    block: Some(("/*", "*/")),
+    nested: false,
+    block_at_line_start: false,
+    hash_after_space: false,
+    line_start_only: false,
+    quotes: b"\"'",
+    triple_quotes: false,
+    char_literals: false,
+};
+
+const HASH: Syntax = Syntax {
+    line: &["#"],
+    block: None,
+    hash_after_space: true,
+    ..C_LIKE
+};
+
+impl Syntax {
+    fn for_language(language: Option<Language>) -> Syntax {
+        match language {
+            Some(Language::JavaScript | Language::TypeScript | Language::Go) => Syntax { quotes: b"\"'`", ..C_LIKE },
+            Some(Language::Java | Language::CSharp) => C_LIKE,
+            Some(Language::Rust) => Syntax { nested: true, quotes: b"\"", char_literals: true, ..C_LIKE },
+            Some(Language::Php) => Syntax { line: &["//", "#"], ..C_LIKE },
+            Some(Language::Terraform) => Syntax { line: &["#", "//"], quotes: b"\"", ..C_LIKE },
+            Some(Language::Sql) => Syntax { line: &["--"], ..C_LIKE },
+            Some(Language::Python) => Syntax { hash_after_space: false, triple_quotes: true, ..HASH },
+            Some(Language::Ruby) => Syntax {
+                block: Some(("=begin", "=end")),
+                block_at_line_start: true,
+                hash_after_space: false,
+                ..HASH
+            },
+            Some(Language::PowerShell) => Syntax { block: Some(("<#", "#>")), ..HASH },
+            Some(Language::Shell | Language::Yaml | Language::Toml | Language::Dockerfile | Language::Env) => HASH,
+            // JSONC (tsconfig.json, editor settings); plain JSON has no
+            // comment markers outside strings
+            Some(Language::Json) => Syntax { quotes: b"\"", ..C_LIKE },
+            Some(Language::Unknown) | None => Syntax {
+                line: &["//", "#"],
+                block: Some(("<!--", "-->")),
+                line_start_only: true,
+                quotes: b"",
+                ..C_LIKE
+            },
+        }
+    }
+}
+
+enum State {
+    Code,
+    LineComment,
+    /// Nesting depth of block comments
+    Block(usize),
+    /// Inside a string closed by this delimiter
+    Str(&'static [u8]),
+    /// Inside a Python docstring closed by this delimiter
+    Docstring(&'static [u8]),
+}
+
+/// `content` with every comment (line and block comments, Python
+/// docstrings, JSX `{/* */}` comments) replaced by spaces. Newlines, byte
+/// offsets, and strings are kept, so agents can match the result line by
+/// line and report the same columns as in the original.
+pub fn mask(content: &str, language: Option<Language>) -> String {
+    mask_with(content, &Syntax::for_language(language))
+}
+
+/// [`mask`] for `#`-commented config formats that have no [`Language`]:
+/// nginx and Apache configs, `.pgpass`
+pub fn mask_hash(content: &str) -> String {
+    mask_with(content, &HASH)
+}
+
+fn mask_with(content: &str, syntax: &Syntax) -> String {
+    let bytes = content.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut state = State::Code;
+    let mut line_start = true;
+    // Last non-blank code byte, to tell docstrings from string arguments
+    let mut last_code: Option<u8> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        let b = bytes[i];
+        match state {
+            State::Code => {
+                if let Some((open, _)) = syntax.block {
+                    if rest.starts_with(open.as_bytes()) && (!syntax.block_at_line_start || line_start) {
+                        state = State::Block(1);
+                        blank(&mut out, i, open.len());
+                        i += open.len();
+                        line_start = false;
+                        continue;
+                    }
+                }
+                if syntax.line.iter().any(|marker| {
+                    rest.starts_with(marker.as_bytes())
+                        && (!syntax.line_start_only || line_start)
+                        && (*marker != "#" || hash_starts_comment(syntax, bytes, i))
+                }) {
+                    state = State::LineComment;
+                    continue;
+                }
+                if syntax.triple_quotes && (rest.starts_with(b"\"\"\"") || rest.starts_with(b"'''")) {
+                    let delimiter: &'static [u8] = if b == b'"' { b"\"\"\"" } else { b"'''" };
+                    if line_start && matches!(last_code, None | Some(b':')) {
+                        state = State::Docstring(delimiter);
+                        blank(&mut out, i, 3);
+                    } else {
+                        state = State::Str(delimiter);
+                    }
+                    i += 3;
+                    line_start = false;
+                    continue;
+                }
+                if syntax.char_literals && b == b'\'' {
+                    // 'x' and '\n' are skipped whole; a lone ' is a lifetime
+                    let len = if rest.get(1) == Some(&b'\\') {
+                        rest.iter().skip(2).position(|&c| c == b'\'').map_or(1, |p| p + 3)
+                    } else if rest.get(2) == Some(&b'\'') {
+                        3
+                    } else {
+                        1
+                    };
+                    i += len;
+                    last_code = Some(b'\'');
+                    line_start = false;
+                    continue;
+                }
+                if syntax.quotes.contains(&b) {
+                    state = State::Str(delimiter(b));
+                    last_code = Some(b);
+                    line_start = false;
+                } else if b == b'\n' {
+                    line_start = true;
+                    i += 1;
+                    continue;
+                } else if !b.is_ascii_whitespace() {
+                    last_code = Some(b);
+                    line_start = false;
+                }
+                i += 1;
+            }
+            State::LineComment => {
+                if b == b'\n' {
+                    state = State::Code;
+                    line_start = true;
+                } else {
+                    blank(&mut out, i, 1);
+                }
+                i += 1;
+            }
+            State::Block(depth) => {
+                let (open, close) = syntax.block.expect("block state needs block syntax");
+                let at_line_start = !syntax.block_at_line_start || i == 0 || bytes[i - 1] == b'\n';
+                if at_line_start && rest.starts_with(close.as_bytes()) {
+                    blank(&mut out, i, close.len());
+                    i += close.len();
+                    state = if depth > 1 { State::Block(depth - 1) } else { State::Code };
+                } else if syntax.nested && rest.starts_with(open.as_bytes()) {
+                    blank(&mut out, i, open.len());
+                    i += open.len();
+                    state = State::Block(depth + 1);
+                } else {
+                    blank(&mut out, i, 1);
+                    i += 1;
+                }
+            }
+            State::Docstring(delimiter) => {
+                if rest.starts_with(delimiter) {
+                    blank(&mut out, i, delimiter.len());
+                    i += delimiter.len();
+                    last_code = Some(delimiter[0]);
+                    state = State::Code;
+                } else {
+                    if b == b'\\' {
+                        blank(&mut out, i, 1);
+                        i += 1;
+                    }
+                    blank(&mut out, i, 1);
+                    i += 1;
+                }
+            }
+            State::Str(delimiter) => {
+                if b == b'\\' {
+                    i += 2;
+                } else if rest.starts_with(delimiter) {
+                    i += delimiter.len();
+                    state = State::Code;
+                } else if b == b'\n' && delimiter.len() == 1 && delimiter[0] != b'`' {
+                    // Unterminated: an apostrophe in prose, not a string
+                    state = State::Code;
+                    line_start = true;
+                    i += 1;
+                } else {
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    // Markers are ASCII, so only whole UTF-8 sequences are ever blanked
+    String::from_utf8(out).unwrap_or_else(|_| content.to_string())
+}
+
+/// For each line of `content`, whether it holds a comment and no code
+pub fn comment_lines(content: &str, language: Option<Language>) -> Vec<bool> {
+    lines_without_code(content, &mask(content, language))
+}
+
+/// [`comment_lines`] for the formats of [`mask_hash`]
+pub fn hash_comment_lines(content: &str) -> Vec<bool> {
+    lines_without_code(content, &mask_hash(content))
+}
+
+fn lines_without_code(content: &str, masked: &str) -> Vec<bool> {
+    content
+        .lines()
+        .zip(masked.lines())
+        .map(|(line, code)| !line.trim().is_empty() && code.trim().is_empty())
+        .collect()
+}
+
+fn hash_starts_comment(syntax: &Syntax, bytes: &[u8], i: usize) -> bool {
+    // PHP 8 attributes: #[Route(...)]
+    if bytes.get(i + 1) == Some(&b'[') && syntax.line.contains(&"//") {
+        return false;
+    }
+    !syntax.hash_after_space || i == 0 || bytes[i - 1].is_ascii_whitespace()
+}
+
+fn delimiter(quote: u8) -> &'static [u8] {
+    match quote {
+        b'"' => b"\"",
+        b'\'' => b"'",
+        _ => b"`",
+    }
+}
+
+/// Replace `len` bytes at `start` with spaces, keeping line breaks
+fn blank(out: &mut [u8], start: usize, len: usize) {
+    for byte in out.iter_mut().skip(start).take(len) {
+        if *byte != b'\n' && *byte != b'\r' {
+            *byte = b' ';
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::engine::score;
+use crate::report::finding::{Confidence, Finding};
+
+/// Directory names that hold tests and their data
+const TEST_DIRS: &[&str] = &[
+    "test",
+    "tests",
+    "__tests__",
+    "spec",
+    "specs",
+    "testing",
+    "e2e",
+    "fixture",
+    "fixtures",
+    "testdata",
+];
+
+/// Words of a file name, split on `.`, `-`, and `_`, that make it a test
+/// file or test config (`test_x.py`, `x.test.ts`, `docker-compose.test.yml`)
+const TEST_NAME_WORDS: &[&str] = &["test", "tests", "testing", "e2e"];
+
+/// What is known about the files findings are in, beyond their paths
+pub struct Context<'a> {
+    /// Git-tracked files relative to the scan root (None outside a repository)
+    pub tracked: Option<&'a HashSet<PathBuf>>,
+    /// Files a gitignore rule matches (scanned with --no-gitignore)
+    pub ignored: &'a HashSet<PathBuf>,
+}
+
+/// Move each finding's confidence one step by where it was found. Runs on
+/// every agent's findings before --min-confidence, so rules only need to
+/// judge the match itself.
+///
+/// Down: test files, commented-out lines, `EXAMPLE_*` / `SAMPLE_*`
+/// constants, gitignored files. Up: production config files
+/// (`.env.production`, `values-prod.yaml`) and `.env` files tracked in git.
+/// Signals in both directions cancel out.
+pub fn calibrate(findings: &mut [Finding], context: &Context) {
+    for f in findings.iter_mut() {
+        let down = is_test_path(&f.file_path)
+            || is_commented_out(&f.evidence)
+            || is_example_constant(&f.evidence)
+            || context.ignored.contains(&f.file_path);
+        let up = score::is_production_file(&f.file_path)
+            || (is_env_file(&f.file_path) && context.tracked.is_some_and(|t| t.contains(&f.file_path)));
+        f.confidence = match (down, up) {
+            (true, false) => lower(f.confidence),
+            (false, true) => raise(f.confidence),
+            _ => f.confidence,
+        };
+    }
+}
+
+fn lower(confidence: Confidence) -> Confidence {
+    match confidence {
+        Confidence::High => Confidence::Medium,
+        Confidence::Medium | Confidence::Low => Confidence::Low,
+    }
+}
+
+fn raise(confidence: Confidence) -> Confidence {
+    match confidence {
+        Confidence::Low => Confidence::Medium,
+        Confidence::Medium | Confidence::High => Confidence::High,
+    }
+}
+
+/// Under a test directory, or named like a test file (test_x.py,
+/// x_test.go, x.test.ts, x.spec.js, XTest.java, values-test.yaml). The
+/// one test check: test findings, --separate-tests, and the `test`
+/// environment all use it.
+pub fn is_test_path(path: &Path) -> bool {
+    let in_test_dir = path.parent().is_some_and(|dir| {
+        dir.components().any(|c| {
+            let name = c.as_os_str().to_string_lossy().to_lowercase();
+            TEST_DIRS.contains(&name.as_str())
+        })
+    });
+    let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+    let stem = path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+    in_test_dir
+        || name.split(['.', '-', '_']).any(|w| TEST_NAME_WORDS.contains(&w))
+        || stem.to_lowercase().ends_with(".spec")
+        || ((stem.ends_with("Test") || stem.ends_with("Tests")) && stem.len() > 4)
+}
+
+/// The matched line is a comment, so the code is not live
+fn is_commented_out(evidence: &str) -> bool {
+    let line = evidence.trim_start();
+    ["//", "#", "/*", "* ", "-- ", "<!--", "REM "]
+        .iter()
+        .any(|marker| line.starts_with(marker))
+        && !line.starts_with("#!")
+}
+
+/// Mentions a constant named as documentation: EXAMPLE_KEY, SAMPLE_TOKEN.
+/// Secret evidence is redacted from the match on, so the name alone counts.
+fn is_example_constant(evidence: &str) -> bool {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN
+        .get_or_init(|| Regex::new(r"\b(EXAMPLE|SAMPLE)_[A-Z0-9_]+").expect("valid regex"))
+        .is_match(evidence)
+}
+
+/// .env, .env.local, .env.production, ...
+fn is_env_file(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|n| n.to_string_lossy().to_lowercase().starts_with(".env"))
+}
@@ -0,0 +1,102 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::report::finding::{Finding, SourceContext, SourceLine};
+
+/// Longer lines (minified code) are cut, so one line cannot bloat the report
+const MAX_LINE_CHARS: usize = 300;
+
+/// Lines holding a secret, by file: the redacted evidence for the first
+/// line of each secret, `…` for the rest of a multi-line one (private keys)
+pub type Redactions = HashMap<PathBuf, HashMap<usize, String>>;
+
+/// Collect the secret lines of `findings`. Taken from the raw findings, so
+/// a secret that a filter or the baseline hides from the report still
+/// stays out of the context of a neighbouring finding.
+pub fn redactions(findings: &[Finding]) -> Redactions {
+    let mut redacted = Redactions::new();
+    for f in findings.iter().filter(|f| f.secret_hash.is_some()) {
+        let lines = redacted.entry(f.file_path.clone()).or_default();
+        lines.insert(f.line_start, f.evidence.clone());
+        for line in f.line_start + 1..=f.line_end {
+            lines.insert(line, "…".to_string());
+        }
+    }
+    redacted
+}
+
+/// Embed `context` lines of source before and after each finding
+/// (--include-context). Each file is read once, up to its last needed line.
+pub fn attach(findings: &mut [Finding], scan_path: &Path, context: usize, redacted: &Redactions) {
+    let mut last_by_file: BTreeMap<PathBuf, usize> = BTreeMap::new();
+    for f in findings.iter() {
+        let last = last_by_file.entry(f.file_path.clone()).or_default();
+        *last = (*last).max(f.line_end + context);
+    }
+
+    let sources: HashMap<PathBuf, Vec<String>> = last_by_file
+        .into_par_iter()
+        .filter_map(|(rel_path, last)| {
+            // A single-file scan reports paths relative to the file itself
+            let path = if rel_path.as_os_str().is_empty() {
+                scan_path.to_path_buf()
+            } else {
+                scan_path.join(&rel_path)
+            };
+            read_lines(&path, last).map(|lines| (rel_path, lines))
+        })
+        .collect();
+
+    for f in findings.iter_mut() {
+        let Some(lines) = sources.get(&f.file_path) else {
+            continue;
+        };
+        if f.line_start == 0 || f.line_start > lines.len() {
+            continue;
+        }
+        let first = f.line_start.saturating_sub(context).max(1);
+        let last = (f.line_end + context).min(lines.len());
+        let secrets = redacted.get(&f.file_path);
+        f.context = Some(SourceContext {
+            lines: (first..=last)
+                .map(|number| SourceLine {
+                    number,
+                    text: match secrets.and_then(|s| s.get(&number)) {
+                        // Evidence is trimmed; keep the line's indentation
+                        Some(evidence) => {
+                            let line = &lines[number - 1];
+                            format!("{}{}", &line[..line.len() - line.trim_start().len()], evidence)
+                        }
+                        None => truncate(&lines[number - 1]),
+                    },
+                    flagged: (f.line_start..=f.line_end).contains(&number),
+                })
+                .collect(),
+        });
+    }
+}
+
+/// The first `count` lines of a file, decoded lossily
+fn read_lines(path: &Path, count: usize) -> Option<Vec<String>> {
+    let file = std::fs::File::open(path).ok()?;
+    BufReader::new(file)
+        .split(b'\n')
+        .take(count)
+        .map(|line| {
+            line.ok().map(|bytes| {
+                let text = String::from_utf8_lossy(&bytes);
+                text.strip_suffix('\r').unwrap_or(&text).to_string()
+            })
+        })
+        .collect()
+}
+
+fn truncate(line: &str) -> String {
+    match line.char_indices().nth(MAX_LINE_CHARS) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
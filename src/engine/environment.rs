@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use crate::engine::{confidence, score};
+use crate::report::finding::{Environment, Finding, Severity};
+
+/// Words that mark local development, samples, and templates
+const DEV_WORDS: &[&str] = &[
+    "dev",
+    "develop",
+    "development",
+    "local",
+    "sample",
+    "samples",
+    "example",
+    "examples",
+    "template",
+];
+
+/// Extensions of configuration files, as opposed to code
+const CONFIG_EXTENSIONS: &[&str] = &[
+    "yml", "yaml", "json", "toml", "ini", "cfg", "conf", "properties", "xml", "env", "tfvars", "sample",
+    "example", "template", "tpl", "dist",
+];
+
+/// The environment `path` points to. Test files and test directories
+/// are test, by the same check that sets test findings apart
+/// (`confidence::is_test_path`). Otherwise the file name decides
+/// (`docker-compose.dev.yml`, `values-prod.yaml`), then the directories
+/// from the nearest up (`config/local/`, `deploy/`).
+pub fn classify(path: &Path) -> Environment {
+    if confidence::is_test_path(path) {
+        return Environment::Test;
+    }
+    if score::is_production_file(path) {
+        return Environment::ProdUnknown;
+    }
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if name.split(['.', '-', '_']).any(|w| DEV_WORDS.contains(&w)) {
+        return Environment::Dev;
+    }
+    let dirs = path.parent().into_iter().flat_map(|dir| dir.components().rev());
+    for dir in dirs {
+        let name = dir.as_os_str().to_string_lossy().to_lowercase();
+        if score::PRODUCTION_DIRS.contains(&name.as_str()) {
+            return Environment::ProdUnknown;
+        }
+        if DEV_WORDS.contains(&name.as_str()) {
+            return Environment::Dev;
+        }
+    }
+    Environment::ProdUnknown
+}
+
+/// Configuration by its name: `.env*`, `Dockerfile*`, `docker-compose*`,
+/// or a config or template extension
+fn is_config_file(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    name.starts_with(".env")
+        || name.starts_with("dockerfile")
+        || name.starts_with("docker-compose")
+        || path
+            .extension()
+            .is_some_and(|ext| CONFIG_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()))
+}
+
+/// Annotate each finding with its environment. A config value that only
+/// dev or test setups read is reported one severity lower: it cannot leak
+/// from or weaken production as it stands.
+pub fn apply(findings: &mut [Finding]) {
+    for f in findings.iter_mut() {
+        let env = classify(&f.file_path);
+        if env != Environment::ProdUnknown && is_config_file(&f.file_path) {
+            f.severity = lower(f.severity);
+        }
+        f.environment = Some(env);
+    }
+}
+
+fn lower(severity: Severity) -> Severity {
+    match severity {
+        Severity::Critical => Severity::High,
+        Severity::High => Severity::Medium,
+        Severity::Medium | Severity::Low => Severity::Low,
+    }
+}
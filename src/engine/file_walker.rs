@@ -1,8 +1,30 @@
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use ignore::overrides::{Override, OverrideBuilder};
 use ignore::WalkBuilder;
-use tracing::debug;
+use tracing::{debug, warn};
+
+use crate::report::finding::{portable_path, SkipReason};
+
+/// Settings that decide which files the walker yields
+#[derive(Debug, Clone, Default)]
+pub struct WalkOptions {
+    /// Include patterns (name substring or extension)
+    pub include: Vec<String>,
+    /// Exclude globs
+    pub exclude: Vec<String>,
+    /// Skip files larger than this (bytes)
+    pub max_file_size: u64,
+    /// Follow symlinks to files and directories (loops are detected and skipped)
+    pub follow_symlinks: bool,
+    /// Scan dotfiles and dot-directories (.env, .github/, .npmrc)
+    pub hidden: bool,
+    /// Ignore .gitignore, the global gitignore, and .git/info/exclude
+    pub no_gitignore: bool,
+}
 
 /// Walk a directory tree and collect file paths to scan.
 ///
@@ -10,41 +32,34 @@ use tracing::debug;
 /// - Skips binary files
 /// - Skips files larger than max_file_size
 /// - Applies include/exclude filters
-pub fn walk_files(
-    root: &std::path::Path,
-    include: &[String],
-    exclude: &[String],
-    max_file_size: u64,
-) -> Result<Vec<PathBuf>> {
+///
+/// Stops early once `deadline` passes; the flag says whether the walk
+/// finished.
+pub fn walk_files(root: &Path, opts: &WalkOptions, deadline: Option<Instant>) -> Result<(Vec<PathBuf>, bool)> {
     let mut builder = WalkBuilder::new(root);
 
     // Standard settings
     builder
-        .hidden(true)          // skip hidden files
-        .git_ignore(true)      // respect .gitignore
-        .git_global(true)      // respect global gitignore
-        .git_exclude(true)     // respect .git/info/exclude
-        .follow_links(false)   // don't follow symlinks
-        .max_filesize(Some(max_file_size));
+        .hidden(!opts.hidden)            // skip hidden files unless --hidden
+        .git_ignore(!opts.no_gitignore)  // respect .gitignore
+        .git_global(!opts.no_gitignore)  // respect global gitignore
+        .git_exclude(!opts.no_gitignore) // respect .git/info/exclude
+        .follow_links(opts.follow_symlinks)
+        .max_filesize(Some(opts.max_file_size));
 
     // Add custom exclude patterns via overrides
-    if !exclude.is_empty() {
-        let mut overrides = ignore::overrides::OverrideBuilder::new(root);
-        for pattern in exclude {
-            // Negate the pattern so matching files are excluded
-            let neg = format!("!{}", pattern);
-            if let Err(e) = overrides.add(&neg) {
-                tracing::warn!("Invalid exclude pattern '{}': {}", pattern, e);
-            }
-        }
-        if let Ok(built) = overrides.build() {
-            builder.overrides(built);
-        }
+    if let Some(overrides) = exclude_overrides(root, &opts.exclude) {
+        builder.overrides(overrides);
     }
 
     let mut files = Vec::new();
+    // With symlinks followed, several links can lead to the same file
+    let mut seen_targets = HashSet::new();
 
     for entry in builder.build() {
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            return Ok((files, false));
+        }
         match entry {
             Ok(entry) => {
                 // Only process files (not dirs)
@@ -55,7 +70,7 @@ pub fn walk_files(
                 let path = entry.path().to_path_buf();
 
                 // Skip common non-source directories/files by path
-                let path_str = path.to_string_lossy().to_lowercase();
+                let path_str = portable_path(&path).to_string_lossy().to_lowercase();
                 if is_excluded_path(&path_str) {
                     debug!("Excluded: {}", path.display());
                     continue;
@@ -68,39 +83,224 @@ pub fn walk_files(
                 }
 
                 // Apply include filter if specified
-                if !include.is_empty() {
-                    let name = path.file_name()
-                        .map(|n| n.to_string_lossy().to_lowercase())
-                        .unwrap_or_default();
-                    let ext = path.extension()
-                        .map(|e| e.to_str().unwrap_or(""))
-                        .unwrap_or("");
-
-                    let matches = include.iter().any(|pattern| {
-                        name.contains(pattern) || ext == pattern.trim_start_matches('.')
-                    });
-
-                    if !matches {
+                if !is_included(&path, &opts.include) {
+                    continue;
+                }
+
+                if opts.follow_symlinks {
+                    let target = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+                    if !seen_targets.insert(target) {
+                        debug!("Already reached via another link: {}", path.display());
                         continue;
                     }
                 }
 
                 files.push(path);
             }
+            Err(e) if is_symlink_loop(&e) => {
+                warn!("Skipping symlink loop: {}", e);
+            }
             Err(e) => {
                 debug!("Walk error: {}", e);
             }
         }
     }
 
-    Ok(files)
+    Ok((files, true))
+}
+
+/// Read the newline-delimited list of paths given to --files-from, from
+/// stdin for `-`. Relative paths are relative to the working directory,
+/// as in `git diff --name-only` output at the repository root.
+pub fn read_file_list(source: &Path) -> Result<Vec<PathBuf>> {
+    let content = if source == Path::new("-") {
+        std::io::read_to_string(std::io::stdin()).context("Could not read the file list from stdin")?
+    } else {
+        std::fs::read_to_string(source).with_context(|| format!("Could not read {}", source.display()))?
+    };
+    let cwd = std::env::current_dir()?;
+    let mut seen = HashSet::new();
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| cwd.join(line))
+        .filter(|path| seen.insert(path.clone()))
+        .collect())
+}
+
+/// The files of a --files-from list to scan, without walking `root`.
+/// Listed files bypass .gitignore, --hidden, and the built-in excluded
+/// directories, since someone chose them; --exclude, --include, binary
+/// extensions, and --max-file-size still apply, and those skips are
+/// returned with their reasons. Files that no longer exist (deleted in a
+/// diff) are left out, and files outside `root` with a warning.
+pub fn list_files(root: &Path, listed: &[PathBuf], opts: &WalkOptions) -> (Vec<PathBuf>, Vec<(PathBuf, SkipReason)>) {
+    let overrides = exclude_overrides(root, &opts.exclude);
+    let mut files = Vec::new();
+    let mut skipped = Vec::new();
+    let mut seen = HashSet::new();
+    let mut outside = 0;
+    for path in listed {
+        let Ok(path) = std::fs::canonicalize(path) else {
+            debug!("Listed file not found: {}", path.display());
+            continue;
+        };
+        let Ok(rel_path) = path.strip_prefix(root) else {
+            debug!("Listed file outside the scan root: {}", path.display());
+            outside += 1;
+            continue;
+        };
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        if !metadata.is_file() || !seen.insert(path.clone()) {
+            continue;
+        }
+        let reason = if overrides.as_ref().is_some_and(|o| is_overridden(o, rel_path)) {
+            Some(SkipReason::ExcludePattern)
+        } else if is_likely_binary(&path) {
+            Some(SkipReason::Binary)
+        } else if metadata.len() > opts.max_file_size {
+            Some(SkipReason::TooLarge)
+        } else if !is_included(&path, &opts.include) {
+            Some(SkipReason::NotIncluded)
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => skipped.push((portable_path(rel_path), reason)),
+            None => files.push(path),
+        }
+    }
+    if outside > 0 {
+        warn!("{} listed files are outside {} and were not scanned", outside, root.display());
+    }
+    (files, skipped)
+}
+
+/// Work out why each file under `root` that is not in `kept` was skipped.
+///
+/// Walks again with the `ignore` crate's own filters turned off, then checks
+/// each filter in turn. Whatever no other filter explains was dropped by a
+/// gitignore rule, the only filter that cannot be checked per path.
+pub fn explain_skips(
+    root: &Path,
+    opts: &WalkOptions,
+    kept: &HashSet<PathBuf>,
+) -> Vec<(PathBuf, SkipReason)> {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .standard_filters(false)
+        .follow_links(opts.follow_symlinks)
+        // Git internals are never scanned and would drown out everything else
+        .filter_entry(|e| e.file_name() != ".git");
+    let overrides = exclude_overrides(root, &opts.exclude);
+
+    let mut skipped = Vec::new();
+    for entry in builder.build().flatten() {
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            continue;
+        }
+        let path = entry.path();
+        if kept.contains(path) {
+            continue;
+        }
+        let rel_path = path.strip_prefix(root).unwrap_or(path);
+
+        let reason = if is_excluded_path(&portable_path(path).to_string_lossy().to_lowercase()) {
+            SkipReason::ExcludedPath
+        } else if overrides.as_ref().is_some_and(|o| is_overridden(o, rel_path)) {
+            SkipReason::ExcludePattern
+        } else if !opts.hidden && is_hidden(rel_path) {
+            SkipReason::Hidden
+        } else if is_likely_binary(path) {
+            SkipReason::Binary
+        } else if entry.metadata().is_ok_and(|m| m.len() > opts.max_file_size) {
+            SkipReason::TooLarge
+        } else if !is_included(path, &opts.include) {
+            SkipReason::NotIncluded
+        } else if opts.follow_symlinks && entry.path_is_symlink() {
+            SkipReason::DuplicateLink
+        } else {
+            SkipReason::Gitignored
+        };
+        skipped.push((portable_path(rel_path), reason));
+    }
+    skipped
+}
+
+/// Build the `ignore` overrides that drop files matching `--exclude` globs
+fn exclude_overrides(root: &Path, exclude: &[String]) -> Option<Override> {
+    if exclude.is_empty() {
+        return None;
+    }
+    let mut overrides = OverrideBuilder::new(root);
+    // Windows file systems ignore case, so `--exclude Vendor/` must match vendor/
+    if let Err(e) = overrides.case_insensitive(cfg!(windows)) {
+        warn!("Could not make exclude patterns case-insensitive: {}", e);
+    }
+    for pattern in exclude {
+        // Negate the pattern so matching files are excluded
+        let neg = format!("!{}", pattern);
+        if let Err(e) = overrides.add(&neg) {
+            warn!("Invalid exclude pattern '{}': {}", pattern, e);
+        }
+    }
+    overrides.build().ok()
+}
+
+/// Whether an exclude glob matches the file or any directory above it
+fn is_overridden(overrides: &Override, rel_path: &Path) -> bool {
+    let is_file = std::iter::once(false).chain(std::iter::repeat(true));
+    rel_path
+        .ancestors()
+        .filter(|p| !p.as_os_str().is_empty())
+        .zip(is_file)
+        .any(|(p, is_dir)| overrides.matched(p, is_dir).is_ignore())
+}
+
+/// Whether any component of a root-relative path is a dotfile or dot-directory
+fn is_hidden(rel_path: &Path) -> bool {
+    rel_path
+        .components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+}
+
+/// Whether a file passes the `--include` filter (always true when unset)
+fn is_included(path: &Path, include: &[String]) -> bool {
+    if include.is_empty() {
+        return true;
+    }
+    let name = path.file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let ext = path.extension()
+        .map(|e| e.to_str().unwrap_or(""))
+        .unwrap_or("");
+
+    include.iter().any(|pattern| {
+        name.contains(pattern) || ext == pattern.trim_start_matches('.')
+    })
+}
+
+/// Whether a walk error is a symlink cycle (only possible with --follow-symlinks)
+fn is_symlink_loop(err: &ignore::Error) -> bool {
+    match err {
+        ignore::Error::Loop { .. } => true,
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => is_symlink_loop(err),
+        ignore::Error::Partial(errs) => errs.iter().any(is_symlink_loop),
+        _ => false,
+    }
 }
 
 /// Check if a path should be excluded based on common patterns
 fn is_excluded_path(path: &str) -> bool {
     let exclusions = [
         "node_modules",
-        ".git",
+        ".git/",
         "target/debug",
         "target/release",
         "__pycache__",
@@ -1,17 +1,69 @@
+pub mod budget;
+pub mod comments;
+pub mod context;
+pub mod confidence;
+pub mod environment;
 pub mod file_walker;
+pub mod packages;
+pub mod progress;
+pub mod reader;
+pub mod score;
+pub mod selection;
 
-use std::path::PathBuf;
-use std::time::Instant;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::IsTerminal;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use rayon::prelude::*;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
+use crate::agents::semgrep::SemgrepAgent;
 use crate::agents::{self, Language, ScannedFile, SecurityAgent};
+use crate::baseline;
 use crate::cli::{Cli, ScanArgs};
+use crate::compliance;
+use crate::fix;
 use crate::config::AntyConfig;
-use crate::report::finding::{ScanReport, ScanSummary};
-use crate::report::merger;
+use crate::git;
+use crate::owners::CodeOwners;
+use crate::triage::TriageFile;
+use crate::policy::Policy;
+use crate::report;
+use crate::report::finding::{
+    portable_path, Blame, Finding, GitContext, Hotspots, LanguageStats, ScanCoverage, ScanError,
+    ScanErrorKind, ScanReport, ScanSummary, ScanTimings, Severity, SkipReason, SkippedFile, SuppressedCounts, REPORT_FORMAT_VERSION,
+};
+use crate::report::merger::{self, FindingLimits};
+use crate::rules;
+use crate::rules::packs::Pack;
+
+use self::budget::{MemoryBudget, Reservation};
+use self::file_walker::WalkOptions;
+use self::reader::{LanguageFilter, LanguageMap};
+use self::selection::Selection;
+use self::progress::Progress;
+
+/// Per-file time budget when neither --file-timeout nor config sets one
+const DEFAULT_FILE_TIMEOUT_SECS: u64 = 10;
+
+/// Findings reported when neither --max-findings nor a profile sets a cap
+const DEFAULT_MAX_FINDINGS: usize = 1000;
+
+/// Files read ahead of the scan; bounds how many contents sit in memory
+const PIPELINE_DEPTH: usize = 64;
+
+/// Threads reading files. Reading is I/O-bound, so a few are enough to
+/// keep the scan threads busy.
+const READER_THREADS: usize = 4;
+
+/// Target of the structured scan events (`--log-format json`). Text logs
+/// filter it out since their messages already say the same.
+pub const TELEMETRY: &str = "anty::telemetry";
 
 /// The core scan engine. Orchestrates file discovery, agent dispatch,
 /// and report generation.
@@ -20,39 +72,142 @@ pub struct Scanner {
     scan_path: PathBuf,
     /// Display path (user-provided, not canonicalized)
     display_path: PathBuf,
-    /// Agents to run
-    agents: Vec<Box<dyn SecurityAgent>>,
-    /// Maximum file size (bytes)
-    max_file_size: u64,
-    /// Include patterns
-    include: Vec<String>,
-    /// Exclude patterns
-    exclude: Vec<String>,
+    /// Agents to run, possibly shared with other scans (`anty batch`)
+    agents: Vec<Arc<dyn SecurityAgent>>,
+    /// Which files to walk (include/exclude, size, symlinks)
+    walk: WalkOptions,
+    /// Languages to scan or leave out (--lang / --exclude-lang)
+    lang_filter: LanguageFilter,
+    /// Extension/filename → language, including `[languages]` config
+    languages: LanguageMap,
     /// Only scan changed files
     #[allow(dead_code)]
     changed_only: bool,
-    /// Maximum number of findings to report (0 = unlimited)
-    max_findings: usize,
+    /// Files listed by --files-from, scanned instead of walking `scan_path`
+    files_from: Option<Vec<PathBuf>>,
+    /// Status, assignee, and notes per finding (`.anty/triage.toml`)
+    triage: Option<TriageFile>,
+    /// Rules, overrides, minimums, and baseline deciding what is reported
+    selection: Selection,
+    /// Caps on reported findings (--max-findings / --max-per-rule)
+    limits: FindingLimits,
+    /// Time budget per file (--file-timeout / scan.file_timeout)
+    file_timeout: Option<Duration>,
+    /// Time budget for the whole scan (--timeout)
+    timeout: Option<Duration>,
+    /// Record every skipped file and why (--explain-skips)
+    explain_skips: bool,
+    /// Attribute findings with git blame (--blame)
+    blame: bool,
+    /// Lines of source to embed around each finding (--include-context)
+    include_context: Option<usize>,
+    /// CODEOWNERS rules, when the repository has a CODEOWNERS file
+    owners: Option<CodeOwners>,
+    /// Break the report down per package root (--monorepo)
+    monorepo: bool,
+    /// Threshold each package is checked against (--fail-on)
+    fail_on: Option<Severity>,
+    /// Stop at the first reportable finding at or above `fail_on` (--fail-fast)
+    fail_fast: bool,
+    /// Report findings in test files apart (--separate-tests / --fail-on-tests)
+    separate_tests: bool,
+    /// Threshold for findings in test files (--fail-on-tests)
+    fail_on_tests: Option<Severity>,
+    /// `[policy]` requirements from config
+    policy: Policy,
+    /// Framework to map findings onto (--compliance)
+    compliance: Option<String>,
+    /// Bytes of file content held at once (--max-memory)
+    max_memory: Option<u64>,
+    /// Order findings by score instead of severity (--sort score)
+    sort_by_score: bool,
+    /// Editor link template for terminal output (`output.editor_url_template`)
+    editor_url_template: Option<String>,
+    /// Leave out run-specific details from the report (--deterministic)
+    deterministic: bool,
+    /// Show progress bars on stderr
+    show_progress: bool,
+}
+
+/// The agents selected by --agents, plus a Semgrep agent for --rules-dir
+pub fn load_agents(args: &ScanArgs) -> Result<Vec<Arc<dyn SecurityAgent>>> {
+    let mut agents = selected_agents(args);
+    if let Some(ref dir) = args.rules_dir {
+        agents.push(Arc::new(SemgrepAgent::load_all(std::slice::from_ref(dir))?));
+    }
+    Ok(agents)
+}
+
+/// The agents a scan runs where `packs` apply: `shared` agents (from
+/// [`load_agents`]) or the ones --agents selects, with the packs joining
+/// --rules-dir in one Semgrep agent
+pub fn agents_with_packs(
+    args: &ScanArgs,
+    shared: Option<&[Arc<dyn SecurityAgent>]>,
+    packs: &[Pack],
+) -> Result<Vec<Arc<dyn SecurityAgent>>> {
+    let mut agents = match shared {
+        Some(shared) if packs.is_empty() => shared.to_vec(),
+        Some(shared) => shared.iter().filter(|a| a.name() != "semgrep").cloned().collect(),
+        None => selected_agents(args),
+    };
+    let rule_dirs: Vec<PathBuf> = args
+        .rules_dir
+        .iter()
+        .cloned()
+        .chain(packs.iter().map(|p| p.dir.clone()))
+        .collect();
+    if !rule_dirs.is_empty() && (shared.is_none() || !packs.is_empty()) {
+        agents.push(Arc::new(SemgrepAgent::load_all(&rule_dirs)?));
+    }
+    Ok(agents)
+}
+
+fn selected_agents(args: &ScanArgs) -> Vec<Arc<dyn SecurityAgent>> {
+    match args.agents {
+        Some(ref names) => agents::agents_by_names(names),
+        None => agents::all_agents(),
+    }
+    .into_iter()
+    .map(Arc::from)
+    .collect()
 }
 
 impl Scanner {
-    pub fn new(_cli: &Cli, args: &ScanArgs) -> Result<Self> {
+    pub fn new(cli: &Cli, args: &ScanArgs) -> Result<Self> {
+        Self::build(cli, args, None)
+    }
+
+    /// A scanner running `shared` agents (from [`load_agents`]) instead of
+    /// loading its own, so scans of many repositories compile the rules
+    /// once. A repository whose config adds rule packs still gets its own
+    /// Semgrep agent.
+    pub fn with_agents(cli: &Cli, args: &ScanArgs, shared: &[Arc<dyn SecurityAgent>]) -> Result<Self> {
+        Self::build(cli, args, Some(shared))
+    }
+
+    fn build(cli: &Cli, args: &ScanArgs, shared: Option<&[Arc<dyn SecurityAgent>]>) -> Result<Self> {
+        report::check_format(args)?;
+        if args.fail_fast && args.fail_on.is_none() {
+            anyhow::bail!("--fail-fast needs a --fail-on threshold (or a --profile that sets one)");
+        }
         let scan_path = std::fs::canonicalize(&args.path)?;
 
-        // Load optional config (skip if --no-config)
+        // Load optional config (user config only with --no-config)
         let config = if args.no_config {
             info!("Ignoring .anty.toml (--no-config)");
-            None
+            AntyConfig::load_user()
         } else {
             AntyConfig::load(&scan_path)
         };
 
-        // Determine which agents to run
-        let agents = if let Some(ref names) = args.agents {
-            agents::agents_by_names(names)
-        } else {
-            agents::all_agents()
+        // Organisation rule packs come from the same config layers
+        let packs = match config {
+            Some(ref cfg) => rules::packs::load(&cfg.rules)?,
+            None => Vec::new(),
         };
+        // Determine which agents to run
+        let agents = agents_with_packs(args, shared, &packs)?;
 
         info!("Loaded {} agents: {}", agents.len(),
             agents.iter().map(|a| a.name()).collect::<Vec<_>>().join(", "));
@@ -68,115 +223,861 @@ impl Scanner {
             include.extend(cfg.scan.include.clone());
         }
 
+        let mut lang = args.lang.clone();
+        let mut exclude_lang = args.exclude_lang.clone();
+        if let Some(ref cfg) = config {
+            lang.extend(cfg.scan.lang.clone());
+            exclude_lang.extend(cfg.scan.exclude_lang.clone());
+        }
+        let lang_filter = LanguageFilter::new(&lang, &exclude_lang)?;
+
+        let selection = Selection::new(args, config.as_ref(), &agents, &scan_path)?;
+
+        // Triage lives in the repo, so it is untrusted input too
+        let triage = if args.no_config {
+            None
+        } else {
+            TriageFile::load(&baseline::root_for(&scan_path))
+        };
+
+        let file_timeout = args
+            .file_timeout
+            .or_else(|| config.as_ref().and_then(|c| c.scan.file_timeout))
+            .unwrap_or(DEFAULT_FILE_TIMEOUT_SECS);
+
+        let owners = CodeOwners::load(&scan_path);
+
+        let mut policy = config.as_ref().map(|c| c.policy.clone()).unwrap_or_default();
+        for pack in packs {
+            if let Some(pack_policy) = pack.policy {
+                policy.extend(pack_policy);
+            }
+        }
+
         Ok(Scanner {
             scan_path,
-            display_path: PathBuf::from(&args.path),
+            display_path: if args.deterministic { relative_to_cwd(&args.path) } else { PathBuf::from(&args.path) },
             agents,
-            max_file_size: args.max_file_size,
-            include,
-            exclude,
+            lang_filter,
+            languages: LanguageMap::new(
+                config.as_ref().map(|c| &c.languages).unwrap_or(&BTreeMap::new()),
+            ),
+            walk: WalkOptions {
+                include,
+                exclude,
+                max_file_size: args.max_file_size,
+                follow_symlinks: args.follow_symlinks
+                    || config.as_ref().is_some_and(|c| c.scan.follow_symlinks),
+                hidden: args.hidden,
+                no_gitignore: args.no_gitignore,
+            },
             changed_only: args.changed_only,
-            max_findings: args.max_findings,
+            files_from: args.files_from.as_deref().map(file_walker::read_file_list).transpose()?,
+            triage,
+            selection,
+            limits: FindingLimits {
+                max_total: args.max_findings.unwrap_or(DEFAULT_MAX_FINDINGS),
+                max_per_rule: args.max_per_rule,
+            },
+            file_timeout: (file_timeout > 0).then(|| Duration::from_secs(file_timeout)),
+            timeout: args.timeout,
+            explain_skips: args.explain_skips,
+            blame: args.blame,
+            include_context: args.include_context,
+            owners,
+            monorepo: args.monorepo,
+            fail_on: args.fail_on.as_deref().map(Severity::from_str),
+            fail_fast: args.fail_fast,
+            separate_tests: args.separate_tests || args.fail_on_tests.is_some(),
+            fail_on_tests: args.fail_on_tests.as_deref().map(Severity::from_str),
+            policy,
+            compliance: args.compliance.clone(),
+            max_memory: args.max_memory,
+            sort_by_score: args.sort == "score",
+            editor_url_template: config.as_ref().and_then(|c| c.output.editor_url_template.clone()),
+            deterministic: args.deterministic,
+            show_progress: !cli.quiet
+                && args.format.split(',').any(|f| f.trim() == "terminal")
+                && std::io::stderr().is_terminal(),
         })
     }
 
     /// Run the full scan pipeline
     pub fn run(&self) -> Result<ScanReport> {
         let start = Instant::now();
+        // --timeout: discovery and scanning stop here, and the report
+        // covers what was done by then
+        let scan_deadline = self.timeout.map(|t| start + t);
+        let out_of_budget = || scan_deadline.is_some_and(|d| Instant::now() >= d);
+        let progress = Progress::new(self.show_progress);
+        let mut timings = ScanTimings::default();
 
         // Step 1: Discover files
         info!("Discovering files in {}", self.scan_path.display());
-        let file_paths = file_walker::walk_files(
-            &self.scan_path,
-            &self.include,
-            &self.exclude,
-            self.max_file_size,
-        )?;
+        let phase = Instant::now();
+        let spinner = progress.spinner("Discovering");
+        let mut skipped = Vec::new();
+        let (mut file_paths, discovery_complete) = match self.files_from {
+            Some(ref listed) => {
+                let (files, skips) = file_walker::list_files(&self.scan_path, listed, &self.walk);
+                if self.explain_skips {
+                    skipped.extend(skips.into_iter().map(|(file_path, reason)| SkippedFile { file_path, reason }));
+                }
+                (files, true)
+            }
+            None => file_walker::walk_files(&self.scan_path, &self.walk, scan_deadline)?,
+        };
+        spinner.finish_and_clear();
+        timings.discovery_ms = phase.elapsed().as_millis() as u64;
+
+        if self.explain_skips && self.files_from.is_none() {
+            let kept: HashSet<PathBuf> = file_paths.iter().cloned().collect();
+            skipped.extend(
+                file_walker::explain_skips(&self.scan_path, &self.walk, &kept)
+                    .into_iter()
+                    .map(|(file_path, reason)| SkippedFile { file_path, reason }),
+            );
+        }
+
+        if !self.lang_filter.is_empty() {
+            file_paths.retain(|path| {
+                let rel_path = portable_path(path.strip_prefix(&self.scan_path).unwrap_or(path));
+                let keep = self.lang_filter.allows(self.languages.detect(path, &rel_path));
+                if !keep && self.explain_skips {
+                    skipped.push(SkippedFile {
+                        file_path: rel_path,
+                        reason: SkipReason::ExcludedLanguage,
+                    });
+                }
+                keep
+            });
+        }
 
         info!("Found {} files to scan", file_paths.len());
+        info!(target: TELEMETRY, phase = "discovery", files = file_paths.len(), duration_ms = timings.discovery_ms, "phase finished");
 
-        // Step 2: Read and classify files (parallel)
-        let (files, skipped): (Vec<_>, Vec<_>) = file_paths
-            .par_iter()
-            .map(|path| {
-                let rel_path = path.strip_prefix(&self.scan_path)
-                    .unwrap_or(path)
-                    .to_path_buf();
-
-                // Read file
-                match std::fs::read_to_string(path) {
-                    Ok(content) => {
-                        // Detect language
-                        let language = path
-                            .extension()
-                            .and_then(|e| e.to_str())
-                            .map(Language::from_extension)
-                            .or_else(|| {
-                                path.file_name()
-                                    .and_then(|n| n.to_str())
-                                    .map(Language::from_filename)
-                            });
-
-                        Ok(ScannedFile {
-                            rel_path,
-                            abs_path: path.clone(),
-                            content,
-                            language,
-                        })
+        // Steps 2–3: Read and scan, pipelined. A small reader pool loads
+        // files into a bounded channel while the scan threads drain it, so
+        // only about PIPELINE_DEPTH file contents (and at most --max-memory
+        // bytes) are held at once. Large files are only noted by the readers
+        // and streamed chunk by chunk.
+        let phase = Instant::now();
+        let tracked = self.tracked_files();
+        // --fail-fast: findings that decided the outcome, and the signal for
+        // readers and scan threads to stop picking up files
+        let tripped = Mutex::new(Vec::new());
+        let stop = AtomicBool::new(false);
+        let halted = || stop.load(Ordering::Relaxed) || out_of_budget();
+        // Files the --timeout budget left unscanned
+        let not_scanned = AtomicUsize::new(0);
+        let check_fail_fast = |findings: &[Finding]| {
+            if !self.fail_fast || findings.is_empty() {
+                return;
+            }
+            let mut reportable = findings.to_vec();
+            let no_ignored = HashSet::new();
+            let ctx = confidence::Context { tracked: tracked.as_ref(), ignored: &no_ignored };
+            self.selection.apply(&mut reportable, &ctx);
+            reportable.retain(|f| self.fail_threshold(f).is_some_and(|threshold| f.severity >= threshold));
+            if !reportable.is_empty() {
+                stop.store(true, Ordering::Relaxed);
+                tripped.lock().unwrap().extend(reportable);
+            }
+        };
+        let bar = progress.bar(file_paths.len(), "Scanning");
+        let agent_nanos: Vec<AtomicU64> = self.agents.iter().map(|_| AtomicU64::new(0)).collect();
+        // Files (chunks of large ones) each agent's pre-filter let through
+        let agent_files: Vec<AtomicUsize> = self.agents.iter().map(|_| AtomicUsize::new(0)).collect();
+        // Files the scan could not finish, listed under the report's errors
+        let errors = Mutex::new(Vec::new());
+        let fail = |file_path: &Path, kind: ScanErrorKind, agent: Option<&str>, message: String| {
+            debug!("Not fully scanned: {}: {}", file_path.display(), message);
+            let mut errors = errors.lock().unwrap();
+            // Chunks of a large file can fail the same way one after another
+            let repeated = errors
+                .iter()
+                .any(|e: &ScanError| e.file_path == file_path && e.kind == kind && e.agent.as_deref() == agent);
+            if !repeated {
+                errors.push(ScanError {
+                    file_path: file_path.to_path_buf(),
+                    kind,
+                    agent: agent.map(str::to_string),
+                    message,
+                });
+            }
+        };
+
+        let scan_one = |file: &ScannedFile| -> Vec<Finding> {
+            // Agents are independent, so they run in parallel too
+            self.agents
+                .par_iter()
+                .zip(&agent_nanos)
+                .zip(&agent_files)
+                .flat_map_iter(|((agent, nanos), files)| {
+                    let agent_start = Instant::now();
+                    // A panic in one rule (a custom rule, unexpected content)
+                    // costs that agent's findings for this file, not the scan
+                    let scanned = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                        if !agent.interested_in(file) {
+                            return Vec::new();
+                        }
+                        files.fetch_add(1, Ordering::Relaxed);
+                        agent.scan_file(file)
+                    }));
+                    nanos.fetch_add(agent_start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+                    scanned.unwrap_or_else(|payload| {
+                        let message = format!("agent panicked: {}", panic_message(&payload));
+                        fail(&file.rel_path, ScanErrorKind::Panic, Some(agent.name()), message);
+                        Vec::new()
+                    })
+                })
+                .collect()
+        };
+
+        // A file that runs past its budget is abandoned: partial results
+        // are dropped and the file is listed under the report's errors.
+        let deadline = || {
+            let file_deadline = self.file_timeout.map(|t| Instant::now() + t);
+            match (file_deadline, scan_deadline) {
+                (Some(file), Some(scan)) => Some(file.min(scan)),
+                (file, scan) => file.or(scan),
+            }
+        };
+        let timed_out = |file: &ScannedFile| {
+            if !file.out_of_time() {
+                return false;
+            }
+            // The whole scan ran out of time, not this file
+            if out_of_budget() {
+                not_scanned.fetch_add(1, Ordering::Relaxed);
+                return true;
+            }
+            let secs = self.file_timeout.map_or(0, |t| t.as_secs());
+            info!("Gave up on {} after {}s", file.rel_path.display(), secs);
+            fail(&file.rel_path, ScanErrorKind::Timeout, None, format!("exceeded the {}s per-file time budget", secs));
+            true
+        };
+
+        // Files and lines per language name, for the report's coverage section
+        let tally = Mutex::new(BTreeMap::<&'static str, (usize, usize)>::new());
+        let count = |language: Option<Language>, files: usize, lines: usize| {
+            let name = language.unwrap_or(Language::Unknown).name();
+            let mut tally = tally.lock().unwrap();
+            let entry = tally.entry(name).or_default();
+            entry.0 += files;
+            entry.1 += lines;
+        };
+
+        // Files each agent asked to see again in the project pass
+        let project_files: Vec<Mutex<Vec<ScannedFile>>> = self.agents.iter().map(|_| Mutex::default()).collect();
+        let keep = |file: &ScannedFile| {
+            for (agent, kept) in self.agents.iter().zip(&project_files) {
+                if agent.wants_project_file(file) {
+                    let mut copy = file.clone();
+                    copy.deadline = None;
+                    kept.lock().unwrap().push(copy);
+                }
+            }
+        };
+
+        let read_skipped = AtomicUsize::new(0);
+        let unreadable = Mutex::new(Vec::new());
+        let scan_loaded = |loaded: Loaded| -> Vec<Finding> {
+            bar.inc(1);
+            if halted() {
+                if !stop.load(Ordering::Relaxed) {
+                    not_scanned.fetch_add(1, Ordering::Relaxed);
+                }
+                return Vec::new();
+            }
+            let findings = match loaded {
+                // The reservation is held until the agents are done
+                Loaded::File(mut file, _reservation) => {
+                    count(file.language, 1, file.content.lines().count());
+                    file.deadline = deadline();
+                    let file_findings = scan_one(&file);
+                    if timed_out(&file) {
+                        return Vec::new();
+                    }
+                    keep(&file);
+                    file_findings
+                }
+                Loaded::Large(path, rel_path, _reservation) => {
+                    let mut file_findings = Vec::new();
+                    let mut abandoned = false;
+                    let language = self.languages.detect(&path, &rel_path);
+                    count(language, 1, 0);
+                    let result =
+                        reader::for_each_chunk(&path, rel_path.clone(), language, deadline(), |chunk, lines| {
+                            if abandoned {
+                                return;
+                            }
+                            if halted() {
+                                if !stop.load(Ordering::Relaxed) {
+                                    not_scanned.fetch_add(1, Ordering::Relaxed);
+                                }
+                                abandoned = true;
+                                return;
+                            }
+                            count(language, 0, lines.len());
+                            file_findings.extend(scan_one(chunk).into_iter().filter(|f| lines.contains(&f.line_start)));
+                            abandoned = timed_out(chunk);
+                        });
+                    match result {
+                        // Skipped like a small file that is not text
+                        Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                            debug!("Skipping {}: {}", path.display(), e);
+                            read_skipped.fetch_add(1, Ordering::Relaxed);
+                            if self.explain_skips {
+                                unreadable
+                                    .lock()
+                                    .unwrap()
+                                    .push(SkippedFile { file_path: rel_path, reason: SkipReason::NonUtf8 });
+                            }
+                            return Vec::new();
+                        }
+                        // Findings from the chunks read so far are kept
+                        Err(e) => fail(&rel_path, ScanErrorKind::Read, None, format!("stopped reading: {}", e)),
+                        Ok(()) => {}
                     }
-                    Err(e) => {
-                        debug!("Skipping {}: {}", path.display(), e);
-                        Err(())
+                    if abandoned {
+                        return Vec::new();
                     }
+                    file_findings
                 }
-            })
-            .partition_map(|r| match r {
-                Ok(f) => rayon::iter::Either::Left(f),
-                Err(()) => rayon::iter::Either::Right(()),
-            });
-
-        let files_scanned = files.len();
-        let files_skipped = skipped.len();
+                Loaded::Failed(rel_path, message) => {
+                    fail(&rel_path, ScanErrorKind::Read, None, message);
+                    Vec::new()
+                }
+                Loaded::Skipped(rel_path, reason) => {
+                    read_skipped.fetch_add(1, Ordering::Relaxed);
+                    if self.explain_skips {
+                        unreadable.lock().unwrap().push(SkippedFile { file_path: rel_path, reason });
+                    }
+                    Vec::new()
+                }
+            };
+            check_fail_fast(&findings);
+            findings
+        };
 
-        info!("Read {} files ({} skipped)", files_scanned, files_skipped);
+        // Readers get their own pool: if they shared the scan threads, a
+        // full channel could leave every thread blocked on send.
+        let readers = rayon::ThreadPoolBuilder::new()
+            .num_threads(READER_THREADS)
+            .thread_name(|i| format!("anty-reader-{}", i))
+            .build()?;
+        let budget = MemoryBudget::new(self.max_memory);
+        let (tx, rx) = mpsc::sync_channel::<Loaded>(PIPELINE_DEPTH);
+        let (mut all_findings, read_time) = std::thread::scope(|s| {
+            let reader = s.spawn(|| {
+                readers.install(|| {
+                    file_paths.par_iter().for_each_with(tx, |tx, path| {
+                        if halted() {
+                            if !stop.load(Ordering::Relaxed) {
+                                not_scanned.fetch_add(1, Ordering::Relaxed);
+                            }
+                            return;
+                        }
+                        // Only fails once the scan side is gone
+                        let _ = tx.send(self.load(path, &budget));
+                    })
+                });
+                phase.elapsed()
+            });
+            let findings: Vec<Finding> = rx.into_iter().par_bridge().flat_map_iter(scan_loaded).collect();
+            (findings, reader.join().expect("reader thread panicked"))
+        });
+        bar.finish_and_clear();
 
-        // Step 3: Run all agents on all files (parallel over files)
-        let all_findings: Vec<_> = files
+        // Project pass: rules over several files, once all were scanned.
+        // After --fail-fast or --timeout stopped the scan, agents are only
+        // reset; their view of the project would be incomplete.
+        let complete = !halted();
+        let project_findings: Vec<Finding> = self
+            .agents
             .par_iter()
-            .flat_map(|file| {
-                let mut file_findings = Vec::new();
-                for agent in &self.agents {
-                    file_findings.extend(agent.scan_file(file));
+            .zip(&agent_nanos)
+            .zip(project_files)
+            .flat_map_iter(|((agent, nanos), files)| {
+                let mut files = files.into_inner().unwrap();
+                files.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
+                let agent_start = Instant::now();
+                let scanned = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                    let mut findings = if complete { agent.scan_project(&files) } else { Vec::new() };
+                    findings.extend(agent.finish());
+                    findings
+                }));
+                nanos.fetch_add(agent_start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+                match scanned {
+                    Ok(findings) if complete => findings,
+                    Ok(_) => Vec::new(),
+                    Err(payload) => {
+                        let message = format!("agent panicked in its project pass: {}", panic_message(&payload));
+                        fail(Path::new(""), ScanErrorKind::Panic, Some(agent.name()), message);
+                        Vec::new()
+                    }
                 }
-                file_findings
             })
             .collect();
+        check_fail_fast(&project_findings);
+        all_findings.extend(project_findings);
+
+        timings.read_ms = read_time.as_millis() as u64;
+        timings.scan_ms = phase.elapsed().as_millis() as u64;
+        timings.agents_ms = self
+            .agents
+            .iter()
+            .zip(&agent_nanos)
+            .map(|(agent, nanos)| {
+                (agent.name().to_string(), nanos.load(Ordering::Relaxed) / 1_000_000)
+            })
+            .collect::<BTreeMap<_, _>>();
+        timings.agent_files = self
+            .agents
+            .iter()
+            .zip(&agent_files)
+            .map(|(agent, files)| (agent.name().to_string(), files.load(Ordering::Relaxed)))
+            .collect();
+
+        let tripped = tripped.into_inner().unwrap();
+        if !tripped.is_empty() {
+            return Ok(self.fail_fast_report(tripped, tally.into_inner().unwrap(), errors.into_inner().unwrap(), timings, start));
+        }
+
+        let read_skipped = read_skipped.into_inner();
+        info!(
+            "Read and scanned {} files ({} unreadable)",
+            file_paths.len().saturating_sub(read_skipped + not_scanned.load(Ordering::Relaxed)),
+            read_skipped
+        );
+
+        skipped.extend(unreadable.into_inner().unwrap());
+        let mut errors = errors.into_inner().unwrap();
+        errors.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        skipped.sort_by(|a, b| a.reason.cmp(&b.reason).then_with(|| a.file_path.cmp(&b.file_path)));
+        // A file one agent failed on was still scanned by the others
+        let failed_files: HashSet<&Path> =
+            errors.iter().filter(|e| e.agent.is_none()).map(|e| e.file_path.as_path()).collect();
+        let files_skipped = read_skipped + failed_files.len();
+        let not_scanned = not_scanned.into_inner();
+        let files_scanned = file_paths.len().saturating_sub(files_skipped + not_scanned);
+        let partial = (!discovery_complete || not_scanned > 0).then(|| ScanCoverage {
+            budget_secs: self.timeout.map_or(0, |t| t.as_secs()),
+            discovery_complete,
+            files_discovered: file_paths.len(),
+            files_scanned,
+            files_not_scanned: not_scanned,
+        });
+        if let Some(ref coverage) = partial {
+            warn!(
+                "The {}s --timeout ran out: scanned {} of {} files{}",
+                coverage.budget_secs,
+                coverage.files_scanned,
+                coverage.files_discovered,
+                if discovery_complete { "" } else { " found before discovery stopped" }
+            );
+        }
 
         info!("Raw findings: {}", all_findings.len());
+        info!(
+            target: TELEMETRY,
+            phase = "scan",
+            files_scanned,
+            files_skipped,
+            findings = all_findings.len(),
+            read_ms = timings.read_ms,
+            duration_ms = timings.scan_ms,
+            "phase finished"
+        );
+        for (agent, ms) in &timings.agents_ms {
+            let files = timings.agent_files.get(agent).copied().unwrap_or_default();
+            info!(target: TELEMETRY, phase = "scan", agent = agent.as_str(), files, duration_ms = *ms, "agent finished");
+        }
+        let languages = self.language_stats(tally.into_inner().unwrap());
+        for stats in languages.iter().filter(|s| s.uncovered) {
+            warn!("No agent has {} rules; {} files ({} lines) got generic checks only", stats.language, stats.files, stats.lines);
+        }
+        for error in &errors {
+            warn!(target: TELEMETRY, file = %error.file_path.display(), kind = ?error.kind, agent = error.agent.as_deref(), error = error.message.as_str(), "file not scanned");
+        }
+
+        let redactions = match self.include_context {
+            Some(_) => context::redactions(&all_findings),
+            None => context::Redactions::new(),
+        };
+        let ignored = self.ignored_files(&all_findings, tracked.as_ref());
+        let suppressed_by_rule = self.selection.apply(
+            &mut all_findings,
+            &confidence::Context { tracked: tracked.as_ref(), ignored: &ignored },
+        );
+        let suppressed: usize = suppressed_by_rule.values().map(SuppressedCounts::total).sum();
+        let expired_suppressions = self
+            .selection
+            .baseline()
+            .map_or_else(Vec::new, |b| b.expired(&all_findings, chrono::Local::now().date_naive()));
+        if !expired_suppressions.is_empty() {
+            warn!(
+                "{} baseline suppressions have expired; their findings are reported again",
+                expired_suppressions.len()
+            );
+        }
+
+        // Step 4: Merge, dedup, sort, and apply --max-findings / --max-per-rule
+        let phase = Instant::now();
+        let (mut merged, mut test_findings) = self.split_tests(merger::consolidate(all_findings));
+        if let Some(ref owners) = self.owners {
+            for f in merged.iter_mut().chain(test_findings.iter_mut()) {
+                f.owners = owners.owners_for(&f.file_path);
+            }
+        }
+        if let Some(ref triage) = self.triage {
+            triage.apply(&mut merged);
+            triage.apply(&mut test_findings);
+        }
+        score::apply(&mut merged, tracked.as_ref());
+        score::apply(&mut test_findings, tracked.as_ref());
+        if self.sort_by_score {
+            score::sort(&mut merged);
+            score::sort(&mut test_findings);
+        }
+        let hotspots = Hotspots::from_findings(&merged);
+        let agent_names: Vec<&str> = self.agents.iter().map(|a| a.name()).collect();
+        let policy_violations = self.policy.evaluate(&merged, &agent_names);
+        if !policy_violations.is_empty() {
+            info!("{} policy violations", policy_violations.len());
+        }
+        let compliance = self.compliance.as_deref().and_then(|framework| {
+            let rules: Vec<_> = self.agents.iter().flat_map(|a| a.rules()).collect();
+            compliance::evaluate(framework, &rules, &merged)
+        });
+        let packages = if self.monorepo {
+            let rel_paths: Vec<PathBuf> = file_paths
+                .iter()
+                .map(|p| portable_path(p.strip_prefix(&self.scan_path).unwrap_or(p)))
+                .collect();
+            let roots = packages::find_roots(&rel_paths);
+            info!("Found {} package roots", roots.len());
+            packages::summarize(&roots, &merged, self.fail_on)
+        } else {
+            Vec::new()
+        };
+        let (mut findings, mut omitted) = merger::apply_limits(merged, self.limits);
+        let (mut test_findings, test_omitted) = merger::apply_limits(test_findings, self.limits);
+        omitted.total += test_omitted.total;
+        for (rule, count) in test_omitted.by_rule {
+            *omitted.by_rule.entry(rule).or_default() += count;
+        }
+        timings.merge_ms = phase.elapsed().as_millis() as u64;
 
-        // Step 4: Merge, dedup, and sort
-        let mut findings = merger::merge_findings(all_findings);
+        if self.blame {
+            self.attach_blame(&mut findings);
+            self.attach_blame(&mut test_findings);
+        }
+        if let Some(lines) = self.include_context {
+            context::attach(&mut findings, &self.scan_path, lines, &redactions);
+            context::attach(&mut test_findings, &self.scan_path, lines, &redactions);
+        }
+        fix::attach(&mut findings, &self.scan_path);
+        fix::attach(&mut test_findings, &self.scan_path);
+        if !test_findings.is_empty() {
+            info!("{} findings in test files reported apart (--separate-tests)", test_findings.len());
+        }
 
-        // Apply --max-findings limit
-        if self.max_findings > 0 && findings.len() > self.max_findings {
-            findings.truncate(self.max_findings);
-            info!("Truncated to {} findings (--max-findings)", self.max_findings);
+        if omitted.total > 0 {
+            info!("Omitted {} findings (--max-findings / --max-per-rule)", omitted.total);
         }
 
         info!("Final findings after dedup: {}", findings.len());
 
         let duration = start.elapsed();
-        let summary = ScanSummary::from_findings(&findings);
+        info!(
+            target: TELEMETRY,
+            phase = "merge",
+            findings = findings.len(),
+            omitted = omitted.total,
+            suppressed,
+            duration_ms = timings.merge_ms,
+            "phase finished"
+        );
+        info!(
+            target: TELEMETRY,
+            files_scanned,
+            files_skipped,
+            findings = findings.len(),
+            duration_ms = duration.as_millis() as u64,
+            "scan finished"
+        );
+        let mut summary = ScanSummary::from_findings(&findings);
+        summary.omitted = omitted;
+        summary.suppressed = suppressed;
+        summary.suppressed_by_rule = suppressed_by_rule;
+        summary.hotspots = hotspots;
 
         Ok(ScanReport {
+            format_version: REPORT_FORMAT_VERSION,
             version: env!("CARGO_PKG_VERSION").to_string(),
             timestamp: chrono::Utc::now().to_rfc3339(),
             scan_path: self.display_path.clone(),
             files_scanned,
             files_skipped,
             duration_ms: duration.as_millis() as u64,
+            timings,
             findings,
+            test_findings,
+            errors,
+            skipped,
+            policy_violations,
+            expired_suppressions,
+            compliance,
+            packages,
+            // Commit and dirty state change with every edit
+            git: if self.deterministic { None } else { self.git_context() },
+            languages,
             summary,
+            partial,
+            stopped_early: false,
+            deterministic: self.deterministic,
+            editor_url_template: self.editor_url_template.clone(),
         })
     }
 }
+
+impl Scanner {
+    /// Read and classify one file for the scan, reserving its memory first
+    fn load<'a>(&self, path: &Path, budget: &'a MemoryBudget) -> Loaded<'a> {
+        let rel_path = portable_path(path.strip_prefix(&self.scan_path).unwrap_or(path));
+
+        let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if size > reader::STREAM_THRESHOLD {
+            // Streaming holds one chunk at a time
+            let reservation = budget.reserve(reader::CHUNK_BYTES as u64);
+            return Loaded::Large(path.to_path_buf(), rel_path, reservation);
+        }
+
+        let reservation = budget.reserve(size);
+        let language = self.languages.detect(path, &rel_path);
+        match reader::read_file(path, rel_path.clone(), language) {
+            Ok(file) => Loaded::File(file, reservation),
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                debug!("Skipping {}: {}", path.display(), e);
+                Loaded::Skipped(rel_path, SkipReason::NonUtf8)
+            }
+            Err(e) => Loaded::Failed(rel_path, format!("could not read: {}", e)),
+        }
+    }
+
+    /// Git-tracked files relative to the scan root, for scoring
+    fn tracked_files(&self) -> Option<HashSet<PathBuf>> {
+        if !self.scan_path.is_file() {
+            return git::tracked_files(&self.scan_path);
+        }
+        let tracked = git::tracked_files(self.scan_path.parent()?)?;
+        let name = PathBuf::from(self.scan_path.file_name()?);
+        // Single-file scans report findings under an empty relative path
+        Some(if tracked.contains(&name) {
+            HashSet::from([PathBuf::new()])
+        } else {
+            HashSet::new()
+        })
+    }
+
+    /// Findings in test files apart from the rest, when they are reported
+    /// separately (--separate-tests)
+    fn split_tests(&self, findings: Vec<Finding>) -> (Vec<Finding>, Vec<Finding>) {
+        if !self.separate_tests {
+            return (findings, Vec::new());
+        }
+        findings.into_iter().partition(|f| !confidence::is_test_path(&f.file_path))
+    }
+
+    /// The severity at which `f` fails the scan: --fail-on-tests for test
+    /// files reported apart, --fail-on for the rest
+    fn fail_threshold(&self, f: &Finding) -> Option<Severity> {
+        if self.separate_tests && confidence::is_test_path(&f.file_path) {
+            self.fail_on_tests
+        } else {
+            self.fail_on
+        }
+    }
+
+    /// The minimal report --fail-fast returns: only the findings that
+    /// stopped the scan, without scores, blame, or summaries that need the
+    /// whole tree
+    fn fail_fast_report(
+        &self,
+        tripped: Vec<Finding>,
+        tally: BTreeMap<&'static str, (usize, usize)>,
+        errors: Vec<ScanError>,
+        timings: ScanTimings,
+        start: Instant,
+    ) -> ScanReport {
+        let (findings, test_findings) = self.split_tests(merger::consolidate(tripped));
+        info!(
+            "Stopped at the first finding at or above --fail-on ({} so far, --fail-fast)",
+            findings.len()
+        );
+        let files_scanned = tally.values().map(|(files, _)| files).sum();
+        ScanReport {
+            format_version: REPORT_FORMAT_VERSION,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            scan_path: self.display_path.clone(),
+            files_scanned,
+            files_skipped: 0,
+            duration_ms: start.elapsed().as_millis() as u64,
+            timings,
+            summary: ScanSummary::from_findings(&findings),
+            findings,
+            test_findings,
+            errors,
+            skipped: Vec::new(),
+            policy_violations: Vec::new(),
+            expired_suppressions: Vec::new(),
+            compliance: None,
+            packages: Vec::new(),
+            git: None,
+            languages: Vec::new(),
+            partial: None,
+            stopped_early: true,
+            deterministic: self.deterministic,
+            editor_url_template: self.editor_url_template.clone(),
+        }
+    }
+
+    /// Commit, branch, and remote of the repository being scanned
+    fn git_context(&self) -> Option<GitContext> {
+        let dir = if self.scan_path.is_file() { self.scan_path.parent()? } else { &self.scan_path };
+        git::context(dir)
+    }
+
+    /// Gitignored files among those with findings. Only --no-gitignore
+    /// scans reach them, so git is not asked otherwise.
+    fn ignored_files(&self, findings: &[Finding], tracked: Option<&HashSet<PathBuf>>) -> HashSet<PathBuf> {
+        if !self.walk.no_gitignore || self.scan_path.is_file() || tracked.is_none() {
+            return HashSet::new();
+        }
+        let untracked: BTreeSet<&Path> = findings
+            .iter()
+            .map(|f| f.file_path.as_path())
+            .filter(|p| !tracked.is_some_and(|t| t.contains(*p)))
+            .collect();
+        git::ignored_files(&self.scan_path, &untracked.into_iter().collect::<Vec<_>>())
+    }
+
+    /// Annotate findings with the last commit that touched their line.
+    /// One `git blame` per file covers all of that file's findings.
+    fn attach_blame(&self, findings: &mut [Finding]) {
+        let (dir, single_file) = if self.scan_path.is_file() {
+            (self.scan_path.parent().unwrap_or(&self.scan_path), self.scan_path.file_name())
+        } else {
+            (self.scan_path.as_path(), None)
+        };
+
+        let mut lines_by_file: BTreeMap<PathBuf, BTreeSet<usize>> = BTreeMap::new();
+        for f in findings.iter() {
+            lines_by_file.entry(f.file_path.clone()).or_default().insert(f.line_start);
+        }
+
+        let blamed: HashMap<PathBuf, HashMap<usize, Blame>> = lines_by_file
+            .into_par_iter()
+            .map(|(rel_path, lines)| {
+                // A single-file scan reports paths relative to the file itself
+                let target = match single_file {
+                    Some(name) if rel_path.as_os_str().is_empty() => PathBuf::from(name),
+                    _ => rel_path.clone(),
+                };
+                let blame = git::blame_lines(dir, &target, &lines);
+                (rel_path, blame)
+            })
+            .collect();
+
+        for f in findings.iter_mut() {
+            f.blame = blamed
+                .get(&f.file_path)
+                .and_then(|lines| lines.get(&f.line_start))
+                .cloned();
+        }
+    }
+
+    /// Per-language totals with the agents that have rules for each
+    fn language_stats(&self, tally: BTreeMap<&'static str, (usize, usize)>) -> Vec<LanguageStats> {
+        let coverage: Vec<(&str, Vec<Language>)> =
+            self.agents.iter().map(|a| (a.name(), a.languages())).collect();
+        let mut stats: Vec<LanguageStats> = tally
+            .into_iter()
+            .map(|(language, (files, lines))| LanguageStats {
+                language: language.to_string(),
+                files,
+                lines,
+                agents: coverage
+                    .iter()
+                    .filter(|(_, langs)| langs.iter().any(|l| l.name() == language))
+                    .map(|(agent, _)| agent.to_string())
+                    .collect(),
+                uncovered: false,
+            })
+            .collect();
+        LanguageStats::finish(&mut stats);
+        stats
+    }
+}
+
+/// Run agents over a single in-memory file, select what a scan would
+/// report, and merge the results.
+///
+/// Used by editor integrations that scan one buffer at a time.
+pub fn scan_single(
+    agents: &[Arc<dyn SecurityAgent>],
+    selection: &Selection,
+    tracked: Option<&HashSet<PathBuf>>,
+    file: &ScannedFile,
+) -> Vec<Finding> {
+    let mut findings = agents.iter().flat_map(|a| agents::scan_alone(a.as_ref(), file)).collect();
+    let ignored = HashSet::new();
+    selection.apply(&mut findings, &confidence::Context { tracked, ignored: &ignored });
+    let mut findings = merger::merge_findings(findings, FindingLimits::default()).0;
+    for f in &mut findings {
+        let line = file.content.lines().nth(f.line_start.wrapping_sub(1));
+        f.fix = line.and_then(|line| fix::suggest(f, line));
+    }
+    findings
+}
+
+/// Result of the read phase for a single path
+enum Loaded<'a> {
+    /// Read whole into memory
+    File(ScannedFile, Reservation<'a>),
+    /// Too large to hold whole; streamed in chunks during the scan
+    Large(PathBuf, PathBuf, Reservation<'a>),
+    /// Not text (non-UTF8)
+    Skipped(PathBuf, SkipReason),
+    /// Could not be read (permissions, vanished, ...), with the error
+    Failed(PathBuf, String),
+}
+
+/// `path` relative to the working directory (with `..` where needed), so a
+/// --deterministic report does not depend on where the tree is checked out
+fn relative_to_cwd(path: &Path) -> PathBuf {
+    let (Ok(path), Ok(cwd)) = (std::fs::canonicalize(path), std::env::current_dir().and_then(std::fs::canonicalize))
+    else {
+        return path.to_path_buf();
+    };
+    let common = path.components().zip(cwd.components()).take_while(|(a, b)| a == b).count();
+    let mut relative: PathBuf = cwd.components().skip(common).map(|_| "..").collect();
+    relative.extend(path.components().skip(common));
+    if relative.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        portable_path(&relative)
+    }
+}
+
+/// The message of a caught panic (`panic!` with a string or format args)
+fn panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown cause".to_string())
+}
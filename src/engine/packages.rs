@@ -0,0 +1,59 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::report::finding::{Finding, PackageSummary, Severity};
+
+/// Files whose presence makes a directory a package root (--monorepo)
+const MANIFESTS: &[&str] = &["package.json", "Cargo.toml", "go.mod", "pyproject.toml"];
+
+/// Directories (relative to the scan root) that hold a package manifest
+pub fn find_roots(rel_paths: &[PathBuf]) -> BTreeSet<PathBuf> {
+    rel_paths
+        .iter()
+        .filter(|p| {
+            p.file_name()
+                .is_some_and(|n| MANIFESTS.contains(&n.to_string_lossy().as_ref()))
+        })
+        .map(|p| p.parent().map(Path::to_path_buf).unwrap_or_default())
+        .collect()
+}
+
+/// Count findings per package. Each finding belongs to the deepest package
+/// containing it; findings outside every package go to the scan root.
+pub fn summarize(
+    roots: &BTreeSet<PathBuf>,
+    findings: &[Finding],
+    fail_on: Option<Severity>,
+) -> Vec<PackageSummary> {
+    let mut packages: Vec<PackageSummary> = roots.iter().map(|r| PackageSummary::new(r)).collect();
+
+    for f in findings {
+        let owner = packages
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| !p.path.as_os_str().is_empty() && f.file_path.starts_with(&p.path))
+            .max_by_key(|(_, p)| p.path.components().count())
+            .map(|(i, _)| i);
+        let i = match owner {
+            Some(i) => i,
+            None => root_package(&mut packages),
+        };
+        packages[i].add(f.severity);
+    }
+
+    if let Some(threshold) = fail_on {
+        for p in packages.iter_mut() {
+            p.failed = p.max_severity.is_some_and(|s| s >= threshold);
+        }
+    }
+    packages
+}
+
+/// Index of the scan-root entry, added on first use (roots sort first)
+fn root_package(packages: &mut Vec<PackageSummary>) -> usize {
+    if packages.first().is_some_and(|p| p.path.as_os_str().is_empty()) {
+        return 0;
+    }
+    packages.insert(0, PackageSummary::new(Path::new("")));
+    0
+}
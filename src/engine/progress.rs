@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// Progress display for the scan phases.
+///
+/// When disabled (not a TTY, `--quiet`, machine-readable output) every bar
+/// is hidden, so callers can update them unconditionally.
+pub struct Progress {
+    enabled: bool,
+}
+
+impl Progress {
+    pub fn new(enabled: bool) -> Self {
+        Progress { enabled }
+    }
+
+    /// Indeterminate spinner for phases with unknown length (discovery)
+    pub fn spinner(&self, message: &'static str) -> ProgressBar {
+        if !self.enabled {
+            return ProgressBar::hidden();
+        }
+        let pb = ProgressBar::new_spinner();
+        pb.set_style(
+            ProgressStyle::with_template("  {spinner} {msg} {pos} files")
+                .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+        );
+        pb.set_message(message);
+        pb.enable_steady_tick(Duration::from_millis(100));
+        pb
+    }
+
+    /// Bar over a known number of files
+    pub fn bar(&self, len: usize, message: &'static str) -> ProgressBar {
+        if !self.enabled {
+            return ProgressBar::hidden();
+        }
+        let pb = ProgressBar::new(len as u64);
+        pb.set_style(
+            ProgressStyle::with_template("  {msg:<10} [{bar:40}] {pos}/{len} ({eta})")
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("=> "),
+        );
+        pb.set_message(message);
+        pb
+    }
+}
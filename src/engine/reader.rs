@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use tracing::warn;
+
+use crate::agents::{Language, ScannedFile};
+
+/// Files larger than this are scanned in chunks instead of being read whole
+pub const STREAM_THRESHOLD: u64 = 4 * 1024 * 1024;
+
+/// Approximate size of each streamed chunk. Chunks always end on a line boundary.
+pub const CHUNK_BYTES: usize = 1024 * 1024;
+
+/// Lines each chunk repeats from the end of the one before, so a match
+/// spanning a boundary (a PEM key, a Kubernetes Secret) is seen whole.
+/// Enough for the longest key block the PEM parser reads.
+const OVERLAP_LINES: usize = 200;
+
+/// Bytes the repeated lines may take, for files of very long lines
+const OVERLAP_BYTES: usize = 64 * 1024;
+
+/// Extension/filename → language mapping, with `[languages]` config
+/// entries checked before the built-in rules.
+#[derive(Debug, Clone, Default)]
+pub struct LanguageMap {
+    globs: GlobSet,
+    languages: Vec<Language>,
+}
+
+impl LanguageMap {
+    /// Build from config entries like `"*.gyb" = "python"` or `"Justfile" = "shell"`
+    pub fn new(entries: &BTreeMap<String, String>) -> Self {
+        let mut builder = GlobSetBuilder::new();
+        let mut languages = Vec::new();
+        for (pattern, name) in entries {
+            let Some(language) = Language::from_name(name) else {
+                warn!("Unknown language '{}' for '{}' in [languages]", name, pattern);
+                continue;
+            };
+            match Glob::new(pattern) {
+                Ok(glob) => {
+                    builder.add(glob);
+                    languages.push(language);
+                }
+                Err(e) => warn!("Invalid [languages] pattern '{}': {}", pattern, e),
+            }
+        }
+        LanguageMap {
+            globs: builder.build().unwrap_or_else(|_| GlobSet::empty()),
+            languages,
+        }
+    }
+
+    /// Language for `path`: a configured mapping if one matches its file
+    /// name or relative path, otherwise the built-in detection
+    pub fn detect(&self, path: &Path, rel_path: &Path) -> Option<Language> {
+        let name = path.file_name().map(Path::new).unwrap_or(path);
+        self.globs
+            .matches(name)
+            .into_iter()
+            .chain(self.globs.matches(rel_path))
+            .min()
+            .map(|idx| self.languages[idx])
+            .or_else(|| detect_language(path))
+    }
+}
+
+/// Languages to scan (--lang / scan.lang) and to leave out
+/// (--exclude-lang / scan.exclude_lang), applied after detection
+#[derive(Debug, Clone, Default)]
+pub struct LanguageFilter {
+    include: Vec<Language>,
+    exclude: Vec<Language>,
+}
+
+impl LanguageFilter {
+    /// Build from language names like "ts" or "python"
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        let parse = |names: &[String]| -> Result<Vec<Language>> {
+            names
+                .iter()
+                .map(|name| {
+                    Language::from_name(name.trim())
+                        .with_context(|| format!("Unknown language '{}' in --lang / --exclude-lang (or scan.lang / scan.exclude_lang)", name))
+                })
+                .collect()
+        };
+        Ok(LanguageFilter {
+            include: parse(include)?,
+            exclude: parse(exclude)?,
+        })
+    }
+
+    /// Whether a file in `language` is scanned. With an include list, files
+    /// of unknown language are left out.
+    pub fn allows(&self, language: Option<Language>) -> bool {
+        let included = self.include.is_empty() || language.is_some_and(|l| self.include.contains(&l));
+        included && !language.is_some_and(|l| self.exclude.contains(&l))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+}
+
+/// Detect a file's language from its extension, falling back to its name
+pub fn detect_language(path: &Path) -> Option<Language> {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(Language::from_extension)
+        .or_else(|| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .map(Language::from_filename)
+        })
+}
+
+/// Read a whole file into memory
+pub fn read_file(path: &Path, rel_path: PathBuf, language: Option<Language>) -> io::Result<ScannedFile> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(ScannedFile {
+        rel_path,
+        abs_path: path.to_path_buf(),
+        content,
+        language,
+        line_offset: 0,
+        deadline: None,
+    })
+}
+
+/// Stream a file in line-aligned chunks, handing each one to `scan`.
+///
+/// Only one chunk is held in memory at a time. Each chunk carries the line
+/// offset of its first line so findings keep file-relative line numbers,
+/// and the whole file shares one `deadline`; streaming stops once it passes.
+/// Chunks start with the last lines of the one before, and `scan` also gets
+/// the 1-based lines whose findings the chunk reports: the lines repeated
+/// in the next chunk are left to it, where they have the lines after them.
+/// Content that is not UTF-8 fails with [`io::ErrorKind::InvalidData`].
+pub fn for_each_chunk(
+    path: &Path,
+    rel_path: PathBuf,
+    language: Option<Language>,
+    deadline: Option<Instant>,
+    mut scan: impl FnMut(&ScannedFile, Range<usize>),
+) -> io::Result<()> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut chunk = ScannedFile {
+        rel_path,
+        abs_path: path.to_path_buf(),
+        content: String::with_capacity(CHUNK_BYTES + 4096),
+        language,
+        line_offset: 0,
+        deadline,
+    };
+    let mut lines_in_chunk = 0;
+
+    loop {
+        let read = reader.read_line(&mut chunk.content)?;
+        if read > 0 {
+            lines_in_chunk += 1;
+        }
+
+        if read == 0 && lines_in_chunk > 0 {
+            scan(&chunk, chunk.line_offset + 1..chunk.line_offset + lines_in_chunk + 1);
+        } else if chunk.content.len() >= CHUNK_BYTES {
+            let (start, carried) = overlap(&chunk.content);
+            let reported = lines_in_chunk - carried;
+            scan(&chunk, chunk.line_offset + 1..chunk.line_offset + reported + 1);
+            chunk.content.drain(..start);
+            chunk.line_offset += reported;
+            lines_in_chunk = carried;
+        }
+
+        if read == 0 || chunk.out_of_time() {
+            return Ok(());
+        }
+    }
+}
+
+/// Where the lines repeated in the next chunk start in `content`, and how
+/// many there are: up to [`OVERLAP_LINES`] and [`OVERLAP_BYTES`], and never
+/// every line, so each chunk reports at least one
+fn overlap(content: &str) -> (usize, usize) {
+    let (mut start, mut lines) = (content.len(), 0);
+    for (i, _) in content[..content.len() - 1].rmatch_indices('\n') {
+        if lines == OVERLAP_LINES || content.len() - (i + 1) > OVERLAP_BYTES {
+            break;
+        }
+        start = i + 1;
+        lines += 1;
+    }
+    (start, lines)
+}
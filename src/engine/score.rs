@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::agents::heuristics;
+use crate::report::finding::{Confidence, Environment, Finding, Severity};
+
+/// Path components that mark deployment or production configuration
+pub const PRODUCTION_DIRS: &[&str] = &[
+    "prod",
+    "production",
+    "prd",
+    "live",
+    "deploy",
+    "deployment",
+    "k8s",
+    "kubernetes",
+    "helm",
+    "infra",
+];
+
+/// A finding's 0.1–10.0 priority, CVSS-style: the severity sets the band
+/// and confidence and context move the score within and across bands.
+///
+/// - Severity: CRITICAL 9.0, HIGH 7.0, MEDIUM 5.0, LOW 2.5
+/// - Confidence: HIGH +0.5, LOW −1.0
+/// - Production config path (`prod/`, `deploy/`, `.env.production`, ...): +1.0
+/// - Example or fixture path: −1.5
+/// - Dev or test environment (`docker-compose.dev.yml`, `config/test.yml`): −1.0
+/// - File tracked in git (so it is in every clone): +0.5
+pub fn score(finding: &Finding, tracked: Option<&HashSet<PathBuf>>) -> f32 {
+    let mut score: f32 = match finding.severity {
+        Severity::Critical => 9.0,
+        Severity::High => 7.0,
+        Severity::Medium => 5.0,
+        Severity::Low => 2.5,
+    };
+    score += match finding.confidence {
+        Confidence::High => 0.5,
+        Confidence::Medium => 0.0,
+        Confidence::Low => -1.0,
+    };
+    if is_production_path(&finding.file_path) {
+        score += 1.0;
+    }
+    if heuristics::is_sample_path(&finding.file_path) {
+        score -= 1.5;
+    }
+    if matches!(finding.environment, Some(Environment::Dev | Environment::Test)) {
+        score -= 1.0;
+    }
+    if tracked.is_some_and(|t| t.contains(&finding.file_path)) {
+        score += 0.5;
+    }
+    // 0.0 would read as "no risk"; every reported finding is worth a look
+    (score.clamp(0.1, 10.0) * 10.0).round() / 10.0
+}
+
+/// Score every finding. `tracked` is the set of git-tracked files relative
+/// to the scan root, or None outside a repository.
+pub fn apply(findings: &mut [Finding], tracked: Option<&HashSet<PathBuf>>) {
+    for f in findings.iter_mut() {
+        f.score = Some(score(f, tracked));
+    }
+}
+
+/// Highest score first; ties keep their severity/path order
+pub fn sort(findings: &mut [Finding]) {
+    findings.sort_by(|a, b| b.score.unwrap_or(0.0).total_cmp(&a.score.unwrap_or(0.0)));
+}
+
+fn is_production_path(path: &Path) -> bool {
+    let in_production_dir = path.parent().is_some_and(|dir| {
+        dir.components().any(|c| {
+            let name = c.as_os_str().to_string_lossy().to_lowercase();
+            PRODUCTION_DIRS.contains(&name.as_str())
+        })
+    });
+    in_production_dir || is_production_file(path)
+}
+
+/// Whether the file name marks production config: .env.production,
+/// values-prod.yaml, config.prod.json, ...
+pub fn is_production_file(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    name.split(['.', '-', '_'])
+        .any(|part| part == "prod" || part == "production")
+}
@@ -0,0 +1,125 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::agents::SecurityAgent;
+use crate::baseline::{self, Baseline};
+use crate::cli::ScanArgs;
+use crate::config::overrides::Overrides;
+use crate::config::AntyConfig;
+use crate::report::finding::{Confidence, Environment, Finding, Severity, SuppressedCounts};
+use crate::rules;
+
+use super::{confidence, environment};
+
+/// Which findings get reported: everything between the agents' raw matches
+/// and the report, shared by scans and the language server
+pub struct Selection {
+    /// Rules selected by --tags / --exclude-tags; None = all rules
+    rule_ids: Option<HashSet<String>>,
+    /// Path-scoped rule and severity settings (`[[overrides]]`)
+    overrides: Overrides,
+    /// Drop findings below this severity (--min-severity / output.min_severity)
+    min_severity: Option<Severity>,
+    /// Per-agent minimum severities (`[agents.thresholds]`), used instead
+    /// of `min_severity` for those agents
+    agent_thresholds: HashMap<String, Severity>,
+    /// Drop findings below this confidence (--min-confidence)
+    min_confidence: Option<Confidence>,
+    /// Only report findings in these environments (--environment); empty = all
+    environments: Vec<Environment>,
+    /// Recorded triage decisions (`.anty/baseline.toml`)
+    baseline: Option<Baseline>,
+}
+
+impl Selection {
+    /// The selection `args` and `config` ask for, over the rules of `agents`,
+    /// with the baseline of the repository holding `scan_path`
+    pub fn new(
+        args: &ScanArgs,
+        config: Option<&AntyConfig>,
+        agents: &[Arc<dyn SecurityAgent>],
+        scan_path: &Path,
+    ) -> Result<Self> {
+        let mut tags = args.tags.clone();
+        let mut exclude_tags = args.exclude_tags.clone();
+        if let Some(cfg) = config {
+            tags.extend(cfg.scan.tags.clone());
+            exclude_tags.extend(cfg.scan.exclude_tags.clone());
+        }
+        // Review-only rules (build and install scripts) matter when the code
+        // itself is untrusted: with --no-config, or when asked for by tag
+        let untrusted = rules::UNTRUSTED_TAG.to_string();
+        if !args.no_config
+            && !tags.contains(&untrusted)
+            && agents.iter().flat_map(|a| a.rules()).any(|r| r.tags.contains(&untrusted))
+        {
+            exclude_tags.push(untrusted);
+        }
+
+        Ok(Selection {
+            rule_ids: rules::select_by_tags(agents, &tags, &exclude_tags)?,
+            overrides: Overrides::new(config.map_or(&[], |c| &c.overrides[..]))?,
+            min_severity: args
+                .min_severity
+                .clone()
+                .or_else(|| config.and_then(|c| c.output.min_severity.clone()))
+                .map(|s| Severity::from_str(&s)),
+            agent_thresholds: config
+                .map(|c| {
+                    c.agents
+                        .thresholds
+                        .iter()
+                        .map(|(agent, severity)| (agent.to_lowercase(), Severity::from_str(severity)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            min_confidence: args.min_confidence.as_deref().map(Confidence::from_str),
+            environments: args.environment.iter().filter_map(|e| Environment::from_name(e)).collect(),
+            // The baseline lives in the repo, so it is untrusted input too
+            baseline: if args.no_config { None } else { Baseline::load(&baseline::root_for(scan_path)) },
+        })
+    }
+
+    /// The baseline findings are checked against, if the repository has one
+    pub fn baseline(&self) -> Option<&Baseline> {
+        self.baseline.as_ref()
+    }
+
+    /// Settle which findings get reported: calibrate confidence and
+    /// severity from context, then apply rule selection, `[[overrides]]`,
+    /// the severity and confidence minimums, the environment filter, and
+    /// the baseline. Returns what the baseline suppressed, per rule.
+    pub fn apply(&self, findings: &mut Vec<Finding>, ctx: &confidence::Context) -> BTreeMap<String, SuppressedCounts> {
+        confidence::calibrate(findings, ctx);
+        environment::apply(findings);
+
+        if let Some(ref rule_ids) = self.rule_ids {
+            findings.retain(|f| rule_ids.contains(&f.rule_id));
+        }
+        if !self.overrides.is_empty() {
+            self.overrides.apply(findings);
+        }
+        if self.min_severity.is_some() || !self.agent_thresholds.is_empty() {
+            findings.retain(|f| {
+                self.agent_thresholds
+                    .get(&f.agent)
+                    .copied()
+                    .or(self.min_severity)
+                    .is_none_or(|min| f.severity >= min)
+            });
+        }
+        if let Some(min) = self.min_confidence {
+            findings.retain(|f| f.confidence >= min);
+        }
+        if !self.environments.is_empty() {
+            findings.retain(|f| f.environment.is_some_and(|e| self.environments.contains(&e)));
+        }
+
+        self.baseline
+            .as_ref()
+            .map_or_else(BTreeMap::new, |b| b.apply(findings, chrono::Local::now().date_naive()))
+    }
+}
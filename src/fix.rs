@@ -0,0 +1,303 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use inquire::Confirm;
+use regex::Regex;
+use tracing::info;
+
+use crate::cli::{exit_code, Cli, FixArgs};
+use crate::engine::Scanner;
+use crate::report::finding::{Finding, Fix};
+
+/// Rules with a mechanical fix that keeps the code's intent
+pub const FIXABLE_RULES: &[&str] = &["ANTY-CFG-004", "ANTY-DNG-007", "ANTY-DNG-012"];
+
+/// Lines of unchanged context around each diff hunk
+const DIFF_CONTEXT: usize = 3;
+
+static YAML_LOAD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\byaml\.load\s*\(").unwrap());
+static SUBPROCESS_LIST: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"subprocess\.\w+\s*\(\s*\[").unwrap());
+static SHELL_TRUE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s*,\s*shell\s*=\s*True").unwrap());
+static COOKIE_FLAG: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\b(secure|httponly)(\s*[:=]\s*)(false|0)\b"#).unwrap()
+});
+
+/// Apply the fix for `rule_id` to a single line, if the rule has one and
+/// this line is a shape we can rewrite safely.
+pub fn fix_line(rule_id: &str, line: &str) -> Option<String> {
+    let fixed = match rule_id {
+        // yaml.load(x) → yaml.safe_load(x); leave explicit Loader= calls to a human
+        "ANTY-DNG-007" if !line.contains("Loader") => {
+            YAML_LOAD.replace_all(line, "yaml.safe_load(").into_owned()
+        }
+        // Dropping shell=True is only safe when the command is already a list
+        "ANTY-DNG-012" if SUBPROCESS_LIST.is_match(line) => {
+            SHELL_TRUE.replace_all(line, "").into_owned()
+        }
+        // secure: false → secure: true (and False → True for Python)
+        "ANTY-CFG-004" => COOKIE_FLAG
+            .replace_all(line, |caps: &regex::Captures| {
+                let value = match &caps[3] {
+                    "0" => "1",
+                    v if v.starts_with('F') => "True",
+                    _ => "true",
+                };
+                format!("{}{}{}", &caps[1], &caps[2], value)
+            })
+            .into_owned(),
+        _ => return None,
+    };
+    (fixed != line).then_some(fixed)
+}
+
+/// What the fix for `rule_id` does, in the words of a code action
+fn describe(rule_id: &str) -> &'static str {
+    match rule_id {
+        "ANTY-DNG-007" => "Use yaml.safe_load",
+        "ANTY-DNG-012" => "Remove shell=True",
+        "ANTY-CFG-004" => "Turn the cookie flag on",
+        _ => "Apply Anty fix",
+    }
+}
+
+/// The fixable rules a finding reports: its own and those folded into it
+/// from the same line, in the order `anty fix` applies them
+fn fixable_rules(finding: &Finding) -> BTreeSet<&str> {
+    std::iter::once(&finding.rule_id)
+        .chain(&finding.related_rules)
+        .map(String::as_str)
+        .filter(|r| FIXABLE_RULES.contains(r))
+        .collect()
+}
+
+/// The fix for `finding` as an edit of `line` (its first line, without
+/// the terminator): what `anty fix` would write, with the text it leaves
+/// alone at either end kept out of the replaced range
+pub fn suggest(finding: &Finding, line: &str) -> Option<Fix> {
+    let rules = fixable_rules(finding);
+    let mut fixed = line.to_string();
+    for rule_id in &rules {
+        if let Some(next) = fix_line(rule_id, &fixed) {
+            fixed = next;
+        }
+    }
+    if fixed == line {
+        return None;
+    }
+
+    let prefix = line
+        .char_indices()
+        .zip(fixed.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(line.len().min(fixed.len()), |((i, _), _)| i);
+    let max_suffix = line.len().min(fixed.len()) - prefix;
+    let suffix = line[prefix..]
+        .chars()
+        .rev()
+        .zip(fixed[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .scan(0, |total, len| {
+            *total += len;
+            Some(*total)
+        })
+        .take_while(|total| *total <= max_suffix)
+        .last()
+        .unwrap_or(0);
+
+    Some(Fix {
+        description: rules.iter().map(|r| describe(r)).collect::<Vec<_>>().join("; "),
+        line: finding.line_start,
+        start_column: prefix + 1,
+        end_column: line.len() - suffix + 1,
+        replacement: fixed[prefix..fixed.len() - suffix].to_string(),
+    })
+}
+
+/// Attach the fix of every fixable finding, reading each file they are in
+/// once. `scan_path` is the scan root (or the file of a single-file scan).
+pub fn attach(findings: &mut [Finding], scan_path: &Path) {
+    let mut files: BTreeMap<PathBuf, Option<String>> = BTreeMap::new();
+    for f in findings.iter_mut().filter(|f| !fixable_rules(f).is_empty()) {
+        let content = files.entry(f.file_path.clone()).or_insert_with(|| {
+            // A single-file scan reports paths relative to the file itself
+            let path = if f.file_path.as_os_str().is_empty() {
+                scan_path.to_path_buf()
+            } else {
+                scan_path.join(&f.file_path)
+            };
+            std::fs::read_to_string(path).ok()
+        });
+        let line = content.as_deref().and_then(|c| c.lines().nth(f.line_start.wrapping_sub(1)));
+        f.fix = line.and_then(|line| suggest(f, line));
+    }
+}
+
+/// Fixed contents of one file
+struct FilePatch {
+    path: PathBuf,
+    /// Original lines, each with its line terminator
+    before: Vec<String>,
+    after: Vec<String>,
+}
+
+/// `anty fix`: scan, rewrite fixable findings, show a diff, and write on confirmation
+pub fn run(cli: &Cli, args: &FixArgs) -> Result<i32> {
+    let mut scan = args.scan.clone();
+    // Every fixable finding should be fixed, not just the ones a report would show
+    scan.max_findings = Some(0);
+    scan.max_per_rule = 0;
+
+    let report = Scanner::new(cli, &scan)?.run()?;
+
+    let mut lines_by_file: BTreeMap<PathBuf, BTreeMap<usize, BTreeSet<&str>>> = BTreeMap::new();
+    for finding in &report.findings {
+        // Rules folded into another finding on the same line still get fixed
+        for rule_id in fixable_rules(finding) {
+            lines_by_file
+                .entry(finding.file_path.clone())
+                .or_default()
+                .entry(finding.line_start)
+                .or_default()
+                .insert(rule_id);
+        }
+    }
+
+    let root = PathBuf::from(&scan.path);
+    let mut patches = Vec::new();
+    for (rel_path, lines) in &lines_by_file {
+        // A single-file scan reports paths relative to the file itself
+        let path = if rel_path.as_os_str().is_empty() {
+            root.clone()
+        } else {
+            root.join(rel_path)
+        };
+        if let Some(patch) = patch_file(&path, lines)? {
+            patches.push(patch);
+        }
+    }
+
+    if patches.is_empty() {
+        println!("No automatic fixes available.");
+        return Ok(exit_code::CLEAN);
+    }
+
+    for patch in &patches {
+        print!("{}", unified_diff(patch));
+    }
+    let changed: usize = patches.iter().map(|p| changed_lines(p).len()).sum();
+    println!();
+    println!("{} lines to fix in {} files", changed, patches.len());
+
+    if args.dry_run {
+        return Ok(exit_code::CLEAN);
+    }
+
+    if !args.yes {
+        if !std::io::stdin().is_terminal() {
+            println!("Re-run with --yes to write these changes, or --dry-run to only preview them.");
+            return Ok(exit_code::CLEAN);
+        }
+        let confirmed = Confirm::new("Apply these fixes?")
+            .with_default(false)
+            .prompt()
+            .unwrap_or(false);
+        if !confirmed {
+            println!("No files changed.");
+            return Ok(exit_code::CLEAN);
+        }
+    }
+
+    for patch in &patches {
+        std::fs::write(&patch.path, patch.after.concat())
+            .with_context(|| format!("Could not write {}", patch.path.display()))?;
+        info!("Fixed {}", patch.path.display());
+    }
+    println!("Fixed {} lines in {} files.", changed, patches.len());
+    Ok(exit_code::CLEAN)
+}
+
+/// Apply fixes to the given 1-based lines of `path`
+fn patch_file(path: &Path, lines: &BTreeMap<usize, BTreeSet<&str>>) -> Result<Option<FilePatch>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Could not read {}", path.display()))?;
+    let before: Vec<String> = content.split_inclusive('\n').map(str::to_string).collect();
+    let mut after = before.clone();
+
+    for (&line_no, rules) in lines {
+        let Some(line) = after.get_mut(line_no.wrapping_sub(1)) else {
+            continue;
+        };
+        let body_len = line.trim_end_matches(['\r', '\n']).len();
+        let (body, ending) = line.split_at(body_len);
+        let mut fixed = body.to_string();
+        for rule_id in rules {
+            if let Some(next) = fix_line(rule_id, &fixed) {
+                fixed = next;
+            }
+        }
+        *line = format!("{}{}", fixed, ending);
+    }
+
+    if before == after {
+        return Ok(None);
+    }
+    Ok(Some(FilePatch {
+        path: path.to_path_buf(),
+        before,
+        after,
+    }))
+}
+
+/// 0-based indexes of lines that differ
+fn changed_lines(patch: &FilePatch) -> Vec<usize> {
+    (0..patch.before.len())
+        .filter(|&i| patch.before[i] != patch.after[i])
+        .collect()
+}
+
+/// Render a patch as a unified diff. Fixes rewrite lines in place, so the
+/// old and new sides of every hunk have the same length.
+fn unified_diff(patch: &FilePatch) -> String {
+    // Paths stay relative to the working directory so the diff works with `git apply`
+    let name = patch.path.strip_prefix(".").unwrap_or(&patch.path).display();
+    let mut out = format!("--- a/{}\n+++ b/{}\n", name, name);
+
+    // Group changed lines into hunks whose context windows touch
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for i in changed_lines(patch) {
+        let start = i.saturating_sub(DIFF_CONTEXT);
+        let end = (i + DIFF_CONTEXT + 1).min(patch.before.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    for (start, end) in hunks {
+        let len = end - start;
+        out.push_str(&format!("@@ -{},{} +{},{} @@\n", start + 1, len, start + 1, len));
+        for i in start..end {
+            let (old, new) = (&patch.before[i], &patch.after[i]);
+            if old == new {
+                push_line(&mut out, ' ', old);
+            } else {
+                push_line(&mut out, '-', old);
+                push_line(&mut out, '+', new);
+            }
+        }
+    }
+    out
+}
+
+fn push_line(out: &mut String, prefix: char, line: &str) {
+    out.push(prefix);
+    out.push_str(line);
+    if !line.ends_with('\n') {
+        out.push_str("\n\\ No newline at end of file\n");
+    }
+}
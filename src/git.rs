@@ -0,0 +1,205 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tracing::debug;
+
+use crate::report::finding::{Blame, GitContext};
+
+/// Run a git command in `dir` and return its stdout, or None on any failure
+pub fn run_git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        debug!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Top-level directory of the repository containing `dir`
+pub fn repo_root(dir: &Path) -> Option<PathBuf> {
+    run_git(dir, &["rev-parse", "--show-toplevel"]).map(|s| PathBuf::from(s.trim()))
+}
+
+/// CI variables naming the branch being built, for detached checkouts
+const BRANCH_VARS: &[&str] = &["GITHUB_HEAD_REF", "GITHUB_REF_NAME", "CI_COMMIT_REF_NAME", "BRANCH_NAME"];
+
+/// HEAD commit, branch, remote, and dirty flag of the repository containing
+/// `dir`. None outside a repository or before the first commit.
+pub fn context(dir: &Path) -> Option<GitContext> {
+    let commit = run_git(dir, &["rev-parse", "HEAD"])?.trim().to_string();
+    let branch = run_git(dir, &["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .map(|b| b.trim().to_string())
+        .or_else(|| {
+            BRANCH_VARS
+                .iter()
+                .filter_map(|var| std::env::var(var).ok())
+                .find(|b| !b.is_empty())
+        });
+    let remote = run_git(dir, &["remote"])
+        .and_then(|remotes| {
+            let remotes: Vec<&str> = remotes.lines().collect();
+            let name = if remotes.contains(&"origin") { "origin" } else { remotes.first()? };
+            run_git(dir, &["remote", "get-url", name])
+        })
+        .map(|url| strip_credentials(url.trim()));
+    let dirty = run_git(dir, &["status", "--porcelain", "--untracked-files=no"]).is_some_and(|s| !s.trim().is_empty());
+    Some(GitContext { commit, branch, remote_url: remote, dirty })
+}
+
+/// `url` without a `user:token@` part, which CI checkouts often embed
+fn strip_credentials(url: &str) -> String {
+    match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let host_start = rest.find('/').unwrap_or(rest.len());
+            match rest[..host_start].rfind('@') {
+                Some(at) => format!("{}://{}", scheme, &rest[at + 1..]),
+                None => url.to_string(),
+            }
+        }
+        // scp-like `git@github.com:org/repo.git` carries no secret
+        None => url.to_string(),
+    }
+}
+
+/// Files tracked by git under `dir`, relative to `dir`.
+/// None when `dir` is not inside a repository or git is unavailable.
+pub fn tracked_files(dir: &Path) -> Option<HashSet<PathBuf>> {
+    let out = run_git(dir, &["ls-files", "-z"])?;
+    Some(out.split('\0').filter(|p| !p.is_empty()).map(PathBuf::from).collect())
+}
+
+/// Which of `files` (relative to `dir`) a gitignore rule matches. Empty
+/// when none are ignored or git is unavailable.
+pub fn ignored_files(dir: &Path, files: &[&Path]) -> HashSet<PathBuf> {
+    if files.is_empty() {
+        return HashSet::new();
+    }
+    let paths: Vec<String> = files.iter().map(|p| p.to_string_lossy().into_owned()).collect();
+    let mut args = vec!["check-ignore", "--"];
+    args.extend(paths.iter().map(String::as_str));
+    // Exits 1 when nothing is ignored
+    run_git(dir, &args)
+        .map(|out| out.lines().filter(|p| !p.is_empty()).map(PathBuf::from).collect())
+        .unwrap_or_default()
+}
+
+/// Lines added or modified since `base`, keyed by repo-relative path (1-based line numbers)
+pub fn changed_lines(repo: &Path, base: &str) -> Option<HashMap<PathBuf, BTreeSet<usize>>> {
+    let range = format!("{}...HEAD", base);
+    let diff = run_git(repo, &["diff", "--unified=0", "--no-color", &range])?;
+    Some(parse_changed_lines(&diff))
+}
+
+/// Parse `git diff --unified=0` output into added line numbers per file
+fn parse_changed_lines(diff: &str) -> HashMap<PathBuf, BTreeSet<usize>> {
+    let mut changed: HashMap<PathBuf, BTreeSet<usize>> = HashMap::new();
+    let mut current: Option<PathBuf> = None;
+
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            current = path.strip_prefix("b/").map(PathBuf::from);
+        } else if let Some(hunk) = line.strip_prefix("@@ ") {
+            let Some(file) = current.as_ref() else {
+                continue;
+            };
+            // "@@ -a,b +c,d @@" — we want c (start) and d (count, default 1)
+            let Some(added) = hunk.split_whitespace().find(|p| p.starts_with('+')) else {
+                continue;
+            };
+            let mut parts = added[1..].split(',');
+            let start: usize = parts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+            let count: usize = parts.next().and_then(|s| s.parse().ok()).unwrap_or(1);
+            changed
+                .entry(file.clone())
+                .or_default()
+                .extend(start..start + count);
+        }
+    }
+
+    changed
+}
+
+/// Who last touched each of `lines` (1-based) in `file`, relative to `dir`.
+/// Lines that are not committed yet are left out.
+pub fn blame_lines(dir: &Path, file: &Path, lines: &BTreeSet<usize>) -> HashMap<usize, Blame> {
+    if lines.is_empty() {
+        return HashMap::new();
+    }
+    let ranges: Vec<String> = lines.iter().map(|l| format!("-L{},{}", l, l)).collect();
+    let file = file.to_string_lossy();
+    let mut args = vec!["blame", "--porcelain"];
+    args.extend(ranges.iter().map(String::as_str));
+    args.extend(["--", file.as_ref()]);
+
+    run_git(dir, &args)
+        .map(|out| parse_blame_porcelain(&out))
+        .unwrap_or_default()
+}
+
+/// Parse `git blame --porcelain` output into blame info per final line number
+fn parse_blame_porcelain(output: &str) -> HashMap<usize, Blame> {
+    // Commit details are only printed the first time a commit appears
+    let mut commits: HashMap<String, Blame> = HashMap::new();
+    let mut line_commits: Vec<(usize, String)> = Vec::new();
+    let mut current: Option<String> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            continue;
+        }
+        let mut parts = line.splitn(2, ' ');
+        let key = parts.next().unwrap_or("");
+        let value = parts.next().unwrap_or("");
+
+        // Header: "<40-hex sha> <orig line> <final line> [<group size>]"
+        if key.len() == 40 && key.chars().all(|c| c.is_ascii_hexdigit()) {
+            let final_line = value.split(' ').nth(1).and_then(|n| n.parse().ok());
+            if let Some(final_line) = final_line {
+                line_commits.push((final_line, key.to_string()));
+            }
+            commits.entry(key.to_string()).or_insert_with(|| Blame {
+                commit: key.to_string(),
+                author: String::new(),
+                date: String::new(),
+                summary: String::new(),
+            });
+            current = Some(key.to_string());
+            continue;
+        }
+
+        let Some(blame) = current.as_ref().and_then(|c| commits.get_mut(c)) else {
+            continue;
+        };
+        match key {
+            "author" => blame.author = value.to_string(),
+            "author-time" => {
+                blame.date = value
+                    .parse()
+                    .ok()
+                    .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default();
+            }
+            "summary" => blame.summary = value.to_string(),
+            _ => {}
+        }
+    }
+
+    line_commits
+        .into_iter()
+        // The all-zero commit marks lines that are not committed yet
+        .filter(|(_, sha)| sha.chars().any(|c| c != '0'))
+        .filter_map(|(line, sha)| commits.get(&sha).map(|b| (line, b.clone())))
+        .collect()
+}
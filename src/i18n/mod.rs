@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Locales with a message catalog, accepted by `--locale`
+pub const LOCALES: &[&str] = &["en", "es"];
+
+const EN: &str = include_str!("en.toml");
+const ES: &str = include_str!("es.toml");
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// Messages for one locale, keyed `section.name`, with English filling in
+/// any key the locale does not translate yet
+struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    fn load(locale: &str) -> Self {
+        let mut messages = flatten(EN);
+        if locale == "es" {
+            messages.extend(flatten(ES));
+        }
+        Catalog { messages }
+    }
+}
+
+/// `[section] name = "text"` tables as `section.name` → text
+fn flatten(source: &str) -> HashMap<String, String> {
+    let table: toml::Table = toml::from_str(source).expect("message catalogs are valid TOML");
+    let mut messages = HashMap::new();
+    for (section, entries) in table {
+        let Some(entries) = entries.as_table() else {
+            continue;
+        };
+        for (name, text) in entries {
+            if let Some(text) = text.as_str() {
+                messages.insert(format!("{}.{}", section, name), text.to_string());
+            }
+        }
+    }
+    messages
+}
+
+/// Apply `--locale`: "auto" picks the locale from the environment
+pub fn init(choice: &str) {
+    let locale = if choice == "auto" { detect() } else { choice.to_string() };
+    let _ = CATALOG.set(Catalog::load(&locale));
+}
+
+/// Locale from ANTY_LOCALE, then LC_ALL / LC_MESSAGES / LANG, e.g.
+/// `es_ES.UTF-8` → "es". English unless a catalog exists for it.
+fn detect() -> String {
+    let language = ["ANTY_LOCALE", "LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            value
+                .split(['_', '-', '.', '@'])
+                .next()
+                .unwrap_or_default()
+                .to_lowercase()
+        })
+        .unwrap_or_default();
+    if LOCALES.contains(&language.as_str()) {
+        language
+    } else {
+        "en".to_string()
+    }
+}
+
+/// The message for `key` in the current locale (the key itself if no
+/// catalog has it)
+pub fn text(key: &str) -> String {
+    CATALOG
+        .get_or_init(|| Catalog::load(&detect()))
+        .messages
+        .get(key)
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// The message for `key` with each `{name}` replaced by its value
+pub fn format(key: &str, args: &[(&str, String)]) -> String {
+    let mut message = text(key);
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), value);
+    }
+    message
+}
+
+/// `t!("wizard.proceed")` or `t!("terminal.found", count = n, parts = list)`
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::text($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+pub(crate) use t;
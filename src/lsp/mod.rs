@@ -0,0 +1,307 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{CodeActionRequest, Request as _};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOptions, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, CodeDescription, Diagnostic, DiagnosticSeverity, InitializeParams,
+    NumberOrString, Position, PublishDiagnosticsParams, Range, SaveOptions, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions, TextDocumentSyncSaveOptions,
+    TextEdit, Url, WorkspaceEdit,
+};
+use tracing::{debug, info, warn};
+
+use crate::agents::{ScannedFile, SecurityAgent};
+use crate::cli::ScanArgs;
+use crate::config::AntyConfig;
+use crate::engine::{self, reader::LanguageMap, selection::Selection};
+use crate::git;
+use crate::profile;
+use crate::report::finding::{portable_path, Finding, Severity};
+use crate::rules;
+
+/// Language server state: open documents plus the agents, selection, and
+/// excludes that apply to the workspace, the same a scan of it would use.
+struct Server {
+    root: Option<PathBuf>,
+    agents: Vec<Arc<dyn SecurityAgent>>,
+    selection: Selection,
+    /// Git-tracked files of the workspace, for confidence calibration
+    tracked: Option<HashSet<PathBuf>>,
+    exclude: GlobSet,
+    languages: LanguageMap,
+    documents: HashMap<Url, String>,
+    /// Findings last published for each open document, for code actions
+    findings: HashMap<Url, Vec<Finding>>,
+}
+
+/// Run the Anty language server over stdio until the client shuts it down.
+pub fn run() -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+            open_close: Some(true),
+            change: Some(TextDocumentSyncKind::FULL),
+            save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
+                include_text: Some(false),
+            })),
+            ..Default::default()
+        })),
+        code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+            code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+            ..Default::default()
+        })),
+        ..Default::default()
+    };
+
+    let init = connection.initialize(serde_json::to_value(capabilities)?)?;
+    let params: InitializeParams = serde_json::from_value(init)?;
+
+    #[allow(deprecated)]
+    let root = params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|f| f.uri.clone())
+        .or(params.root_uri)
+        .and_then(|uri| uri.to_file_path().ok());
+
+    let config = root.as_deref().and_then(AntyConfig::load);
+    let exclude = build_globset(config.as_ref().map(|c| c.scan.exclude.as_slice()).unwrap_or(&[]));
+
+    let languages = LanguageMap::new(config.as_ref().map(|c| &c.languages).unwrap_or(&BTreeMap::new()));
+
+    // Diagnostics are what `anty scan` of the workspace would report
+    let workspace = root.clone().unwrap_or_else(|| PathBuf::from("."));
+    let mut args = ScanArgs::for_path(&workspace);
+    profile::apply(&mut args)?;
+    let packs = match config {
+        Some(ref cfg) => rules::packs::load(&cfg.rules)?,
+        None => Vec::new(),
+    };
+    let agents = engine::agents_with_packs(&args, Some(&engine::load_agents(&args)?), &packs)?;
+    let selection = Selection::new(&args, config.as_ref(), &agents, &workspace)?;
+
+    let mut server = Server {
+        tracked: root.as_deref().and_then(git::tracked_files),
+        root,
+        agents,
+        selection,
+        exclude,
+        languages,
+        documents: HashMap::new(),
+        findings: HashMap::new(),
+    };
+    info!("Language server ready");
+
+    for msg in &connection.receiver {
+        match msg {
+            Message::Request(req) => {
+                if connection.handle_shutdown(&req)? {
+                    break;
+                }
+                server.handle_request(&connection, req)?;
+            }
+            Message::Notification(not) => server.handle_notification(&connection, not)?,
+            Message::Response(_) => {}
+        }
+    }
+
+    drop(connection);
+    io_threads.join().context("LSP I/O threads failed")?;
+    Ok(())
+}
+
+impl Server {
+    fn handle_request(&self, connection: &Connection, req: Request) -> Result<()> {
+        let response = match req.method.as_str() {
+            CodeActionRequest::METHOD => {
+                let params: CodeActionParams = serde_json::from_value(req.params)?;
+                Response::new_ok(req.id, self.code_actions(&params))
+            }
+            other => {
+                debug!("Ignoring request {}", other);
+                Response::new_err(req.id, ErrorCode::MethodNotFound as i32, format!("unsupported request {}", other))
+            }
+        };
+        connection.sender.send(Message::Response(response))?;
+        Ok(())
+    }
+
+    /// Quick fixes for the fixable findings on the requested lines: the
+    /// same edits `anty fix` makes
+    fn code_actions(&self, params: &CodeActionParams) -> Vec<CodeActionOrCommand> {
+        let uri = &params.text_document.uri;
+        let (Some(text), Some(findings)) = (self.documents.get(uri), self.findings.get(uri)) else {
+            return Vec::new();
+        };
+        let lines = params.range.start.line as usize..=params.range.end.line as usize;
+        findings
+            .iter()
+            .filter_map(|f| Some((f, f.fix.as_ref()?)))
+            .filter(|(_, fix)| lines.contains(&fix.line.saturating_sub(1)))
+            .filter_map(|(f, fix)| {
+                let line = text.lines().nth(fix.line - 1)?;
+                let position = |column: usize| {
+                    let character = line.get(..column - 1)?.encode_utf16().count();
+                    Some(Position::new((fix.line - 1) as u32, character as u32))
+                };
+                let edit = TextEdit {
+                    range: Range { start: position(fix.start_column)?, end: position(fix.end_column)? },
+                    new_text: fix.replacement.clone(),
+                };
+                Some(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Anty: {}", fix.description),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![to_diagnostic(f, text)]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(true),
+                    ..Default::default()
+                }))
+            })
+            .collect()
+    }
+
+    fn handle_notification(&mut self, connection: &Connection, not: Notification) -> Result<()> {
+        match not.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: lsp_types::DidOpenTextDocumentParams = serde_json::from_value(not.params)?;
+                let uri = params.text_document.uri;
+                self.documents.insert(uri.clone(), params.text_document.text);
+                self.publish(connection, &uri)?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: lsp_types::DidChangeTextDocumentParams = serde_json::from_value(not.params)?;
+                // Full sync: the last change carries the whole document
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    let uri = params.text_document.uri;
+                    self.documents.insert(uri.clone(), change.text);
+                    self.publish(connection, &uri)?;
+                }
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: lsp_types::DidSaveTextDocumentParams = serde_json::from_value(not.params)?;
+                self.publish(connection, &params.text_document.uri)?;
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: lsp_types::DidCloseTextDocumentParams = serde_json::from_value(not.params)?;
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                self.findings.remove(&uri);
+                send_diagnostics(connection, uri, Vec::new())?;
+            }
+            other => debug!("Ignoring notification {}", other),
+        }
+        Ok(())
+    }
+
+    /// Scan the stored contents of `uri` and publish its diagnostics
+    fn publish(&mut self, connection: &Connection, uri: &Url) -> Result<()> {
+        let Some(text) = self.documents.get(uri) else {
+            return Ok(());
+        };
+        let Ok(abs_path) = uri.to_file_path() else {
+            warn!("Skipping non-file URI {}", uri);
+            return Ok(());
+        };
+
+        let rel_path = self.relative(&abs_path);
+        if self.exclude.is_match(&rel_path) {
+            self.findings.remove(uri);
+            return send_diagnostics(connection, uri.clone(), Vec::new());
+        }
+
+        let file = ScannedFile {
+            language: self.languages.detect(&abs_path, &rel_path),
+            rel_path,
+            abs_path,
+            content: text.clone(),
+            line_offset: 0,
+            deadline: None,
+        };
+        let findings = engine::scan_single(&self.agents, &self.selection, self.tracked.as_ref(), &file);
+        let diagnostics = findings.iter().map(|f| to_diagnostic(f, text)).collect();
+        self.findings.insert(uri.clone(), findings);
+
+        send_diagnostics(connection, uri.clone(), diagnostics)
+    }
+
+    fn relative(&self, path: &Path) -> PathBuf {
+        portable_path(
+            self.root
+                .as_deref()
+                .and_then(|root| path.strip_prefix(root).ok())
+                .unwrap_or(path),
+        )
+    }
+}
+
+fn send_diagnostics(connection: &Connection, uri: Url, diagnostics: Vec<Diagnostic>) -> Result<()> {
+    let params = PublishDiagnosticsParams {
+        uri,
+        diagnostics,
+        version: None,
+    };
+    connection.sender.send(Message::Notification(Notification::new(
+        PublishDiagnostics::METHOD.to_string(),
+        params,
+    )))?;
+    Ok(())
+}
+
+/// Convert a finding into an LSP diagnostic spanning its lines
+fn to_diagnostic(finding: &Finding, text: &str) -> Diagnostic {
+    let start_line = finding.line_start.saturating_sub(1);
+    let end_line = finding.line_end.saturating_sub(1);
+    let end_char = text
+        .lines()
+        .nth(end_line)
+        .map(|l| l.encode_utf16().count())
+        .unwrap_or(0);
+
+    Diagnostic {
+        range: Range {
+            start: Position::new(start_line as u32, 0),
+            end: Position::new(end_line as u32, end_char as u32),
+        },
+        severity: Some(match finding.severity {
+            Severity::Critical | Severity::High => DiagnosticSeverity::ERROR,
+            Severity::Medium => DiagnosticSeverity::WARNING,
+            Severity::Low => DiagnosticSeverity::INFORMATION,
+        }),
+        code: Some(NumberOrString::String(finding.rule_id.clone())),
+        code_description: finding
+            .docs_url
+            .as_deref()
+            .and_then(|url| Url::parse(url).ok())
+            .map(|href| CodeDescription { href }),
+        source: Some("anty".to_string()),
+        message: format!("{}\n{}", finding.title, finding.recommendation),
+        ..Default::default()
+    }
+}
+
+fn build_globset(patterns: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        match Glob::new(pattern) {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(e) => warn!("Invalid exclude pattern '{}': {}", pattern, e),
+        }
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
@@ -1,55 +1,149 @@
+mod baseline;
 mod cli;
+mod compliance;
 mod engine;
+mod fix;
+mod batch;
 mod agents;
 mod report;
 mod rules;
 mod config;
+mod ci;
+mod git;
+mod lsp;
+mod owners;
+mod policy;
+mod profile;
+mod triage;
+mod i18n;
+
+use std::io::IsTerminal;
 
 use anyhow::Result;
-use clap::Parser;
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches};
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
-use cli::Cli;
+use cli::{exit_code, Cli};
 use engine::Scanner;
 
-fn main() -> Result<()> {
-    let raw_args: Vec<String> = std::env::args().collect();
+fn main() {
+    let code = match run() {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            exit_code::ERROR
+        }
+    };
+    std::process::exit(code);
+}
+
+fn run() -> Result<i32> {
+    let mut raw_args: Vec<String> = std::env::args().collect();
+
+    // Prompts need a terminal on both ends; without one (scripts, CI, some
+    // double-click launchers) fall back to a plain scan instead of hanging.
+    let interactive = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
 
     // ── Wizard / drag-drop detection (before clap parsing) ──────────
     if raw_args.len() == 1 {
-        // No arguments at all → interactive wizard
-        return cli::wizard::run_wizard();
+        if interactive {
+            // No arguments at all → interactive wizard
+            cli::wizard::run_wizard()?;
+            return Ok(exit_code::CLEAN);
+        }
+        eprintln!("No terminal detected; scanning the current directory. Run `anty --help` for usage.");
+        raw_args.extend(["scan".to_string(), ".".to_string()]);
     }
 
     if raw_args.len() == 2 {
         let candidate = std::path::Path::new(&raw_args[1]);
         // If the single argument is an existing directory AND not a known
         // subcommand, treat it as a drag-and-drop folder.
-        let known_commands = ["scan", "init", "list-rules", "help", "-h", "--help", "-V", "--version", "-v", "--verbose", "-q", "--quiet"];
+        let known_commands = ["scan", "fix", "init", "list-rules", "rules", "test-rules", "explain", "mask", "badge", "triage", "batch", "last", "merge", "update-rules", "lsp", "ci", "validate-config", "schema", "verify-report", "help", "-h", "--help", "-V", "--version", "-v", "--verbose", "-q", "--quiet"];
         if candidate.is_dir() && !known_commands.contains(&raw_args[1].as_str()) {
-            return cli::wizard::run_drag_drop(candidate);
+            if interactive {
+                cli::wizard::run_drag_drop(candidate)?;
+                return Ok(exit_code::CLEAN);
+            }
+            raw_args.insert(1, "scan".to_string());
         }
     }
 
     // ── Normal clap flow ────────────────────────────────────────────
-    // Parse CLI arguments
-    let cli = Cli::parse();
+    // Parse CLI arguments (usage errors map to our own exit code, not clap's 2)
+    let parsed = Cli::command()
+        .try_get_matches_from(&raw_args)
+        .and_then(|matches| Cli::from_arg_matches(&matches).map(|cli| (cli, matches)));
+    let (mut cli, matches) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let _ = e.print();
+            return Ok(if e.use_stderr() { exit_code::ERROR } else { exit_code::CLEAN });
+        }
+    };
+
+    // Resolve --profile into plain settings up front, so exit codes and CI
+    // annotations see the same --fail-on as the scan. This runs before
+    // logging starts, so the config is only reported once, by the scan.
+    if let Some(args) = cli.command.scan_args_mut() {
+        profile::apply(args)?;
+    }
+
+    cli::color::init(&cli.color);
+    i18n::init(&cli.locale);
 
     // Initialize logging
-    let filter = if cli.verbose {
-        EnvFilter::new("anty=debug")
+    let level = if cli.verbose {
+        "debug"
     } else if cli.quiet {
-        EnvFilter::new("anty=error")
+        "error"
     } else {
-        EnvFilter::new("anty=info")
+        "info"
     };
 
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_target(false)
-        .without_time()
-        .init();
+    if cli.log_format == "json" {
+        // JSON lines always go to stderr so stdout stays parseable. Each
+        // event is formatted in full and written with a single call, so
+        // lines from parallel scan threads never interleave.
+        tracing_subscriber::fmt()
+            .json()
+            .flatten_event(true)
+            .with_current_span(false)
+            .with_span_list(false)
+            .with_env_filter(EnvFilter::new(format!("anty={}", level)))
+            .with_writer(std::io::stderr)
+            .init();
+    } else {
+        // Telemetry events only carry fields for the JSON format
+        let filter = EnvFilter::new(format!("anty={},{}=off", level, engine::TELEMETRY));
+
+        // The language server owns stdout for the protocol, `mask` output
+        // is read by the CI runner, and `schema`, `merge`, and
+        // --deterministic output is meant to be redirected to a file, so
+        // their logs go to stderr
+        let to_stderr = matches!(
+            cli.command,
+            cli::Commands::Lsp
+                | cli::Commands::Mask { .. }
+                | cli::Commands::Schema
+                | cli::Commands::Merge { out: None, .. }
+        ) || matches!(&cli.command, cli::Commands::Scan(args) if args.deterministic);
+        tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .with_target(false)
+            .without_time()
+            .with_ansi(to_stderr || cli::color::enabled())
+            .with_writer(move || -> Box<dyn std::io::Write> {
+                if to_stderr {
+                    Box::new(std::io::stderr())
+                } else {
+                    Box::new(std::io::stdout())
+                }
+            })
+            .init();
+    }
 
     info!("Anty v{}", env!("CARGO_PKG_VERSION"));
 
@@ -58,42 +152,92 @@ fn main() -> Result<()> {
             let scanner = Scanner::new(&cli, args)?;
             let report = scanner.run()?;
 
-            // Output the report
-            match args.format.as_str() {
-                "json" => {
-                    let output = report::json::render(&report)?;
-                    if let Some(ref path) = args.out {
-                        std::fs::write(path, &output)?;
-                        info!("Report written to {}", path.display());
-                    } else {
-                        println!("{}", output);
-                    }
+            report::emit(&report, args, cli.verbose)?;
+            // --fail-fast and --timeout reports cover part of the tree; keep
+            // the last full one
+            if !report.stopped_early && report.partial.is_none() {
+                report::cache::save(&report);
+            }
+            return Ok(exit_code::for_report(
+                &report,
+                args.fail_on.as_deref(),
+                args.fail_on_score,
+                args.fail_on_tests.as_deref(),
+            ));
+        }
+        cli::Commands::Fix(args) => return fix::run(&cli, args),
+        cli::Commands::Batch(args) => return batch::run(&cli, args),
+        cli::Commands::Init { template, interactive } => {
+            config::init::run(template.as_deref(), *interactive)?;
+        }
+        cli::Commands::Schema => {
+            println!("{}", report::json::schema()?);
+        }
+        cli::Commands::VerifyReport { report, key, signature } => {
+            let signature = signature
+                .clone()
+                .unwrap_or_else(|| report::signature::signature_path(report));
+            return match report::signature::verify(report, &signature, key) {
+                Ok(()) => {
+                    println!("{}{} matches its signature", cli::color::icon("✅ ", ""), report.display());
+                    Ok(exit_code::CLEAN)
                 }
-                _ => {
-                    report::terminal::render(&report);
-                    if let Some(ref path) = args.out {
-                        let json_output = report::json::render(&report)?;
-                        std::fs::write(path, &json_output)?;
-                        info!("JSON report also written to {}", path.display());
-                    }
+                Err(e) => {
+                    println!("{} {}: {:#}", cli::color::icon("✗", "x"), report.display(), e);
+                    Ok(exit_code::ERROR)
                 }
+            };
+        }
+        cli::Commands::ValidateConfig { path } => return config::validate::run(path),
+        cli::Commands::ListRules => {
+            rules::list_rules();
+        }
+        cli::Commands::Ci(args) => match &args.command {
+            Some(cli::CiCommand::Github(gh)) => return ci::github::run(&cli, gh),
+            None => {
+                // `anty ci` only switches to compact output when --format was not given
+                let format_given = matches
+                    .subcommand_matches("ci")
+                    .is_some_and(|m| m.value_source("format") == Some(ValueSource::CommandLine));
+                return ci::run(&cli, &args.scan, format_given);
             }
-
-            // Exit code based on findings
-            if let Some(ref fail_on) = args.fail_on {
-                let threshold = report::finding::Severity::from_str(fail_on);
-                if report.has_findings_at_or_above(threshold) {
-                    std::process::exit(1);
-                }
+        },
+        cli::Commands::Lsp => {
+            lsp::run()?;
+        }
+        cli::Commands::Rules { command } => match command {
+            cli::RulesCommand::Export { path } => rules::snapshot::export(path)?,
+            cli::RulesCommand::Import { path } => rules::snapshot::import(path)?,
+            cli::RulesCommand::Stats { path, report, top, format } => {
+                return rules::stats::run(path.as_deref(), report.as_deref(), *top, format)
             }
+        },
+        cli::Commands::Explain { id, report, context } => {
+            return report::explain::run(id, report.as_deref(), *context)
         }
-        cli::Commands::Init => {
-            config::init_config()?;
+        cli::Commands::Mask { report, format } => return report::mask::run(report.as_deref(), format.as_deref()),
+        cli::Commands::Badge { report, out, endpoint } => {
+            return report::badge::run(report.as_deref(), out, endpoint.as_deref())
         }
-        cli::Commands::ListRules => {
-            rules::list_rules();
+        cli::Commands::Triage { command } => match command {
+            cli::TriageCommand::Set { id, status, assignee, note, report } => {
+                return triage::set(id, status.as_deref(), assignee.as_deref(), note.as_deref(), report.as_deref())
+            }
+        },
+        cli::Commands::Last { path, format, group_by, summary_only } => {
+            let report = report::cache::load(path.as_deref())?;
+            let opts = report::terminal::RenderOptions {
+                group_by: group_by.as_deref().and_then(report::terminal::GroupBy::from_str),
+                summary_only: *summary_only,
+                show_timings: cli.verbose,
+            };
+            report::print(&report, format, &opts)?;
+            return Ok(exit_code::for_report(&report, None, None, None));
         }
+        cli::Commands::Merge { reports, out } => return report::merger::run(reports, out.as_deref()),
+        cli::Commands::UpdateRules { url, check } => rules::bundle::update(url.as_deref(), *check)?,
+        cli::Commands::TestRules { fixtures } => return rules::selftest::run(fixtures.as_deref()),
     }
 
-    Ok(())
+    Ok(exit_code::CLEAN)
 }
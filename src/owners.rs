@@ -0,0 +1,96 @@
+use std::path::{Path, PathBuf};
+
+use globset::{GlobBuilder, GlobMatcher};
+use tracing::{debug, info, warn};
+
+/// Where GitHub and GitLab look for CODEOWNERS, in order
+const CODEOWNERS_PATHS: &[&str] = &["CODEOWNERS", ".github/CODEOWNERS", "docs/CODEOWNERS", ".gitlab/CODEOWNERS"];
+
+/// One CODEOWNERS line: a path pattern and its owners
+struct Rule {
+    matchers: Vec<GlobMatcher>,
+    owners: Vec<String>,
+}
+
+/// Parsed CODEOWNERS file. Later rules take precedence, as on GitHub.
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+    /// Scan root relative to the repository root, prepended to finding paths
+    prefix: PathBuf,
+}
+
+impl CodeOwners {
+    /// Load CODEOWNERS from the repository containing `scan_root`, if any
+    pub fn load(scan_root: &Path) -> Option<Self> {
+        let dir = if scan_root.is_file() {
+            scan_root.parent()?
+        } else {
+            scan_root
+        };
+        let repo_root = crate::git::repo_root(dir).unwrap_or_else(|| dir.to_path_buf());
+
+        let path = CODEOWNERS_PATHS
+            .iter()
+            .map(|p| repo_root.join(p))
+            .find(|p| p.is_file())?;
+        let content = std::fs::read_to_string(&path).ok()?;
+
+        let rules: Vec<Rule> = content.lines().filter_map(parse_line).collect();
+        info!("Loaded {} CODEOWNERS rules from {}", rules.len(), path.display());
+
+        let prefix = dir
+            .canonicalize()
+            .ok()
+            .zip(repo_root.canonicalize().ok())
+            .and_then(|(d, r)| d.strip_prefix(r).ok().map(Path::to_path_buf))
+            .unwrap_or_default();
+
+        Some(CodeOwners { rules, prefix })
+    }
+
+    /// Owners of a path relative to the scan root (empty if unowned)
+    pub fn owners_for(&self, rel_path: &Path) -> Vec<String> {
+        let path = self.prefix.join(rel_path);
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matchers.iter().any(|m| m.is_match(&path)))
+            .map(|rule| rule.owners.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Parse one CODEOWNERS line into a rule, skipping comments and blanks
+fn parse_line(line: &str) -> Option<Rule> {
+    let line = line.split(" #").next().unwrap_or(line).trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let mut parts = line.split_whitespace();
+    let pattern = parts.next()?;
+    let owners: Vec<String> = parts.map(str::to_string).collect();
+
+    // gitignore-style: a leading or inner slash anchors the pattern to the
+    // repo root, otherwise it matches at any depth; a match on a directory
+    // covers everything below it.
+    let anchored = pattern.trim_end_matches('/').contains('/');
+    let pattern = pattern.trim_start_matches('/').trim_end_matches('/');
+    let base = if anchored {
+        pattern.to_string()
+    } else {
+        format!("**/{}", pattern)
+    };
+
+    let mut matchers = Vec::new();
+    for glob in [base.clone(), format!("{}/**", base)] {
+        match GlobBuilder::new(&glob).literal_separator(true).build() {
+            Ok(g) => matchers.push(g.compile_matcher()),
+            Err(e) => {
+                warn!("Invalid CODEOWNERS pattern '{}': {}", pattern, e);
+                return None;
+            }
+        }
+    }
+    debug!("CODEOWNERS: {} -> {:?}", base, owners);
+    Some(Rule { matchers, owners })
+}
@@ -0,0 +1,128 @@
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::report::finding::{Finding, PolicyViolation, Severity};
+
+/// Organisation policy evaluated after a scan (`[policy]` in .anty.toml)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Policy {
+    /// Agents that must run; excluding one with --agents is a violation
+    #[serde(default)]
+    pub require_agents: Vec<String>,
+
+    /// Limits on findings, optionally scoped to paths
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+}
+
+/// A cap on the number of findings at or above a severity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyRule {
+    /// Shown in the report instead of the generated description
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Globs relative to the scan root (empty = whole scan)
+    #[serde(default)]
+    pub paths: Vec<String>,
+
+    /// Count findings at or above this severity (default: all)
+    #[serde(default)]
+    pub severity: Option<String>,
+
+    /// Most findings allowed (default 0)
+    #[serde(default)]
+    pub max: usize,
+}
+
+impl Policy {
+    /// Add another policy's requirements (from a rule pack) to this one
+    pub fn extend(&mut self, other: Policy) {
+        self.require_agents.extend(other.require_agents);
+        self.rules.extend(other.rules);
+    }
+
+    /// Check findings (before any caps) and the agents that ran against the policy
+    pub fn evaluate(&self, findings: &[Finding], agents_run: &[&str]) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+
+        for agent in &self.require_agents {
+            if !agents_run.contains(&agent.as_str()) {
+                violations.push(PolicyViolation {
+                    policy: format!("agent {} must run", agent),
+                    message: format!("the {} agent was not run", agent),
+                    findings: Vec::new(),
+                });
+            }
+        }
+
+        for rule in &self.rules {
+            let threshold = rule.severity.as_deref().map(Severity::from_str);
+            let paths = match rule.path_matcher() {
+                Ok(paths) => paths,
+                Err(message) => {
+                    // A broken policy must not pass silently
+                    violations.push(PolicyViolation {
+                        policy: rule.describe(),
+                        message,
+                        findings: Vec::new(),
+                    });
+                    continue;
+                }
+            };
+            let matched: Vec<&Finding> = findings
+                .iter()
+                .filter(|f| threshold.is_none_or(|t| f.severity >= t))
+                .filter(|f| paths.as_ref().is_none_or(|p| p.is_match(&f.file_path)))
+                .collect();
+            if matched.len() > rule.max {
+                violations.push(PolicyViolation {
+                    policy: rule.describe(),
+                    message: format!("{} findings, at most {} allowed", matched.len(), rule.max),
+                    findings: matched.iter().map(|f| f.id.clone()).collect(),
+                });
+            }
+        }
+        violations
+    }
+}
+
+impl PolicyRule {
+    /// Human-readable form, e.g. "max 0 CRITICAL+ in src/payments/**"
+    fn describe(&self) -> String {
+        if let Some(ref name) = self.name {
+            return name.clone();
+        }
+        let severity = match self.severity {
+            Some(ref s) => format!("{}+", Severity::from_str(s).as_str()),
+            None => "findings".to_string(),
+        };
+        let scope = if self.paths.is_empty() {
+            "in total".to_string()
+        } else {
+            format!("in {}", self.paths.join(", "))
+        };
+        format!("max {} {} {}", self.max, severity, scope)
+    }
+
+    /// Compiled `paths`, or `None` when the rule covers the whole scan
+    pub fn path_matcher(&self) -> Result<Option<GlobSet>, String> {
+        if self.paths.is_empty() {
+            return Ok(None);
+        }
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.paths {
+            match GlobBuilder::new(pattern).literal_separator(true).build() {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(e) => {
+                    warn!("Invalid policy path '{}': {}", pattern, e);
+                    return Err(format!("invalid path pattern '{}': {}", pattern, e));
+                }
+            }
+        }
+        builder.build().map(Some).map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ScanArgs;
+use crate::config::AntyConfig;
+use crate::report::finding::{Confidence, Severity};
+
+/// Names accepted by `--profile` and `[profile] name`
+pub const NAMES: &[&str] = &["strict", "default", "lenient"];
+
+/// Profile chosen in config (`[profile]` in .anty.toml)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileConfig {
+    /// Preset to use when --profile is not given
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// A named preset of scan settings
+pub struct Profile {
+    pub name: &'static str,
+    pub min_severity: Option<Severity>,
+    pub min_confidence: Option<Confidence>,
+    /// Rule tags to run (empty = all rules)
+    pub tags: &'static [&'static str],
+    /// 0 = unlimited
+    pub max_findings: usize,
+    pub fail_on: Option<Severity>,
+}
+
+const PROFILES: &[Profile] = &[
+    // Release gates: every rule, every finding, and any finding fails
+    Profile {
+        name: "strict",
+        min_severity: None,
+        min_confidence: None,
+        tags: &[],
+        max_findings: 0,
+        fail_on: Some(Severity::Low),
+    },
+    // CI: the built-in defaults, failing on HIGH like `anty ci`
+    Profile {
+        name: "default",
+        min_severity: None,
+        min_confidence: None,
+        tags: &[],
+        max_findings: 1000,
+        fail_on: Some(Severity::High),
+    },
+    // Pre-commit: quiet, likely-real findings only
+    Profile {
+        name: "lenient",
+        min_severity: Some(Severity::Medium),
+        min_confidence: Some(Confidence::Medium),
+        tags: &["low-noise"],
+        max_findings: 50,
+        fail_on: Some(Severity::Critical),
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Profile> {
+    PROFILES.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
+/// Fill in the settings of the selected profile (--profile, else
+/// `[profile] name`) that neither flags nor config set. Explicit flags and
+/// config values always win over the preset.
+pub fn apply(args: &mut ScanArgs) -> Result<()> {
+    let config = if args.no_config {
+        AntyConfig::load_user()
+    } else {
+        AntyConfig::load(&std::fs::canonicalize(&args.path).unwrap_or_else(|_| args.path.clone()))
+    };
+    let Some(name) = args
+        .profile
+        .clone()
+        .or_else(|| config.as_ref().and_then(|c| c.profile.name.clone()))
+    else {
+        return Ok(());
+    };
+    let profile = find(&name)
+        .with_context(|| format!("Unknown profile '{}' in [profile] (expected {})", name, NAMES.join(", ")))?;
+    apply_profile(profile, args, config.as_ref());
+    Ok(())
+}
+
+fn apply_profile(profile: &Profile, args: &mut ScanArgs, config: Option<&AntyConfig>) {
+    let configured_min_severity = config.is_some_and(|c| c.output.min_severity.is_some());
+    if args.min_severity.is_none() && !configured_min_severity {
+        args.min_severity = profile.min_severity.map(|s| s.as_str().to_string());
+    }
+    if args.min_confidence.is_none() {
+        args.min_confidence = profile.min_confidence.map(|c| c.as_str().to_string());
+    }
+    let configured_tags = config.is_some_and(|c| !c.scan.tags.is_empty() || !c.scan.exclude_tags.is_empty());
+    if args.tags.is_empty() && args.exclude_tags.is_empty() && !configured_tags {
+        args.tags = profile.tags.iter().map(|t| t.to_string()).collect();
+    }
+    if args.max_findings.is_none() {
+        args.max_findings = Some(profile.max_findings);
+    }
+    if args.fail_on.is_none() {
+        args.fail_on = profile.fail_on.map(|s| s.as_str().to_string());
+    }
+}
+
@@ -0,0 +1,113 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tracing::info;
+
+use crate::cli::exit_code;
+use crate::report::cache;
+use crate::report::finding::ScanReport;
+
+/// Left-hand text of the badge
+const LABEL: &str = "security";
+
+/// shields.io endpoint badge (https://shields.io/badges/endpoint-badge)
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Endpoint {
+    schema_version: u32,
+    label: &'static str,
+    message: String,
+    color: &'static str,
+}
+
+/// `anty badge`: write the report's status as a shields-style SVG to `out`
+/// and as an endpoint JSON for shields.io to `endpoint` (by default next to
+/// the SVG, with a `.json` extension)
+pub fn run(report_path: Option<&Path>, out: &Path, endpoint: Option<&Path>) -> Result<i32> {
+    let report = match report_path {
+        Some(path) => cache::read(path)?,
+        None => cache::load(None)?,
+    };
+    let (message, (color, hex)) = status(&report);
+
+    std::fs::write(out, svg(&message, hex)).with_context(|| format!("Could not write {}", out.display()))?;
+    info!("Badge written to {}", out.display());
+
+    let endpoint_path = endpoint.map_or_else(|| out.with_extension("json"), Path::to_path_buf);
+    let json = serde_json::to_string_pretty(&Endpoint {
+        schema_version: 1,
+        label: LABEL,
+        message,
+        color,
+    })?;
+    std::fs::write(&endpoint_path, json + "\n")
+        .with_context(|| format!("Could not write {}", endpoint_path.display()))?;
+    info!("shields.io endpoint written to {}", endpoint_path.display());
+    Ok(exit_code::CLEAN)
+}
+
+/// Badge message and its color, as a shields.io name and its hex value
+fn status(report: &ScanReport) -> (String, (&'static str, &'static str)) {
+    let summary = &report.summary;
+    let message = format!("{} critical / {} high", summary.critical, summary.high);
+    let color = if summary.critical > 0 {
+        ("red", "#e05d44")
+    } else if summary.high > 0 {
+        ("orange", "#fe7d37")
+    } else if summary.medium + summary.low > 0 {
+        ("yellow", "#dfb317")
+    } else {
+        ("brightgreen", "#4c1")
+    };
+    (message, color)
+}
+
+/// A flat badge in the shields.io layout: grey label, colored message
+fn svg(message: &str, color: &str) -> String {
+    let label_width = text_width(LABEL) + 10;
+    let message_width = text_width(message) + 10;
+    let width = label_width + message_width;
+    // Text is drawn at 10x scale for sub-pixel positioning, as shields does
+    let label_x = label_width * 5;
+    let message_x = label_width * 10 + message_width * 5;
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{LABEL}: {message}">
+  <title>{LABEL}: {message}</title>
+  <linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+  <clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>
+    <rect width="{width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" text-rendering="geometricPrecision" font-size="110">
+    <text x="{label_x}" y="150" fill="#010101" fill-opacity=".3" transform="scale(.1)" textLength="{label_text}">{LABEL}</text>
+    <text x="{label_x}" y="140" transform="scale(.1)" textLength="{label_text}">{LABEL}</text>
+    <text x="{message_x}" y="150" fill="#010101" fill-opacity=".3" transform="scale(.1)" textLength="{message_text}">{message}</text>
+    <text x="{message_x}" y="140" transform="scale(.1)" textLength="{message_text}">{message}</text>
+  </g>
+</svg>
+"##,
+        label_text = (label_width - 10) * 10,
+        message_text = (message_width - 10) * 10,
+    )
+}
+
+/// Approximate width in pixels of `text` in 11px Verdana
+fn text_width(text: &str) -> usize {
+    let tenths: usize = text
+        .chars()
+        .map(|c| match c {
+            ' ' => 39,
+            '/' => 49,
+            'i' | 'l' | 'j' => 30,
+            'f' | 't' | 'r' => 47,
+            'm' | 'w' => 106,
+            c if c.is_ascii_digit() => 70,
+            c if c.is_ascii_uppercase() => 75,
+            _ => 68,
+        })
+        .sum();
+    tenths.div_ceil(10)
+}
@@ -0,0 +1,79 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use crate::config::{self, AntyConfig};
+use crate::report::finding::ScanReport;
+
+/// Directory holding the most recent report of every scanned path
+pub fn reports_dir() -> Option<PathBuf> {
+    config::anty_home().map(|home| home.join("reports"))
+}
+
+/// Cache file of the last scan of `scan_path`, keyed by a hash of its
+/// absolute path
+pub fn report_path(scan_path: &Path) -> Option<PathBuf> {
+    let root = std::fs::canonicalize(scan_path).unwrap_or_else(|_| scan_path.to_path_buf());
+    let hex = format!("{:x}", Sha256::digest(root.to_string_lossy().as_bytes()));
+    reports_dir().map(|dir| dir.join(format!("{}.json", &hex[..16])))
+}
+
+/// Keep `report` as the last scan of its path. The scan path is stored
+/// absolute so the report can be used from any directory. Failing to
+/// write the copy never fails the scan.
+pub fn save(report: &ScanReport) {
+    let Some(path) = report_path(&report.scan_path) else {
+        return;
+    };
+    let mut report = report.clone();
+    if let Ok(root) = std::fs::canonicalize(&report.scan_path) {
+        report.scan_path = root;
+    }
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&path, serde_json::to_vec(&report)?));
+    if let Err(e) = result {
+        debug!("Could not cache the report in {}: {}", path.display(), e);
+    }
+}
+
+/// The cached report to use: the last scan of `scan_path` if given;
+/// otherwise the last scan of the current directory, falling back to the
+/// most recent scan of any path
+pub fn load(scan_path: Option<&Path>) -> Result<ScanReport> {
+    let path = match scan_path {
+        Some(scan_path) => report_path(scan_path)
+            .filter(|p| p.is_file())
+            .with_context(|| format!("No cached scan of {}. Scan it with `anty scan` first", scan_path.display()))?,
+        None => report_path(Path::new("."))
+            .filter(|p| p.is_file())
+            .or_else(most_recent)
+            .context("No previous scan found. Run `anty scan` first")?,
+    };
+    read(&path)
+}
+
+/// Read a JSON report, from the cache or written with `--out`. The editor
+/// link template is a display setting left out of reports, so it is taken
+/// from the current config.
+pub fn read(path: &Path) -> Result<ScanReport> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Could not read {}", path.display()))?;
+    let mut report: ScanReport = serde_json::from_str(&raw)
+        .with_context(|| format!("{} is not an Anty JSON report", path.display()))?;
+    report.editor_url_template = AntyConfig::load(&report.scan_path)
+        .and_then(|c| c.output.editor_url_template);
+    Ok(report)
+}
+
+fn most_recent() -> Option<PathBuf> {
+    std::fs::read_dir(reports_dir()?)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|e| e == "json"))
+        .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .map(|entry| entry.path())
+}
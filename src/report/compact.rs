@@ -0,0 +1,19 @@
+use crate::report::finding::ScanReport;
+
+/// Render one finding per line as `path:line:col: SEVERITY RULE-ID title`,
+/// the grep-style format editors and CI problem matchers understand.
+pub fn render(report: &ScanReport) -> String {
+    let mut out = String::new();
+    for f in &report.findings {
+        out.push_str(&format!(
+            "{}:{}:{}: {} {} {}\n",
+            f.file_path.display(),
+            f.line_start,
+            f.column.max(1),
+            f.severity,
+            f.rule_id,
+            f.title
+        ));
+    }
+    out
+}
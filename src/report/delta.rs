@@ -0,0 +1,48 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::report::finding::{Finding, ScanReport};
+
+/// What changed between two reports of the same path
+pub struct Delta<'a> {
+    /// Findings that were not in the previous report
+    pub new: Vec<&'a Finding>,
+    /// Findings of the previous report that are gone
+    pub resolved: Vec<&'a Finding>,
+    /// Findings present in both
+    pub unchanged: usize,
+}
+
+/// Compare `current` with `previous`. A finding is the same one if it has
+/// the same ID, or the same rule, file, and evidence (so findings that only
+/// moved to another line are not reported as new).
+pub fn compare<'a>(previous: &'a ScanReport, current: &'a ScanReport) -> Delta<'a> {
+    let ids = |report: &'a ScanReport| -> HashSet<&'a str> {
+        report.findings.iter().map(|f| f.id.as_str()).collect()
+    };
+    let contents = |report: &'a ScanReport| -> HashSet<(&'a str, &'a Path, &'a str)> {
+        report.findings.iter().map(content_key).collect()
+    };
+    let (previous_ids, previous_contents) = (ids(previous), contents(previous));
+    let (current_ids, current_contents) = (ids(current), contents(current));
+
+    let new: Vec<&Finding> = current
+        .findings
+        .iter()
+        .filter(|f| !previous_ids.contains(f.id.as_str()) && !previous_contents.contains(&content_key(f)))
+        .collect();
+    let resolved = previous
+        .findings
+        .iter()
+        .filter(|f| !current_ids.contains(f.id.as_str()) && !current_contents.contains(&content_key(f)))
+        .collect();
+    Delta {
+        unchanged: current.findings.len() - new.len(),
+        new,
+        resolved,
+    }
+}
+
+fn content_key(finding: &Finding) -> (&str, &Path, &str) {
+    (finding.rule_id.as_str(), finding.file_path.as_path(), finding.evidence.trim())
+}
@@ -0,0 +1,144 @@
+use std::path::Path;
+
+use anstream::println;
+use anyhow::{bail, Result};
+use owo_colors::OwoColorize;
+
+use crate::cli::{color, exit_code};
+use crate::report::cache;
+use crate::report::finding::{Finding, ScanReport};
+use crate::report::terminal::{self, Links};
+use crate::rules::selftest;
+
+/// `anty explain`: print one finding in full with the source lines around
+/// it, the rule's examples, and its documentation. `id` may be a unique
+/// prefix, with or without the `ANTY-` part. Without `report_path`, the
+/// cached report of the current directory (or the latest scan) is used.
+pub fn run(id: &str, report_path: Option<&Path>, context: usize) -> Result<i32> {
+    let report = match report_path {
+        Some(path) => cache::read(path)?,
+        None => cache::load(None)?,
+    };
+
+    let finding = match find(&report.findings, id) {
+        Ok(finding) => finding,
+        Err(e) => {
+            println!("{} {}", color::icon("✗", "x").red().bold(), e);
+            return Ok(exit_code::ERROR);
+        }
+    };
+
+    println!();
+    terminal::render_finding(finding, &Links::for_report(&report));
+
+    section("Description");
+    println!("  {}", finding.description);
+    if let Some(ref cwe) = finding.cwe_id {
+        println!("  {}", cwe.dimmed());
+    }
+
+    section("Source");
+    render_source(&report, finding, context);
+
+    if let Some((file, positives, negatives)) = selftest::examples(&finding.rule_id) {
+        section(&format!("Examples ({})", file));
+        for snippet in &positives {
+            print_snippet(&color::icon("✗", "x").red().to_string(), snippet);
+        }
+        for snippet in &negatives {
+            print_snippet(&color::icon("✓", "ok").green().to_string(), snippet);
+        }
+    }
+
+    if finding.docs_url.is_some() || !finding.references.is_empty() {
+        section("Documentation");
+        for url in finding.docs_url.iter().chain(&finding.references) {
+            println!("  {}", url.cyan());
+        }
+    }
+    println!();
+
+    Ok(exit_code::CLEAN)
+}
+
+/// The finding whose ID equals `id` or, failing that, the only one it prefixes
+pub(crate) fn find<'r>(findings: &'r [Finding], id: &str) -> Result<&'r Finding> {
+    let wanted = id.trim().to_lowercase();
+    let wanted = wanted.strip_prefix("anty-").unwrap_or(&wanted);
+    let key = |f: &Finding| f.id.to_lowercase().trim_start_matches("anty-").to_string();
+
+    if let Some(finding) = findings.iter().find(|f| key(f) == wanted) {
+        return Ok(finding);
+    }
+    let matches: Vec<&Finding> = findings.iter().filter(|f| key(f).starts_with(wanted)).collect();
+    match matches.as_slice() {
+        [finding] => Ok(finding),
+        [] => bail!("No finding {} in the report", id),
+        _ => {
+            let mut ids: Vec<&str> = matches.iter().take(5).map(|f| f.id.as_str()).collect();
+            if matches.len() > ids.len() {
+                ids.push("…");
+            }
+            bail!("{} matches {} findings: {}", id, matches.len(), ids.join(", "))
+        }
+    }
+}
+
+/// Print the finding's lines with `context` lines either side. The flagged
+/// lines show the report's evidence, which has secrets redacted.
+fn render_source(report: &ScanReport, finding: &Finding, context: usize) {
+    // Single-file scans report an empty path relative to the file itself
+    let path = if finding.file_path.as_os_str().is_empty() {
+        report.scan_path.clone()
+    } else {
+        report.scan_path.join(&finding.file_path)
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        println!("  {}", format!("{} is not readable from here", path.display()).dimmed());
+        return;
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    if finding.line_end > lines.len() {
+        println!("  {}", "The file has changed since the scan".dimmed());
+        return;
+    }
+    let first = finding.line_start.saturating_sub(context).max(1);
+    let last = (finding.line_end + context).min(lines.len());
+
+    let flagged = finding.line_start..=finding.line_end;
+    let width = last.to_string().len();
+    // Secret evidence is redacted to `abcd…****…wxyz`; never print the raw line
+    let redact = finding.evidence.contains("****");
+    for number in first..=last {
+        let line = lines[number - 1];
+        if !flagged.contains(&number) {
+            println!("    {:>width$} │ {}", number.dimmed(), line.dimmed());
+            continue;
+        }
+        let text = match (redact, number == finding.line_start) {
+            (false, _) => line,
+            (true, true) => finding.evidence.as_str(),
+            (true, false) => "…",
+        };
+        println!("  {} {:>width$} │ {}", "▶".red(), number, text);
+    }
+    let changed = !redact
+        && finding.line_start == finding.line_end
+        && lines[finding.line_start - 1].trim() != finding.evidence.trim();
+    if changed {
+        println!("  {}", "The file has changed since the scan".dimmed());
+    }
+}
+
+fn section(title: &str) {
+    println!();
+    println!("{}", title.bold());
+}
+
+fn print_snippet(marker: &str, snippet: &str) {
+    let mut lines = snippet.lines();
+    println!("  {} {}", marker, lines.next().unwrap_or_default());
+    for line in lines {
+        println!("    {}", line);
+    }
+}
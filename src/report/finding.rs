@@ -1,9 +1,11 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 /// Severity level of a security finding
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum Severity {
     Low,
@@ -39,7 +41,7 @@ impl std::fmt::Display for Severity {
 }
 
 /// Confidence level of a finding
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum Confidence {
     Low,
@@ -48,6 +50,14 @@ pub enum Confidence {
 }
 
 impl Confidence {
+    pub fn from_str(s: &str) -> Self {
+        match s.to_uppercase().as_str() {
+            "HIGH" => Confidence::High,
+            "MEDIUM" => Confidence::Medium,
+            _ => Confidence::Low,
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Confidence::Low => "LOW",
@@ -64,7 +74,7 @@ impl std::fmt::Display for Confidence {
 }
 
 /// A single security finding
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Finding {
     /// Deterministic ID (hash-based) e.g. "ANTY-a1b2c3d4"
     pub id: String,
@@ -87,7 +97,8 @@ pub struct Finding {
     /// Human-readable description
     pub description: String,
 
-    /// File where the issue was found
+    /// File where the issue was found, relative to the scan root with `/` separators
+    #[serde(deserialize_with = "deserialize_portable_path")]
     pub file_path: PathBuf,
 
     /// Starting line number (1-based)
@@ -96,6 +107,15 @@ pub struct Finding {
     /// Ending line number (1-based)
     pub line_end: usize,
 
+    /// Starting column of the match on `line_start` (1-based, in bytes)
+    #[serde(default)]
+    pub column: usize,
+
+    /// Column just past the match on `line_start` (1-based, in bytes); the
+    /// same as `column` where only the start of the match is known
+    #[serde(default)]
+    pub column_end: usize,
+
     /// Evidence snippet (code)
     pub evidence: String,
 
@@ -104,9 +124,242 @@ pub struct Finding {
 
     /// Optional CWE ID
     pub cwe_id: Option<String>,
+
+    /// Provider-specific response steps (leaked credentials)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<Remediation>,
+
+    /// Hash of the matched secret value, used to correlate the same
+    /// credential across files. Never written out.
+    #[serde(skip)]
+    pub secret_hash: Option<String>,
+
+    /// Other rules whose overlapping matches were folded into this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related_rules: Vec<String>,
+
+    /// Other places the same secret was found (one credential to rotate)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_locations: Vec<Location>,
+
+    /// Owning teams or users from CODEOWNERS
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+
+    /// Last commit to touch the offending line (--blame)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame: Option<Blame>,
+
+    /// Source lines around the finding (--include-context)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<SourceContext>,
+
+    /// Status, assignee, and note recorded with `anty triage set`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub triage: Option<Triage>,
+
+    /// The edit `anty fix` would make, for rules with a mechanical fix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+
+    /// Documentation page for the rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+
+    /// CWE, OWASP, and CVE links for the weakness
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+
+    /// Deployment environment the file's path points to (see
+    /// `engine::environment`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Environment>,
+
+    /// 0.1–10.0 priority from severity, confidence, and context
+    /// (see `engine::score`); finer-grained than the four severities
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+}
+
+/// `git blame` attribution for a finding's line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Blame {
+    pub commit: String,
+    pub author: String,
+    /// Author date, YYYY-MM-DD
+    pub date: String,
+    /// First line of the commit message
+    pub summary: String,
+}
+
+/// Where a finding stands in the team's triage (`.anty/triage.toml`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Triage {
+    pub status: TriageStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Date the state was last set, YYYY-MM-DD
+    pub updated: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TriageStatus {
+    /// Still to be dealt with
+    Open,
+    /// Real, and the risk is accepted
+    Accepted,
+    /// Not actually a problem
+    FalsePositive,
+}
+
+impl TriageStatus {
+    /// Values of `anty triage set --status`
+    pub const NAMES: &'static [&'static str] = &["open", "accepted", "false-positive"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "open" => Some(TriageStatus::Open),
+            "accepted" => Some(TriageStatus::Accepted),
+            "false-positive" => Some(TriageStatus::FalsePositive),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TriageStatus::Open => "open",
+            TriageStatus::Accepted => "accepted",
+            TriageStatus::FalsePositive => "false-positive",
+        }
+    }
+}
+
+impl std::fmt::Display for TriageStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Where the code or config a finding is in runs, as far as its path tells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Environment {
+    /// Local development, samples, and templates (`docker-compose.dev.yml`,
+    /// `.env.sample`)
+    Dev,
+    /// Tests and CI (`config/test.yml`, `tests/`)
+    Test,
+    /// Production, or nothing in the path rules it out
+    ProdUnknown,
+}
+
+impl Environment {
+    /// Values of `--environment`
+    pub const NAMES: &'static [&'static str] = &["dev", "test", "prod-unknown"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dev" => Some(Environment::Dev),
+            "test" => Some(Environment::Test),
+            "prod-unknown" => Some(Environment::ProdUnknown),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Environment::Dev => "dev",
+            Environment::Test => "test",
+            Environment::ProdUnknown => "prod-unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for Environment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Source lines around a finding, with secrets redacted as in `evidence`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SourceContext {
+    pub lines: Vec<SourceLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SourceLine {
+    /// 1-based line number
+    pub number: usize,
+    pub text: String,
+    /// One of the finding's own lines
+    pub flagged: bool,
+}
+
+/// The commit a scan ran against, so findings can be tied to exactly
+/// what was scanned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GitContext {
+    /// Full SHA of HEAD
+    pub commit: String,
+    /// Checked-out branch. On a detached HEAD, taken from the CI
+    /// environment when it names one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// URL of the `origin` remote (or the first remote), without credentials
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_url: Option<String>,
+    /// Tracked files had uncommitted changes, so the scan may not match
+    /// `commit` exactly
+    pub dirty: bool,
+}
+
+/// A file/line position
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Location {
+    #[serde(deserialize_with = "deserialize_portable_path")]
+    pub file_path: PathBuf,
+    pub line: usize,
+}
+
+/// A fix as a replacement within one line, so editors and SARIF viewers
+/// can apply it without rewriting the line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Fix {
+    /// What the fix does, e.g. "Use yaml.safe_load"
+    pub description: String,
+    /// 1-based line
+    pub line: usize,
+    /// 1-based byte columns of the replaced text; `end_column` is exclusive
+    pub start_column: usize,
+    pub end_column: usize,
+    /// Text to put in their place
+    pub replacement: String,
+}
+
+/// Structured remediation for a finding: where to revoke, how to rotate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Remediation {
+    /// Service that issued the credential, e.g. "AWS"
+    pub provider: String,
+    /// Console page where the credential can be revoked
+    pub revoke_url: Option<String>,
+    /// Ordered rotation steps
+    pub rotation_steps: Vec<String>,
+    /// Provider documentation
+    pub docs_url: Option<String>,
 }
 
 impl Finding {
+    /// Hash a secret value so duplicates can be matched without keeping it
+    pub fn hash_secret(value: &str) -> String {
+        let hex = format!("{:x}", Sha256::digest(value.as_bytes()));
+        hex[..16].to_string()
+    }
+
     /// Generate a deterministic ID based on rule, file, and location
     pub fn generate_id(rule_id: &str, file_path: &std::path::Path, line_start: usize) -> String {
         let mut hasher = Sha256::new();
@@ -117,15 +370,46 @@ impl Finding {
         let hex = format!("{:x}", result);
         format!("ANTY-{}", &hex[..8])
     }
+
+    /// ID of a further match of the same rule on one line, told apart by
+    /// its column (the first match keeps [`Finding::generate_id`])
+    pub fn generate_id_at(rule_id: &str, file_path: &std::path::Path, line_start: usize, column: usize) -> String {
+        Finding::generate_id(&format!("{}@{}", rule_id, column), file_path, line_start)
+    }
+}
+
+/// `path` with `/` separators, the form reports use on every platform so
+/// that scans from Windows and Linux have the same paths and finding IDs
+pub fn portable_path(path: &Path) -> PathBuf {
+    if std::path::MAIN_SEPARATOR == '/' {
+        return path.to_path_buf();
+    }
+    PathBuf::from(path.to_string_lossy().replace(std::path::MAIN_SEPARATOR, "/"))
 }
 
+/// Read a report path, turning the backslashes of reports written on
+/// Windows by older versions into `/`
+pub fn deserialize_portable_path<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<PathBuf, D::Error> {
+    let path = String::deserialize(deserializer)?;
+    Ok(PathBuf::from(path.replace('\\', "/")))
+}
+
+/// Version of the JSON report format. Bump on any change that could break
+/// a consumer (removed or renamed fields, changed types or meanings);
+/// new optional fields do not need a bump.
+pub const REPORT_FORMAT_VERSION: u32 = 1;
+
 /// The complete scan report
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScanReport {
+    /// Report format version (see `anty schema`)
+    pub format_version: u32,
+
     /// Anty version
     pub version: String,
 
-    /// When the scan was performed
+    /// When the scan was performed (absent with --deterministic)
+    #[serde(default)]
     pub timestamp: String,
 
     /// Root path that was scanned
@@ -137,14 +421,78 @@ pub struct ScanReport {
     /// Total files skipped
     pub files_skipped: usize,
 
-    /// Duration in milliseconds
+    /// Duration in milliseconds (absent with --deterministic)
+    #[serde(default)]
     pub duration_ms: u64,
 
+    /// Per-phase and per-agent timing breakdown
+    #[serde(default)]
+    pub timings: ScanTimings,
+
     /// All findings, sorted by severity (critical first)
     pub findings: Vec<Finding>,
 
+    /// Findings in test files, with --separate-tests or --fail-on-tests.
+    /// Sorted like `findings` and not counted in `summary`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub test_findings: Vec<Finding>,
+
+    /// Files that could not be scanned completely
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ScanError>,
+
+    /// Every file left out of the scan and why (--explain-skips only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedFile>,
+
+    /// `[policy]` requirements the scan did not meet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policy_violations: Vec<PolicyViolation>,
+
+    /// Baseline entries past their `expires` date whose findings are
+    /// reported again
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expired_suppressions: Vec<ExpiredSuppression>,
+
+    /// Framework controls covered and violated (--compliance only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compliance: Option<ComplianceSummary>,
+
+    /// Per-package counts, before any caps (--monorepo only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<PackageSummary>,
+
+    /// Commit, branch, and remote of the scanned repository (when the
+    /// scan path is inside one)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitContext>,
+
+    /// Files and lines scanned per language, most lines first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<LanguageStats>,
+
     /// Summary counts
     pub summary: ScanSummary,
+
+    /// What was covered when the --timeout budget ran out; absent for
+    /// scans that finished
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial: Option<ScanCoverage>,
+
+    /// The scan stopped at the first finding at or above --fail-on
+    /// (--fail-fast), so the findings and counts cover only part of the tree
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stopped_early: bool,
+
+    /// Rendered without timestamp and durations (--deterministic). A
+    /// display setting, so it is not part of the JSON report.
+    #[serde(skip)]
+    pub deterministic: bool,
+
+    /// `output.editor_url_template` from the config, for terminal links.
+    /// A display setting, so it is not part of the JSON report.
+    #[serde(skip)]
+    pub editor_url_template: Option<String>,
 }
 
 impl ScanReport {
@@ -152,16 +500,416 @@ impl ScanReport {
     pub fn has_findings_at_or_above(&self, threshold: Severity) -> bool {
         self.findings.iter().any(|f| f.severity >= threshold)
     }
+
+    /// Check if any finding scores at or above `threshold`
+    pub fn has_findings_scored_at_or_above(&self, threshold: f32) -> bool {
+        self.findings.iter().any(|f| f.score.is_some_and(|s| s >= threshold))
+    }
+}
+
+/// A `[policy]` requirement the scan did not meet
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PolicyViolation {
+    /// The requirement, as configured or described
+    pub policy: String,
+    /// What was found instead
+    pub message: String,
+    /// IDs of the findings that count against the requirement
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub findings: Vec<String>,
+}
+
+/// A baseline entry that no longer suppresses its finding
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExpiredSuppression {
+    /// ID of the finding the entry suppressed
+    pub id: String,
+    pub rule_id: String,
+    #[serde(deserialize_with = "deserialize_portable_path")]
+    pub file_path: PathBuf,
+    /// The entry's `expires` date (YYYY-MM-DD)
+    pub expires: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Scan results expressed as controls of a compliance framework
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ComplianceSummary {
+    /// Framework name and version, e.g. "PCI DSS v4.0"
+    pub framework: String,
+    pub controls: Vec<ControlResult>,
+}
+
+/// Evidence for one framework control
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ControlResult {
+    /// Control ID in the framework's own numbering, e.g. "6.2.4"
+    pub id: String,
+    pub title: String,
+    pub status: ControlStatus,
+    /// Rules that provide evidence for the control
+    pub rules: Vec<String>,
+    /// IDs of findings that violate the control, before any caps
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub findings: Vec<String>,
+}
+
+/// Outcome for a control
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ControlStatus {
+    /// At least one finding maps to the control
+    Violated,
+    /// Rules covering the control ran and found nothing
+    Passed,
+    /// No rule covering the control ran (see --agents)
+    NotChecked,
+}
+
+impl ControlStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ControlStatus::Violated => "VIOLATED",
+            ControlStatus::Passed => "PASSED",
+            ControlStatus::NotChecked => "NOT CHECKED",
+        }
+    }
+}
+
+/// Findings in one package of a monorepo
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PackageSummary {
+    /// Package root relative to the scan path ("" for the scan root itself)
+    pub path: PathBuf,
+    pub total: usize,
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    /// Most severe finding in the package
+    pub max_severity: Option<Severity>,
+    /// Whether this package alone would fail `--fail-on`
+    #[serde(default)]
+    pub failed: bool,
+}
+
+impl PackageSummary {
+    pub fn new(path: &Path) -> Self {
+        PackageSummary {
+            path: path.to_path_buf(),
+            total: 0,
+            critical: 0,
+            high: 0,
+            medium: 0,
+            low: 0,
+            max_severity: None,
+            failed: false,
+        }
+    }
+
+    pub fn add(&mut self, severity: Severity) {
+        self.total += 1;
+        match severity {
+            Severity::Critical => self.critical += 1,
+            Severity::High => self.high += 1,
+            Severity::Medium => self.medium += 1,
+            Severity::Low => self.low += 1,
+        }
+        self.max_severity = self.max_severity.max(Some(severity));
+    }
+
+    /// Package path for display ("." for the scan root)
+    pub fn name(&self) -> String {
+        if self.path.as_os_str().is_empty() {
+            ".".to_string()
+        } else {
+            self.path.display().to_string()
+        }
+    }
+}
+
+/// How far a scan got before its --timeout budget ran out
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScanCoverage {
+    /// The --timeout budget in seconds
+    pub budget_secs: u64,
+    /// Whether file discovery finished. If not, `files_discovered` counts
+    /// only the files found before the budget ran out.
+    pub discovery_complete: bool,
+    pub files_discovered: usize,
+    pub files_scanned: usize,
+    /// Discovered files that were never scanned or were cut off mid-scan
+    pub files_not_scanned: usize,
+}
+
+/// Share of scanned lines above which a language without rules of its own
+/// is reported as a coverage gap
+pub const SIGNIFICANT_LANGUAGE_SHARE: f64 = 0.1;
+
+/// What was scanned in one language and which agents have rules for it
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LanguageStats {
+    /// Language name as used by `--lang` ("other" for unrecognized files)
+    pub language: String,
+    pub files: usize,
+    pub lines: usize,
+    /// Agents with rules specific to this language. Agents that look at
+    /// every file, such as secrets, are not listed.
+    pub agents: Vec<String>,
+    /// A significant share of the scan with no language-specific rules
+    #[serde(default)]
+    pub uncovered: bool,
+}
+
+impl LanguageStats {
+    /// Sort most lines first and flag languages that make up at least
+    /// SIGNIFICANT_LANGUAGE_SHARE of the lines but no agent has rules for
+    pub fn finish(stats: &mut [LanguageStats]) {
+        stats.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.language.cmp(&b.language)));
+        let total: usize = stats.iter().map(|s| s.lines).sum();
+        for s in stats.iter_mut() {
+            s.uncovered = s.agents.is_empty()
+                && s.language != "other"
+                && s.lines > 0
+                && s.lines as f64 >= total as f64 * SIGNIFICANT_LANGUAGE_SHARE;
+        }
+    }
+}
+
+/// A file the scan could not finish, so its findings may be missing
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScanError {
+    #[serde(deserialize_with = "deserialize_portable_path")]
+    pub file_path: PathBuf,
+    pub kind: ScanErrorKind,
+    /// Agent that failed, when only one agent was affected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    pub message: String,
+}
+
+/// What went wrong with a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScanErrorKind {
+    /// Exceeded the per-file time budget (--file-timeout)
+    Timeout,
+    /// Could not be read, or reading stopped partway (permissions, I/O errors)
+    Read,
+    /// A rule or agent panicked while scanning the file
+    Panic,
+}
+
+/// A file that was not scanned
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SkippedFile {
+    #[serde(deserialize_with = "deserialize_portable_path")]
+    pub file_path: PathBuf,
+    pub reason: SkipReason,
+}
+
+/// Why a file was not scanned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkipReason {
+    /// Larger than --max-file-size
+    TooLarge,
+    /// Binary file extension
+    Binary,
+    /// Built-in excluded directory (node_modules, dist/, .venv/, ...)
+    ExcludedPath,
+    /// Matched an --exclude / scan.exclude glob
+    ExcludePattern,
+    /// Did not match --include
+    NotIncluded,
+    /// Language left out by --lang / --exclude-lang
+    ExcludedLanguage,
+    /// Dotfile or inside a dot-directory (see --hidden)
+    Hidden,
+    /// Ignored by .gitignore (see --no-gitignore)
+    Gitignored,
+    /// Same file already reached through another symlink
+    DuplicateLink,
+    /// Content is not valid UTF-8
+    NonUtf8,
+    /// Could not be read. Only in reports of older versions: read failures
+    /// are now listed under the report's `errors`.
+    Unreadable,
+}
+
+impl SkipReason {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            SkipReason::TooLarge => "too large (--max-file-size)",
+            SkipReason::Binary => "binary file extension",
+            SkipReason::ExcludedPath => "built-in excluded path",
+            SkipReason::ExcludePattern => "matched an exclude pattern",
+            SkipReason::NotIncluded => "not matched by --include",
+            SkipReason::ExcludedLanguage => "language filtered out (--lang / --exclude-lang)",
+            SkipReason::Hidden => "hidden (use --hidden)",
+            SkipReason::Gitignored => "gitignored (use --no-gitignore)",
+            SkipReason::DuplicateLink => "already scanned via another symlink",
+            SkipReason::NonUtf8 => "not valid UTF-8",
+            SkipReason::Unreadable => "unreadable",
+        }
+    }
+}
+
+/// Time spent in each scan phase, in milliseconds
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ScanTimings {
+    pub discovery_ms: u64,
+    /// Until the last file was read. Reading overlaps scanning, so this is
+    /// part of `scan_ms` rather than added to it.
+    pub read_ms: u64,
+    /// Reading and scanning every file
+    pub scan_ms: u64,
+    pub merge_ms: u64,
+    /// Cumulative time per agent, summed across worker threads
+    pub agents_ms: BTreeMap<String, u64>,
+    /// Files (chunks, for files too large to read whole) each agent's
+    /// `interested_in` pre-filter handed to it
+    #[serde(default)]
+    pub agent_files: BTreeMap<String, usize>,
 }
 
 /// Summary statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScanSummary {
     pub total: usize,
     pub critical: usize,
     pub high: usize,
     pub medium: usize,
     pub low: usize,
+
+    /// Findings dropped by --max-findings / --max-per-rule
+    #[serde(default)]
+    pub omitted: Omitted,
+
+    /// Findings hidden by false-positive / accepted-risk baseline entries
+    #[serde(default)]
+    pub suppressed: usize,
+
+    /// Baseline suppressions by rule ID, for `anty rules stats`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub suppressed_by_rule: BTreeMap<String, SuppressedCounts>,
+
+    /// Files and rules with the most findings, before any caps
+    #[serde(default)]
+    pub hotspots: Hotspots,
+
+    /// Findings by triage status; untriaged findings are not counted
+    #[serde(default, skip_serializing_if = "TriageCounts::is_empty")]
+    pub triage: TriageCounts,
+}
+
+/// Findings of one rule hidden by the baseline, by decision
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SuppressedCounts {
+    pub false_positive: usize,
+    pub accepted_risk: usize,
+}
+
+impl SuppressedCounts {
+    pub fn total(&self) -> usize {
+        self.false_positive + self.accepted_risk
+    }
+}
+
+/// Findings per `anty triage set` status
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TriageCounts {
+    pub open: usize,
+    pub accepted: usize,
+    pub false_positive: usize,
+    /// Triaged findings with an assignee, whatever their status
+    pub assigned: usize,
+}
+
+impl TriageCounts {
+    pub fn is_empty(&self) -> bool {
+        *self == TriageCounts::default()
+    }
+}
+
+/// Number of files and rules listed as top offenders
+pub const HOTSPOT_LIMIT: usize = 10;
+
+/// Where findings concentrate — the "top offenders" to prioritize cleanup
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Hotspots {
+    /// Files with the most findings, most first
+    pub files: Vec<HotspotCount>,
+    /// Rules with the most findings, most first
+    pub rules: Vec<HotspotCount>,
+    /// Findings per agent
+    pub agents: BTreeMap<String, usize>,
+    /// Findings per CODEOWNERS owner ("(unowned)" for the rest)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub owners: BTreeMap<String, usize>,
+}
+
+/// Owner bucket for findings no CODEOWNERS rule covers
+pub const UNOWNED: &str = "(unowned)";
+
+/// One entry in a hotspot ranking
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HotspotCount {
+    pub name: String,
+    pub count: usize,
+}
+
+impl Hotspots {
+    pub fn from_findings(findings: &[Finding]) -> Self {
+        let mut files: BTreeMap<String, usize> = BTreeMap::new();
+        let mut rules: BTreeMap<String, usize> = BTreeMap::new();
+        let mut agents: BTreeMap<String, usize> = BTreeMap::new();
+        let mut owners: BTreeMap<String, usize> = BTreeMap::new();
+        let any_owned = findings.iter().any(|f| !f.owners.is_empty());
+        for f in findings {
+            *files.entry(f.file_path.display().to_string()).or_default() += 1;
+            *rules.entry(f.rule_id.clone()).or_default() += 1;
+            *agents.entry(f.agent.clone()).or_default() += 1;
+            if !any_owned {
+                continue;
+            }
+            if f.owners.is_empty() {
+                *owners.entry(UNOWNED.to_string()).or_default() += 1;
+            }
+            for owner in &f.owners {
+                *owners.entry(owner.clone()).or_default() += 1;
+            }
+        }
+        Hotspots {
+            files: top(files),
+            rules: top(rules),
+            agents,
+            owners,
+        }
+    }
+}
+
+/// Highest counts first, ties by name, limited to HOTSPOT_LIMIT
+fn top(counts: BTreeMap<String, usize>) -> Vec<HotspotCount> {
+    let mut ranked: Vec<HotspotCount> = counts
+        .into_iter()
+        .map(|(name, count)| HotspotCount { name, count })
+        .collect();
+    // Stable sort keeps the BTreeMap's name order among equal counts
+    ranked.sort_by_key(|h| std::cmp::Reverse(h.count));
+    ranked.truncate(HOTSPOT_LIMIT);
+    ranked
+}
+
+/// Findings left out of the report by the configured caps
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Omitted {
+    /// Total findings dropped by any cap
+    pub total: usize,
+    /// Findings dropped by the per-rule cap, keyed by rule ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub by_rule: BTreeMap<String, usize>,
 }
 
 impl ScanSummary {
@@ -172,8 +920,23 @@ impl ScanSummary {
             high: 0,
             medium: 0,
             low: 0,
+            omitted: Omitted::default(),
+            suppressed: 0,
+            suppressed_by_rule: BTreeMap::new(),
+            hotspots: Hotspots::default(),
+            triage: TriageCounts::default(),
         };
         for f in findings {
+            if let Some(ref triage) = f.triage {
+                match triage.status {
+                    TriageStatus::Open => summary.triage.open += 1,
+                    TriageStatus::Accepted => summary.triage.accepted += 1,
+                    TriageStatus::FalsePositive => summary.triage.false_positive += 1,
+                }
+                if triage.assignee.is_some() {
+                    summary.triage.assigned += 1;
+                }
+            }
             match f.severity {
                 Severity::Critical => summary.critical += 1,
                 Severity::High => summary.high += 1,
@@ -0,0 +1,262 @@
+use std::fmt::Write;
+
+use crate::report::finding::{Finding, ScanReport, Severity, SourceContext};
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Roboto, sans-serif; margin: 0 auto; max-width: 1100px; padding: 24px; color: #1f2328; }
+h1 { margin-bottom: 4px; }
+.meta { color: #656d76; margin-bottom: 24px; }
+.counts { display: flex; gap: 12px; margin-bottom: 24px; }
+.count { border-radius: 6px; padding: 8px 16px; color: #fff; font-weight: 600; }
+.finding { border: 1px solid #d0d7de; border-left-width: 6px; border-radius: 6px; padding: 12px 16px; margin-bottom: 16px; }
+.finding h2 { font-size: 1.05em; margin: 0 0 4px; }
+.location { font-family: ui-monospace, Consolas, monospace; color: #656d76; }
+pre { background: #f6f8fa; padding: 8px; border-radius: 6px; overflow-x: auto; }
+.badge { border-radius: 4px; padding: 1px 6px; color: #fff; font-size: 0.8em; margin-right: 6px; }
+.critical { background: #a40e26; border-left-color: #a40e26; }
+.high { background: #d1242f; border-left-color: #d1242f; }
+.medium { background: #bf8700; border-left-color: #bf8700; }
+.low { background: #0969da; border-left-color: #0969da; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #d0d7de; padding: 4px 10px; text-align: left; }
+.uncovered { color: #9a6700; }
+.context .line { display: block; }
+.context .number { color: #656d76; display: inline-block; min-width: 3em; user-select: none; }
+.context .flagged { background: #fff8c5; }
+.finding.critical, .finding.high, .finding.medium, .finding.low { background: none; }
+"#;
+
+/// Render a scan report as a single self-contained HTML page, for people
+/// who will not read JSON. No scripts or external resources.
+pub fn render(report: &ScanReport) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(out, "<title>Anty report: {}</title>", escape(&report.scan_path.display().to_string()));
+    let _ = writeln!(out, "<style>{}</style>\n</head>\n<body>", STYLE);
+
+    let _ = writeln!(out, "<h1>Anty security report</h1>");
+    if report.deterministic {
+        let _ = writeln!(
+            out,
+            "<div class=\"meta\">{} &middot; {} files scanned &middot; Anty v{}</div>",
+            escape(&report.scan_path.display().to_string()),
+            report.files_scanned,
+            escape(&report.version)
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "<div class=\"meta\">{} &middot; {} files scanned in {:.1}s &middot; {} &middot; Anty v{}</div>",
+            escape(&report.scan_path.display().to_string()),
+            report.files_scanned,
+            report.duration_ms as f64 / 1000.0,
+            escape(&report.timestamp),
+            escape(&report.version)
+        );
+    }
+
+    if let Some(ref git) = report.git {
+        let short = &git.commit[..git.commit.len().min(12)];
+        let branch = git.branch.as_deref().map(|b| format!(" on {}", escape(b))).unwrap_or_default();
+        let dirty = if git.dirty { " (uncommitted changes)" } else { "" };
+        let _ = writeln!(out, "<div class=\"meta\">Commit {}{}{}</div>", escape(short), branch, dirty);
+    }
+
+    let s = &report.summary;
+    out.push_str("<div class=\"counts\">\n");
+    for (severity, count) in [
+        (Severity::Critical, s.critical),
+        (Severity::High, s.high),
+        (Severity::Medium, s.medium),
+        (Severity::Low, s.low),
+    ] {
+        let _ = writeln!(out, "<div class=\"count {}\">{} {}</div>", class(severity), count, severity);
+    }
+    out.push_str("</div>\n");
+
+    if report.findings.is_empty() {
+        out.push_str("<p>No issues found.</p>\n");
+    }
+    for finding in &report.findings {
+        render_finding(&mut out, finding);
+    }
+
+    if !report.test_findings.is_empty() {
+        let _ = writeln!(out, "<h2>Findings in test files ({})</h2>", report.test_findings.len());
+        for finding in &report.test_findings {
+            render_finding(&mut out, finding);
+        }
+    }
+
+    if !s.hotspots.owners.is_empty() {
+        out.push_str("<h2>Findings by owner</h2>\n<table>\n<tr><th>Owner</th><th>Findings</th></tr>\n");
+        let mut owners: Vec<(&String, &usize)> = s.hotspots.owners.iter().collect();
+        owners.sort_by_key(|(_, count)| std::cmp::Reverse(**count));
+        for (owner, count) in owners {
+            let _ = writeln!(out, "<tr><td>{}</td><td>{}</td></tr>", escape(owner), count);
+        }
+        out.push_str("</table>\n");
+    }
+
+    if !report.policy_violations.is_empty() {
+        out.push_str("<h2>Policy violations</h2>\n<ul>\n");
+        for v in &report.policy_violations {
+            let _ = writeln!(out, "<li><strong>{}</strong>: {}</li>", escape(&v.policy), escape(&v.message));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    if !report.expired_suppressions.is_empty() {
+        out.push_str("<h2>Expired suppressions</h2>\n<ul>\n");
+        for e in &report.expired_suppressions {
+            let _ = writeln!(
+                out,
+                "<li><strong>{}</strong> in {}: expired {}{}</li>",
+                escape(&e.rule_id),
+                escape(&e.file_path.display().to_string()),
+                escape(&e.expires),
+                e.reason.as_deref().map(|r| format!(" ({})", escape(r))).unwrap_or_default()
+            );
+        }
+        out.push_str("</ul>\n");
+    }
+
+    if !report.languages.is_empty() {
+        out.push_str("<h2>Languages</h2>\n<table>\n<tr><th>Language</th><th>Files</th><th>Lines</th><th>Agents</th></tr>\n");
+        for l in &report.languages {
+            let agents = if l.agents.is_empty() { "generic only".to_string() } else { l.agents.join(", ") };
+            let _ = writeln!(
+                out,
+                "<tr{}><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                if l.uncovered { " class=\"uncovered\"" } else { "" },
+                escape(&l.language),
+                l.files,
+                l.lines,
+                escape(&agents)
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn render_finding(out: &mut String, f: &Finding) {
+    let _ = writeln!(out, "<div class=\"finding {}\" id=\"{}\">", class(f.severity), escape(&f.id));
+    let _ = writeln!(
+        out,
+        "<h2><span class=\"badge {}\">{}</span>{} <small>{}</small></h2>",
+        class(f.severity),
+        f.severity,
+        escape(&f.title),
+        escape(&f.rule_id)
+    );
+    let _ = writeln!(
+        out,
+        "<div class=\"location\">{}:{}</div>",
+        escape(&f.file_path.display().to_string()),
+        f.line_start
+    );
+    if !f.owners.is_empty() {
+        let _ = writeln!(out, "<p><strong>Owners:</strong> {}</p>", escape(&f.owners.join(", ")));
+    }
+    if let Some(ref blame) = f.blame {
+        let short = &blame.commit[..blame.commit.len().min(8)];
+        let _ = writeln!(
+            out,
+            "<p><strong>Added by</strong> {} on {} ({} {})</p>",
+            escape(&blame.author),
+            escape(&blame.date),
+            escape(short),
+            escape(&blame.summary)
+        );
+    }
+    if let Some(ref triage) = f.triage {
+        let mut state = triage.status.to_string();
+        if let Some(ref assignee) = triage.assignee {
+            state.push_str(&format!(" · assigned to {}", assignee));
+        }
+        if let Some(ref note) = triage.note {
+            state.push_str(&format!(" — {}", note));
+        }
+        let _ = writeln!(out, "<p><strong>Triage:</strong> {}</p>", escape(&state));
+    }
+    let _ = writeln!(out, "<p>{}</p>", escape(&f.description));
+    match f.context {
+        Some(ref context) => render_context(out, context),
+        None => {
+            let _ = writeln!(out, "<pre>{}</pre>", escape(&f.evidence));
+        }
+    }
+    let _ = writeln!(out, "<p><strong>Fix:</strong> {}</p>", escape(&f.recommendation));
+
+    if let Some(ref r) = f.remediation {
+        let _ = writeln!(out, "<p><strong>Respond ({}):</strong></p>\n<ol>", escape(&r.provider));
+        for step in &r.rotation_steps {
+            let _ = writeln!(out, "<li>{}</li>", escape(step));
+        }
+        out.push_str("</ol>\n");
+        if let Some(ref url) = r.revoke_url {
+            let _ = writeln!(out, "<p><a href=\"{0}\">Revoke: {0}</a></p>", escape(url));
+        }
+    }
+    if !f.additional_locations.is_empty() {
+        let places: Vec<String> = f
+            .additional_locations
+            .iter()
+            .map(|l| escape(&format!("{}:{}", l.file_path.display(), l.line)))
+            .collect();
+        let _ = writeln!(out, "<p>Also found at: {}</p>", places.join(", "));
+    }
+
+    let links: Vec<String> = f
+        .docs_url
+        .iter()
+        .chain(&f.references)
+        .map(|url| format!("<a href=\"{0}\">{0}</a>", escape(url)))
+        .collect();
+    if !links.is_empty() {
+        let _ = writeln!(out, "<p>{}</p>", links.join(" &middot; "));
+    }
+    out.push_str("</div>\n");
+}
+
+/// Source lines around a finding (--include-context), its own lines highlighted
+fn render_context(out: &mut String, context: &SourceContext) {
+    out.push_str("<pre class=\"context\">");
+    for line in &context.lines {
+        let _ = write!(
+            out,
+            "<span class=\"line{}\"><span class=\"number\">{}</span>{}</span>",
+            if line.flagged { " flagged" } else { "" },
+            line.number,
+            escape(&line.text)
+        );
+    }
+    out.push_str("</pre>\n");
+}
+
+fn class(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "critical",
+        Severity::High => "high",
+        Severity::Medium => "medium",
+        Severity::Low => "low",
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
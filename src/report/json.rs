@@ -1,8 +1,34 @@
 use anyhow::Result;
-use crate::report::finding::ScanReport;
+use crate::report::finding::{ScanReport, REPORT_FORMAT_VERSION};
 
 /// Render a scan report as pretty-printed JSON
 pub fn render(report: &ScanReport) -> Result<String> {
+    if report.deterministic {
+        return deterministic(report);
+    }
     let json = serde_json::to_string_pretty(report)?;
     Ok(json)
 }
+
+/// The report without the fields that differ between two scans of the
+/// same tree (--deterministic)
+fn deterministic(report: &ScanReport) -> Result<String> {
+    let mut value = serde_json::to_value(report)?;
+    if let Some(fields) = value.as_object_mut() {
+        for field in ["timestamp", "duration_ms", "timings"] {
+            fields.remove(field);
+        }
+    }
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+/// JSON Schema for the report, the contract for tools that consume it
+pub fn schema() -> Result<String> {
+    let mut schema = schemars::schema_for!(ScanReport);
+    schema.insert("title".to_string(), "Anty scan report".into());
+    schema.insert(
+        "description".to_string(),
+        format!("Anty JSON report, format version {}", REPORT_FORMAT_VERSION).into(),
+    );
+    Ok(serde_json::to_string_pretty(&schema)?)
+}
@@ -0,0 +1,215 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use tracing::{info, warn};
+
+use crate::agents::{self, SecurityAgent};
+use crate::ci::Provider;
+use crate::cli::exit_code;
+use crate::engine::reader;
+use crate::report::cache;
+use crate::report::finding::{Finding, ScanReport};
+
+/// Output formats of `anty mask`
+pub const FORMATS: &[&str] = &["github", "azure", "plain", "regex"];
+
+/// Longest secret searched for on a line
+const MAX_SECRET_LEN: usize = 512;
+
+/// Shorter values would scrub ordinary words from the log
+const MIN_SECRET_LEN: usize = 4;
+
+/// `anty mask`: print every secret value a report found in the form a CI
+/// system uses to scrub it from later log output. Reports never hold the
+/// secrets, so each flagged file is scanned again and the value whose hash
+/// the agent reports for the same rule and line is read back from it.
+/// Without `format`, the detected CI system's is used.
+pub fn run(report_path: Option<&Path>, format: Option<&str>) -> Result<i32> {
+    let report = match report_path {
+        Some(path) => cache::read(path)?,
+        None => cache::load(None)?,
+    };
+    let format = match format {
+        Some(format) => format,
+        None if Provider::detect() == Provider::GithubActions => "github",
+        None if std::env::var_os("TF_BUILD").is_some() => "azure",
+        None => "plain",
+    };
+    let agents = agents::all_agents();
+    let mut rescans = Rescans::default();
+    let mut values = BTreeSet::new();
+    let mut missing = 0;
+    for finding in report.findings.iter().chain(&report.test_findings) {
+        let Some(found) = recover(&report, finding, &agents, &mut rescans) else {
+            continue;
+        };
+        if found.is_empty() {
+            warn!(
+                "Could not read the secret of {} back from {}:{}; the file changed or is not readable from here",
+                finding.id,
+                finding.file_path.display(),
+                finding.line_start
+            );
+            missing += 1;
+        }
+        values.extend(found);
+    }
+
+    for value in &values {
+        match format {
+            "github" => println!("::add-mask::{}", value),
+            "azure" => println!("##vso[task.setsecret]{}", value),
+            "regex" => println!("{}", regex::escape(value)),
+            _ => println!("{}", value),
+        }
+    }
+    info!("{} values to mask ({} secrets could not be recovered)", values.len(), missing);
+    Ok(exit_code::CLEAN)
+}
+
+/// Findings of a fresh scan of each file, by path in the report
+type Rescans = HashMap<PathBuf, Option<(String, Vec<Finding>)>>;
+
+/// The values to mask for one finding, read from its locations. Values are
+/// split into lines, since CI systems mask line by line. None when the
+/// finding is not about a secret.
+fn recover(
+    report: &ScanReport,
+    finding: &Finding,
+    agents: &[Box<dyn SecurityAgent>],
+    rescans: &mut Rescans,
+) -> Option<Vec<String>> {
+    let locations = std::iter::once((&finding.file_path, finding.line_start))
+        .chain(finding.additional_locations.iter().map(|l| (&l.file_path, l.line)));
+
+    let mut secret = false;
+    for (file, line) in locations {
+        let Some((content, findings)) = rescans.entry(file.clone()).or_insert_with(|| rescan(report, file, agents))
+        else {
+            continue;
+        };
+        // Consolidated duplicates may have been found by another rule
+        let Some((hash, last)) = findings
+            .iter()
+            .filter(|f| {
+                f.line_start == line
+                    && (line != finding.line_start
+                        || (f.rule_id == finding.rule_id && f.column == finding.column))
+            })
+            .find_map(|f| f.secret_hash.as_deref().map(|hash| (hash, f.line_end)))
+        else {
+            continue;
+        };
+        secret = true;
+        let lines: Vec<&str> = content.lines().collect();
+        if line == 0 || last > lines.len() {
+            continue;
+        }
+        let flagged = &lines[line - 1..last];
+
+        if let Some(body) = private_key(&lines, line - 1, hash) {
+            return Some(body);
+        }
+        for text in flagged {
+            let found = on_line(text, hash);
+            if !found.is_empty() {
+                return Some(found
+                    .iter()
+                    .flat_map(|v| v.lines())
+                    .filter(|v| v.len() >= MIN_SECRET_LEN)
+                    .map(str::to_string)
+                    .collect());
+            }
+        }
+    }
+    secret.then(Vec::new)
+}
+
+/// Scan one file of the report again, keeping its content
+fn rescan(report: &ScanReport, file: &Path, agents: &[Box<dyn SecurityAgent>]) -> Option<(String, Vec<Finding>)> {
+    let path = source_path(report, file);
+    let scanned = match reader::read_file(&path, file.to_path_buf(), reader::detect_language(&path)) {
+        Ok(scanned) => scanned,
+        Err(e) => {
+            warn!("Could not read {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    let findings = agents.iter().flat_map(|a| agents::scan_alone(a.as_ref(), &scanned)).collect();
+    Some((scanned.content, findings))
+}
+
+/// Reports hold paths relative to the scan root; single-file scans have
+/// an empty one
+fn source_path(report: &ScanReport, file: &Path) -> PathBuf {
+    if file.as_os_str().is_empty() {
+        report.scan_path.clone()
+    } else {
+        report.scan_path.join(file)
+    }
+}
+
+/// The body lines of a PEM private key starting at `start`, when its body
+/// is the hashed secret. Keys on one line with `\n` escapes give one value
+/// per escaped line.
+fn private_key(lines: &[&str], start: usize, hash: &str) -> Option<Vec<String>> {
+    let key = crate::agents::pem::key_block(lines, start)?;
+    if key.body.is_empty() || Finding::hash_secret(&key.body) != hash {
+        return None;
+    }
+    Some(
+        lines[start..=key.end]
+            .iter()
+            .flat_map(|line| line.split("\\n"))
+            .filter(|piece| !piece.contains("-----") && !piece.contains(':'))
+            .map(|piece| {
+                piece
+                    .chars()
+                    .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '='))
+                    .collect::<String>()
+            })
+            .filter(|piece| piece.len() >= MIN_SECRET_LEN)
+            .collect(),
+    )
+}
+
+/// Substrings of `line` whose hash is `hash`. Secrets start and end at a
+/// word boundary, which keeps the search small. A base64 value whose
+/// decoded form matches (Kubernetes Secret data) is returned in both forms.
+fn on_line(line: &str, hash: &str) -> Vec<String> {
+    let word = |c: char| c.is_alphanumeric() || c == '_';
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    let starts = chars
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i == 0 || !word(chars[i - 1].1) || !word(chars[i].1))
+        .map(|(_, &(offset, _))| offset);
+    let ends: Vec<usize> = chars
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i > 0 && (!word(chars[i].1) || !word(chars[i - 1].1)))
+        .map(|(_, &(offset, _))| offset)
+        .chain([line.len()])
+        .collect();
+
+    for start in starts {
+        for &end in ends.iter().filter(|&&end| end > start && end - start <= MAX_SECRET_LEN) {
+            let candidate = &line[start..end];
+            if Finding::hash_secret(candidate) == hash {
+                return vec![candidate.to_string()];
+            }
+            if let Some(decoded) = STANDARD
+                .decode(candidate)
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .filter(|decoded| Finding::hash_secret(decoded) == hash)
+            {
+                return vec![decoded, candidate.to_string()];
+            }
+        }
+    }
+    Vec::new()
+}
@@ -1,19 +1,432 @@
-use std::collections::HashSet;
-use crate::report::finding::Finding;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
 
-/// Deduplicate and sort findings
-pub fn merge_findings(mut findings: Vec<Finding>) -> Vec<Finding> {
+use anyhow::{bail, Result};
+use tracing::info;
+
+use crate::cli::exit_code;
+use crate::report::cache;
+use crate::report::finding::{
+    portable_path, ComplianceSummary, ControlResult, ControlStatus, Finding, GitContext, Hotspots, LanguageStats, Location,
+    Omitted, PolicyViolation, ScanCoverage, ScanReport, ScanSummary, ScanTimings, SuppressedCounts, REPORT_FORMAT_VERSION,
+};
+
+/// Caps applied after deduplication and sorting
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FindingLimits {
+    /// Maximum findings overall (0 = unlimited)
+    pub max_total: usize,
+    /// Maximum findings per rule (0 = unlimited)
+    pub max_per_rule: usize,
+}
+
+/// Deduplicate, sort, and cap findings.
+///
+/// Returns the kept findings and a tally of what the caps dropped.
+pub fn merge_findings(findings: Vec<Finding>, limits: FindingLimits) -> (Vec<Finding>, Omitted) {
+    apply_limits(consolidate(findings), limits)
+}
+
+/// Deduplicate, fold overlapping and repeated matches, and sort by severity
+pub fn consolidate(mut findings: Vec<Finding>) -> Vec<Finding> {
     // Deduplicate by deterministic ID
     let mut seen = HashSet::new();
     findings.retain(|f| seen.insert(f.id.clone()));
 
+    consolidate_overlaps(&mut findings);
+
     // Sort by severity (critical first), then by file path, then by line
     findings.sort_by(|a, b| {
         b.severity
             .cmp(&a.severity)
             .then_with(|| a.file_path.cmp(&b.file_path))
             .then_with(|| a.line_start.cmp(&b.line_start))
+            .then_with(|| a.column.cmp(&b.column))
     });
 
+    correlate_secrets(&mut findings);
     findings
 }
+
+/// Apply --max-findings / --max-per-rule to sorted findings
+pub fn apply_limits(mut findings: Vec<Finding>, limits: FindingLimits) -> (Vec<Finding>, Omitted) {
+    let mut omitted = Omitted::default();
+
+    // Per-rule cap: keep the first N of each rule (already in sorted order)
+    if limits.max_per_rule > 0 {
+        let mut per_rule: HashMap<String, usize> = HashMap::new();
+        let mut dropped: BTreeMap<String, usize> = BTreeMap::new();
+        findings.retain(|f| {
+            let count = per_rule.entry(f.rule_id.clone()).or_default();
+            *count += 1;
+            if *count > limits.max_per_rule {
+                *dropped.entry(f.rule_id.clone()).or_default() += 1;
+                false
+            } else {
+                true
+            }
+        });
+        omitted.total += dropped.values().sum::<usize>();
+        omitted.by_rule = dropped;
+    }
+
+    // Global cap: lowest-severity findings fall off the end
+    if limits.max_total > 0 && findings.len() > limits.max_total {
+        omitted.total += findings.len() - limits.max_total;
+        findings.truncate(limits.max_total);
+    }
+
+    (findings, omitted)
+}
+
+/// Fold matches of one agent that overlap on a line, and findings of the
+/// same secret on a line: the highest-confidence, then most severe match
+/// wins, and the other rules are listed in its `related_rules`. Unrelated
+/// issues on one line (an `eval` next to a hardcoded key) are each kept,
+/// and so are different secrets (a key ID and its secret key), since each
+/// needs rotating.
+fn consolidate_overlaps(findings: &mut Vec<Finding>) {
+    findings.sort_by(|a, b| {
+        a.file_path
+            .cmp(&b.file_path)
+            .then_with(|| a.line_start.cmp(&b.line_start))
+            .then_with(|| b.confidence.cmp(&a.confidence))
+            .then_with(|| b.severity.cmp(&a.severity))
+            .then_with(|| a.rule_id.cmp(&b.rule_id))
+    });
+
+    let mut merged: Vec<Finding> = Vec::with_capacity(findings.len());
+    // Start of the current file/line in `merged`
+    let mut line_start = 0;
+    for f in findings.drain(..) {
+        let same_line = merged
+            .get(line_start)
+            .is_some_and(|k| k.file_path == f.file_path && k.line_start == f.line_start);
+        if !same_line {
+            line_start = merged.len();
+        }
+        let folds = |k: &Finding| match (&k.secret_hash, &f.secret_hash) {
+            (Some(a), Some(b)) => a == b,
+            _ => k.agent == f.agent && overlaps(k, &f),
+        };
+        match merged[line_start..].iter_mut().find(|k| folds(k)) {
+            Some(kept) => {
+                for rule in std::iter::once(f.rule_id).chain(f.related_rules) {
+                    if kept.rule_id != rule && !kept.related_rules.contains(&rule) {
+                        kept.related_rules.push(rule);
+                    }
+                }
+            }
+            None => merged.push(f),
+        }
+    }
+    *findings = merged;
+}
+
+/// Whether two findings on one line cover any of the same columns. A
+/// finding that only knows where its match starts covers that column.
+fn overlaps(a: &Finding, b: &Finding) -> bool {
+    let end = |f: &Finding| f.column_end.max(f.column + 1);
+    a.column < end(b) && b.column < end(a)
+}
+
+/// Fold findings of the same secret value into the first (most severe)
+/// one, recording the others as additional locations.
+fn correlate_secrets(findings: &mut Vec<Finding>) {
+    let mut primary: HashMap<String, usize> = HashMap::new();
+    let mut folded: Vec<(usize, Location)> = Vec::new();
+    let mut keep = vec![true; findings.len()];
+
+    for (i, f) in findings.iter().enumerate() {
+        let Some(ref hash) = f.secret_hash else {
+            continue;
+        };
+        match primary.get(hash) {
+            Some(&first) => {
+                folded.push((first, Location { file_path: f.file_path.clone(), line: f.line_start }));
+                keep[i] = false;
+            }
+            None => {
+                primary.insert(hash.clone(), i);
+            }
+        }
+    }
+
+    for (first, location) in folded {
+        findings[first].additional_locations.push(location);
+    }
+    let mut keep = keep.into_iter();
+    findings.retain(|_| keep.next().unwrap_or(true));
+}
+
+/// `anty merge`: combine JSON reports of separate scans and write the result
+/// to `out`, or print it. Exits like `anty last`: findings give
+/// [`exit_code::FINDINGS`].
+pub fn run(inputs: &[PathBuf], out: Option<&Path>) -> Result<i32> {
+    let mut reports = Vec::with_capacity(inputs.len());
+    for path in inputs {
+        let report = cache::read(path)?;
+        if report.format_version > REPORT_FORMAT_VERSION {
+            bail!(
+                "{} is report format {}, newer than this version of Anty reads ({})",
+                path.display(),
+                report.format_version,
+                REPORT_FORMAT_VERSION
+            );
+        }
+        reports.push(report);
+    }
+
+    let merged = merge_reports(reports);
+    info!("Merged {} reports: {} findings", inputs.len(), merged.findings.len());
+    let json = crate::report::json::render(&merged)?;
+    match out {
+        Some(path) => {
+            std::fs::write(path, json)?;
+            info!("Report written to {}", path.display());
+        }
+        None => println!("{}", json),
+    }
+    Ok(exit_code::for_report(&merged, None, None, None))
+}
+
+/// Combine reports of separate scans (e.g. one per package in a sharded CI
+/// matrix) into one. Paths are rebased so that a scan of `packages/a`
+/// reports `packages/a/...` and its findings get the IDs a scan of the
+/// whole tree would give them. Findings are then deduplicated
+/// and sorted like a single scan's, and the summary is recomputed.
+pub fn merge_reports(reports: Vec<ScanReport>) -> ScanReport {
+    // Relative scan paths are relative to where each scan ran, usually the
+    // same checkout root in every shard, so they are kept whole
+    let roots: Vec<PathBuf> = reports.iter().map(|r| normalize(&r.scan_path)).collect();
+    let common = if roots.iter().all(|r| r.is_relative()) {
+        PathBuf::new()
+    } else {
+        roots.iter().skip(1).fold(roots.first().cloned().unwrap_or_default(), |common, root| {
+            common.components().zip(root.components()).take_while(|(a, b)| a == b).map(|(a, _)| a).collect()
+        })
+    };
+
+    let mut findings = Vec::new();
+    let mut test_findings = Vec::new();
+    let mut errors = Vec::new();
+    let mut skipped = Vec::new();
+    let mut packages = Vec::new();
+    let mut languages: Vec<LanguageStats> = Vec::new();
+    // Shards of one checkout share a commit; mixed commits have none
+    let mut git: Option<Option<GitContext>> = None;
+    let mut policy_violations: Vec<PolicyViolation> = Vec::new();
+    let mut expired_suppressions = Vec::new();
+    let mut compliance: Option<ComplianceSummary> = None;
+    let mut timings = ScanTimings::default();
+    let (mut files_scanned, mut files_skipped, mut duration_ms) = (0, 0, 0);
+    let mut omitted = Omitted::default();
+    let mut suppressed = 0;
+    let mut suppressed_by_rule: BTreeMap<String, SuppressedCounts> = BTreeMap::new();
+    // Any shard cut short by --timeout makes the merged report partial
+    let mut partial: Option<ScanCoverage> = None;
+    let mut stopped_early = false;
+
+    for (mut report, root) in reports.into_iter().zip(&roots) {
+        let prefix = root.strip_prefix(&common).unwrap_or(Path::new(""));
+        let rebase = |path: &Path| {
+            if prefix.as_os_str().is_empty() {
+                path.to_path_buf()
+            } else {
+                portable_path(&prefix.join(path))
+            }
+        };
+
+        // New IDs for rebased findings, to update the references to them
+        let mut ids: HashMap<String, String> = HashMap::new();
+        for f in report.findings.iter_mut().chain(report.test_findings.iter_mut()) {
+            f.file_path = rebase(&f.file_path);
+            for location in &mut f.additional_locations {
+                location.file_path = rebase(&location.file_path);
+            }
+            let id = Finding::generate_id(&f.rule_id, &f.file_path, f.line_start);
+            ids.insert(std::mem::replace(&mut f.id, id.clone()), id);
+        }
+        let remap = |old: &mut Vec<String>| {
+            for id in old.iter_mut() {
+                if let Some(new) = ids.get(id.as_str()) {
+                    id.clone_from(new);
+                }
+            }
+        };
+
+        for v in &mut report.policy_violations {
+            remap(&mut v.findings);
+        }
+        for v in report.policy_violations {
+            if !policy_violations.iter().any(|p| p.policy == v.policy && p.message == v.message) {
+                policy_violations.push(v);
+            }
+        }
+        for mut expired in report.expired_suppressions {
+            expired.file_path = rebase(&expired.file_path);
+            if let Some(new) = ids.get(&expired.id) {
+                expired.id.clone_from(new);
+            }
+            expired_suppressions.push(expired);
+        }
+        if let Some(mut summary) = report.compliance {
+            for control in &mut summary.controls {
+                remap(&mut control.findings);
+            }
+            match compliance {
+                Some(ref mut merged) if merged.framework == summary.framework => {
+                    merge_controls(&mut merged.controls, summary.controls)
+                }
+                Some(_) => {}
+                None => compliance = Some(summary),
+            }
+        }
+
+        findings.extend(report.findings);
+        test_findings.extend(report.test_findings);
+        errors.extend(report.errors.into_iter().map(|mut e| {
+            e.file_path = rebase(&e.file_path);
+            e
+        }));
+        skipped.extend(report.skipped.into_iter().map(|mut s| {
+            s.file_path = rebase(&s.file_path);
+            s
+        }));
+        packages.extend(report.packages.into_iter().map(|mut p| {
+            p.path = rebase(&p.path);
+            p
+        }));
+        git = Some(match (git, report.git) {
+            (None, this) => this,
+            (Some(Some(mut merged)), Some(this)) if merged.commit == this.commit => {
+                merged.dirty |= this.dirty;
+                Some(merged)
+            }
+            _ => None,
+        });
+        for stats in report.languages {
+            let Some(existing) = languages.iter_mut().find(|l| l.language == stats.language) else {
+                languages.push(stats);
+                continue;
+            };
+            existing.files += stats.files;
+            existing.lines += stats.lines;
+            for agent in stats.agents {
+                if !existing.agents.contains(&agent) {
+                    existing.agents.push(agent);
+                }
+            }
+        }
+
+        files_scanned += report.files_scanned;
+        files_skipped += report.files_skipped;
+        // Shards usually run side by side; the timings add up the work
+        duration_ms = duration_ms.max(report.duration_ms);
+        timings.discovery_ms += report.timings.discovery_ms;
+        timings.read_ms += report.timings.read_ms;
+        timings.scan_ms += report.timings.scan_ms;
+        timings.merge_ms += report.timings.merge_ms;
+        for (agent, ms) in report.timings.agents_ms {
+            *timings.agents_ms.entry(agent).or_default() += ms;
+        }
+        for (agent, files) in report.timings.agent_files {
+            *timings.agent_files.entry(agent).or_default() += files;
+        }
+        omitted.total += report.summary.omitted.total;
+        for (rule, count) in report.summary.omitted.by_rule {
+            *omitted.by_rule.entry(rule).or_default() += count;
+        }
+        suppressed += report.summary.suppressed;
+        for (rule, counts) in report.summary.suppressed_by_rule {
+            let merged = suppressed_by_rule.entry(rule).or_default();
+            merged.false_positive += counts.false_positive;
+            merged.accepted_risk += counts.accepted_risk;
+        }
+        stopped_early |= report.stopped_early;
+        if let Some(coverage) = report.partial {
+            let merged = partial.get_or_insert(ScanCoverage {
+                budget_secs: 0,
+                discovery_complete: true,
+                files_discovered: 0,
+                files_scanned: 0,
+                files_not_scanned: 0,
+            });
+            merged.budget_secs = merged.budget_secs.max(coverage.budget_secs);
+            merged.discovery_complete &= coverage.discovery_complete;
+            merged.files_discovered += coverage.files_discovered;
+            merged.files_scanned += coverage.files_scanned;
+            merged.files_not_scanned += coverage.files_not_scanned;
+        }
+    }
+
+    let findings = consolidate(findings);
+    let test_findings = consolidate(test_findings);
+    LanguageStats::finish(&mut languages);
+    let mut summary = ScanSummary::from_findings(&findings);
+    summary.omitted = omitted;
+    summary.suppressed = suppressed;
+    summary.suppressed_by_rule = suppressed_by_rule;
+    summary.hotspots = Hotspots::from_findings(&findings);
+
+    ScanReport {
+        format_version: REPORT_FORMAT_VERSION,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        scan_path: if common.as_os_str().is_empty() { PathBuf::from(".") } else { common },
+        files_scanned,
+        files_skipped,
+        duration_ms,
+        timings,
+        findings,
+        test_findings,
+        errors,
+        skipped,
+        policy_violations,
+        expired_suppressions,
+        compliance,
+        packages,
+        git: git.flatten(),
+        languages,
+        summary,
+        partial,
+        stopped_early,
+        deterministic: false,
+        editor_url_template: None,
+    }
+}
+
+/// Fold one report's framework controls into another's: a control is
+/// violated if any scan violated it, passed if any scan checked it
+fn merge_controls(into: &mut Vec<ControlResult>, controls: Vec<ControlResult>) {
+    let rank = |status: ControlStatus| match status {
+        ControlStatus::Violated => 2,
+        ControlStatus::Passed => 1,
+        ControlStatus::NotChecked => 0,
+    };
+    for control in controls {
+        let Some(existing) = into.iter_mut().find(|c| c.id == control.id) else {
+            into.push(control);
+            continue;
+        };
+        if rank(control.status) > rank(existing.status) {
+            existing.status = control.status;
+        }
+        for rule in control.rules {
+            if !existing.rules.contains(&rule) {
+                existing.rules.push(rule);
+            }
+        }
+        for id in control.findings {
+            if !existing.findings.contains(&id) {
+                existing.findings.push(id);
+            }
+        }
+    }
+}
+
+/// `path` without `.` components, so `./packages/a` and `packages/a` share
+/// a root
+fn normalize(path: &Path) -> PathBuf {
+    portable_path(&path.components().filter(|c| *c != Component::CurDir).collect::<PathBuf>())
+}
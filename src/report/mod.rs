@@ -1,4 +1,186 @@
 pub mod finding;
 pub mod merger;
 pub mod json;
+pub mod compact;
+pub mod sarif;
+pub mod html;
 pub mod terminal;
+pub mod signature;
+pub mod explain;
+pub mod mask;
+pub mod badge;
+pub mod cache;
+pub mod delta;
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use tracing::info;
+
+use crate::cli::ScanArgs;
+use finding::ScanReport;
+
+/// Every report format, for `--format` and `output.format`
+pub const FORMATS: &[&str] = &["terminal", "json", "sarif", "html", "compact", "count"];
+
+/// Formats written to files when `--out` is a directory, with their extensions
+const FILE_FORMATS: &[(&str, &str)] = &[("json", "json"), ("sarif", "sarif"), ("html", "html"), ("compact", "txt")];
+
+/// Formats `--sign-report` can sign: the signature covers canonical JSON
+const SIGNED_FORMATS: &[&str] = &["json", "sarif"];
+
+/// File name (without extension) of the reports written to an `--out` directory
+pub const REPORT_STEM: &str = "anty-report";
+
+/// Render a finished scan in the format requested by `args`, writing the
+/// JSON report to `--out` when given. With several formats (`--format
+/// json,sarif,html`) or a directory for `--out`, each file format is
+/// written to `<dir>/anty-report.<ext>` instead.
+pub fn emit(report: &ScanReport, args: &ScanArgs, verbose: bool) -> Result<()> {
+    let opts = terminal::RenderOptions {
+        group_by: args.group_by.as_deref().and_then(terminal::GroupBy::from_str),
+        summary_only: args.summary_only,
+        show_timings: verbose,
+    };
+    let formats = check_format(args)?;
+    if formats.len() > 1 || args.out.as_deref().is_some_and(Path::is_dir) {
+        if let Some(ref dir) = args.out {
+            return emit_to_dir(report, args, &formats, dir, &opts);
+        }
+    }
+
+    match (formats[0], &args.out) {
+        (format @ ("json" | "sarif" | "html"), Some(path)) => {
+            std::fs::write(path, render(report, format)?)?;
+            info!("Report written to {}", path.display());
+            return sign_if_requested(path, args);
+        }
+        ("terminal", Some(path)) if args.diff_against_previous => render_delta(report, path, &opts),
+        (format, _) => print(report, format, &opts)?,
+    }
+
+    if let Some(ref path) = args.out {
+        let json_output = json::render(report)?;
+        std::fs::write(path, &json_output)?;
+        info!("JSON report also written to {}", path.display());
+        sign_if_requested(path, args)?;
+    }
+    Ok(())
+}
+
+/// Print the stdout formats (terminal, count) first, then write one file
+/// per file format into `dir`, so --diff-against-previous still sees the
+/// previous JSON report
+fn emit_to_dir(
+    report: &ScanReport,
+    args: &ScanArgs,
+    formats: &[&str],
+    dir: &Path,
+    opts: &terminal::RenderOptions,
+) -> Result<()> {
+    let json_path = dir.join(format!("{}.json", REPORT_STEM));
+    for format in formats.iter().filter(|f| extension(f).is_none()) {
+        if *format == "terminal" && args.diff_against_previous {
+            render_delta(report, &json_path, opts);
+        } else {
+            print(report, format, opts)?;
+        }
+    }
+
+    std::fs::create_dir_all(dir).with_context(|| format!("Could not create report directory {}", dir.display()))?;
+    for format in formats {
+        let Some(ext) = extension(format) else {
+            continue;
+        };
+        let path = dir.join(format!("{}.{}", REPORT_STEM, ext));
+        std::fs::write(&path, render(report, format)?)
+            .with_context(|| format!("Could not write {}", path.display()))?;
+        info!("{} report written to {}", format, path.display());
+        if SIGNED_FORMATS.contains(format) {
+            sign_if_requested(&path, args)?;
+        }
+    }
+    Ok(())
+}
+
+/// The formats of `--format`, checked before scanning so that a typo does
+/// not cost a whole scan
+pub fn check_format(args: &ScanArgs) -> Result<Vec<&str>> {
+    let formats = parse_formats(&args.format)?;
+    if formats.len() > 1 && args.out.is_none() {
+        bail!("--format {} writes several reports; give a directory for them with --out", args.format);
+    }
+    // Written to a file alone, terminal and count still put JSON in --out
+    let to_dir = formats.len() > 1 || args.out.as_deref().is_some_and(Path::is_dir);
+    let signs = formats
+        .iter()
+        .any(|f| SIGNED_FORMATS.contains(f) || (!to_dir && extension(f).is_none()));
+    if args.sign_report.is_some() && !signs {
+        bail!("--sign-report signs JSON and SARIF reports; add json or sarif to --format {}", args.format);
+    }
+    Ok(formats)
+}
+
+/// Split a comma-separated `--format` value, rejecting unknown formats
+fn parse_formats(value: &str) -> Result<Vec<&str>> {
+    let mut formats: Vec<&str> = Vec::new();
+    for format in value.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+        if !FORMATS.contains(&format) {
+            bail!("Unknown format '{}' (expected {})", format, FORMATS.join(", "));
+        }
+        if !formats.contains(&format) {
+            formats.push(format);
+        }
+    }
+    if formats.is_empty() {
+        bail!("No format given (expected {})", FORMATS.join(", "));
+    }
+    Ok(formats)
+}
+
+fn extension(format: &str) -> Option<&'static str> {
+    FILE_FORMATS.iter().find(|(f, _)| *f == format).map(|(_, ext)| *ext)
+}
+
+/// A report as file contents, for the formats that have one
+fn render(report: &ScanReport, format: &str) -> Result<String> {
+    Ok(match format {
+        "sarif" => sarif::render(report)?,
+        "html" => html::render(report),
+        "compact" => compact::render(report),
+        _ => json::render(report)?,
+    })
+}
+
+/// Print only new and resolved findings relative to the report at `previous`
+fn render_delta(report: &ScanReport, previous: &Path, opts: &terminal::RenderOptions) {
+    match cache::read(previous) {
+        Ok(previous) => terminal::render_delta(report, &previous),
+        Err(e) => {
+            info!("No previous report to compare with ({:#}); showing the full report", e);
+            terminal::render(report, opts);
+        }
+    }
+}
+
+/// Print a report to stdout in `format` (see [`FORMATS`])
+pub fn print(report: &ScanReport, format: &str, opts: &terminal::RenderOptions) -> Result<()> {
+    match format {
+        "json" => println!("{}", json::render(report)?),
+        "sarif" => println!("{}", sarif::render(report)?),
+        "html" => print!("{}", html::render(report)),
+        "compact" => print!("{}", compact::render(report)),
+        "count" => println!("{}", report.summary.total),
+        _ => terminal::render(report, opts),
+    }
+    Ok(())
+}
+
+/// Write a detached signature for the report at `path` (--sign-report)
+fn sign_if_requested(path: &std::path::Path, args: &ScanArgs) -> Result<()> {
+    if let Some(ref key_file) = args.sign_report {
+        let sig = signature::sign(path, key_file)?;
+        info!("Report signature written to {}", sig.display());
+    }
+    Ok(())
+}
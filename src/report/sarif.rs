@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::report::finding::{Finding, Location, ScanReport, Severity};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Render a scan report as SARIF 2.1.0, the format GitHub code scanning and
+/// most IDEs and security dashboards import
+pub fn render(report: &ScanReport) -> Result<String> {
+    // One rule entry per rule that has findings, in rule ID order
+    let mut rules: BTreeMap<&str, &Finding> = BTreeMap::new();
+    for f in &report.findings {
+        rules.entry(f.rule_id.as_str()).or_insert(f);
+    }
+    let index: BTreeMap<&str, usize> = rules.keys().enumerate().map(|(i, id)| (*id, i)).collect();
+
+    let results: Vec<Value> = report
+        .findings
+        .iter()
+        .map(|f| {
+            let mut result = json!({
+                "ruleId": f.rule_id,
+                "ruleIndex": index[f.rule_id.as_str()],
+                "level": level(f.severity),
+                "message": { "text": format!("{}: {}", f.title, f.description) },
+                "locations": [location(&f.file_path, f.line_start, f.line_end, f.column, Some(&f.evidence))],
+                "partialFingerprints": { "antyFindingId/v1": f.id },
+                "properties": {
+                    "agent": f.agent,
+                    "confidence": f.confidence.as_str(),
+                },
+            });
+            if !f.additional_locations.is_empty() {
+                result["relatedLocations"] = f
+                    .additional_locations
+                    .iter()
+                    .enumerate()
+                    .map(|(i, Location { file_path, line })| {
+                        let mut related = location(file_path, *line, *line, 0, None);
+                        related["id"] = json!(i + 1);
+                        related
+                    })
+                    .collect();
+            }
+            if let Some(score) = f.score {
+                result["properties"]["score"] = json!(score);
+            }
+            if let Some(ref fix) = f.fix {
+                result["fixes"] = json!([{
+                    "description": { "text": fix.description },
+                    "artifactChanges": [{
+                        "artifactLocation": { "uri": f.file_path.to_string_lossy().replace('\\', "/") },
+                        "replacements": [{
+                            "deletedRegion": {
+                                "startLine": fix.line,
+                                "startColumn": fix.start_column,
+                                "endLine": fix.line,
+                                "endColumn": fix.end_column,
+                            },
+                            "insertedContent": { "text": fix.replacement },
+                        }],
+                    }],
+                }]);
+            }
+            if let Some(environment) = f.environment {
+                result["properties"]["environment"] = json!(environment.as_str());
+            }
+            result
+        })
+        .collect();
+
+    let mut run = json!({
+        "tool": {
+            "driver": {
+                "name": "Anty",
+                "version": report.version,
+                "informationUri": "https://anty.dev",
+                "rules": rules.values().map(|f| rule(f)).collect::<Vec<_>>(),
+            },
+        },
+        "results": results,
+    });
+    // SARIF requires a repository URI for version control details
+    if let Some(git) = report.git.as_ref().filter(|g| g.remote_url.is_some()) {
+        let mut provenance = json!({ "repositoryUri": git.remote_url, "revisionId": git.commit });
+        if let Some(ref branch) = git.branch {
+            provenance["branch"] = json!(branch);
+        }
+        run["versionControlProvenance"] = json!([provenance]);
+    }
+
+    let sarif = json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [run],
+    });
+    Ok(serde_json::to_string_pretty(&sarif)?)
+}
+
+fn rule(f: &Finding) -> Value {
+    let mut tags = vec!["security".to_string()];
+    tags.extend(f.cwe_id.clone());
+    let mut rule = json!({
+        "id": f.rule_id,
+        "name": f.title,
+        "shortDescription": { "text": f.title },
+        "fullDescription": { "text": f.description },
+        "help": { "text": f.recommendation },
+        "defaultConfiguration": { "level": level(f.severity) },
+        "properties": {
+            "tags": tags,
+            // GitHub buckets these into its own critical/high/medium/low
+            "security-severity": security_severity(f.severity),
+        },
+    });
+    if let Some(ref url) = f.docs_url {
+        rule["helpUri"] = json!(url);
+    }
+    rule
+}
+
+fn location(path: &std::path::Path, line_start: usize, line_end: usize, column: usize, snippet: Option<&str>) -> Value {
+    let mut region = json!({
+        "startLine": line_start.max(1),
+        "endLine": line_end.max(line_start).max(1),
+    });
+    if column > 0 {
+        region["startColumn"] = json!(column);
+    }
+    if let Some(text) = snippet {
+        region["snippet"] = json!({ "text": text });
+    }
+    json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": path.to_string_lossy().replace('\\', "/") },
+            "region": region,
+        }
+    })
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "note",
+    }
+}
+
+fn security_severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "9.5",
+        Severity::High => "8.0",
+        Severity::Medium => "5.5",
+        Severity::Low => "3.0",
+    }
+}
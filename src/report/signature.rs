@@ -0,0 +1,116 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::report::finding::REPORT_FORMAT_VERSION;
+
+const ALGORITHM: &str = "hmac-sha256";
+
+/// Detached signature stored next to a report (`report.json.sig`)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Signature {
+    pub algorithm: String,
+    pub format_version: u32,
+    /// First 16 hex digits of SHA-256 over the key, to tell keys apart
+    pub key_fingerprint: String,
+    /// Hex HMAC over the canonical report JSON
+    pub signature: String,
+}
+
+/// Default location of the signature for `report`
+pub fn signature_path(report: &Path) -> PathBuf {
+    let mut name = report.as_os_str().to_owned();
+    name.push(".sig");
+    PathBuf::from(name)
+}
+
+/// Sign the JSON report at `report` with the key in `key_file`, writing
+/// the signature beside it. Returns the signature path.
+pub fn sign(report: &Path, key_file: &Path) -> Result<PathBuf> {
+    let key = read_key(key_file)?;
+    let canonical = canonical_json(report)?;
+
+    let mut mac = new_mac(&key);
+    mac.update(canonical.as_bytes());
+    let signature = Signature {
+        algorithm: ALGORITHM.to_string(),
+        format_version: REPORT_FORMAT_VERSION,
+        key_fingerprint: fingerprint(&key),
+        signature: format!("{:x}", mac.finalize().into_bytes()),
+    };
+
+    let path = signature_path(report);
+    std::fs::write(&path, serde_json::to_string_pretty(&signature)?)
+        .with_context(|| format!("Could not write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Check `report` against its detached signature. Errors describe why
+/// verification failed; `Ok` means the report is unchanged since signing.
+pub fn verify(report: &Path, signature_file: &Path, key_file: &Path) -> Result<()> {
+    let key = read_key(key_file)?;
+    let raw = std::fs::read_to_string(signature_file)
+        .with_context(|| format!("Could not read {}", signature_file.display()))?;
+    let signature: Signature = serde_json::from_str(&raw)
+        .with_context(|| format!("{} is not an Anty signature", signature_file.display()))?;
+
+    if signature.algorithm != ALGORITHM {
+        bail!("Unsupported signature algorithm '{}'", signature.algorithm);
+    }
+    if signature.key_fingerprint != fingerprint(&key) {
+        bail!("Signed with a different key (fingerprint {})", signature.key_fingerprint);
+    }
+    let expected = decode_hex(&signature.signature).context("Malformed signature")?;
+
+    let mut mac = new_mac(&key);
+    mac.update(canonical_json(report)?.as_bytes());
+    // Constant-time comparison
+    mac.verify_slice(&expected)
+        .map_err(|_| anyhow::anyhow!("Signature does not match: the report was modified"))
+}
+
+/// The report as compact JSON with sorted keys, so reformatting the file
+/// (pretty-printing, key order) does not invalidate the signature
+fn canonical_json(report: &Path) -> Result<String> {
+    let raw = std::fs::read_to_string(report)
+        .with_context(|| format!("Could not read {}", report.display()))?;
+    let value: serde_json::Value = serde_json::from_str(&raw)
+        .with_context(|| format!("{} is not a JSON report", report.display()))?;
+    // serde_json's map is a BTreeMap, so keys serialize in sorted order
+    Ok(serde_json::to_string(&value)?)
+}
+
+fn read_key(key_file: &Path) -> Result<Vec<u8>> {
+    let mut key = std::fs::read(key_file)
+        .with_context(|| format!("Could not read key file {}", key_file.display()))?;
+    // Key files written with `echo` end in a newline
+    while key.last().is_some_and(|b| *b == b'\n' || *b == b'\r') {
+        key.pop();
+    }
+    if key.is_empty() {
+        bail!("Key file {} is empty", key_file.display());
+    }
+    Ok(key)
+}
+
+fn new_mac(key: &[u8]) -> Hmac<Sha256> {
+    Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length")
+}
+
+fn fingerprint(key: &[u8]) -> String {
+    let hex = format!("{:x}", Sha256::digest(key));
+    hex[..16].to_string()
+}
+
+pub(crate) fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
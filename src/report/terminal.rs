@@ -1,108 +1,879 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anstream::println;
+use comfy_table::{presets::UTF8_FULL_CONDENSED, Table};
 use owo_colors::OwoColorize;
-// comfy-table available for future use
 
-use crate::report::finding::{ScanReport, Severity};
+use crate::cli::color;
+use crate::i18n::t;
+use crate::report::delta;
+use crate::report::finding::{ControlStatus, Environment, Finding, Remediation, ScanReport, Severity, UNOWNED};
+
+/// Maximum findings listed under a single group header
+const MAX_PER_GROUP: usize = 20;
+
+/// How to group findings in the terminal report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    File,
+    Rule,
+    Severity,
+    Agent,
+    Owner,
+}
+
+impl GroupBy {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "file" => Some(GroupBy::File),
+            "rule" => Some(GroupBy::Rule),
+            "severity" => Some(GroupBy::Severity),
+            "agent" => Some(GroupBy::Agent),
+            "owner" => Some(GroupBy::Owner),
+            _ => None,
+        }
+    }
+}
+
+/// Terminal rendering options
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// Group findings under headers instead of listing each one in full
+    pub group_by: Option<GroupBy>,
+    /// Only print the severity counts table
+    pub summary_only: bool,
+    /// Print the per-phase / per-agent timing breakdown
+    pub show_timings: bool,
+}
+
+/// OSC 8 hyperlinks from `file:line` to an editor and from rule IDs to
+/// their docs. Plain text when the terminal cannot show links.
+pub struct Links {
+    enabled: bool,
+    /// Absolute scan path that finding paths are relative to
+    root: Option<PathBuf>,
+    /// `output.editor_url_template`; `file:///{path}` when unset
+    template: Option<String>,
+}
+
+impl Links {
+    pub fn for_report(report: &ScanReport) -> Self {
+        let enabled = color::hyperlinks();
+        Links {
+            enabled,
+            // Only touch the filesystem when links will actually be shown
+            root: enabled.then(|| std::fs::canonicalize(&report.scan_path).ok()).flatten(),
+            template: report.editor_url_template.clone(),
+        }
+    }
+
+    /// `path:line` of a finding, linked to the file in the editor
+    fn location(&self, finding: &Finding) -> String {
+        let text = format!("{}:{}", finding.file_path.display(), finding.line_start);
+        let Some(ref root) = self.root else {
+            return text;
+        };
+        // Single-file scans report an empty path relative to the file itself
+        let path = if finding.file_path.as_os_str().is_empty() {
+            root.clone()
+        } else {
+            root.join(&finding.file_path)
+        };
+        let path = url_path(&path);
+        let url = match self.template {
+            Some(ref template) => template
+                .replace("{path}", &path)
+                .replace("{line}", &finding.line_start.to_string())
+                .replace("{column}", &finding.column.to_string()),
+            None => format!("file:///{}", path),
+        };
+        color::link(&text, &url)
+    }
+
+    /// A finding's rule ID, linked to the rule's documentation page
+    fn rule(&self, finding: &Finding) -> String {
+        match finding.docs_url {
+            Some(ref url) if self.enabled => color::link(&finding.rule_id, url),
+            _ => finding.rule_id.clone(),
+        }
+    }
+}
+
+/// An absolute path in URL form, without the leading slash so that
+/// `scheme://file/{path}` works for both `/home/...` and `C:\...`.
+/// Backslashes become slashes; spaces and other unsafe bytes are encoded.
+fn url_path(path: &std::path::Path) -> String {
+    let path = path.to_string_lossy();
+    // canonicalize() returns verbatim paths (\\?\C:\...) on Windows
+    let path = path.strip_prefix(r"\\?\").unwrap_or(&path).replace('\\', "/");
+    let path = path.trim_start_matches('/');
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Render a scan report to the terminal, with colors when enabled
+pub fn render(report: &ScanReport, opts: &RenderOptions) {
+    println!();
+    let header = if report.deterministic {
+        t!("terminal.header_deterministic", version = report.version, files = report.files_scanned)
+    } else {
+        t!(
+            "terminal.header",
+            version = report.version,
+            files = report.files_scanned,
+            seconds = format!("{:.2}", report.duration_ms as f64 / 1000.0)
+        )
+    };
+    println!("{}{}", color::icon("🔍  ", "").bold(), header);
+    println!();
+
+    if report.stopped_early {
+        println!("  {}", t!("terminal.stopped_early").yellow());
+        println!();
+    }
+    if let Some(ref coverage) = report.partial {
+        let key = if coverage.discovery_complete { "terminal.partial" } else { "terminal.partial_discovery" };
+        println!(
+            "  {}",
+            t!(
+                key,
+                seconds = coverage.budget_secs,
+                scanned = coverage.files_scanned,
+                discovered = coverage.files_discovered
+            )
+            .yellow()
+        );
+        println!();
+    }
+
+    if opts.show_timings {
+        if !report.deterministic {
+            render_timings(report);
+        }
+        render_languages(report);
+    }
+
+    if opts.summary_only {
+        render_counts_table(report);
+        render_test_findings(report, true);
+        render_policy(report);
+        render_expired(report);
+        render_compliance(report);
+        render_packages(report);
+        render_hotspots(report);
+        render_coverage_gaps(report);
+        return;
+    }
 
-/// Render a scan report to the terminal with colors
-pub fn render(report: &ScanReport) {
+    if report.findings.is_empty() {
+        println!(
+            "  {}{}",
+            color::icon("✅  ", "").bold(),
+            t!("terminal.no_issues")
+        );
+        if report.summary.suppressed > 0 {
+            println!(
+                "      {}",
+                t!("terminal.suppressed", count = report.summary.suppressed).dimmed()
+            );
+        }
+        println!();
+        render_test_findings(report, false);
+        render_policy(report);
+        render_expired(report);
+        render_compliance(report);
+        render_coverage_gaps(report);
+        render_errors(report);
+        render_skipped(report);
+        return;
+    }
+
+    let links = Links::for_report(report);
+    match opts.group_by {
+        Some(group_by) => render_grouped(&report.findings, group_by, &links),
+        None => {
+            for finding in &report.findings {
+                render_finding(finding, &links);
+            }
+        }
+    }
+
+    render_summary(report);
+    render_test_findings(report, false);
+    render_policy(report);
+    render_expired(report);
+    render_compliance(report);
+    render_packages(report);
+    render_hotspots(report);
+    render_coverage_gaps(report);
+    render_errors(report);
+    render_skipped(report);
+}
+
+/// Print only what changed since `previous` (--diff-against-previous):
+/// new findings in full and resolved ones as one line each
+pub fn render_delta(report: &ScanReport, previous: &ScanReport) {
+    let delta = delta::compare(previous, report);
     println!();
     println!(
-        "{}  Anty v{} — Scanned {} files in {:.2}s",
-        "🔍".bold(),
+        "{}Anty v{} — Scanned {} files in {:.2}s, compared with the report from {}",
+        color::icon("🔍  ", "").bold(),
         report.version,
         report.files_scanned,
-        report.duration_ms as f64 / 1000.0
+        report.duration_ms as f64 / 1000.0,
+        previous.timestamp
     );
     println!();
 
-    if report.findings.is_empty() {
+    if delta.new.is_empty() && delta.resolved.is_empty() {
         println!(
-            "  {}  No security issues found!",
-            "✅".bold()
+            "  {}No changes since the previous scan ({} findings)",
+            color::icon("✅  ", "").bold(),
+            delta.unchanged
         );
         println!();
+    }
+
+    let links = Links::for_report(report);
+    if !delta.new.is_empty() {
+        println!(" {}", format!("{} new:", delta.new.len()).red().bold());
+        println!();
+        for finding in &delta.new {
+            render_finding(finding, &links);
+        }
+    }
+
+    if !delta.resolved.is_empty() {
+        println!(" {}", format!("{} resolved:", delta.resolved.len()).green().bold());
+        for finding in &delta.resolved {
+            println!(
+                "  {} {}  {}:{}  {}  {}",
+                color::icon("✓", "-").green(),
+                severity_badge(finding.severity),
+                finding.file_path.display(),
+                finding.line_start,
+                finding.rule_id.dimmed(),
+                finding.title.dimmed()
+            );
+        }
+        println!();
+    }
+
+    println!("{}", "━".repeat(60));
+    println!(
+        " {} new{}, {} resolved, {} unchanged",
+        delta.new.len().to_string().bold(),
+        severity_counts(&delta.new),
+        delta.resolved.len().to_string().bold(),
+        delta.unchanged
+    );
+    println!();
+    render_policy(report);
+    render_expired(report);
+    render_errors(report);
+}
+
+/// " (1 critical, 2 medium)" for a list of findings, colored like the summary bar
+fn severity_counts(findings: &[&Finding]) -> String {
+    let count = |severity| findings.iter().filter(|f| f.severity == severity).count();
+    let parts: Vec<String> = [
+        (Severity::Critical, "critical"),
+        (Severity::High, "high"),
+        (Severity::Medium, "medium"),
+        (Severity::Low, "low"),
+    ]
+    .into_iter()
+    .filter(|(severity, _)| count(*severity) > 0)
+    .map(|(severity, name)| {
+        let text = format!("{} {}", count(severity), name);
+        match severity {
+            Severity::Critical => text.red().bold().to_string(),
+            Severity::High => text.yellow().bold().to_string(),
+            Severity::Medium => text.blue().to_string(),
+            Severity::Low => text.white().to_string(),
+        }
+    })
+    .collect();
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", parts.join(", "))
+    }
+}
+
+/// Findings in test files (--separate-tests), one line each: real, but
+/// not held to the same bar as production code
+fn render_test_findings(report: &ScanReport, summary_only: bool) {
+    if report.test_findings.is_empty() {
         return;
     }
+    let findings: Vec<&Finding> = report.test_findings.iter().collect();
+    println!(
+        " {}{}",
+        t!("terminal.test_findings", count = findings.len()).bold(),
+        severity_counts(&findings)
+    );
+    if !summary_only {
+        for f in &findings {
+            println!(
+                "   {:<8}  {}:{}  {}  {}",
+                f.severity.as_str(),
+                f.file_path.display(),
+                f.line_start,
+                f.rule_id.dimmed(),
+                f.title
+            );
+        }
+    }
+    println!();
+}
 
-    // Print each finding
-    for finding in &report.findings {
-        let severity_display = format!(" {} ", finding.severity);
-        let severity_colored = match finding.severity {
-            Severity::Critical => severity_display.on_red().white().bold().to_string(),
-            Severity::High => severity_display.on_yellow().black().bold().to_string(),
-            Severity::Medium => severity_display.on_blue().white().bold().to_string(),
-            Severity::Low => severity_display.on_white().black().to_string(),
-        };
+/// List skipped files grouped by reason (--explain-skips)
+fn render_skipped(report: &ScanReport) {
+    if report.skipped.is_empty() {
+        return;
+    }
+    println!(" {}", t!("terminal.skipped", count = report.skipped.len()).bold());
+    // Already sorted by reason, then path
+    let mut current = None;
+    for skip in &report.skipped {
+        if current != Some(skip.reason) {
+            current = Some(skip.reason);
+            println!("   {}", skip.reason.describe().dimmed());
+        }
+        println!("     {}", skip.file_path.display());
+    }
+    println!();
+}
 
+/// List files the scan gave up on, so gaps in coverage are visible
+fn render_errors(report: &ScanReport) {
+    if report.errors.is_empty() {
+        return;
+    }
+    println!(
+        " {} {}",
+        color::icon("⚠", "!").yellow(),
+        t!("terminal.not_fully_scanned", count = report.errors.len()).yellow()
+    );
+    for error in &report.errors {
+        let message = match error.agent {
+            Some(ref agent) => format!("{}: {}", agent, error.message),
+            None => error.message.clone(),
+        };
         println!(
-            "  {}  {}:{}",
-            severity_colored,
-            finding.file_path.display().dimmed(),
-            finding.line_start.to_string().dimmed(),
+            "   {}  {}",
+            error.file_path.display(),
+            message.dimmed()
         );
+    }
+    println!();
+}
+
+/// Colored severity badge, e.g. " CRITICAL " on red
+fn severity_badge(severity: Severity) -> String {
+    let severity_display = format!(" {} ", severity);
+    match severity {
+        Severity::Critical => severity_display.on_red().white().bold().to_string(),
+        Severity::High => severity_display.on_yellow().black().bold().to_string(),
+        Severity::Medium => severity_display.on_blue().white().bold().to_string(),
+        Severity::Low => severity_display.on_white().black().to_string(),
+    }
+}
+
+/// Print a single finding as a full block
+pub fn render_finding(finding: &Finding, links: &Links) {
+    let score = finding.score.map(|s| format!("  score {:.1}", s)).unwrap_or_default();
+    // prod-unknown is the default, so only dev and test get a tag
+    let environment = match finding.environment {
+        Some(env @ (Environment::Dev | Environment::Test)) => format!("  [{}]", env),
+        _ => String::new(),
+    };
+    println!(
+        "  {}  {}  {}{}{}",
+        severity_badge(finding.severity),
+        links.location(finding).dimmed(),
+        links.rule(finding).dimmed(),
+        score.dimmed(),
+        environment.cyan(),
+    );
+    println!(
+        "           {}",
+        finding.title.bold()
+    );
+
+    // Evidence (trimmed, max 120 chars per line)
+    let evidence = finding.evidence.trim();
+    if !evidence.is_empty() {
+        for line in evidence.lines().take(3) {
+            let trimmed = if line.chars().count() > 120 {
+                let truncated: String = line.chars().take(119).collect();
+                format!("{}…", truncated)
+            } else {
+                line.to_string()
+            };
+            println!("           → {}", trimmed.dimmed());
+        }
+    }
+
+    // Recommendation
+    println!(
+        "           {} {}",
+        color::icon("⮕", "->").green(),
+        finding.recommendation.green()
+    );
+
+    if let Some(ref blame) = finding.blame {
+        let short = &blame.commit[..blame.commit.len().min(8)];
         println!(
             "           {}",
-            finding.title.bold()
-        );
-
-        // Evidence (trimmed, max 120 chars per line)
-        let evidence = finding.evidence.trim();
-        if !evidence.is_empty() {
-            for line in evidence.lines().take(3) {
-                let trimmed = if line.chars().count() > 120 {
-                    let truncated: String = line.chars().take(119).collect();
-                    format!("{}…", truncated)
-                } else {
-                    line.to_string()
-                };
-                println!("           → {}", trimmed.dimmed());
-            }
+            t!(
+                "terminal.added_by",
+                author = blame.author,
+                date = blame.date,
+                commit = short,
+                summary = blame.summary
+            )
+            .dimmed()
+        );
+    }
+
+    if let Some(ref triage) = finding.triage {
+        let mut line = t!("terminal.triage", status = triage.status).to_string();
+        if let Some(ref assignee) = triage.assignee {
+            line.push_str(&format!(" · {}", t!("terminal.assigned_to", assignee = assignee)));
         }
+        if let Some(ref note) = triage.note {
+            line.push_str(&format!(" — {}", note));
+        }
+        println!("           {}", line.cyan());
+    }
 
-        // Recommendation
+    if !finding.related_rules.is_empty() {
+        println!(
+            "           {}",
+            t!("terminal.also_matched", rules = finding.related_rules.join(", ")).dimmed()
+        );
+    }
+
+    if !finding.additional_locations.is_empty() {
+        let locations: Vec<String> = finding
+            .additional_locations
+            .iter()
+            .map(|l| format!("{}:{}", l.file_path.display(), l.line))
+            .collect();
+        println!(
+            "           {} {}",
+            t!("terminal.same_secret", count = locations.len()).bold(),
+            locations.join(", ").dimmed()
+        );
+    }
+
+    if let Some(ref remediation) = finding.remediation {
+        render_remediation(remediation);
+    }
+    println!();
+}
+
+/// Print a provider playbook under a finding
+fn render_remediation(remediation: &Remediation) {
+    if let Some(ref url) = remediation.revoke_url {
         println!(
             "           {} {}",
-            "⮕".green(),
-            finding.recommendation.green()
+            t!("terminal.revoke", provider = remediation.provider).bold(),
+            url.cyan()
         );
+    }
+    for (i, step) in remediation.rotation_steps.iter().enumerate() {
+        println!("             {}. {}", i + 1, step);
+    }
+    if let Some(ref url) = remediation.docs_url {
+        println!("           {} {}", t!("terminal.docs").bold(), url.dimmed());
+    }
+}
+
+/// Print findings grouped under one header per key, with one line per finding
+fn render_grouped(findings: &[Finding], group_by: GroupBy, links: &Links) {
+    let mut groups: BTreeMap<String, Vec<&Finding>> = BTreeMap::new();
+    for finding in findings {
+        let keys = match group_by {
+            GroupBy::File => vec![finding.file_path.display().to_string()],
+            GroupBy::Rule => vec![format!("{} — {}", finding.rule_id, finding.title)],
+            GroupBy::Severity => vec![finding.severity.to_string()],
+            GroupBy::Agent => vec![finding.agent.clone()],
+            // A finding with several owners is listed under each of them
+            GroupBy::Owner if finding.owners.is_empty() => vec![UNOWNED.to_string()],
+            GroupBy::Owner => finding.owners.clone(),
+        };
+        for key in keys {
+            groups.entry(key).or_default().push(finding);
+        }
+    }
+
+    // Most severe groups first, then the largest
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by(|(_, a), (_, b)| {
+        let max_a = a.iter().map(|f| f.severity).max();
+        let max_b = b.iter().map(|f| f.severity).max();
+        max_b.cmp(&max_a).then_with(|| b.len().cmp(&a.len()))
+    });
+
+    for (key, members) in &groups {
+        let top = members.iter().map(|f| f.severity).max().unwrap_or(Severity::Low);
+        let header = match group_by {
+            GroupBy::Rule => format!("{} — {}", links.rule(members[0]), members[0].title),
+            _ => key.clone(),
+        };
+        println!(
+            "  {}  {} {}",
+            severity_badge(top),
+            header.bold(),
+            format!("({})", members.len()).dimmed()
+        );
+
+        if group_by == GroupBy::Rule {
+            println!(
+                "           {} {}",
+                color::icon("⮕", "->").green(),
+                members[0].recommendation.green()
+            );
+        }
+
+        for finding in members.iter().take(MAX_PER_GROUP) {
+            let location = links.location(finding);
+            let detail = match group_by {
+                GroupBy::File => format!(
+                    "{:>5}  {}  {}  {}",
+                    finding.line_start,
+                    finding.severity,
+                    finding.title,
+                    links.rule(finding)
+                ),
+                GroupBy::Rule => location,
+                GroupBy::Severity | GroupBy::Agent | GroupBy::Owner => {
+                    format!("{}  {}", location, finding.title)
+                }
+            };
+            println!("           {}", detail.dimmed());
+        }
+
+        if members.len() > MAX_PER_GROUP {
+            println!(
+                "           {}",
+                format!("… and {} more", members.len() - MAX_PER_GROUP).dimmed()
+            );
+        }
         println!();
     }
+}
+
+/// Print where the scan spent its time
+fn render_timings(report: &ScanReport) {
+    let t = &report.timings;
+    println!(
+        "  {}",
+        format!(
+            "discover {}ms · scan {}ms (reading done at {}ms) · merge {}ms",
+            t.discovery_ms, t.scan_ms, t.read_ms, t.merge_ms
+        )
+        .dimmed()
+    );
+    let agents: Vec<String> = t
+        .agents_ms
+        .iter()
+        .map(|(name, ms)| match t.agent_files.get(name) {
+            Some(files) => format!("{} {}ms ({} files)", name, ms, files),
+            None => format!("{} {}ms", name, ms),
+        })
+        .collect();
+    if !agents.is_empty() {
+        println!("  {}", format!("agents: {}", agents.join(" · ")).dimmed());
+    }
+    println!();
+}
+
+/// Print files and lines per language and the agents with rules for each
+fn render_languages(report: &ScanReport) {
+    if report.languages.is_empty() {
+        return;
+    }
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    table.set_header(vec!["Language", "Files", "Lines", "Agents"]);
+    for l in &report.languages {
+        let agents = if l.agents.is_empty() { "generic only".to_string() } else { l.agents.join(", ") };
+        table.add_row(vec![l.language.clone(), l.files.to_string(), l.lines.to_string(), agents]);
+    }
+    for line in table.to_string().lines() {
+        println!("  {}", line);
+    }
+    println!();
+}
+
+/// Warn about languages that make up much of the scan but have no rules
+fn render_coverage_gaps(report: &ScanReport) {
+    let gaps: Vec<_> = report.languages.iter().filter(|l| l.uncovered).collect();
+    if gaps.is_empty() {
+        return;
+    }
+    let total: usize = report.languages.iter().map(|l| l.lines).sum();
+    for l in gaps {
+        println!(
+            " {} {}",
+            color::icon("⚠", "!").yellow(),
+            t!("terminal.uncovered", language = l.language, percent = l.lines * 100 / total.max(1)).yellow()
+        );
+    }
+    println!("   {}", t!("terminal.uncovered_hint").dimmed());
+    println!();
+}
+
+/// Print the severity counts as a table
+fn render_counts_table(report: &ScanReport) {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    table.set_header(vec!["Severity", "Findings"]);
+    table.add_row(vec!["CRITICAL".to_string(), report.summary.critical.to_string()]);
+    table.add_row(vec!["HIGH".to_string(), report.summary.high.to_string()]);
+    table.add_row(vec!["MEDIUM".to_string(), report.summary.medium.to_string()]);
+    table.add_row(vec!["LOW".to_string(), report.summary.low.to_string()]);
+    table.add_row(vec!["TOTAL".to_string(), report.summary.total.to_string()]);
+
+    for line in table.to_string().lines() {
+        println!("  {}", line);
+    }
+    println!();
+}
+
+/// List `[policy]` requirements the scan did not meet
+fn render_policy(report: &ScanReport) {
+    if report.policy_violations.is_empty() {
+        return;
+    }
+    println!(
+        " {} {}",
+        color::icon("✗", "x").red().bold(),
+        format!("{} policy violations:", report.policy_violations.len()).red().bold()
+    );
+    for violation in &report.policy_violations {
+        println!("   {} {}", violation.policy.bold(), format!("— {}", violation.message).dimmed());
+    }
+    println!();
+}
+
+/// Baseline entries past their `expires` date, whose findings are back
+fn render_expired(report: &ScanReport) {
+    if report.expired_suppressions.is_empty() {
+        return;
+    }
+    println!(
+        " {} {}",
+        color::icon("⚠", "!").yellow().bold(),
+        format!("{} baseline suppressions expired:", report.expired_suppressions.len()).yellow().bold()
+    );
+    for e in &report.expired_suppressions {
+        let reason = e.reason.as_deref().map(|r| format!(", {}", r)).unwrap_or_default();
+        println!(
+            "   {} {} {}",
+            e.rule_id.bold(),
+            e.file_path.display(),
+            format!("— expired {}{}", e.expires, reason).dimmed()
+        );
+    }
+    println!();
+}
 
-    // Summary bar
+/// Print the status of each framework control (--compliance)
+fn render_compliance(report: &ScanReport) {
+    let Some(ref compliance) = report.compliance else {
+        return;
+    };
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    table.set_header(vec!["Control", "Requirement", "Status", "Findings"]);
+    for control in &compliance.controls {
+        table.add_row(vec![
+            control.id.clone(),
+            control.title.clone(),
+            control.status.as_str().to_string(),
+            control.findings.len().to_string(),
+        ]);
+    }
+
+    let violated = compliance
+        .controls
+        .iter()
+        .filter(|c| c.status == ControlStatus::Violated)
+        .count();
+    println!(
+        " {} {}",
+        compliance.framework.bold(),
+        format!("— {} of {} controls violated", violated, compliance.controls.len()).dimmed()
+    );
+    for line in table.to_string().lines() {
+        println!("  {}", line);
+    }
+    println!();
+}
+
+/// Print per-package counts and which packages fail --fail-on (--monorepo)
+fn render_packages(report: &ScanReport) {
+    if report.packages.is_empty() {
+        return;
+    }
+    let failing = report.packages.iter().filter(|p| p.failed).count();
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    let mut header = vec!["Package", "Critical", "High", "Medium", "Low", "Total"];
+    if failing > 0 {
+        header.push("Status");
+    }
+    table.set_header(header);
+    for p in &report.packages {
+        let mut row = vec![
+            p.name(),
+            p.critical.to_string(),
+            p.high.to_string(),
+            p.medium.to_string(),
+            p.low.to_string(),
+            p.total.to_string(),
+        ];
+        if failing > 0 {
+            row.push(if p.failed { "FAIL" } else { "ok" }.to_string());
+        }
+        table.add_row(row);
+    }
+
+    println!(" {}", format!("{} packages", report.packages.len()).bold());
+    for line in table.to_string().lines() {
+        println!("  {}", line);
+    }
+    if failing > 0 {
+        println!(
+            "  {}",
+            format!("{} of {} packages fail --fail-on", failing, report.packages.len()).red()
+        );
+    }
+    println!();
+}
+
+/// Print the top offending files and rules, and counts per agent
+fn render_hotspots(report: &ScanReport) {
+    let hotspots = &report.summary.hotspots;
+    // Nothing to rank with a single file and a single rule
+    if hotspots.files.len() < 2 && hotspots.rules.len() < 2 {
+        return;
+    }
+
+    println!(" {}", t!("terminal.top_offenders").bold());
+    for (label, ranking) in [("files", &hotspots.files), ("rules", &hotspots.rules)] {
+        println!("   {}", label.dimmed());
+        for entry in ranking {
+            println!("   {:>5}  {}", entry.count, entry.name);
+        }
+    }
+    let agents: Vec<String> = hotspots
+        .agents
+        .iter()
+        .map(|(name, n)| format!("{} {}", name, n))
+        .collect();
+    println!("   {} {}", "agents".dimmed(), agents.join(" · "));
+    if !hotspots.owners.is_empty() {
+        let owners: Vec<String> = hotspots
+            .owners
+            .iter()
+            .map(|(name, n)| format!("{} {}", name, n))
+            .collect();
+        println!("   {} {}", "owners".dimmed(), owners.join(" · "));
+    }
+    println!();
+}
+
+/// Print the severity summary bar
+fn render_summary(report: &ScanReport) {
     println!("{}", "━".repeat(60));
 
     let mut summary_parts = Vec::new();
     if report.summary.critical > 0 {
         summary_parts.push(
-            format!("{} critical", report.summary.critical).red().bold().to_string()
+            t!("terminal.critical", count = report.summary.critical).red().bold().to_string()
         );
     }
     if report.summary.high > 0 {
         summary_parts.push(
-            format!("{} high", report.summary.high).yellow().bold().to_string()
+            t!("terminal.high", count = report.summary.high).yellow().bold().to_string()
         );
     }
     if report.summary.medium > 0 {
         summary_parts.push(
-            format!("{} medium", report.summary.medium).blue().to_string()
+            t!("terminal.medium", count = report.summary.medium).blue().to_string()
         );
     }
     if report.summary.low > 0 {
         summary_parts.push(
-            format!("{} low", report.summary.low).white().to_string()
+            t!("terminal.low", count = report.summary.low).white().to_string()
         );
     }
 
     println!(
-        " Found {} issues: {}",
-        report.summary.total.to_string().bold(),
-        summary_parts.join(", ")
+        " {}",
+        t!("terminal.found", count = report.summary.total.to_string().bold(), parts = summary_parts.join(", "))
     );
 
+    let omitted = &report.summary.omitted;
+    if omitted.total > 0 {
+        let capped: Vec<String> = omitted
+            .by_rule
+            .iter()
+            .map(|(rule, n)| format!("{} +{}", rule, n))
+            .collect();
+        let detail = if capped.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", t!("terminal.capped", rules = capped.join(", ")))
+        };
+        println!(
+            " {}",
+            format!("{}{}", t!("terminal.more_not_shown", count = omitted.total), detail).dimmed()
+        );
+    }
+
+    if report.summary.suppressed > 0 {
+        println!(
+            " {}",
+            t!("terminal.suppressed", count = report.summary.suppressed.to_string().dimmed())
+        );
+    }
+
+    let triage = &report.summary.triage;
+    if !triage.is_empty() {
+        println!(
+            " {}",
+            t!(
+                "terminal.triaged",
+                open = triage.open,
+                accepted = triage.accepted,
+                false_positive = triage.false_positive,
+                assigned = triage.assigned
+            )
+            .dimmed()
+        );
+    }
+
     if report.files_skipped > 0 {
         println!(
-            " ({} files skipped)",
-            report.files_skipped.to_string().dimmed()
+            " {}",
+            t!("terminal.files_skipped", count = report.files_skipped.to_string().dimmed())
         );
     }
 
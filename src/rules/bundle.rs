@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use anyhow::{bail, Context, Result};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::cli::color;
+use crate::config::AntyConfig;
+use crate::report::finding::{Confidence, Severity};
+use crate::report::signature::{decode_hex, signature_path};
+use crate::rules::snapshot;
+
+/// Bundle layout version. Bump when fields are added or change meaning;
+/// older binaries then keep their embedded rules instead of misreading it.
+const FORMAT_VERSION: u32 = 3;
+
+const ALGORITHM: &str = "ed25519";
+
+/// File name of the installed bundle inside the packs directory
+const BUNDLE_FILE: &str = "bundle.toml";
+
+/// Rules compiled into this binary
+const EMBEDDED: &str = include_str!("bundle.toml");
+
+/// Where `anty update-rules` looks unless `rules.update_url` says otherwise
+pub const DEFAULT_UPDATE_URL: &str = "https://anty.dev/rules/bundle.toml";
+
+/// Hex Ed25519 public keys that sign official bundles. Empty until the
+/// maintainers publish a release key; until then `update-rules` only
+/// trusts keys the user lists in `rules.trusted_keys`.
+const OFFICIAL_KEYS: &[&str] = &[];
+
+/// Versioned rule definitions that can be updated without a new binary
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Bundle {
+    pub format_version: u32,
+    /// Increases with every published bundle; an installed bundle is only
+    /// used when it is newer than the embedded one
+    pub revision: u64,
+    #[serde(default, rename = "secret")]
+    pub secrets: Vec<SecretRule>,
+}
+
+/// One secret-provider pattern
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SecretRule {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub pattern: String,
+    pub severity: Severity,
+    pub confidence: Confidence,
+    pub recommendation: String,
+    pub cwe: String,
+    /// Pattern that must also match elsewhere in the file, for keys that
+    /// are only recognizable with context (a service account's email)
+    #[serde(default)]
+    pub requires: Option<String>,
+    /// Labels for `--tags` / `--exclude-tags`
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Detached signature stored next to a bundle (`bundle.toml.sig`)
+#[derive(Debug, Serialize, Deserialize)]
+struct BundleSignature {
+    algorithm: String,
+    /// Hex public key the bundle was signed with
+    public_key: String,
+    /// Hex Ed25519 signature over the bundle file's bytes
+    signature: String,
+}
+
+/// The bundle the agents load rules from: the installed one if it is
+/// present, correctly signed, and newer than the embedded one; otherwise
+/// the embedded one. Loaded once per process.
+pub fn active() -> &'static Bundle {
+    static ACTIVE: OnceLock<Bundle> = OnceLock::new();
+    ACTIVE.get_or_init(|| {
+        let embedded = parse(EMBEDDED).expect("embedded rule bundle is valid");
+        match load_installed() {
+            Ok(Some(installed)) if installed.revision > embedded.revision => {
+                debug!("Using installed rule bundle revision {}", installed.revision);
+                installed
+            }
+            Ok(_) => embedded,
+            Err(e) => {
+                warn!("Ignoring installed rule bundle: {:#}", e);
+                embedded
+            }
+        }
+    })
+}
+
+/// `anty update-rules`: download the bundle and its signature, verify them,
+/// and install the bundle if it is newer than the active one. With `check`,
+/// only report whether an update is available.
+pub fn update(url: Option<&str>, check: bool) -> Result<()> {
+    let config = AntyConfig::load_user();
+    let url = url
+        .map(str::to_string)
+        .or_else(|| config.as_ref().and_then(|c| c.rules.update_url.clone()))
+        .unwrap_or_else(|| DEFAULT_UPDATE_URL.to_string());
+    let keys = trusted_keys(config.as_ref())?;
+
+    info!("Fetching rule bundle from {}", url);
+    let content = fetch(&url)?;
+    let signature = fetch(&format!("{}.sig", url))?;
+    verify(content.as_bytes(), &signature, &keys)?;
+    let bundle = parse(&content)?;
+
+    let current = active();
+    if bundle.revision <= current.revision {
+        println!(
+            "{}Rules are up to date (revision {}, {} secret rules)",
+            color::icon("✅ ", ""),
+            current.revision,
+            current.secrets.len()
+        );
+        return Ok(());
+    }
+    if check {
+        println!(
+            "Rule bundle revision {} is available (installed: {}). Run `anty update-rules` to install it.",
+            bundle.revision, current.revision
+        );
+        return Ok(());
+    }
+
+    let dir = snapshot::packs_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(BUNDLE_FILE);
+    std::fs::write(&path, &content).with_context(|| format!("Could not write {}", path.display()))?;
+    std::fs::write(signature_path(&path), &signature)?;
+
+    println!(
+        "{}Installed rule bundle revision {} ({} secret rules) into {}",
+        color::icon("✅ ", ""),
+        bundle.revision,
+        bundle.secrets.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Read and verify the installed bundle, if there is one
+fn load_installed() -> Result<Option<Bundle>> {
+    let Ok(dir) = snapshot::packs_dir() else {
+        return Ok(None);
+    };
+    let path = dir.join(BUNDLE_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = std::fs::read(&path)?;
+    let signature = std::fs::read_to_string(signature_path(&path))
+        .with_context(|| format!("{} has no signature file", path.display()))?;
+    verify(&content, &signature, &trusted_keys(AntyConfig::load_user().as_ref())?)?;
+    let content = String::from_utf8(content).context("Bundle is not UTF-8")?;
+    parse(&content).map(Some)
+}
+
+/// Parse a bundle and check that it can be loaded: a supported format,
+/// unique rule IDs, and patterns that compile
+fn parse(content: &str) -> Result<Bundle> {
+    let bundle: Bundle = toml::from_str(content).context("Invalid rule bundle")?;
+    if bundle.format_version != FORMAT_VERSION {
+        bail!(
+            "Unsupported rule bundle format version {} (expected {})",
+            bundle.format_version,
+            FORMAT_VERSION
+        );
+    }
+    let mut ids = HashSet::new();
+    for rule in &bundle.secrets {
+        if !ids.insert(rule.id.as_str()) {
+            bail!("Rule bundle defines {} twice", rule.id);
+        }
+        Regex::new(&rule.pattern).with_context(|| format!("Invalid pattern for {}", rule.id))?;
+        if let Some(ref requires) = rule.requires {
+            Regex::new(requires).with_context(|| format!("Invalid requires pattern for {}", rule.id))?;
+        }
+    }
+    Ok(bundle)
+}
+
+/// Official keys plus any the user trusts in `rules.trusted_keys`. Only the
+/// user config is consulted, so a scanned repository cannot add keys.
+fn trusted_keys(config: Option<&AntyConfig>) -> Result<Vec<VerifyingKey>> {
+    let extra = config.map(|c| c.rules.trusted_keys.as_slice()).unwrap_or_default();
+    if OFFICIAL_KEYS.is_empty() && extra.is_empty() {
+        bail!(
+            "No trusted rule signing keys: add the key that signs your bundles to rules.trusted_keys in {}",
+            crate::config::user_config_path().map_or_else(|| "the user config".to_string(), |p| p.display().to_string())
+        );
+    }
+    OFFICIAL_KEYS
+        .iter()
+        .copied()
+        .chain(extra.iter().map(String::as_str))
+        .map(|hex| parse_key(hex).with_context(|| format!("Invalid public key '{}'", hex)))
+        .collect()
+}
+
+/// Decode a hex Ed25519 public key
+pub fn parse_key(hex: &str) -> Result<VerifyingKey> {
+    let bytes: [u8; 32] = decode_hex(hex.trim())
+        .and_then(|b| b.try_into().ok())
+        .context("expected 64 hex digits")?;
+    Ok(VerifyingKey::from_bytes(&bytes)?)
+}
+
+fn verify(content: &[u8], signature: &str, keys: &[VerifyingKey]) -> Result<()> {
+    let signature: BundleSignature =
+        serde_json::from_str(signature).context("Invalid bundle signature file")?;
+    if signature.algorithm != ALGORITHM {
+        bail!("Unsupported bundle signature algorithm '{}'", signature.algorithm);
+    }
+    let key = parse_key(&signature.public_key)?;
+    if !keys.contains(&key) {
+        bail!("Bundle is signed with an untrusted key {}", signature.public_key);
+    }
+    let bytes: [u8; 64] = decode_hex(&signature.signature)
+        .and_then(|b| b.try_into().ok())
+        .context("Malformed bundle signature")?;
+    key.verify(content, &Signature::from_bytes(&bytes))
+        .map_err(|_| anyhow::anyhow!("Bundle signature does not match its contents"))
+}
+
+fn fetch(url: &str) -> Result<String> {
+    ureq::get(url)
+        .set("User-Agent", concat!("anty/", env!("CARGO_PKG_VERSION")))
+        .call()
+        .with_context(|| format!("Could not download {}", url))?
+        .into_string()
+        .with_context(|| format!("Could not read {}", url))
+}
@@ -0,0 +1,164 @@
+pub mod bundle;
+pub mod packs;
+pub mod selftest;
+pub mod snapshot;
+pub mod stats;
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::agents::{self, SecurityAgent};
+use crate::report::finding::{Confidence, Remediation, Severity};
+
+/// Static metadata describing a single built-in rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleMeta {
+    /// Rule ID, e.g. "ANTY-SEC-001"
+    pub id: String,
+    /// Agent that owns the rule
+    pub agent: String,
+    pub title: String,
+    pub description: String,
+    pub severity: Severity,
+    pub confidence: Confidence,
+    pub recommendation: String,
+    pub cwe_id: Option<String>,
+    /// Regex source the rule matches with
+    pub pattern: String,
+    /// Provider-specific response steps, for secret rules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<Remediation>,
+    /// Documentation page for the rule
+    #[serde(default)]
+    pub docs_url: String,
+    /// CWE, OWASP, and CVE links
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+    /// Labels for selecting curated subsets, e.g. "secrets" or "low-noise"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// OWASP Top 10 (2021) categories and the CWEs they list, for the CWEs
+/// built-in rules report
+const OWASP_TOP_10: &[(&str, &[&str])] = &[
+    ("A01_2021-Broken_Access_Control", &["CWE-668", "CWE-732"]),
+    ("A02_2021-Cryptographic_Failures", &["CWE-319", "CWE-321", "CWE-328"]),
+    ("A03_2021-Injection", &["CWE-78", "CWE-79", "CWE-89", "CWE-95"]),
+    ("A04_2021-Insecure_Design", &["CWE-269", "CWE-522"]),
+    ("A05_2021-Security_Misconfiguration", &["CWE-614", "CWE-942"]),
+    ("A06_2021-Vulnerable_and_Outdated_Components", &["CWE-1104"]),
+    ("A07_2021-Identification_and_Authentication_Failures", &["CWE-295", "CWE-306", "CWE-307", "CWE-798"]),
+    ("A08_2021-Software_and_Data_Integrity_Failures", &["CWE-494", "CWE-502", "CWE-829"]),
+];
+
+/// Tag of rules that only report with --no-config (or `--tags untrusted`):
+/// things worth a look before running code you do not trust, but normal
+/// in your own
+pub const UNTRUSTED_TAG: &str = "untrusted";
+
+/// Documentation page for a rule
+pub fn docs_url(rule_id: &str) -> String {
+    format!("https://anty.dev/docs/rules/{}", rule_id)
+}
+
+/// External references for a weakness: its CWE entry and, when the CWE
+/// falls under one, the OWASP Top 10 category
+pub fn references(cwe_id: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    if let Some(number) = cwe_id.strip_prefix("CWE-") {
+        refs.push(format!("https://cwe.mitre.org/data/definitions/{}.html", number));
+    }
+    if let Some((category, _)) = OWASP_TOP_10.iter().find(|(_, cwes)| cwes.contains(&cwe_id)) {
+        refs.push(format!("https://owasp.org/Top10/{}/", category));
+    }
+    refs
+}
+
+/// Metadata for every built-in rule, sorted by rule ID
+pub fn builtin_rules() -> Vec<RuleMeta> {
+    let mut rules: Vec<RuleMeta> = agents::all_agents()
+        .iter()
+        .flat_map(|a| a.rules())
+        .collect();
+    rules.sort_by(|a, b| a.id.cmp(&b.id));
+    rules
+}
+
+/// IDs of the rules selected by `--tags` / `--exclude-tags`: rules with
+/// any of `include` (every rule when it is empty) and none of `exclude`.
+/// None when no tags are given, so that every rule is kept.
+pub fn select_by_tags(
+    agents: &[Arc<dyn SecurityAgent>],
+    include: &[String],
+    exclude: &[String],
+) -> Result<Option<HashSet<String>>> {
+    if include.is_empty() && exclude.is_empty() {
+        return Ok(None);
+    }
+    let rules: Vec<RuleMeta> = agents.iter().flat_map(|a| a.rules()).collect();
+    let known = tag_counts(&rules);
+    for tag in include.iter().chain(exclude) {
+        if !known.contains_key(tag.as_str()) {
+            bail!(
+                "Unknown tag '{}' in --tags / --exclude-tags (or scan.tags / scan.exclude_tags). Known tags: {}",
+                tag,
+                known.keys().copied().collect::<Vec<_>>().join(", ")
+            );
+        }
+    }
+    let has_any = |rule: &RuleMeta, tags: &[String]| rule.tags.iter().any(|t| tags.contains(t));
+    Ok(Some(
+        rules
+            .iter()
+            .filter(|r| (include.is_empty() || has_any(r, include)) && !has_any(r, exclude))
+            .map(|r| r.id.clone())
+            .collect(),
+    ))
+}
+
+/// Number of rules carrying each tag
+fn tag_counts(rules: &[RuleMeta]) -> BTreeMap<&str, usize> {
+    let mut counts = BTreeMap::new();
+    for tag in rules.iter().flat_map(|r| &r.tags) {
+        *counts.entry(tag.as_str()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// List all available security rules
+pub fn list_rules() {
+    println!();
+    println!("🐜 Anty — Available Security Agents & Rules");
+    println!("{}", "━".repeat(55));
+    println!();
+
+    let all_agents = agents::all_agents();
+
+    for agent in &all_agents {
+        println!("  📋 {} ", agent.name());
+        println!("     {}", agent.description());
+        println!();
+    }
+
+    let rules = builtin_rules();
+    println!("  🏷️  Tags");
+    for (tag, count) in tag_counts(&rules) {
+        println!("     {:<16} {} rule{}", tag, count, if count == 1 { "" } else { "s" });
+    }
+    println!();
+
+    println!("{}", "━".repeat(55));
+    println!(
+        "  {} agents loaded",
+        all_agents.len()
+    );
+    println!();
+    println!("  Run `anty scan .` to scan your project");
+    println!("  Run `anty scan . --agents secrets` to run specific agents");
+    println!("  Run `anty scan . --tags low-noise` to run a curated subset of rules");
+    println!();
+}
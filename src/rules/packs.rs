@@ -0,0 +1,219 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tracing::{debug, info, warn};
+
+use crate::config::RulesConfig;
+use crate::policy::Policy;
+use crate::rules::snapshot;
+
+/// Policy file at the root of a pack
+const PACK_FILE: &str = "anty-pack.toml";
+
+/// How long the commit an unpinned ref resolved to is reused before the
+/// pack is fetched again
+const REFRESH_AFTER: Duration = Duration::from_secs(60 * 60);
+
+/// A rule pack reference: `git+<url>#<ref>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackSpec {
+    pub url: String,
+    /// Branch, tag, or commit; None = the remote's default branch
+    pub reference: Option<String>,
+}
+
+impl PackSpec {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let Some(rest) = spec.strip_prefix("git+") else {
+            bail!("'{}' is not a git pack (expected git+<url>#<ref>)", spec);
+        };
+        let (url, reference) = match rest.rsplit_once('#') {
+            Some((url, reference)) => (url, Some(reference)),
+            None => (rest, None),
+        };
+        if url.is_empty() {
+            bail!("'{}' has no repository URL", spec);
+        }
+        // Keep refs and URLs from being read as git options
+        if url.starts_with('-') || reference.is_some_and(|r| r.is_empty() || r.starts_with('-')) {
+            bail!("'{}' is not a valid pack reference", spec);
+        }
+        Ok(PackSpec {
+            url: url.to_string(),
+            reference: reference.map(str::to_string),
+        })
+    }
+}
+
+/// Check a `rules.pins` value: a full commit hash
+pub fn check_pin(pin: &str) -> Result<()> {
+    if !matches!(pin.len(), 40 | 64) || !pin.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!("'{}' is not a full commit hash (40 or 64 hex digits)", pin);
+    }
+    Ok(())
+}
+
+/// `anty-pack.toml`: what a pack adds besides its rules
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PackFile {
+    #[serde(default)]
+    policy: Option<Policy>,
+}
+
+/// A rule pack checked out at one commit
+#[derive(Debug)]
+pub struct Pack {
+    pub commit: String,
+    /// Checkout holding the pack's YAML rules
+    pub dir: PathBuf,
+    /// `[policy]` from the pack's `anty-pack.toml`
+    pub policy: Option<Policy>,
+}
+
+/// Fetch (or reuse from the cache) every pack in `rules.packs`. A pack that
+/// cannot be fetched, or whose ref no longer matches its pin, fails the scan:
+/// silently scanning without the organisation's rules would pass code that
+/// should not.
+pub fn load(config: &RulesConfig) -> Result<Vec<Pack>> {
+    let mut packs = Vec::new();
+    for spec in &config.packs {
+        let pin = config.pins.get(spec).map(|p| p.to_lowercase());
+        let pack = fetch(spec, pin.as_deref()).with_context(|| format!("Could not load rule pack {}", spec))?;
+        if pin.is_none() {
+            warn!(
+                "Rule pack {} is not pinned; add `\"{}\" = \"{}\"` under [rules.pins] to pin it",
+                spec, spec, pack.commit
+            );
+        }
+        info!("Using rule pack {} at {}", spec, &pack.commit[..12]);
+        packs.push(pack);
+    }
+    Ok(packs)
+}
+
+fn fetch(spec: &str, pin: Option<&str>) -> Result<Pack> {
+    let parsed = PackSpec::parse(spec)?;
+    if let Some(pin) = pin {
+        check_pin(pin)?;
+    }
+    let cache = snapshot::packs_dir()?.join("git").join(cache_key(&parsed.url));
+    let ref_file = cache.join("refs").join(cache_key(parsed.reference.as_deref().unwrap_or("HEAD")));
+
+    // A pinned pack never changes, so a cached checkout is used as is
+    if let Some(pin) = pin {
+        let dir = cache.join(pin);
+        if dir.is_dir() {
+            debug!("Rule pack {} cached at {}", spec, dir.display());
+            return open(spec, pin, dir);
+        }
+    } else if let Some(commit) = cached_ref(&ref_file, true) {
+        return open(spec, &commit, cache.join(&commit));
+    }
+
+    let commit = match checkout(&parsed, &cache) {
+        Ok(commit) => commit,
+        Err(e) => match cached_ref(&ref_file, false).filter(|_| pin.is_none()) {
+            Some(commit) => {
+                warn!("Could not update rule pack {} ({:#}); using cached commit {}", spec, e, &commit[..12]);
+                return open(spec, &commit, cache.join(&commit));
+            }
+            None => return Err(e),
+        },
+    };
+    if let Some(pin) = pin {
+        if commit != pin {
+            bail!(
+                "it resolves to commit {}, but rules.pins expects {}; the ref was moved or the pin is out of date",
+                commit,
+                pin
+            );
+        }
+    }
+    if let Some(parent) = ref_file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&ref_file, &commit)?;
+    open(spec, &commit, cache.join(&commit))
+}
+
+/// Shallow-fetch the ref into the cache and return its commit. The checkout
+/// is stored without `.git`, under the commit hash.
+fn checkout(spec: &PackSpec, cache: &Path) -> Result<String> {
+    std::fs::create_dir_all(cache)?;
+    let tmp = cache.join(format!("tmp-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&tmp);
+    std::fs::create_dir_all(&tmp)?;
+    let result = (|| {
+        git(&tmp, &["init", "--quiet"])?;
+        let reference = spec.reference.as_deref().unwrap_or("HEAD");
+        info!("Fetching rule pack {}#{}", spec.url, reference);
+        git(&tmp, &["fetch", "--quiet", "--depth", "1", "--", &spec.url, reference])?;
+        let commit = git(&tmp, &["rev-parse", "FETCH_HEAD^{commit}"])?.trim().to_lowercase();
+        let dir = cache.join(&commit);
+        if !dir.is_dir() {
+            git(&tmp, &["-c", "advice.detachedHead=false", "checkout", "--quiet", "FETCH_HEAD"])?;
+            std::fs::remove_dir_all(tmp.join(".git"))?;
+            std::fs::rename(&tmp, &dir).with_context(|| format!("Could not write {}", dir.display()))?;
+        }
+        Ok(commit)
+    })();
+    let _ = std::fs::remove_dir_all(&tmp);
+    result
+}
+
+/// The commit a ref last resolved to, if its checkout is still cached
+/// (and, with `fresh`, it was resolved within [`REFRESH_AFTER`])
+fn cached_ref(ref_file: &Path, fresh: bool) -> Option<String> {
+    if fresh {
+        let age = std::fs::metadata(ref_file).ok()?.modified().ok()?.elapsed().ok()?;
+        if age > REFRESH_AFTER {
+            return None;
+        }
+    }
+    let commit = std::fs::read_to_string(ref_file).ok()?.trim().to_string();
+    let dir = ref_file.parent()?.parent()?.join(&commit);
+    (check_pin(&commit).is_ok() && dir.is_dir()).then_some(commit)
+}
+
+fn open(spec: &str, commit: &str, dir: PathBuf) -> Result<Pack> {
+    let manifest = dir.join(PACK_FILE);
+    let policy = if manifest.is_file() {
+        let content = std::fs::read_to_string(&manifest)?;
+        let file: PackFile =
+            toml::from_str(&content).with_context(|| format!("Invalid {} in rule pack {}", PACK_FILE, spec))?;
+        file.policy
+    } else {
+        None
+    };
+    Ok(Pack {
+        commit: commit.to_string(),
+        dir,
+        policy,
+    })
+}
+
+/// Run git in `dir`, failing with its stderr
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        // Never stop a scan to ask for credentials
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .context("Could not run git; rule packs need git installed")?;
+    if !output.status.success() {
+        bail!("git {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Cache directory name for a URL or ref
+fn cache_key(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))[..16].to_string()
+}
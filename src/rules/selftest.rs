@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anstream::println;
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use serde::Deserialize;
+
+use crate::agents::{self, ScannedFile, SecurityAgent};
+use crate::cli::color;
+use crate::cli::exit_code;
+use crate::engine::reader;
+
+/// Example fixtures shipped with the binary, one file per agent
+const BUILTIN_FIXTURES: &[(&str, &str)] = &[
+    ("secrets.toml", include_str!("../../tests/rules/secrets.toml")),
+    ("dangerous_functions.toml", include_str!("../../tests/rules/dangerous_functions.toml")),
+    ("config_issues.toml", include_str!("../../tests/rules/config_issues.toml")),
+    ("mobile.toml", include_str!("../../tests/rules/mobile.toml")),
+    ("auth.toml", include_str!("../../tests/rules/auth.toml")),
+    ("build_files.toml", include_str!("../../tests/rules/build_files.toml")),
+    ("web_server.toml", include_str!("../../tests/rules/web_server.toml")),
+    ("manifests.toml", include_str!("../../tests/rules/manifests.toml")),
+];
+
+/// A fixture file: a list of example cases
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Fixture {
+    #[serde(default, rename = "case")]
+    cases: Vec<Case>,
+}
+
+/// Example snippets for one rule
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Case {
+    rule: String,
+    /// File name the snippets are scanned as; picks the language and
+    /// file-specific rules (Dockerfile, .env, AndroidManifest.xml, ...)
+    file: String,
+    /// Snippets the rule must report
+    #[serde(default, rename = "match")]
+    positives: Vec<String>,
+    /// Snippets the rule must not report
+    #[serde(default, rename = "no_match")]
+    negatives: Vec<String>,
+}
+
+/// `anty test-rules`: scan every rule's example snippets and check that it
+/// reports its positives and none of its negatives. Fixtures come from
+/// `dir` (every `*.toml` in it) or, by default, the ones built into Anty.
+/// Exits with [`exit_code::ERROR`] if a snippet fails or a rule has no
+/// positive example.
+pub fn run(dir: Option<&Path>) -> Result<i32> {
+    let fixtures = match dir {
+        Some(dir) => load_dir(dir)?,
+        None => BUILTIN_FIXTURES
+            .iter()
+            .map(|(name, content)| Ok((name.to_string(), parse(name, content)?)))
+            .collect::<Result<_>>()?,
+    };
+
+    let agents = agents::all_agents();
+    let mut owners: BTreeMap<String, &dyn SecurityAgent> = BTreeMap::new();
+    for agent in &agents {
+        for rule in agent.rules() {
+            owners.insert(rule.id, agent.as_ref());
+        }
+    }
+
+    let mut failures: Vec<String> = Vec::new();
+    let mut positives: BTreeMap<&str, usize> = owners.keys().map(|id| (id.as_str(), 0)).collect();
+    let mut checked = 0;
+
+    for (name, fixture) in &fixtures {
+        for case in &fixture.cases {
+            let Some(agent) = owners.get(&case.rule) else {
+                failures.push(format!("{}: unknown rule {}", name, case.rule));
+                continue;
+            };
+            if let Some(count) = positives.get_mut(case.rule.as_str()) {
+                *count += case.positives.len();
+            }
+            let snippets = case
+                .positives
+                .iter()
+                .map(|s| (s, true))
+                .chain(case.negatives.iter().map(|s| (s, false)));
+            for (snippet, expected) in snippets {
+                checked += 1;
+                let reported = reports(*agent, &case.rule, &case.file, snippet);
+                if reported != expected {
+                    failures.push(format!(
+                        "{} {} example in {}:\n      {}",
+                        case.rule.bold(),
+                        if expected { "missed a positive" } else { "matched a negative" },
+                        case.file,
+                        snippet.replace('\n', "\n      ")
+                    ));
+                }
+            }
+        }
+    }
+
+    for (rule, count) in &positives {
+        if *count == 0 {
+            failures.push(format!("{} has no positive example", rule));
+        }
+    }
+
+    if failures.is_empty() {
+        println!(
+            "{}{} rules, {} examples passed",
+            color::icon("✅ ", ""),
+            positives.len(),
+            checked
+        );
+        return Ok(exit_code::CLEAN);
+    }
+
+    println!(
+        "{} {} rule test failure{}",
+        color::icon("✗", "x").red().bold(),
+        failures.len(),
+        if failures.len() == 1 { "" } else { "s" }
+    );
+    for failure in &failures {
+        println!("    {}", failure);
+    }
+    Ok(exit_code::ERROR)
+}
+
+/// A built-in rule's example snippets: (file name, positives, negatives)
+pub fn examples(rule_id: &str) -> Option<(String, Vec<String>, Vec<String>)> {
+    BUILTIN_FIXTURES
+        .iter()
+        .filter_map(|(name, content)| parse(name, content).ok())
+        .flat_map(|fixture| fixture.cases)
+        .find(|case| case.rule == rule_id)
+        .map(|case| (case.file, case.positives, case.negatives))
+}
+
+/// Whether `agent` reports `rule` when `snippet` is the whole of `file`,
+/// on its own or folded into an overlapping match of another rule
+fn reports(agent: &dyn SecurityAgent, rule: &str, file: &str, snippet: &str) -> bool {
+    let path = PathBuf::from(file);
+    let scanned = ScannedFile {
+        language: reader::detect_language(&path),
+        rel_path: path.clone(),
+        abs_path: path,
+        content: format!("{}\n", snippet),
+        line_offset: 0,
+        deadline: None,
+    };
+    agents::scan_alone(agent, &scanned)
+        .iter()
+        .any(|f| f.rule_id == rule || f.related_rules.iter().any(|r| r == rule))
+}
+
+fn load_dir(dir: &Path) -> Result<Vec<(String, Fixture)>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Could not read fixture directory {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "toml"))
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Could not read {}", path.display()))?;
+            let name = path.display().to_string();
+            let fixture = parse(&name, &content)?;
+            Ok((name, fixture))
+        })
+        .collect()
+}
+
+fn parse(name: &str, content: &str) -> Result<Fixture> {
+    toml::from_str(content).with_context(|| format!("Invalid rule fixture {}", name))
+}
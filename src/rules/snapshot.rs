@@ -0,0 +1,284 @@
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, info, warn};
+
+use crate::cli::color;
+use crate::config;
+use crate::rules::{self, RuleMeta};
+
+/// Snapshot layout version. Bump when the archive structure changes.
+const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_PATH: &str = "manifest.json";
+const BUILTIN_PATH: &str = "builtin.json";
+const PACKS_PREFIX: &str = "packs/";
+
+/// Version manifest stored at the root of every snapshot archive
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    format_version: u32,
+    /// Anty version that produced the snapshot
+    anty_version: String,
+    /// Number of built-in rules recorded in builtin.json
+    builtin_rules: usize,
+    /// Every other file in the archive with its SHA-256, sorted by path
+    entries: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    path: String,
+    sha256: String,
+}
+
+/// Directory holding installed rule packs (`~/.anty/rules`)
+pub fn packs_dir() -> Result<PathBuf> {
+    config::anty_home()
+        .map(|home| home.join("rules"))
+        .context("Could not determine the Anty home directory (set ANTY_HOME)")
+}
+
+/// Write a snapshot of built-in rule metadata and installed packs to `out`.
+///
+/// The archive is byte-for-byte reproducible: entries are sorted and all
+/// tar headers use fixed ownership, permissions, and timestamps.
+pub fn export(out: &Path) -> Result<()> {
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+
+    let builtin = rules::builtin_rules();
+    files.insert(BUILTIN_PATH.to_string(), serde_json::to_vec_pretty(&builtin)?);
+
+    let packs = packs_dir()?;
+    if packs.is_dir() {
+        collect_pack_files(&packs, &packs, &mut files)?;
+    }
+
+    let manifest = Manifest {
+        format_version: FORMAT_VERSION,
+        anty_version: env!("CARGO_PKG_VERSION").to_string(),
+        builtin_rules: builtin.len(),
+        entries: files
+            .iter()
+            .map(|(path, data)| ManifestEntry {
+                path: path.clone(),
+                sha256: sha256_hex(data),
+            })
+            .collect(),
+    };
+
+    let file = std::fs::File::create(out)
+        .with_context(|| format!("Could not create {}", out.display()))?;
+    let mut builder = tar::Builder::new(file);
+    append_entry(&mut builder, MANIFEST_PATH, &serde_json::to_vec_pretty(&manifest)?)?;
+    for (path, data) in &files {
+        append_entry(&mut builder, path, data)?;
+    }
+    builder.finish()?;
+
+    println!(
+        "{}Exported {} built-in rules and {} pack files to {}",
+        color::icon("✅ ", ""),
+        builtin.len(),
+        files.len() - 1,
+        out.display()
+    );
+    Ok(())
+}
+
+/// Verify a snapshot archive and replace the rule packs in `~/.anty/rules`
+/// with its own.
+pub fn import(archive: &Path) -> Result<()> {
+    let file = std::fs::File::open(archive)
+        .with_context(|| format!("Could not open {}", archive.display()))?;
+    let mut tar = tar::Archive::new(file);
+
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.to_string_lossy().replace('\\', "/");
+        if !is_safe_relative(Path::new(&path)) {
+            bail!("Refusing unsafe path in snapshot: {}", path);
+        }
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        files.insert(path, data);
+    }
+
+    let manifest: Manifest = files
+        .remove(MANIFEST_PATH)
+        .context("Snapshot has no manifest.json")
+        .and_then(|raw| serde_json::from_slice(&raw).context("Invalid manifest.json"))?;
+
+    if manifest.format_version != FORMAT_VERSION {
+        bail!(
+            "Unsupported snapshot format version {} (expected {})",
+            manifest.format_version,
+            FORMAT_VERSION
+        );
+    }
+
+    // Every file must be listed with a matching hash, and nothing extra may be present
+    for entry in &manifest.entries {
+        let data = files
+            .get(&entry.path)
+            .with_context(|| format!("Snapshot is missing {}", entry.path))?;
+        if sha256_hex(data) != entry.sha256 {
+            bail!("Checksum mismatch for {}", entry.path);
+        }
+    }
+    if files.len() != manifest.entries.len() {
+        bail!("Snapshot contains files not listed in its manifest");
+    }
+
+    let snapshot_rules: Vec<RuleMeta> = files
+        .get(BUILTIN_PATH)
+        .context("Snapshot has no builtin.json")
+        .and_then(|raw| serde_json::from_slice(raw).context("Invalid builtin.json"))?;
+    report_builtin_drift(&manifest, &snapshot_rules);
+
+    // The packs are written next to the installed ones and swapped in
+    // whole, so packs removed since the export go away and a failed import
+    // leaves the old ones in place
+    let packs = packs_dir()?;
+    let home = packs.parent().context("Rule packs directory has no parent")?;
+    std::fs::create_dir_all(home)?;
+    let staging = home.join(format!("rules.import-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(&staging)?;
+    let written = (|| {
+        let mut installed = 0;
+        for (path, data) in &files {
+            let Some(rel) = path.strip_prefix(PACKS_PREFIX) else {
+                continue;
+            };
+            let dest = staging.join(rel);
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&dest, data)
+                .with_context(|| format!("Could not write {}", dest.display()))?;
+            debug!("Installed {}", packs.join(rel).display());
+            installed += 1;
+        }
+        Ok(installed)
+    })();
+    let installed = match written.and_then(|installed| swap_in(&staging, &packs).map(|()| installed)) {
+        Ok(installed) => installed,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+
+    info!("Imported snapshot from Anty v{}", manifest.anty_version);
+    println!(
+        "{}Installed {} pack files into {}",
+        color::icon("✅ ", ""),
+        installed,
+        packs.display()
+    );
+    Ok(())
+}
+
+/// Replace `dir` with `staging`, restoring `dir` if the swap fails
+fn swap_in(staging: &Path, dir: &Path) -> Result<()> {
+    let old = dir.with_file_name(format!("rules.old-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&old);
+    if dir.exists() {
+        std::fs::rename(dir, &old).with_context(|| format!("Could not replace {}", dir.display()))?;
+    }
+    if let Err(e) = std::fs::rename(staging, dir) {
+        let _ = std::fs::rename(&old, dir);
+        return Err(e).with_context(|| format!("Could not replace {}", dir.display()));
+    }
+    let _ = std::fs::remove_dir_all(&old);
+    Ok(())
+}
+
+/// Warn when the snapshot's built-in rules differ from this binary's
+fn report_builtin_drift(manifest: &Manifest, snapshot_rules: &[RuleMeta]) {
+    let local: BTreeMap<String, RuleMeta> = rules::builtin_rules()
+        .into_iter()
+        .map(|r| (r.id.clone(), r))
+        .collect();
+
+    let mut missing = Vec::new();
+    let mut changed = Vec::new();
+    for rule in snapshot_rules {
+        match local.get(&rule.id) {
+            None => missing.push(rule.id.as_str()),
+            Some(l) if l != rule => changed.push(rule.id.as_str()),
+            Some(_) => {}
+        }
+    }
+
+    if !missing.is_empty() {
+        warn!(
+            "Snapshot from Anty v{} has {} built-in rules not in this version: {}",
+            manifest.anty_version,
+            missing.len(),
+            missing.join(", ")
+        );
+    }
+    if !changed.is_empty() {
+        warn!(
+            "{} built-in rules differ from the snapshot: {}",
+            changed.len(),
+            changed.join(", ")
+        );
+    }
+}
+
+/// Recursively collect pack files under `dir` keyed by `packs/<relative path>`
+fn collect_pack_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut BTreeMap<String, Vec<u8>>,
+) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_pack_files(root, &path, files)?;
+        } else if path.is_file() {
+            let rel = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            files.insert(format!("{}{}", PACKS_PREFIX, rel), std::fs::read(&path)?);
+        }
+    }
+    Ok(())
+}
+
+fn append_entry<W: std::io::Write>(
+    builder: &mut tar::Builder<W>,
+    path: &str,
+    data: &[u8],
+) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_uid(0);
+    header.set_gid(0);
+    header.set_mtime(0);
+    header.set_entry_type(tar::EntryType::Regular);
+    builder.append_data(&mut header, path, data)?;
+    Ok(())
+}
+
+fn is_safe_relative(path: &Path) -> bool {
+    path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
@@ -0,0 +1,277 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path};
+
+use anstream::println;
+use anyhow::Result;
+use comfy_table::{presets::UTF8_FULL_CONDENSED, Table};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use crate::baseline::{self, Baseline, Decision};
+use crate::cli::exit_code;
+use crate::report::cache;
+use crate::report::finding::{ScanReport, TriageStatus};
+use crate::rules;
+
+/// Output formats of `anty rules stats`
+pub const FORMATS: &[&str] = &["terminal", "json"];
+
+/// False positives needed before a rule's rate is trusted enough to
+/// suggest disabling it
+const MIN_FALSE_POSITIVES: usize = 3;
+
+/// Share of a rule's matches marked false positive above which disabling
+/// it is suggested
+const DISABLE_RATE: f64 = 0.5;
+
+/// Share above which scoping or capping the rule is suggested
+const TUNE_RATE: f64 = 0.2;
+
+/// Matches above which an untriaged rule is worth sampling
+const REVIEW_MATCHES: usize = 20;
+
+/// Share of a rule's false positives that must sit in one top-level
+/// directory to suggest an override for that directory alone
+const CONCENTRATION: f64 = 0.75;
+
+/// One rule's numbers in a scan
+#[derive(Debug, Serialize)]
+struct RuleStats {
+    rule_id: String,
+    title: String,
+    /// Everything the rule matched: reported, capped, and suppressed
+    matches: usize,
+    reported: usize,
+    /// Dropped by --max-findings / --max-per-rule
+    omitted: usize,
+    /// Hidden by the baseline
+    suppressed: usize,
+    /// Suppressed as false positives or triaged `false-positive`
+    false_positives: usize,
+    /// Suppressed as accepted risks or triaged `accepted`
+    accepted: usize,
+    /// Reported findings with any triage status
+    triaged: usize,
+    false_positive_rate: f64,
+    suppression_rate: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggestion: Option<String>,
+}
+
+#[derive(Serialize)]
+struct StatsReport<'a> {
+    scan_path: &'a Path,
+    timestamp: &'a str,
+    rules: Vec<RuleStats>,
+}
+
+/// `anty rules stats`: how much each rule matched in the last scan of
+/// `scan_path` (or in `report_path`), how much of that the baseline and
+/// triage marked as false positives or accepted risks, and what to do
+/// about the noisiest rules. Rules are listed by false positives, then by
+/// matches.
+pub fn run(scan_path: Option<&Path>, report_path: Option<&Path>, top: usize, format: &str) -> Result<i32> {
+    let report = match report_path {
+        Some(path) => cache::read(path)?,
+        None => cache::load(scan_path)?,
+    };
+    let baseline = Baseline::load(&baseline::root_for(&report.scan_path));
+    let mut stats = collect(&report, baseline.as_ref());
+    stats.sort_by(|a, b| {
+        b.false_positives
+            .cmp(&a.false_positives)
+            .then_with(|| b.matches.cmp(&a.matches))
+            .then_with(|| a.rule_id.cmp(&b.rule_id))
+    });
+    let total = stats.len();
+    let matches: usize = stats.iter().map(|s| s.matches).sum();
+    stats.truncate(top);
+
+    if format == "json" {
+        let out = StatsReport { scan_path: &report.scan_path, timestamp: &report.timestamp, rules: stats };
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(exit_code::CLEAN);
+    }
+
+    println!();
+    println!(
+        "🐜 Rule noise in {} {}",
+        report.scan_path.display().bold(),
+        format!("(scan of {})", report.timestamp).dimmed()
+    );
+    println!();
+    if stats.is_empty() {
+        println!("  No rule matched anything in this scan.");
+        println!();
+        return Ok(exit_code::CLEAN);
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    table.set_header(vec!["Rule", "Matches", "Reported", "Suppressed", "False pos.", "Accepted", "FP rate"]);
+    for s in &stats {
+        table.add_row(vec![
+            s.rule_id.clone(),
+            s.matches.to_string(),
+            s.reported.to_string(),
+            s.suppressed.to_string(),
+            s.false_positives.to_string(),
+            s.accepted.to_string(),
+            format!("{:.0}%", s.false_positive_rate * 100.0),
+        ]);
+    }
+    for line in table.to_string().lines() {
+        println!("  {}", line);
+    }
+    println!();
+    println!(
+        "  {}",
+        format!("Top {} of {} rules; {} matches in all", stats.len(), total, matches).dimmed()
+    );
+
+    let suggestions: Vec<&RuleStats> = stats.iter().filter(|s| s.suggestion.is_some()).collect();
+    if !suggestions.is_empty() {
+        println!();
+        println!("  {}", "Suggestions".bold());
+        for s in suggestions {
+            println!("  {} {} {}", "→".cyan(), s.rule_id.bold(), s.title.dimmed());
+            println!("    {}", s.suggestion.as_deref().unwrap_or_default());
+        }
+    }
+    if stats.iter().all(|s| s.false_positives == 0) {
+        println!();
+        println!(
+            "  {}",
+            "No false positives recorded yet. Mark them with `anty triage set <id> --status false-positive` \
+             or in the wizard's baseline to measure each rule's precision."
+                .dimmed()
+        );
+    }
+    println!();
+    Ok(exit_code::CLEAN)
+}
+
+/// Per-rule numbers for every rule that matched in `report`
+fn collect(report: &ScanReport, baseline: Option<&Baseline>) -> Vec<RuleStats> {
+    let titles: HashMap<String, String> = rules::builtin_rules().into_iter().map(|r| (r.id, r.title)).collect();
+    let mut stats: BTreeMap<&str, RuleStats> = BTreeMap::new();
+    let entry = |rule_id: &str| -> RuleStats {
+        RuleStats {
+            rule_id: rule_id.to_string(),
+            title: titles.get(rule_id).cloned().unwrap_or_default(),
+            matches: 0,
+            reported: 0,
+            omitted: 0,
+            suppressed: 0,
+            false_positives: 0,
+            accepted: 0,
+            triaged: 0,
+            false_positive_rate: 0.0,
+            suppression_rate: 0.0,
+            suggestion: None,
+        }
+    };
+    // Top-level directories of each rule's false positives
+    let mut fp_dirs: HashMap<&str, Vec<Option<String>>> = HashMap::new();
+
+    for f in report.findings.iter().chain(&report.test_findings) {
+        let s = stats.entry(&f.rule_id).or_insert_with(|| entry(&f.rule_id));
+        s.reported += 1;
+        if s.title.is_empty() {
+            s.title = f.title.clone();
+        }
+        let Some(ref triage) = f.triage else {
+            continue;
+        };
+        s.triaged += 1;
+        match triage.status {
+            TriageStatus::FalsePositive => {
+                s.false_positives += 1;
+                fp_dirs.entry(&f.rule_id).or_default().push(top_dir(&f.file_path));
+            }
+            TriageStatus::Accepted => s.accepted += 1,
+            TriageStatus::Open => {}
+        }
+    }
+    for (rule_id, count) in &report.summary.omitted.by_rule {
+        stats.entry(rule_id).or_insert_with(|| entry(rule_id)).omitted += count;
+    }
+    for (rule_id, counts) in &report.summary.suppressed_by_rule {
+        let s = stats.entry(rule_id).or_insert_with(|| entry(rule_id));
+        s.suppressed += counts.total();
+        s.false_positives += counts.false_positive;
+        s.accepted += counts.accepted_risk;
+    }
+    // The report only counts suppressions; where they are comes from the
+    // baseline's entries
+    for e in baseline.iter().flat_map(|b| &b.entries) {
+        if e.decision == Decision::FalsePositive && report.summary.suppressed_by_rule.contains_key(&e.rule_id) {
+            fp_dirs.entry(&e.rule_id).or_default().push(top_dir(&e.file));
+        }
+    }
+
+    stats
+        .into_values()
+        .map(|mut s| {
+            s.matches = s.reported + s.omitted + s.suppressed;
+            s.false_positive_rate = s.false_positives as f64 / s.matches as f64;
+            s.suppression_rate = s.suppressed as f64 / s.matches as f64;
+            s.suggestion = suggest(&s, fp_dirs.get(s.rule_id.as_str()).map_or(&[], Vec::as_slice));
+            s
+        })
+        .collect()
+}
+
+/// What to do about a rule, given the directories its false positives are in
+fn suggest(s: &RuleStats, fp_dirs: &[Option<String>]) -> Option<String> {
+    let scoped = |dir: &str| {
+        format!(
+            "add [[overrides]] with paths = [\"{}/**\"] and disable_rules = [\"{}\"] to .anty.toml",
+            dir, s.rule_id
+        )
+    };
+    let dir = concentrated(fp_dirs);
+    if s.false_positives >= MIN_FALSE_POSITIVES && s.false_positive_rate >= DISABLE_RATE {
+        return Some(match dir {
+            Some(dir) => format!("Mostly false positives, most of them under {}/: {}", dir, scoped(&dir)),
+            None => format!(
+                "Mostly false positives: disable it with [[overrides]] paths = [\"**\"] and disable_rules = [\"{}\"]",
+                s.rule_id
+            ),
+        });
+    }
+    if s.false_positives >= 2 && s.false_positive_rate >= TUNE_RATE {
+        return Some(match dir {
+            Some(dir) => format!("False positives cluster under {}/: {}", dir, scoped(&dir)),
+            None => "Frequent false positives: cap it with max_severity = \"LOW\" in [[overrides]] so it stops \
+                     failing builds, and report the pattern"
+                .to_string(),
+        });
+    }
+    if s.matches >= REVIEW_MATCHES && s.triaged == 0 && s.suppressed == 0 {
+        return Some(format!(
+            "{} matches and none triaged: mark a sample with `anty triage set` to measure its precision",
+            s.matches
+        ));
+    }
+    None
+}
+
+/// The top-level directory holding most false positives, if any does.
+/// Files at the root have no directory and count against every one.
+fn concentrated(dirs: &[Option<String>]) -> Option<String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for dir in dirs.iter().flatten() {
+        *counts.entry(dir).or_default() += 1;
+    }
+    let (dir, count) = counts.into_iter().max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))?;
+    (count >= 2 && count as f64 >= dirs.len() as f64 * CONCENTRATION).then(|| dir.to_string())
+}
+
+/// First directory of a path relative to the scan root
+fn top_dir(path: &Path) -> Option<String> {
+    let mut components = path.components().filter(|c| matches!(c, Component::Normal(_)));
+    let first = components.next()?;
+    components.next()?;
+    Some(first.as_os_str().to_string_lossy().into_owned())
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anstream::println;
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::baseline;
+use crate::cli::{color, exit_code};
+use crate::report::finding::{self, Finding, Triage, TriageStatus};
+use crate::report::{cache, explain};
+
+/// Location of the triage file, relative to the scan root
+pub const TRIAGE_PATH: &str = ".anty/triage.toml";
+
+/// One finding's triage state, keyed by its deterministic ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub id: String,
+    pub rule_id: String,
+    #[serde(deserialize_with = "finding::deserialize_portable_path")]
+    pub file: PathBuf,
+    pub status: TriageStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Date of the last change, YYYY-MM-DD
+    pub updated: String,
+}
+
+/// Triage states for a repository (`.anty/triage.toml`). Unlike the
+/// baseline, triage hides nothing: findings stay in reports with their
+/// status, assignee, and note attached.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TriageFile {
+    #[serde(default, rename = "finding")]
+    pub entries: Vec<Entry>,
+}
+
+impl TriageFile {
+    /// Load the triage file under `root`, if present and valid
+    pub fn load(root: &Path) -> Option<Self> {
+        match Self::read(root) {
+            Ok(triage) if triage.entries.is_empty() => None,
+            Ok(triage) => {
+                info!("Loaded {} triage entries from {}", triage.entries.len(), root.join(TRIAGE_PATH).display());
+                Some(triage)
+            }
+            Err(e) => {
+                warn!("{:#}", e);
+                None
+            }
+        }
+    }
+
+    /// The triage file under `root`; empty when there is none
+    fn read(root: &Path) -> Result<Self> {
+        let path = root.join(TRIAGE_PATH);
+        if !path.is_file() {
+            return Ok(TriageFile::default());
+        }
+        let content =
+            std::fs::read_to_string(&path).with_context(|| format!("Could not read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Write the triage file under `root`, creating `.anty/` as needed
+    pub fn save(&self, root: &Path) -> Result<PathBuf> {
+        let path = root.join(TRIAGE_PATH);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = format!(
+            "# Anty triage: status, assignee, and notes for findings in this repository.\n\
+             # Set with `anty triage set <id>`; findings stay in reports either way.\n\n{}",
+            toml::to_string_pretty(self)?
+        );
+        std::fs::write(&path, content).with_context(|| format!("Could not write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Attach each finding's triage state
+    pub fn apply(&self, findings: &mut [Finding]) {
+        let states: HashMap<&str, &Entry> = self.entries.iter().map(|e| (e.id.as_str(), e)).collect();
+        for f in findings {
+            f.triage = states.get(f.id.as_str()).map(|e| Triage {
+                status: e.status,
+                assignee: e.assignee.clone(),
+                note: e.note.clone(),
+                updated: e.updated.clone(),
+            });
+        }
+    }
+}
+
+/// `anty triage set`: record a finding's status, assignee, or note in the
+/// triage file of the scanned repository. `id` may be a unique prefix, as
+/// for `anty explain`. What is not given keeps its earlier value; an empty
+/// `--assignee` or `--note` clears it.
+pub fn set(
+    id: &str,
+    status: Option<&str>,
+    assignee: Option<&str>,
+    note: Option<&str>,
+    report_path: Option<&Path>,
+) -> Result<i32> {
+    if status.is_none() && assignee.is_none() && note.is_none() {
+        bail!("Nothing to set: give --status, --assignee, or --note");
+    }
+    let report = match report_path {
+        Some(path) => cache::read(path)?,
+        None => cache::load(None)?,
+    };
+    let findings: Vec<Finding> = report.findings.iter().chain(&report.test_findings).cloned().collect();
+    let finding = match explain::find(&findings, id) {
+        Ok(finding) => finding,
+        Err(e) => {
+            println!("{} {}", color::icon("✗", "x").red().bold(), e);
+            return Ok(exit_code::ERROR);
+        }
+    };
+
+    let root = baseline::root_for(&report.scan_path);
+    let mut triage = TriageFile::read(&root)?;
+    let earlier = triage.entries.iter().position(|e| e.id == finding.id).map(|i| triage.entries.remove(i));
+    let text = |value: Option<&str>, earlier: Option<String>| match value {
+        Some(value) => Some(value.trim().to_string()).filter(|v| !v.is_empty()),
+        None => earlier,
+    };
+    let entry = Entry {
+        id: finding.id.clone(),
+        rule_id: finding.rule_id.clone(),
+        file: finding.file_path.clone(),
+        status: status
+            .and_then(TriageStatus::from_name)
+            .or(earlier.as_ref().map(|e| e.status))
+            .unwrap_or(TriageStatus::Open),
+        assignee: text(assignee, earlier.as_ref().and_then(|e| e.assignee.clone())),
+        note: text(note, earlier.and_then(|e| e.note)),
+        updated: chrono::Local::now().date_naive().to_string(),
+    };
+    let summary = match entry.assignee {
+        Some(ref assignee) => format!("{}, assigned to {}", entry.status, assignee),
+        None => entry.status.to_string(),
+    };
+    triage.entries.push(entry);
+    triage.entries.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.id.cmp(&b.id)));
+    let path = triage.save(&root)?;
+
+    println!(
+        "{} {} {}: {} {}",
+        color::icon("✓", "ok").green().bold(),
+        finding.id,
+        finding.rule_id.dimmed(),
+        summary,
+        format!("({})", path.display()).dimmed()
+    );
+    Ok(exit_code::CLEAN)
+}
@@ -0,0 +1,49 @@
+//! Runs `anty test-rules` against the fixtures in tests/rules, so a rule
+//! change that breaks one of its examples fails `cargo test`, and checks
+//! how matches of different agents on one line are reported.
+
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn every_rule_matches_its_examples() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/rules");
+    let output = Command::new(env!("CARGO_BIN_EXE_anty"))
+        .args(["-q", "test-rules", "--fixtures"])
+        .arg(&fixtures)
+        .output()
+        .expect("failed to run anty");
+    assert!(
+        output.status.success(),
+        "anty test-rules failed:\n{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn unrelated_rules_on_one_line_are_reported_separately() {
+    let dir = std::env::temp_dir().join(format!("anty-overlap-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("app.py"),
+        "x = eval(input()); AWS_SECRET_ACCESS_KEY = \"9HGjw1GyArA9O2gjaEvOaCMXTdbLYnAngR6/7GNh\"\n",
+    )
+    .unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_anty"))
+        .args(["-q", "scan", "--format", "json"])
+        .arg(&dir)
+        .output()
+        .expect("failed to run anty");
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("scan did not print JSON");
+    let findings = report["findings"].as_array().unwrap();
+    for rule in ["ANTY-DNG-001", "ANTY-SEC-002"] {
+        assert!(
+            findings.iter().any(|f| f["rule_id"] == rule),
+            "{} was not reported on its own:\n{:#}",
+            rule,
+            report["findings"]
+        );
+    }
+}